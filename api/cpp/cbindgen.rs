@@ -64,6 +64,8 @@ fn default_config() -> cbindgen::Config {
                 ("KeyEventArg".into(), "KeyEvent".into()),
                 ("PointerEventArg".into(), "PointerEvent".into()),
                 ("PointArg".into(), "Point".into()),
+                ("StringArg".into(), "SharedString".into()),
+                ("MessageDialogResultArg".into(), "MessageDialogResult".into()),
                 ("FloatArg".into(), "float".into()),
                 ("Coord".into(), "float".into()),
             ]
@@ -130,6 +132,15 @@ fn gen_corelib(
         "Rotate",
         "Opacity",
         "Layer",
+        "Effect",
+        "MenuItem",
+        "Menu",
+        "MenuBarItem",
+        "SystemTrayIcon",
+        "Shortcut",
+        "TimerItem",
+        "FileDialog",
+        "MessageDialog",
     ];
 
     config.export.include = [
@@ -138,6 +149,7 @@ fn gen_corelib(
         "WindowAdapterRcOpaque",
         "PropertyAnimation",
         "EasingCurve",
+        "AnimationDirection",
         "TextHorizontalAlignment",
         "TextVerticalAlignment",
         "TextOverflow",
@@ -188,12 +200,15 @@ fn gen_corelib(
         "slint_property_listener_scope_evaluate",
         "slint_property_listener_scope_is_dirty",
         "PropertyTrackerOpaque",
+        "ChangeTrackerOpaque",
         "CallbackOpaque",
         "WindowAdapterRc",
         "VoidArg",
         "KeyEventArg",
         "PointerEventArg",
         "PointArg",
+        "StringArg",
+        "MessageDialogResultArg",
         "Point",
         "slint_color_brighter",
         "slint_color_darker",
@@ -238,6 +253,8 @@ fn gen_corelib(
 
     let mut properties_config = config.clone();
     properties_config.export.exclude.clear();
+    // Defined in slint_properties.h, because the easing curve is not known yet in this header
+    properties_config.export.exclude.push("KeyFrame".into());
     properties_config.export.include.push("StateInfo".into());
     properties_config
         .export
@@ -418,7 +435,10 @@ fn gen_corelib(
     config.export.body.insert(
         "EasingCurve".to_owned(),
         "    constexpr EasingCurve() : tag(Tag::Linear), cubic_bezier{{0,0,1,1}} {}
-    constexpr explicit EasingCurve(EasingCurve::Tag tag, float a, float b, float c, float d) : tag(tag), cubic_bezier{{a,b,c,d}} {}".into()
+    constexpr explicit EasingCurve(EasingCurve::Tag tag) : tag(tag), cubic_bezier{{0,0,1,1}} {}
+    constexpr explicit EasingCurve(EasingCurve::Tag tag, float a, float b, float c, float d) : tag(tag), cubic_bezier{{a,b,c,d}} {}
+    constexpr explicit EasingCurve(EasingCurve::Tag tag, float stiffness, float damping, float mass) : tag(tag), spring{{stiffness,damping,mass}} {}
+    constexpr explicit EasingCurve(EasingCurve::Tag tag, uint32_t steps) : tag(tag), steps{steps} {}".into()
     );
     config.export.body.insert(
         "LayoutInfo".to_owned(),
@@ -435,6 +455,18 @@ fn gen_corelib(
         .body
         .insert("Flickable".to_owned(), "    inline Flickable(); inline ~Flickable();".into());
     config.export.pre_body.insert("FlickableDataBox".to_owned(), "struct FlickableData;".into());
+    config
+        .export
+        .body
+        .insert("TimerItem".to_owned(), "    inline TimerItem(); inline ~TimerItem();".into());
+    config.export.pre_body.insert("TimerItemDataBox".to_owned(), "struct TimerItemData;".into());
+    config.export.body.insert("Text".to_owned(), "    inline Text(); inline ~Text();".into());
+    config.export.pre_body.insert("TextDataBox".to_owned(), "struct TextData;".into());
+    config
+        .export
+        .body
+        .insert("TextInput".to_owned(), "    inline TextInput(); inline ~TextInput();".into());
+    config.export.pre_body.insert("TextInputDataBox".to_owned(), "struct TextInputData;".into());
     config.export.include.push("StandardListViewItem".into());
     cbindgen::Builder::new()
         .with_config(config)
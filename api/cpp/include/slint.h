@@ -18,6 +18,8 @@
 #include <mutex>
 #include <condition_variable>
 #include <span>
+#include <functional>
+#include <string>
 
 namespace slint::cbindgen_private {
 // Workaround https://github.com/eqrion/cbindgen/issues/43
@@ -576,6 +578,59 @@ box_layout_info_ortho(cbindgen_private::Slice<cbindgen_private::BoxLayoutCellDat
     return cbindgen_private::slint_box_layout_info_ortho(cells, &padding);
 }
 
+inline SharedVector<float>
+solve_flow_layout_horizontal(const cbindgen_private::FlowLayoutData &data,
+                             cbindgen_private::Slice<int> repeater_indexes)
+{
+    SharedVector<float> result;
+    cbindgen_private::Slice<uint32_t> ri { reinterpret_cast<uint32_t *>(repeater_indexes.ptr),
+                                           repeater_indexes.len };
+    cbindgen_private::slint_solve_flow_layout_horizontal(&data, ri, &result);
+    return result;
+}
+
+inline SharedVector<float> solve_flow_layout_vertical(const cbindgen_private::FlowLayoutData &data,
+                                                      cbindgen_private::Slice<int> repeater_indexes)
+{
+    SharedVector<float> result;
+    cbindgen_private::Slice<uint32_t> ri { reinterpret_cast<uint32_t *>(repeater_indexes.ptr),
+                                           repeater_indexes.len };
+    cbindgen_private::slint_solve_flow_layout_vertical(&data, ri, &result);
+    return result;
+}
+
+inline cbindgen_private::LayoutInfo
+flow_layout_info_horizontal(cbindgen_private::Slice<cbindgen_private::BoxLayoutCellData> cells,
+                            float spacing, const cbindgen_private::Padding &padding)
+{
+    return cbindgen_private::slint_flow_layout_info_horizontal(cells, spacing, &padding);
+}
+
+inline cbindgen_private::LayoutInfo
+flow_layout_info_vertical(const cbindgen_private::FlowLayoutData &data)
+{
+    return cbindgen_private::slint_flow_layout_info_vertical(&data);
+}
+
+inline SharedVector<float> solve_stack_layout(const cbindgen_private::StackLayoutData &data,
+                                              cbindgen_private::Slice<int> repeater_indexes)
+{
+    SharedVector<float> result;
+    cbindgen_private::Slice<uint32_t> ri { reinterpret_cast<uint32_t *>(repeater_indexes.ptr),
+                                           repeater_indexes.len };
+    cbindgen_private::slint_solve_stack_layout(&data, ri, &result);
+    return result;
+}
+
+inline cbindgen_private::LayoutInfo
+stack_layout_info(const cbindgen_private::StackLayoutData &data,
+                  cbindgen_private::Slice<int> repeater_indexes)
+{
+    cbindgen_private::Slice<uint32_t> ri { reinterpret_cast<uint32_t *>(repeater_indexes.ptr),
+                                           repeater_indexes.len };
+    return cbindgen_private::slint_stack_layout_info(&data, ri);
+}
+
 inline SharedVector<float> solve_path_layout(const cbindgen_private::PathLayoutData &data,
                                              cbindgen_private::Slice<int> repeater_indexes)
 {
@@ -830,6 +885,236 @@ public:
 
 namespace private_api {
 
+/// Base class for the models that provide the rows of a source model in a different way.
+/// The derived class is notified of the changes of the source model.
+/// \private
+template<typename SourceData, typename ModelData>
+class AdapterModel : public Model<ModelData>
+{
+    struct Listener : AbstractRepeaterView
+    {
+        AdapterModel *model;
+        Listener(AdapterModel *model) : model(model) { }
+        void row_added(int index, int count) override { model->source_row_added(index, count); }
+        void row_removed(int index, int count) override
+        {
+            model->source_row_removed(index, count);
+        }
+        void row_changed(int index) override { model->source_row_changed(index); }
+        void reset() override { model->source_reset(); }
+    };
+    std::shared_ptr<Listener> listener = std::make_shared<Listener>(this);
+
+protected:
+    /// The model providing the rows
+    std::shared_ptr<Model<SourceData>> source;
+
+    AdapterModel(std::shared_ptr<Model<SourceData>> source) : source(std::move(source))
+    {
+        this->source->attach_peer(listener);
+    }
+
+    virtual void source_row_added(int index, int count) = 0;
+    virtual void source_row_removed(int index, int count) = 0;
+    virtual void source_row_changed(int index) = 0;
+    virtual void source_reset() = 0;
+};
+
+/// A Model with the rows of the source model for which the filter function returns true.
+/// \private
+template<typename ModelData>
+class FilterModel : public AdapterModel<ModelData, ModelData>
+{
+    std::function<bool(const ModelData &)> filter_fn;
+    /// The indices in the source model of the rows that are not filtered out
+    std::vector<int> mapping;
+
+    void build_mapping()
+    {
+        mapping.clear();
+        for (int i = 0; i < this->source->row_count(); ++i) {
+            if (auto data = this->source->row_data(i); data && filter_fn(*data)) {
+                mapping.push_back(i);
+            }
+        }
+    }
+
+    void source_row_added(int index, int count) override
+    {
+        auto insertion_point = std::lower_bound(mapping.begin(), mapping.end(), index);
+        std::for_each(insertion_point, mapping.end(), [count](int &row) { row += count; });
+        std::vector<int> inserted;
+        for (int i = index; i < index + count; ++i) {
+            if (auto data = this->source->row_data(i); data && filter_fn(*data)) {
+                inserted.push_back(i);
+            }
+        }
+        if (!inserted.empty()) {
+            int filtered_index = int(insertion_point - mapping.begin());
+            mapping.insert(insertion_point, inserted.begin(), inserted.end());
+            this->row_added(filtered_index, int(inserted.size()));
+        }
+    }
+
+    void source_row_removed(int index, int count) override
+    {
+        auto begin = std::lower_bound(mapping.begin(), mapping.end(), index);
+        auto end = std::lower_bound(begin, mapping.end(), index + count);
+        int filtered_index = int(begin - mapping.begin());
+        int removed = int(end - begin);
+        std::for_each(end, mapping.end(), [count](int &row) { row -= count; });
+        mapping.erase(begin, end);
+        if (removed > 0) {
+            this->row_removed(filtered_index, removed);
+        }
+    }
+
+    void source_row_changed(int row) override
+    {
+        auto it = std::lower_bound(mapping.begin(), mapping.end(), row);
+        int filtered_index = int(it - mapping.begin());
+        bool is_contained = it != mapping.end() && *it == row;
+        auto data = this->source->row_data(row);
+        bool should_be_contained = data && filter_fn(*data);
+        if (is_contained && should_be_contained) {
+            this->row_changed(filtered_index);
+        } else if (!is_contained && should_be_contained) {
+            mapping.insert(it, row);
+            this->row_added(filtered_index, 1);
+        } else if (is_contained && !should_be_contained) {
+            mapping.erase(it);
+            this->row_removed(filtered_index, 1);
+        }
+    }
+
+    void source_reset() override
+    {
+        build_mapping();
+        this->reset();
+    }
+
+public:
+    /// Constructs a FilterModel with the rows of \a source for which \a filter_fn returns true.
+    FilterModel(std::shared_ptr<Model<ModelData>> source,
+                std::function<bool(const ModelData &)> filter_fn)
+        : AdapterModel<ModelData, ModelData>(std::move(source)), filter_fn(std::move(filter_fn))
+    {
+        build_mapping();
+    }
+
+    int row_count() const override { return int(mapping.size()); }
+
+    std::optional<ModelData> row_data(int i) const override
+    {
+        if (i < 0 || i >= row_count())
+            return {};
+        return this->source->row_data(mapping[i]);
+    }
+};
+
+/// A Model with the rows of the source model converted by the map function. The map function is
+/// called when a row is read.
+/// \private
+template<typename SourceData, typename ModelData>
+class MapModel : public AdapterModel<SourceData, ModelData>
+{
+    std::function<ModelData(const SourceData &)> map_fn;
+
+    void source_row_added(int index, int count) override { this->row_added(index, count); }
+    void source_row_removed(int index, int count) override { this->row_removed(index, count); }
+    void source_row_changed(int index) override { this->row_changed(index); }
+    void source_reset() override { this->reset(); }
+
+public:
+    /// Constructs a MapModel with the rows of \a source converted with \a map_fn.
+    MapModel(std::shared_ptr<Model<SourceData>> source,
+             std::function<ModelData(const SourceData &)> map_fn)
+        : AdapterModel<SourceData, ModelData>(std::move(source)), map_fn(std::move(map_fn))
+    {
+    }
+
+    int row_count() const override { return this->source->row_count(); }
+
+    std::optional<ModelData> row_data(int i) const override
+    {
+        if (auto data = this->source->row_data(i)) {
+            return map_fn(*data);
+        }
+        return {};
+    }
+};
+
+/// A Model with the rows of the source model from a start index up to, but not including, an end
+/// index.
+/// \private
+template<typename ModelData>
+class SliceModel : public AdapterModel<ModelData, ModelData>
+{
+    int start;
+    std::optional<int> end;
+
+    /// The index in the source model of the first row after the slice
+    int slice_end() const
+    {
+        int count = this->source->row_count();
+        return end ? std::min(*end, count) : count;
+    }
+
+    void source_row_added(int index, int count) override
+    {
+        if (!end && index >= start) {
+            this->row_added(index - start, count);
+        } else if (!end || index < *end) {
+            // The rows of the slice are shifted
+            this->reset();
+        }
+    }
+
+    void source_row_removed(int index, int count) override
+    {
+        if (!end && index >= start) {
+            this->row_removed(index - start, count);
+        } else if (!end || index < *end) {
+            this->reset();
+        }
+    }
+
+    void source_row_changed(int index) override
+    {
+        if (index >= start && index < slice_end()) {
+            this->row_changed(index - start);
+        }
+    }
+
+    void source_reset() override { this->reset(); }
+
+public:
+    /// Constructs a SliceModel with the rows of \a source from \a start up to \a end, or up to the
+    /// last row if \a end is not set.
+    SliceModel(std::shared_ptr<Model<ModelData>> source, int start, std::optional<int> end)
+        : AdapterModel<ModelData, ModelData>(std::move(source)),
+          start(std::max(start, 0)),
+          end(end ? std::optional<int>(std::max(*end, 0)) : std::nullopt)
+    {
+    }
+
+    int row_count() const override { return std::max(slice_end() - start, 0); }
+
+    std::optional<ModelData> row_data(int i) const override
+    {
+        if (i < 0 || i >= row_count())
+            return {};
+        return this->source->row_data(start + i);
+    }
+
+    void set_row_data(int i, const ModelData &value) override
+    {
+        if (i >= 0 && i < row_count()) {
+            this->source->set_row_data(start + i, value);
+        }
+    }
+};
+
 template<typename C, typename ModelData>
 class Repeater
 {
@@ -1005,6 +1290,33 @@ cbindgen_private::Flickable::~Flickable()
     slint_flickable_data_free(&data);
 }
 
+cbindgen_private::TimerItem::TimerItem()
+{
+    slint_timer_item_data_init(&data);
+}
+cbindgen_private::TimerItem::~TimerItem()
+{
+    slint_timer_item_data_free(&data);
+}
+
+cbindgen_private::Text::Text()
+{
+    slint_text_data_init(&data);
+}
+cbindgen_private::Text::~Text()
+{
+    slint_text_data_free(&data);
+}
+
+cbindgen_private::TextInput::TextInput()
+{
+    slint_text_input_data_init(&data);
+}
+cbindgen_private::TextInput::~TextInput()
+{
+    slint_text_input_data_free(&data);
+}
+
 cbindgen_private::NativeStyleMetrics::NativeStyleMetrics(void *)
 {
     slint_native_style_metrics_init(this);
@@ -1159,4 +1471,141 @@ auto blocking_invoke_from_event_loop(Functor f) -> void
     cv.wait(lock, [&] { return ok; });
 }
 
+using cbindgen_private::MessageDialogButtons;
+using cbindgen_private::MessageDialogKind;
+using cbindgen_private::MessageDialogResult;
+
+/// A filter that restricts the files shown in a file dialog.
+struct FileDialogFilter
+{
+    /// The name of the filter shown to the user, for example "Images"
+    SharedString name;
+    /// The patterns of the file names matched by this filter, for example `*.png`
+    std::vector<SharedString> patterns;
+};
+
+/// The options of the dialogs opened with open_file_dialog() and save_file_dialog().
+struct FileDialogOptions
+{
+    /// The title of the dialog window
+    SharedString title;
+    /// The directory shown when the dialog opens. When empty, the current directory is used.
+    SharedString directory;
+    /// The file name that is initially selected, typically used for save dialogs
+    SharedString file_name;
+    /// The filters the user can choose from. When empty, all files are shown.
+    std::vector<FileDialogFilter> filters;
+};
+
+/// The options of the dialog opened with show_message_dialog().
+struct MessageDialogOptions
+{
+    /// The title of the dialog window
+    SharedString title;
+    /// The message shown to the user
+    SharedString text;
+    /// The kind of message, which controls the icon
+    MessageDialogKind kind = MessageDialogKind::Information;
+    /// The buttons shown in the dialog
+    MessageDialogButtons buttons = MessageDialogButtons::Ok;
+};
+
+namespace private_api {
+template<typename Functor>
+void file_dialog(const FileDialogOptions &options, bool save, Functor callback)
+{
+    std::vector<SharedString> filter_names;
+    std::vector<SharedString> filter_patterns;
+    for (const auto &filter : options.filters) {
+        filter_names.push_back(filter.name);
+        std::string patterns;
+        for (const auto &pattern : filter.patterns) {
+            if (!patterns.empty()) {
+                patterns += ' ';
+            }
+            patterns += std::string_view(pattern);
+        }
+        filter_patterns.push_back(SharedString(patterns));
+    }
+    cbindgen_private::slint_file_dialog(
+            &options.title, &options.directory, &options.file_name,
+            { filter_names.data(), filter_names.size() },
+            { filter_patterns.data(), filter_patterns.size() }, save,
+            [](void *data, const SharedString *path) {
+                auto &callback = *reinterpret_cast<Functor *>(data);
+                if (path) {
+                    callback(std::optional<SharedString>(*path));
+                } else {
+                    callback(std::optional<SharedString>());
+                }
+            },
+            new Functor(std::move(callback)),
+            [](void *data) { delete reinterpret_cast<Functor *>(data); });
+}
+
+inline void show_file_dialog(const ComponentRc &component_rc, uintptr_t item_index)
+{
+    cbindgen_private::ItemRc item_rc { component_rc, item_index };
+    cbindgen_private::slint_show_file_dialog_item(&item_rc);
+}
+
+inline void show_message_dialog(const ComponentRc &component_rc, uintptr_t item_index)
+{
+    cbindgen_private::ItemRc item_rc { component_rc, item_index };
+    cbindgen_private::slint_show_message_dialog_item(&item_rc);
+}
+} // namespace private_api
+
+/// Shows a dialog to choose an existing file. The \a callback is invoked from the event loop when
+/// the dialog is closed, with the path of the chosen file, or an empty optional if the user
+/// cancelled.
+///
+/// This uses the native file dialog of the platform. If the platform doesn't provide one, the
+/// \a callback is invoked right away with an empty optional.
+///
+/// ```
+/// slint::FileDialogOptions options;
+/// options.title = "Open Image";
+/// options.filters.push_back({ "Images", { "*.png", "*.jpg" } });
+/// slint::open_file_dialog(options, [](std::optional<slint::SharedString> path) {
+///     if (path) {
+///         std::cout << "Opening " << *path << std::endl;
+///     }
+/// });
+/// ```
+template<typename Functor>
+void open_file_dialog(const FileDialogOptions &options, Functor callback)
+{
+    private_api::file_dialog(options, false, std::move(callback));
+}
+
+/// Shows a dialog to choose the path of a file to save. The \a callback is invoked from the event
+/// loop when the dialog is closed, with the chosen path, or an empty optional if the user
+/// cancelled.
+///
+/// This uses the native file dialog of the platform. If the platform doesn't provide one, the
+/// \a callback is invoked right away with an empty optional.
+template<typename Functor>
+void save_file_dialog(const FileDialogOptions &options, Functor callback)
+{
+    private_api::file_dialog(options, true, std::move(callback));
+}
+
+/// Shows a message box. The \a callback is invoked from the event loop when the dialog is closed,
+/// with the MessageDialogResult of the button the user clicked.
+///
+/// This uses the native message box of the platform. If the platform doesn't provide one, the
+/// \a callback is invoked right away as if the user closed the dialog.
+template<typename Functor>
+void show_message_dialog(const MessageDialogOptions &options, Functor callback)
+{
+    cbindgen_private::slint_message_dialog(
+            &options.title, &options.text, options.kind, options.buttons,
+            [](void *data, MessageDialogResult result) {
+                (*reinterpret_cast<Functor *>(data))(result);
+            },
+            new Functor(std::move(callback)),
+            [](void *data) { delete reinterpret_cast<Functor *>(data); });
+}
+
 } // namespace slint
@@ -222,12 +222,12 @@ RgbaColor<float> Color::to_argb_float() const
 namespace private_api {
 
 template<>
-inline void
-Property<Color>::set_animated_value(const Color &new_value,
-                                    const cbindgen_private::PropertyAnimation &animation_data) const
+inline void Property<Color>::set_animated_value(
+        const Color &new_value, const cbindgen_private::PropertyAnimation &animation_data,
+        std::initializer_list<cbindgen_private::KeyFrame<Color>> keyframes) const
 {
-    cbindgen_private::slint_property_set_animated_value_color(&inner, value, new_value,
-                                                              &animation_data);
+    cbindgen_private::slint_property_set_animated_value_color(
+            &inner, value, new_value, &animation_data, keyframes.begin(), keyframes.size());
 }
 
 } // namespace private_api
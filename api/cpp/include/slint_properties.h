@@ -4,9 +4,24 @@
 #pragma once
 #include <string_view>
 #include <memory>
+#include <optional>
+#include <type_traits>
+#include <initializer_list>
+#include <vector>
 
 namespace slint::cbindgen_private {
 struct PropertyAnimation;
+struct EasingCurve;
+
+/// Same layout as the KeyFrame struct in properties_animations.rs
+/// (The Easing parameter is there so that EasingCurve doesn't need to be complete yet)
+template<typename T, typename Easing = EasingCurve>
+struct KeyFrame
+{
+    float position;
+    T value;
+    Easing easing;
+};
 }
 
 #include "slint_properties_internal.h"
@@ -19,40 +34,52 @@ inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, int32_t *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(
+                void *, uint64_t *, const cbindgen_private::KeyFrame<int32_t> **, uintptr_t *),
+        std::initializer_list<cbindgen_private::KeyFrame<int32_t>> keyframes)
 {
     cbindgen_private::slint_property_set_animated_binding_int(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            keyframes.begin(), keyframes.size());
 }
 
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, float *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(
+                void *, uint64_t *, const cbindgen_private::KeyFrame<float> **, uintptr_t *),
+        std::initializer_list<cbindgen_private::KeyFrame<float>> keyframes)
 {
     cbindgen_private::slint_property_set_animated_binding_float(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            keyframes.begin(), keyframes.size());
 }
 
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, Color *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(
+                void *, uint64_t *, const cbindgen_private::KeyFrame<Color> **, uintptr_t *),
+        std::initializer_list<cbindgen_private::KeyFrame<Color>> keyframes)
 {
     cbindgen_private::slint_property_set_animated_binding_color(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            keyframes.begin(), keyframes.size());
 }
 
 inline void slint_property_set_animated_binding_helper(
         const cbindgen_private::PropertyHandleOpaque *handle, void (*binding)(void *, Brush *),
         void *user_data, void (*drop_user_data)(void *),
         const cbindgen_private::PropertyAnimation *animation_data,
-        cbindgen_private::PropertyAnimation (*transition_data)(void *, uint64_t *))
+        cbindgen_private::PropertyAnimation (*transition_data)(
+                void *, uint64_t *, const cbindgen_private::KeyFrame<Brush> **, uintptr_t *),
+        std::initializer_list<cbindgen_private::KeyFrame<Brush>> keyframes)
 {
     cbindgen_private::slint_property_set_animated_binding_brush(
-            handle, binding, user_data, drop_user_data, animation_data, transition_data);
+            handle, binding, user_data, drop_user_data, animation_data, transition_data,
+            keyframes.begin(), keyframes.size());
 }
 
 template<typename T>
@@ -99,11 +126,13 @@ struct Property
                 nullptr, nullptr);
     }
 
-    inline void set_animated_value(const T &value,
-                                   const cbindgen_private::PropertyAnimation &animation_data) const;
+    inline void
+    set_animated_value(const T &value, const cbindgen_private::PropertyAnimation &animation_data,
+                       std::initializer_list<cbindgen_private::KeyFrame<T>> keyframes = {}) const;
     template<typename F>
     inline void
-    set_animated_binding(F binding, const cbindgen_private::PropertyAnimation &animation_data) const
+    set_animated_binding(F binding, const cbindgen_private::PropertyAnimation &animation_data,
+                         std::initializer_list<cbindgen_private::KeyFrame<T>> keyframes = {}) const
     {
         private_api::slint_property_set_animated_binding_helper(
                 &inner,
@@ -111,9 +140,11 @@ struct Property
                     *reinterpret_cast<T *>(value) = (*reinterpret_cast<F *>(user_data))();
                 },
                 new F(binding), [](void *user_data) { delete reinterpret_cast<F *>(user_data); },
-                &animation_data, nullptr);
+                &animation_data, nullptr, keyframes);
     }
 
+    /// `animation` is called with a pointer to the start time of the transition and to the
+    /// keyframes, which it sets, and returns the animation
     template<typename F, typename Trans>
     inline void set_animated_binding_for_transition(F binding, Trans animation) const
     {
@@ -121,6 +152,8 @@ struct Property
         {
             F binding;
             Trans animation;
+            // Must stay valid until the next call of the animation
+            std::vector<cbindgen_private::KeyFrame<T>> keyframes;
         };
         private_api::slint_property_set_animated_binding_helper(
                 &inner,
@@ -128,11 +161,17 @@ struct Property
                     *reinterpret_cast<T *>(value) =
                             reinterpret_cast<UserData *>(user_data)->binding();
                 },
-                new UserData { binding, animation },
+                new UserData { binding, animation, {} },
                 [](void *user_data) { delete reinterpret_cast<UserData *>(user_data); }, nullptr,
-                [](void *user_data, uint64_t *instant) {
-                    return reinterpret_cast<UserData *>(user_data)->animation(instant);
-                });
+                [](void *user_data, uint64_t *instant,
+                   const cbindgen_private::KeyFrame<T> **keyframes, uintptr_t *keyframes_len) {
+                    auto data = reinterpret_cast<UserData *>(user_data);
+                    auto animation = data->animation(instant, &data->keyframes);
+                    *keyframes = data->keyframes.data();
+                    *keyframes_len = data->keyframes.size();
+                    return animation;
+                },
+                {});
     }
 
     bool is_dirty() const { return cbindgen_private::slint_property_is_dirty(&inner); }
@@ -188,19 +227,20 @@ private:
 
 template<>
 inline void Property<int32_t>::set_animated_value(
-        const int32_t &new_value, const cbindgen_private::PropertyAnimation &animation_data) const
+        const int32_t &new_value, const cbindgen_private::PropertyAnimation &animation_data,
+        std::initializer_list<cbindgen_private::KeyFrame<int32_t>> keyframes) const
 {
-    cbindgen_private::slint_property_set_animated_value_int(&inner, value, new_value,
-                                                            &animation_data);
+    cbindgen_private::slint_property_set_animated_value_int(
+            &inner, value, new_value, &animation_data, keyframes.begin(), keyframes.size());
 }
 
 template<>
-inline void
-Property<float>::set_animated_value(const float &new_value,
-                                    const cbindgen_private::PropertyAnimation &animation_data) const
+inline void Property<float>::set_animated_value(
+        const float &new_value, const cbindgen_private::PropertyAnimation &animation_data,
+        std::initializer_list<cbindgen_private::KeyFrame<float>> keyframes) const
 {
-    cbindgen_private::slint_property_set_animated_value_float(&inner, value, new_value,
-                                                              &animation_data);
+    cbindgen_private::slint_property_set_animated_value_float(
+            &inner, value, new_value, &animation_data, keyframes.begin(), keyframes.size());
 }
 
 template<typename F>
@@ -292,4 +332,53 @@ private:
     cbindgen_private::PropertyTrackerOpaque inner;
 };
 
+/// ChangeTracker calls a handler after the value returned by an evaluation functor has changed.
+/// The evaluation is done lazily from the event loop, and the handler is only called if the new
+/// value is different from the previous one. This is used to implement `changed` handlers.
+struct ChangeTracker
+{
+    /// Constructs a new change tracker instance.
+    ChangeTracker() { cbindgen_private::slint_change_tracker_construct(&inner); }
+    /// Destroys the change tracker.
+    ~ChangeTracker() { cbindgen_private::slint_change_tracker_drop(&inner); }
+    /// The copy constructor is intentionally deleted, change trackers cannot be copied.
+    ChangeTracker(const ChangeTracker &) = delete;
+    /// The assignment operator is intentionally deleted, change trackers cannot be copied.
+    ChangeTracker &operator=(const ChangeTracker &) = delete;
+
+    /// Starts tracking the value returned by \a fn_eval called with \a data, and calls
+    /// \a fn_notify with \a data and the new value after the value changed.
+    template<typename Data, typename FnEval, typename FnNotify>
+    void init(Data data, FnEval fn_eval, FnNotify fn_notify) const
+    {
+        using Value = std::invoke_result_t<FnEval, const Data &>;
+        struct Inner
+        {
+            Data data;
+            FnEval fn_eval;
+            FnNotify fn_notify;
+            std::optional<Value> value;
+        };
+        cbindgen_private::slint_change_tracker_init(
+                &inner, new Inner { std::move(data), std::move(fn_eval), std::move(fn_notify), {} },
+                [](void *user_data) { delete reinterpret_cast<Inner *>(user_data); },
+                [](void *user_data) {
+                    auto self = reinterpret_cast<Inner *>(user_data);
+                    auto new_value = self->fn_eval(self->data);
+                    if (self->value && *self->value == new_value) {
+                        return false;
+                    }
+                    self->value = std::move(new_value);
+                    return true;
+                },
+                [](void *user_data) {
+                    auto self = reinterpret_cast<Inner *>(user_data);
+                    self->fn_notify(self->data, *self->value);
+                });
+    }
+
+private:
+    cbindgen_private::ChangeTrackerOpaque inner;
+};
+
 } // namespace slint::private_api
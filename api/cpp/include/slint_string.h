@@ -149,6 +149,73 @@ struct SharedString
         return SharedString(n);
     }
 
+    /// Creates a new SharedString from the given number \a n, formatted with exactly \a digits
+    /// digits after the decimal point.
+    ///
+    /// For example:
+    /// \code
+    ///     auto str = slint::SharedString::from_number_fixed(3.14159, 2); // creates "3.14"
+    /// \endcode
+    static SharedString from_number_fixed(double n, int digits)
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_from_number_fixed(&result, n, digits);
+        return result;
+    }
+
+    /// Creates a new SharedString from the given number \a n, formatted with \a precision
+    /// significant digits. The exponential notation is used for very big or very small numbers.
+    ///
+    /// For example:
+    /// \code
+    ///     auto str = slint::SharedString::from_number_precision(3.14159, 3); // creates "3.14"
+    /// \endcode
+    static SharedString from_number_precision(double n, int precision)
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_from_number_precision(&result, n, precision);
+        return result;
+    }
+
+    /// \return a copy of this string where all characters are converted to upper case.
+    SharedString to_uppercase() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_to_uppercase(&result, this);
+        return result;
+    }
+
+    /// \return a copy of this string where all characters are converted to lower case.
+    SharedString to_lowercase() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_to_lowercase(&result, this);
+        return result;
+    }
+
+    /// \return the number of characters (unicode scalar values) in this string. This is
+    /// different from the number of bytes of its UTF-8 representation.
+    int character_count() const
+    {
+        return cbindgen_private::slint_shared_string_character_count(this);
+    }
+
+    /// \return the characters from index \a start (included) to index \a end (excluded).
+    /// The indices are expressed in characters, not in bytes. They are clamped to the length of
+    /// the string, and swapped if \a start is greater than \a end.
+    SharedString substring(int start, int end) const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_substring(&result, this, start, end);
+        return result;
+    }
+
+    /// \return true if the string contains \a needle; false otherwise
+    bool contains(std::string_view needle) const
+    {
+        return std::string_view(*this).find(needle) != std::string_view::npos;
+    }
+
     /// Returns true if \a a is equal to \a b; otherwise returns false.
     friend bool operator==(const SharedString &a, const SharedString &b)
     {
@@ -4,7 +4,11 @@
 /*! This crate just expose the function used by the C++ integration */
 
 use core::ffi::c_void;
+use i_slint_core::api::{FileDialogFilter, FileDialogOptions, MessageDialogOptions};
+use i_slint_core::items::{ItemRc, MessageDialogButtons, MessageDialogKind, MessageDialogResult};
+use i_slint_core::slice::Slice;
 use i_slint_core::window::{ffi::WindowAdapterRcOpaque, WindowAdapter};
+use i_slint_core::SharedString;
 use std::rc::Rc;
 
 #[doc(hidden)]
@@ -97,6 +101,92 @@ unsafe impl Send for UserData {}
     )
 }
 
+/// Owns the user data of the callback of a dialog
+struct DialogUserData {
+    user_data: *mut c_void,
+    drop_user_data: Option<extern "C" fn(*mut c_void)>,
+}
+
+impl Drop for DialogUserData {
+    fn drop(&mut self) {
+        if let Some(x) = self.drop_user_data {
+            x(self.user_data)
+        }
+    }
+}
+
+/// Shows an open or save file dialog. `filter_patterns` has the patterns of each filter of
+/// `filter_names`, separated by spaces. The callback gets a null path if the user cancelled.
+#[no_mangle]
+pub unsafe extern "C" fn slint_file_dialog(
+    title: &SharedString,
+    directory: &SharedString,
+    file_name: &SharedString,
+    filter_names: Slice<SharedString>,
+    filter_patterns: Slice<SharedString>,
+    save: bool,
+    callback: extern "C" fn(user_data: *mut c_void, path: Option<&SharedString>),
+    user_data: *mut c_void,
+    drop_user_data: Option<extern "C" fn(*mut c_void)>,
+) {
+    let options = FileDialogOptions {
+        title: title.clone(),
+        directory: directory.clone(),
+        file_name: file_name.clone(),
+        filters: filter_names
+            .iter()
+            .zip(filter_patterns.iter())
+            .map(|(name, patterns)| FileDialogFilter {
+                name: name.clone(),
+                patterns: patterns.split_whitespace().map(Into::into).collect(),
+            })
+            .collect(),
+    };
+    let ud = DialogUserData { user_data, drop_user_data };
+    let callback = Box::new(move |path: Option<SharedString>| {
+        let ud = &ud;
+        callback(ud.user_data, path.as_ref())
+    });
+    if save {
+        i_slint_backend_selector::save_file_dialog(&options, callback)
+    } else {
+        i_slint_backend_selector::open_file_dialog(&options, callback)
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn slint_message_dialog(
+    title: &SharedString,
+    text: &SharedString,
+    kind: MessageDialogKind,
+    buttons: MessageDialogButtons,
+    callback: extern "C" fn(user_data: *mut c_void, result: MessageDialogResult),
+    user_data: *mut c_void,
+    drop_user_data: Option<extern "C" fn(*mut c_void)>,
+) {
+    let options = MessageDialogOptions { title: title.clone(), text: text.clone(), kind, buttons };
+    let ud = DialogUserData { user_data, drop_user_data };
+    i_slint_backend_selector::show_message_dialog(
+        &options,
+        Box::new(move |result| {
+            let ud = &ud;
+            callback(ud.user_data, result)
+        }),
+    )
+}
+
+/// Shows the dialog of a `FileDialog` element
+#[no_mangle]
+pub extern "C" fn slint_show_file_dialog_item(item: &ItemRc) {
+    i_slint_backend_selector::show_file_dialog_element(item)
+}
+
+/// Shows the dialog of a `MessageDialog` element
+#[no_mangle]
+pub extern "C" fn slint_show_message_dialog_item(item: &ItemRc) {
+    i_slint_backend_selector::show_message_dialog_element(item)
+}
+
 #[cfg(feature = "testing")]
 #[no_mangle]
 pub unsafe extern "C" fn slint_testing_init_backend() {
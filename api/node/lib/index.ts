@@ -146,6 +146,14 @@ require.extensions['.60'] = require.extensions['.slint'] =
                     enumerable: true,
                 })
             });
+            c.functions().forEach((x: string) => {
+                Object.defineProperty(ret, x.replace(/-/g, '_'), {
+                    get() {
+                        return function () { return comp.invoke_callback(x, [...arguments]); };
+                    },
+                    enumerable: true,
+                })
+            });
             return ret;
         }
     }
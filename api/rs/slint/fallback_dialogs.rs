@@ -0,0 +1,518 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! File and message dialogs made of Slint elements, used when the platform doesn't provide native dialogs.
+
+use crate::{
+    CloseRequestResponse, ComponentHandle, FileDialogFilter, FileDialogOptions,
+    MessageDialogButtons, MessageDialogKind, MessageDialogOptions, MessageDialogResult, ModelRc,
+    SharedString, Timer, VecModel,
+};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+crate::slint! {
+    DialogButton := Rectangle {
+        property <string> text;
+        callback clicked;
+        min-width: 80px;
+        min-height: 28px;
+        horizontal-stretch: 0;
+        border-width: 1px;
+        border-radius: 3px;
+        border-color: #a0a0a0;
+        background: touch.pressed ? #d0d0d0 : touch.has-hover ? #e8e8e8 : #f4f4f4;
+        Text {
+            text: root.text;
+            color: black;
+            horizontal-alignment: center;
+            vertical-alignment: center;
+        }
+        touch := TouchArea {
+            clicked => { root.clicked(); }
+        }
+    }
+
+    export FallbackFileDialog := Window {
+        property <string> dialog-title;
+        property <string> directory;
+        property <string> file-name;
+        /// The content of the directory. The directories end with a '/'
+        property <[string]> entries;
+        property <string> accept-text;
+        callback entry-clicked(string);
+        callback go-up();
+        callback accept();
+        callback cancel();
+
+        title: dialog-title;
+        background: #f0f0f0;
+        preferred-width: 500px;
+        preferred-height: 400px;
+
+        VerticalLayout {
+            padding: 8px;
+            spacing: 6px;
+            HorizontalLayout {
+                spacing: 6px;
+                DialogButton {
+                    text: "Up";
+                    clicked => { root.go-up(); }
+                }
+                Text {
+                    text: root.directory;
+                    color: black;
+                    vertical-alignment: center;
+                    overflow: elide;
+                }
+            }
+            Rectangle {
+                border-width: 1px;
+                border-color: #a0a0a0;
+                background: white;
+                Flickable {
+                    viewport-height: root.entries.length * 24px;
+                    for entry[idx] in root.entries: Rectangle {
+                        y: idx * 24px;
+                        height: 24px;
+                        background: entry == root.file-name ? #cde3f7 : transparent;
+                        Text {
+                            x: 6px;
+                            text: entry;
+                            color: black;
+                            vertical-alignment: center;
+                        }
+                        TouchArea {
+                            clicked => { root.entry-clicked(entry); }
+                        }
+                    }
+                }
+            }
+            Rectangle {
+                height: 28px;
+                border-width: 1px;
+                border-color: #a0a0a0;
+                background: white;
+                TextInput {
+                    x: 4px;
+                    width: parent.width - 8px;
+                    text <=> root.file-name;
+                    color: black;
+                    vertical-alignment: center;
+                    accepted => { root.accept(); }
+                }
+            }
+            HorizontalLayout {
+                alignment: end;
+                spacing: 6px;
+                DialogButton {
+                    text: root.accept-text;
+                    clicked => { root.accept(); }
+                }
+                DialogButton {
+                    text: "Cancel";
+                    clicked => { root.cancel(); }
+                }
+            }
+        }
+    }
+
+    export FallbackMessageDialog := Window {
+        property <string> dialog-title;
+        property <string> text;
+        property <string> icon;
+        property <bool> has-ok;
+        property <bool> has-cancel;
+        property <bool> has-yes;
+        property <bool> has-no;
+        /// Called with 0 for Ok, 1 for Cancel, 2 for Yes and 3 for No
+        callback button-clicked(int);
+
+        title: dialog-title;
+        background: #f0f0f0;
+
+        VerticalLayout {
+            padding: 12px;
+            spacing: 12px;
+            HorizontalLayout {
+                spacing: 12px;
+                Text {
+                    text: root.icon;
+                    color: black;
+                    font-size: 24px;
+                    horizontal-stretch: 0;
+                }
+                Text {
+                    text: root.text;
+                    color: black;
+                    wrap: word-wrap;
+                    vertical-alignment: center;
+                    min-width: 200px;
+                }
+            }
+            HorizontalLayout {
+                alignment: end;
+                spacing: 6px;
+                if root.has-ok: DialogButton {
+                    text: "Ok";
+                    clicked => { root.button-clicked(0); }
+                }
+                if root.has-yes: DialogButton {
+                    text: "Yes";
+                    clicked => { root.button-clicked(2); }
+                }
+                if root.has-no: DialogButton {
+                    text: "No";
+                    clicked => { root.button-clicked(3); }
+                }
+                if root.has-cancel: DialogButton {
+                    text: "Cancel";
+                    clicked => { root.button-clicked(1); }
+                }
+            }
+        }
+    }
+}
+
+/// Returns true if the file name matches one of the patterns of the filter, such as `*.png`
+fn matches_filter(file_name: &str, filter: &FileDialogFilter) -> bool {
+    filter.patterns.iter().any(|pattern| match pattern.as_str() {
+        "*" | "*.*" => true,
+        pattern => match pattern.strip_prefix('*') {
+            Some(suffix) => file_name.ends_with(suffix),
+            None => file_name == pattern,
+        },
+    })
+}
+
+/// The entries of the directory, the directories first and ending with a '/'
+fn directory_entries(
+    directory: &std::path::Path,
+    filters: &[FileDialogFilter],
+) -> ModelRc<SharedString> {
+    let mut directories = Vec::new();
+    let mut files = Vec::new();
+    if let Ok(read_dir) = std::fs::read_dir(directory) {
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() {
+                directories.push(format!("{}/", name));
+            } else if filters.is_empty() || filters.iter().any(|f| matches_filter(&name, f)) {
+                files.push(name);
+            }
+        }
+    }
+    directories.sort();
+    files.sort();
+    let entries: Vec<SharedString> =
+        directories.into_iter().chain(files.into_iter()).map(Into::into).collect();
+    ModelRc::new(VecModel::from(entries))
+}
+
+/// Holds the dialog and the user callback until the dialog is closed
+struct DialogState<Dialog, Result> {
+    dialog: Option<Dialog>,
+    callback: Option<Box<dyn FnOnce(Result)>>,
+}
+
+impl<Dialog: ComponentHandle + 'static, Result> DialogState<Dialog, Result> {
+    fn finish(&mut self, result: Result) {
+        if let Some(dialog) = self.dialog.take() {
+            dialog.hide();
+            // We can't destroy the dialog while we are in one of its callbacks
+            Timer::single_shot(Default::default(), move || drop(dialog));
+        }
+        if let Some(callback) = self.callback.take() {
+            callback(result)
+        }
+    }
+}
+
+pub(crate) fn file_dialog(
+    options: &FileDialogOptions,
+    save: bool,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) {
+    create_file_dialog(options, save, callback).show();
+}
+
+fn create_file_dialog(
+    options: &FileDialogOptions,
+    save: bool,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) -> FallbackFileDialog {
+    let dialog = FallbackFileDialog::new();
+    dialog.set_dialog_title(options.title.clone());
+    dialog.set_accept_text(if save { "Save".into() } else { "Open".into() });
+    dialog.set_file_name(options.file_name.clone());
+
+    let directory = if options.directory.is_empty() {
+        std::env::current_dir().unwrap_or_default()
+    } else {
+        PathBuf::from(options.directory.as_str())
+    };
+    let directory = Rc::new(RefCell::new(directory));
+    let filters = Rc::new(options.filters.clone());
+
+    let refresh = {
+        let dialog_weak = dialog.as_weak();
+        let directory = directory.clone();
+        let filters = filters.clone();
+        move || {
+            let dialog = dialog_weak.unwrap();
+            let directory = directory.borrow();
+            dialog.set_directory(directory.to_string_lossy().to_string().into());
+            dialog.set_entries(directory_entries(&directory, &filters));
+        }
+    };
+    refresh();
+    let refresh = Rc::new(refresh);
+
+    let state = Rc::new(RefCell::new(DialogState {
+        dialog: Some(dialog.clone_strong()),
+        callback: Some(callback),
+    }));
+
+    dialog.on_entry_clicked({
+        let dialog_weak = dialog.as_weak();
+        let directory = directory.clone();
+        let refresh = refresh.clone();
+        move |entry| match entry.strip_suffix('/') {
+            Some(sub_directory) => {
+                directory.borrow_mut().push(sub_directory);
+                refresh();
+            }
+            None => dialog_weak.unwrap().set_file_name(entry),
+        }
+    });
+
+    dialog.on_go_up({
+        let directory = directory.clone();
+        let refresh = refresh.clone();
+        move || {
+            if directory.borrow_mut().pop() {
+                refresh();
+            }
+        }
+    });
+
+    dialog.on_accept({
+        let dialog_weak = dialog.as_weak();
+        let state = state.clone();
+        move || {
+            let file_name = dialog_weak.unwrap().get_file_name();
+            if file_name.is_empty() {
+                return;
+            }
+            let path = directory.borrow().join(file_name.as_str());
+            if path.is_dir() {
+                *directory.borrow_mut() = path;
+                dialog_weak.unwrap().set_file_name(Default::default());
+                refresh();
+                return;
+            }
+            if !save && !path.is_file() {
+                return;
+            }
+            state.borrow_mut().finish(Some(path.to_string_lossy().to_string().into()));
+        }
+    });
+
+    dialog.on_cancel({
+        let state = state.clone();
+        move || state.borrow_mut().finish(None)
+    });
+
+    dialog.window().on_close_requested(move || {
+        state.borrow_mut().finish(None);
+        CloseRequestResponse::HideWindow
+    });
+
+    dialog
+}
+
+pub(crate) fn message_dialog(
+    options: &MessageDialogOptions,
+    callback: Box<dyn FnOnce(MessageDialogResult)>,
+) {
+    let dialog = FallbackMessageDialog::new();
+    dialog.set_dialog_title(options.title.clone());
+    dialog.set_text(options.text.clone());
+    dialog.set_icon(
+        match options.kind {
+            MessageDialogKind::Information => "ℹ",
+            MessageDialogKind::Warning => "⚠",
+            MessageDialogKind::Error => "✖",
+            MessageDialogKind::Question => "?",
+        }
+        .into(),
+    );
+    let (has_ok, has_cancel, has_yes, has_no) = match options.buttons {
+        MessageDialogButtons::Ok => (true, false, false, false),
+        MessageDialogButtons::OkCancel => (true, true, false, false),
+        MessageDialogButtons::YesNo => (false, false, true, true),
+        MessageDialogButtons::YesNoCancel => (false, true, true, true),
+    };
+    dialog.set_has_ok(has_ok);
+    dialog.set_has_cancel(has_cancel);
+    dialog.set_has_yes(has_yes);
+    dialog.set_has_no(has_no);
+
+    let state = Rc::new(RefCell::new(DialogState {
+        dialog: Some(dialog.clone_strong()),
+        callback: Some(callback),
+    }));
+
+    dialog.on_button_clicked({
+        let state = state.clone();
+        move |button| {
+            let result = match button {
+                0 => MessageDialogResult::Ok,
+                2 => MessageDialogResult::Yes,
+                3 => MessageDialogResult::No,
+                _ => MessageDialogResult::Cancel,
+            };
+            state.borrow_mut().finish(result)
+        }
+    });
+
+    let buttons = options.buttons;
+    dialog.window().on_close_requested(move || {
+        state
+            .borrow_mut()
+            .finish(i_slint_backend_selector::dismissed_message_dialog_result(buttons));
+        CloseRequestResponse::HideWindow
+    });
+
+    dialog.show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(patterns: &[&str]) -> FileDialogFilter {
+        FileDialogFilter {
+            name: "Filter".into(),
+            patterns: patterns.iter().map(|p| SharedString::from(*p)).collect(),
+        }
+    }
+
+    /// Creates a directory with a sub-directory and a few files, removed when dropped
+    struct TestDirectory(PathBuf);
+
+    impl TestDirectory {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "slint_fallback_dialogs_{}_{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(path.join("pictures")).unwrap();
+            for file in ["b.png", "a.jpg", "notes.txt"] {
+                std::fs::write(path.join(file), "").unwrap();
+            }
+            Self(path)
+        }
+    }
+
+    impl Drop for TestDirectory {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn init_testing_backend() {
+        // Each test thread needs the backend, but a thread may run several tests
+        let _ = i_slint_core::platform::set_platform(Box::new(
+            i_slint_backend_testing::TestingBackend::default(),
+        ));
+    }
+
+    fn entries(model: &ModelRc<SharedString>) -> Vec<String> {
+        use crate::Model;
+        model.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        assert!(matches_filter("image.png", &filter(&["*.png", "*.jpg"])));
+        assert!(matches_filter("image.jpg", &filter(&["*.png", "*.jpg"])));
+        assert!(!matches_filter("image.gif", &filter(&["*.png", "*.jpg"])));
+        assert!(!matches_filter("png", &filter(&["*.png"])));
+        assert!(matches_filter("anything", &filter(&["*"])));
+        assert!(matches_filter("anything", &filter(&["*.*"])));
+        assert!(matches_filter("Makefile", &filter(&["Makefile"])));
+        assert!(!matches_filter("Makefile.am", &filter(&["Makefile"])));
+        assert!(!matches_filter("image.png", &filter(&[])));
+    }
+
+    #[test]
+    fn test_directory_entries() {
+        let directory = TestDirectory::new("entries");
+        assert_eq!(
+            entries(&directory_entries(&directory.0, &[])),
+            ["pictures/", "a.jpg", "b.png", "notes.txt"]
+        );
+        assert_eq!(
+            entries(&directory_entries(&directory.0, &[filter(&["*.png"]), filter(&["*.jpg"])])),
+            ["pictures/", "a.jpg", "b.png"]
+        );
+        assert!(entries(&directory_entries(&directory.0.join("missing"), &[])).is_empty());
+    }
+
+    #[test]
+    fn test_fallback_file_dialog() {
+        init_testing_backend();
+        let directory = TestDirectory::new("dialog");
+        let options = FileDialogOptions {
+            directory: directory.0.to_string_lossy().to_string().into(),
+            filters: vec![filter(&["*.png"])],
+            ..Default::default()
+        };
+        let result = Rc::new(RefCell::new(None));
+
+        let r = result.clone();
+        let dialog =
+            create_file_dialog(&options, false, Box::new(move |path| *r.borrow_mut() = Some(path)));
+        assert_eq!(entries(&dialog.get_entries()), ["pictures/", "b.png"]);
+
+        // Entering a directory and going back up
+        dialog.invoke_entry_clicked("pictures/".into());
+        assert!(entries(&dialog.get_entries()).is_empty());
+        dialog.invoke_go_up();
+        assert_eq!(entries(&dialog.get_entries()), ["pictures/", "b.png"]);
+
+        // A file that doesn't exist can't be opened
+        dialog.set_file_name("missing.png".into());
+        dialog.invoke_accept();
+        assert!(result.borrow().is_none());
+
+        dialog.invoke_entry_clicked("b.png".into());
+        assert_eq!(dialog.get_file_name(), "b.png");
+        dialog.invoke_accept();
+        assert_eq!(
+            result.borrow_mut().take(),
+            Some(Some(directory.0.join("b.png").to_string_lossy().to_string().into()))
+        );
+
+        // A save dialog accepts a new file name, and cancelling gives no path
+        let r = result.clone();
+        let dialog =
+            create_file_dialog(&options, true, Box::new(move |path| *r.borrow_mut() = Some(path)));
+        dialog.set_file_name("new.png".into());
+        dialog.invoke_accept();
+        assert_eq!(
+            result.borrow_mut().take(),
+            Some(Some(directory.0.join("new.png").to_string_lossy().to_string().into()))
+        );
+
+        let r = result.clone();
+        let dialog =
+            create_file_dialog(&options, true, Box::new(move |path| *r.borrow_mut() = Some(path)));
+        dialog.invoke_cancel();
+        assert_eq!(result.borrow_mut().take(), Some(None));
+    }
+}
@@ -232,6 +232,9 @@ struct MyStruct {
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
+// Allows the use of the slint! macro within this crate, for the fallback dialogs
+#[cfg(feature = "std")]
+extern crate self as slint;
 
 #[cfg(not(feature = "compat-0-3-0"))]
 compile_error!(
@@ -262,6 +265,88 @@ pub fn run_event_loop() {
     i_slint_backend_selector::with_platform(|b| b.run_event_loop())
 }
 
+/// Shows a dialog to choose an existing file. The `callback` is invoked from the event loop when the
+/// dialog is closed, with the path of the chosen file, or `None` if the user cancelled.
+///
+/// This uses the native file dialog of the platform if available, otherwise a dialog made of Slint elements.
+/// Without the `std` feature, there is no such fallback and the `callback` is invoked as if the user cancelled.
+///
+/// ```no_run
+/// let options = slint::FileDialogOptions {
+///     title: "Open Image".into(),
+///     filters: vec![slint::FileDialogFilter {
+///         name: "Images".into(),
+///         patterns: vec!["*.png".into(), "*.jpg".into()],
+///     }],
+///     ..Default::default()
+/// };
+/// slint::open_file_dialog(&options, |path| {
+///     if let Some(path) = path {
+///         println!("Opening {}", path);
+///     }
+/// });
+/// ```
+pub fn open_file_dialog(
+    options: &FileDialogOptions,
+    callback: impl FnOnce(Option<SharedString>) + 'static,
+) {
+    #[cfg(feature = "std")]
+    i_slint_backend_selector::show_with_fallback(
+        Box::new(callback),
+        |callback| {
+            i_slint_backend_selector::with_platform(|b| b.open_file_dialog(options, callback))
+        },
+        |callback| fallback_dialogs::file_dialog(options, false, callback),
+    );
+    #[cfg(not(feature = "std"))]
+    i_slint_backend_selector::open_file_dialog(options, alloc::boxed::Box::new(callback));
+}
+
+/// Shows a dialog to choose the path of a file to save. The `callback` is invoked from the event loop
+/// when the dialog is closed, with the chosen path, or `None` if the user cancelled.
+///
+/// This uses the native file dialog of the platform if available, otherwise a dialog made of Slint elements.
+/// Without the `std` feature, there is no such fallback and the `callback` is invoked as if the user cancelled.
+pub fn save_file_dialog(
+    options: &FileDialogOptions,
+    callback: impl FnOnce(Option<SharedString>) + 'static,
+) {
+    #[cfg(feature = "std")]
+    i_slint_backend_selector::show_with_fallback(
+        Box::new(callback),
+        |callback| {
+            i_slint_backend_selector::with_platform(|b| b.save_file_dialog(options, callback))
+        },
+        |callback| fallback_dialogs::file_dialog(options, true, callback),
+    );
+    #[cfg(not(feature = "std"))]
+    i_slint_backend_selector::save_file_dialog(options, alloc::boxed::Box::new(callback));
+}
+
+/// Shows a message box. The `callback` is invoked from the event loop when the dialog is closed,
+/// with the button the user clicked.
+///
+/// This uses the native message box of the platform if available, otherwise a dialog made of Slint elements.
+/// Without the `std` feature, there is no such fallback and the `callback` is invoked as if the user closed the dialog.
+pub fn show_message_dialog(
+    options: &MessageDialogOptions,
+    callback: impl FnOnce(MessageDialogResult) + 'static,
+) {
+    #[cfg(feature = "std")]
+    i_slint_backend_selector::show_with_fallback(
+        Box::new(callback),
+        |callback| {
+            i_slint_backend_selector::with_platform(|b| b.show_message_dialog(options, callback))
+        },
+        |callback| fallback_dialogs::message_dialog(options, callback),
+    );
+    #[cfg(not(feature = "std"))]
+    i_slint_backend_selector::show_message_dialog(options, alloc::boxed::Box::new(callback));
+}
+
+#[cfg(feature = "std")]
+mod fallback_dialogs;
+
 /// Include the code generated with the slint-build crate from the build script. After calling `slint_build::compile`
 /// in your `build.rs` build script, the use of this macro includes the generated Rust code and makes the exported types
 /// available for you to instantiate.
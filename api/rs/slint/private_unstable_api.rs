@@ -69,11 +69,13 @@ pub fn set_animated_property_binding<
     component_strong: &StrongRef,
     binding: fn(StrongRef) -> T,
     animation_data: PropertyAnimation,
+    keyframes: Vec<KeyFrame<T>>,
 ) {
     let weak = component_strong.to_weak();
-    property.set_animated_binding(
+    property.set_animated_binding_with_keyframes(
         move || binding(<StrongRef as StrongComponentRef>::from_weak(&weak).unwrap()),
         animation_data,
+        keyframes,
     )
 }
 
@@ -86,7 +88,11 @@ pub fn set_animated_property_binding_for_transition<
     binding: fn(StrongRef) -> T,
     compute_animation_details: fn(
         StrongRef,
-    ) -> (PropertyAnimation, i_slint_core::animations::Instant),
+    ) -> (
+        PropertyAnimation,
+        i_slint_core::animations::Instant,
+        Vec<KeyFrame<T>>,
+    ),
 ) {
     let weak_1 = component_strong.to_weak();
     let weak_2 = weak_1.clone();
@@ -143,6 +149,24 @@ pub fn create_window_adapter() -> alloc::rc::Rc<dyn i_slint_core::window::Window
     i_slint_backend_selector::with_platform(|b| b.create_window_adapter())
 }
 
+/// Shows the dialog of a `FileDialog` element, and invokes its callbacks when it is closed.
+pub fn show_file_dialog(item_rc: &ItemRc) {
+    i_slint_core::items::show_file_dialog(item_rc, |options, save, callback| {
+        if save {
+            crate::save_file_dialog(options, callback)
+        } else {
+            crate::open_file_dialog(options, callback)
+        }
+    })
+}
+
+/// Shows the dialog of a `MessageDialog` element, and invokes its callback when it is closed.
+pub fn show_message_dialog(item_rc: &ItemRc) {
+    i_slint_core::items::show_message_dialog(item_rc, |options, callback| {
+        crate::show_message_dialog(options, callback)
+    })
+}
+
 /// internal re_exports used by the macro generated
 pub mod re_exports {
     pub use alloc::boxed::Box;
@@ -162,7 +186,8 @@ pub mod re_exports {
     };
     pub use i_slint_core::graphics::*;
     pub use i_slint_core::input::{
-        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers, MouseEvent,
+        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyEventType, KeyboardModifiers,
+        MouseEvent,
     };
     pub use i_slint_core::item_tree::{
         visit_item_tree, ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, ItemWeak,
@@ -171,8 +196,11 @@ pub mod re_exports {
     pub use i_slint_core::items::*;
     pub use i_slint_core::layout::*;
     pub use i_slint_core::model::*;
-    pub use i_slint_core::properties::{set_state_binding, Property, PropertyTracker, StateInfo};
+    pub use i_slint_core::properties::{
+        set_state_binding, ChangeTracker, KeyFrame, Property, PropertyTracker, StateInfo,
+    };
     pub use i_slint_core::slice::Slice;
+    pub use i_slint_core::string::{number_to_fixed, number_to_precision, substring};
     pub use i_slint_core::window::{WindowAdapter, WindowInner};
     pub use i_slint_core::Color;
     pub use i_slint_core::ComponentVTable_static;
@@ -14,6 +14,10 @@
 #[cfg(not(no_qt))]
 mod qt_accessible;
 #[cfg(not(no_qt))]
+mod qt_dialogs;
+#[cfg(not(no_qt))]
+mod qt_menus;
+#[cfg(not(no_qt))]
 mod qt_widgets;
 #[cfg(not(no_qt))]
 mod qt_window;
@@ -231,6 +235,57 @@ struct EventHolder {
         Some(Box::new(Proxy))
     }
 
+    #[cfg(not(no_qt))]
+    fn open_file_dialog(
+        &self,
+        options: &i_slint_core::api::FileDialogOptions,
+        callback: Box<dyn FnOnce(Option<i_slint_core::SharedString>)>,
+    ) -> Result<(), i_slint_core::api::DialogError> {
+        qt_dialogs::open_file_dialog(options, callback);
+        Ok(())
+    }
+
+    #[cfg(not(no_qt))]
+    fn save_file_dialog(
+        &self,
+        options: &i_slint_core::api::FileDialogOptions,
+        callback: Box<dyn FnOnce(Option<i_slint_core::SharedString>)>,
+    ) -> Result<(), i_slint_core::api::DialogError> {
+        qt_dialogs::save_file_dialog(options, callback);
+        Ok(())
+    }
+
+    #[cfg(not(no_qt))]
+    fn show_message_dialog(
+        &self,
+        options: &i_slint_core::api::MessageDialogOptions,
+        callback: Box<dyn FnOnce(i_slint_core::api::MessageDialogResult)>,
+    ) -> Result<(), i_slint_core::api::DialogError> {
+        qt_dialogs::show_message_dialog(options, callback);
+        Ok(())
+    }
+
+    #[cfg(not(no_qt))]
+    fn set_menu_bar(
+        &self,
+        window_adapter: &Rc<dyn i_slint_core::window::WindowAdapter>,
+        menus: Option<&[i_slint_core::platform::MenuEntry]>,
+    ) -> Option<f32> {
+        window_adapter.as_any().downcast_ref::<qt_window::QtWindow>()?.set_menu_bar(menus)
+    }
+
+    #[cfg(not(no_qt))]
+    fn set_system_tray_icon(
+        &self,
+        window_adapter: &Rc<dyn i_slint_core::window::WindowAdapter>,
+        tray_icon: Option<&i_slint_core::platform::SystemTrayIconData>,
+    ) -> bool {
+        window_adapter
+            .as_any()
+            .downcast_ref::<qt_window::QtWindow>()
+            .map_or(false, |window| window.set_system_tray_icon(tray_icon))
+    }
+
     #[cfg(not(no_qt))]
     fn set_clipboard_text(&self, _text: &str) {
         use cpp::cpp;
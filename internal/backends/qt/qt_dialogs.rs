@@ -0,0 +1,172 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+// cSpell: ignore fnbox
+
+//! Implementation of the file and message dialogs of the Platform with QFileDialog and QMessageBox
+
+use cpp::cpp;
+use i_slint_core::api::{
+    FileDialogOptions, MessageDialogButtons, MessageDialogKind, MessageDialogOptions,
+    MessageDialogResult,
+};
+use i_slint_core::SharedString;
+
+/// Called with the QDialog result code and the selected file (if any)
+type DialogCallback = dyn FnOnce(i32, qttypes::QString);
+
+cpp! {{
+    #include <QtWidgets/QtWidgets>
+    #include <memory>
+    #include <functional>
+
+    void ensure_initialized(bool from_qt_backend);
+
+    struct DialogCallbackHolder {
+        struct TraitObject { void *a, *b; };
+        TraitObject fnbox = {nullptr, nullptr};
+        DialogCallbackHolder(TraitObject f) : fnbox(f) {}
+        DialogCallbackHolder(const DialogCallbackHolder&) = delete;
+        DialogCallbackHolder& operator=(const DialogCallbackHolder&) = delete;
+        ~DialogCallbackHolder() {
+            if (fnbox.a != nullptr || fnbox.b != nullptr) {
+                rust!(Slint_delete_dialog_callback [fnbox: *mut DialogCallback as "TraitObject"] {
+                    drop(Box::from_raw(fnbox))
+                });
+            }
+        }
+        void operator()(int result, QString file) {
+            if (fnbox.a != nullptr || fnbox.b != nullptr) {
+                TraitObject fnbox = std::move(this->fnbox);
+                this->fnbox = {nullptr, nullptr};
+                rust!(Slint_call_dialog_callback [fnbox: *mut DialogCallback as "TraitObject", result: i32 as "int", file: qttypes::QString as "QString"] {
+                    let b = Box::from_raw(fnbox);
+                    b(result, file);
+                });
+            }
+        }
+    };
+
+    /// Calls the holder when the dialog is finished. The dialog is deleted when closed, and the
+    /// holder with it (which releases the callback if the dialog was never finished)
+    void connect_dialog_callback(QDialog *dialog, std::shared_ptr<DialogCallbackHolder> holder,
+                                 std::function<QString()> selected_file) {
+        dialog->setAttribute(Qt::WA_DeleteOnClose);
+        QObject::connect(dialog, &QDialog::finished, dialog, [holder, selected_file](int result) {
+            (*holder)(result, selected_file());
+        });
+        dialog->open();
+    }
+}}
+
+fn file_dialog(
+    options: &FileDialogOptions,
+    save: bool,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) {
+    let title: qttypes::QString = options.title.as_str().into();
+    let directory: qttypes::QString = options.directory.as_str().into();
+    let file_name: qttypes::QString = options.file_name.as_str().into();
+    // QFileDialog::setNameFilter accepts several filters separated by ";;"
+    let filters: qttypes::QString = options
+        .filters
+        .iter()
+        .map(|filter| {
+            let patterns = filter.patterns.iter().map(|p| p.as_str()).collect::<Vec<_>>();
+            format!("{} ({})", filter.name, patterns.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join(";;")
+        .as_str()
+        .into();
+
+    let callback: Box<DialogCallback> = Box::new(move |result, file: qttypes::QString| {
+        let file = String::from(file);
+        callback(if result != 0 && !file.is_empty() { Some(file.into()) } else { None })
+    });
+    let fnbox = Box::into_raw(callback);
+
+    cpp! {unsafe [fnbox as "DialogCallbackHolder::TraitObject", title as "QString", directory as "QString",
+            file_name as "QString", filters as "QString", save as "bool"] {
+        ensure_initialized(true);
+        auto holder = std::make_shared<DialogCallbackHolder>(fnbox);
+        auto dialog = new QFileDialog(nullptr, title, directory);
+        if (save) {
+            dialog->setAcceptMode(QFileDialog::AcceptSave);
+            dialog->setFileMode(QFileDialog::AnyFile);
+        } else {
+            dialog->setAcceptMode(QFileDialog::AcceptOpen);
+            dialog->setFileMode(QFileDialog::ExistingFile);
+        }
+        if (!filters.isEmpty())
+            dialog->setNameFilter(filters);
+        if (!file_name.isEmpty())
+            dialog->selectFile(file_name);
+        connect_dialog_callback(dialog, holder, [dialog] {
+            auto files = dialog->selectedFiles();
+            return files.isEmpty() ? QString() : files.first();
+        });
+    }}
+}
+
+pub fn open_file_dialog(
+    options: &FileDialogOptions,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) {
+    file_dialog(options, false, callback)
+}
+
+pub fn save_file_dialog(
+    options: &FileDialogOptions,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) {
+    file_dialog(options, true, callback)
+}
+
+pub fn show_message_dialog(
+    options: &MessageDialogOptions,
+    callback: Box<dyn FnOnce(MessageDialogResult)>,
+) {
+    let title: qttypes::QString = options.title.as_str().into();
+    let text: qttypes::QString = options.text.as_str().into();
+    let icon: i32 = match options.kind {
+        MessageDialogKind::Information => 1,
+        MessageDialogKind::Warning => 2,
+        MessageDialogKind::Error => 3,
+        MessageDialogKind::Question => 4,
+    };
+    let buttons: i32 = match options.buttons {
+        MessageDialogButtons::Ok => 0,
+        MessageDialogButtons::OkCancel => 1,
+        MessageDialogButtons::YesNo => 2,
+        MessageDialogButtons::YesNoCancel => 3,
+    };
+
+    // The result is the QMessageBox::StandardButton the user clicked
+    let callback: Box<DialogCallback> = Box::new(move |result, _| {
+        let result = match result {
+            0x00000400 /* QMessageBox::Ok */ => MessageDialogResult::Ok,
+            0x00004000 /* QMessageBox::Yes */ => MessageDialogResult::Yes,
+            0x00010000 /* QMessageBox::No */ => MessageDialogResult::No,
+            _ => MessageDialogResult::Cancel,
+        };
+        callback(result)
+    });
+    let fnbox = Box::into_raw(callback);
+
+    cpp! {unsafe [fnbox as "DialogCallbackHolder::TraitObject", title as "QString", text as "QString",
+            icon as "int", buttons as "int"] {
+        ensure_initialized(true);
+        auto holder = std::make_shared<DialogCallbackHolder>(fnbox);
+        QMessageBox::StandardButtons standard_buttons;
+        switch (buttons) {
+            case 0: standard_buttons = QMessageBox::Ok; break;
+            case 1: standard_buttons = QMessageBox::Ok | QMessageBox::Cancel; break;
+            case 2: standard_buttons = QMessageBox::Yes | QMessageBox::No; break;
+            default: standard_buttons = QMessageBox::Yes | QMessageBox::No | QMessageBox::Cancel; break;
+        }
+        auto dialog = new QMessageBox(QMessageBox::Icon(icon), title, text, standard_buttons);
+        // QMessageBox::finished reports the standard button that was clicked
+        connect_dialog_callback(dialog, holder, [] { return QString(); });
+    }}
+}
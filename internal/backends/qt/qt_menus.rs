@@ -0,0 +1,92 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+// cSpell: ignore fnbox
+
+//! Fills a QMenuBar or a QMenu with the entries of the MenuBar and SystemTrayIcon elements
+
+use cpp::cpp;
+use i_slint_core::platform::MenuEntry;
+use std::ptr::NonNull;
+
+/// Called when the QAction is triggered, or when the QSystemTrayIcon is activated
+type ActionCallback = dyn Fn();
+
+cpp! {{
+    #include <QtWidgets/QtWidgets>
+    #include <memory>
+
+    struct ActionCallbackHolder {
+        struct TraitObject { void *a, *b; };
+        TraitObject fnbox = {nullptr, nullptr};
+        ActionCallbackHolder(TraitObject f) : fnbox(f) {}
+        ActionCallbackHolder(const ActionCallbackHolder&) = delete;
+        ActionCallbackHolder& operator=(const ActionCallbackHolder&) = delete;
+        ~ActionCallbackHolder() {
+            rust!(Slint_delete_action_callback [fnbox: *mut ActionCallback as "TraitObject"] {
+                drop(Box::from_raw(fnbox))
+            });
+        }
+        void operator()() {
+            rust!(Slint_call_action_callback [fnbox: *const ActionCallback as "TraitObject"] {
+                (*fnbox)()
+            });
+        }
+    };
+}}
+
+/// Append the entries to `parent`, which is a QMenuBar or a QMenu
+pub fn add_menu_entries(parent: NonNull<()>, entries: &[MenuEntry]) {
+    for entry in entries {
+        let title: qttypes::QString = entry.title.as_str().into();
+        let enabled = entry.enabled;
+        match &entry.sub_menu {
+            Some(sub_menu) => {
+                let menu = cpp! {unsafe [parent as "QWidget*", title as "QString", enabled as "bool"] -> NonNull<()> as "QMenu*" {
+                    auto menu = new QMenu(title, parent);
+                    menu->setEnabled(enabled);
+                    parent->addAction(menu->menuAction());
+                    return menu;
+                }};
+                add_menu_entries(menu, sub_menu);
+            }
+            None => {
+                let shortcut: qttypes::QString = entry.shortcut.as_str().into();
+                let checkable = entry.checkable;
+                let checked = entry.checked;
+                let callback: Box<ActionCallback> = Box::new({
+                    let entry = entry.clone();
+                    move || entry.activate()
+                });
+                let fnbox = Box::into_raw(callback);
+                cpp! {unsafe [parent as "QWidget*", title as "QString", shortcut as "QString",
+                        enabled as "bool", checkable as "bool", checked as "bool",
+                        fnbox as "ActionCallbackHolder::TraitObject"] {
+                    auto holder = std::make_shared<ActionCallbackHolder>(fnbox);
+                    auto action = new QAction(title, parent);
+                    action->setShortcut(QKeySequence(shortcut));
+                    action->setEnabled(enabled);
+                    action->setCheckable(checkable);
+                    action->setChecked(checked);
+                    QObject::connect(action, &QAction::triggered, action, [holder] { (*holder)(); });
+                    parent->addAction(action);
+                }};
+            }
+        }
+    }
+}
+
+/// Connects the activation of the QSystemTrayIcon to `callback`, replacing the previous connection
+pub fn set_tray_icon_callback(tray_icon: NonNull<()>, callback: Box<ActionCallback>) {
+    let fnbox = Box::into_raw(callback);
+    cpp! {unsafe [tray_icon as "QSystemTrayIcon*", fnbox as "ActionCallbackHolder::TraitObject"] {
+        auto holder = std::make_shared<ActionCallbackHolder>(fnbox);
+        QObject::disconnect(tray_icon, &QSystemTrayIcon::activated, nullptr, nullptr);
+        QObject::connect(tray_icon, &QSystemTrayIcon::activated, tray_icon,
+                [holder](QSystemTrayIcon::ActivationReason reason) {
+            // The context menu is shown for the other reasons
+            if (reason == QSystemTrayIcon::Trigger || reason == QSystemTrayIcon::DoubleClick)
+                (*holder)();
+        });
+    }}
+}
@@ -287,7 +287,9 @@ fn key_event(
                 self.activate();
                 KeyEventResult::EventAccepted
             }
-            KeyEventType::KeyReleased => KeyEventResult::EventIgnored,
+            KeyEventType::KeyReleased | KeyEventType::UpdateComposition => {
+                KeyEventResult::EventIgnored
+            }
         }
     }
 
@@ -94,7 +94,9 @@ fn key_event(
                 KeyEventResult::EventAccepted
             }
             KeyEventType::KeyPressed => KeyEventResult::EventIgnored,
-            KeyEventType::KeyReleased => KeyEventResult::EventIgnored,
+            KeyEventType::KeyReleased | KeyEventType::UpdateComposition => {
+                KeyEventResult::EventIgnored
+            }
         }
     }
 
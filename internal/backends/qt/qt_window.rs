@@ -16,9 +16,10 @@
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::{
     self, FillRule, ImageRendering, InputType, ItemRc, ItemRef, Layer, MouseCursor, Opacity,
-    PointerEventButton, RenderingResult, TextOverflow, TextWrap, WindowItem,
+    PointerEventButton, RenderingResult, TextFormat, TextOverflow, TextWrap, WindowItem,
 };
 use i_slint_core::layout::Orientation;
+use i_slint_core::platform::{MenuEntry, SystemTrayIconData};
 use i_slint_core::window::{WindowAdapter, WindowAdapterSealed, WindowInner};
 use i_slint_core::{ImageInner, PathData, Property, SharedString};
 use items::{ImageFit, TextHorizontalAlignment, TextVerticalAlignment};
@@ -30,6 +31,7 @@
 
 use crate::key_generated;
 use i_slint_core::renderer::Renderer;
+use i_slint_core::styled_text::StyledText;
 
 cpp! {{
     #include <QtWidgets/QtWidgets>
@@ -45,6 +47,8 @@
     #include <QtGui/QTextLayout>
     #include <QtGui/QImageReader>
     #include <QtGui/QCursor>
+    #include <QtGui/QInputMethod>
+    #include <QtGui/QInputMethodEvent>
     #include <QtCore/QBasicTimer>
     #include <QtCore/QTimer>
     #include <QtCore/QPointer>
@@ -79,6 +83,10 @@ struct TimerHandler : QObject {
     struct SlintWidget : QWidget {
         void *rust_window;
         bool isMouseButtonDown = false;
+        QPointer<QMenuBar> menuBar;
+        QPointer<QSystemTrayIcon> trayIcon;
+        // The position of the text cursor reported to the input method
+        QRectF imeCursorRect;
 
         SlintWidget() {
             setMouseTracking(true);
@@ -97,6 +105,9 @@ struct SlintWidget : QWidget {
 
         void resizeEvent(QResizeEvent *event) override {
             QSize size = event->size();
+            if (menuBar && !menuBar->isNativeMenuBar()) {
+                menuBar->setGeometry(0, 0, size.width(), menuBar->sizeHint().height());
+            }
             rust!(Slint_resizeEvent [rust_window: &QtWindow as "void*", size: qttypes::QSize as "QSize"] {
                 rust_window.resize_event(size)
             });
@@ -184,6 +195,22 @@ struct SlintWidget : QWidget {
             });
         }
 
+        void inputMethodEvent(QInputMethodEvent *event) override {
+            QString commit_string = event->commitString();
+            QString preedit_string = event->preeditString();
+            rust!(Slint_inputMethodEvent [rust_window: &QtWindow as "void*", commit_string: qttypes::QString as "QString", preedit_string: qttypes::QString as "QString"] {
+                rust_window.input_method_event(commit_string.clone(), preedit_string.clone());
+            });
+            event->accept();
+        }
+
+        QVariant inputMethodQuery(Qt::InputMethodQuery query) const override {
+            if (query == Qt::ImCursorRectangle) {
+                return imeCursorRect;
+            }
+            return QWidget::inputMethodQuery(query);
+        }
+
         void customEvent(QEvent *event) override {
             if (event->type() == QEvent::User) {
                 rust!(Slint_updateWindowProps [rust_window: &QtWindow as "void*"] {
@@ -200,6 +227,19 @@ struct SlintWidget : QWidget {
                 rust!(Slint_updateWindowActivation [rust_window: &QtWindow as "void*", active: bool as "bool"] {
                     WindowInner::from_pub(&rust_window.window).set_active(active)
                  });
+            } else if (event->type() == QEvent::WindowStateChange) {
+                auto state = windowState();
+                bool minimized = state & Qt::WindowMinimized;
+                bool maximized = state & Qt::WindowMaximized;
+                bool fullscreen = state & Qt::WindowFullScreen;
+                rust!(Slint_updateWindowState [rust_window: &QtWindow as "void*", minimized: bool as "bool", maximized: bool as "bool", fullscreen: bool as "bool"] {
+                    if let Some(window_item) = WindowInner::from_pub(&rust_window.window).window_item() {
+                        let window_item = window_item.as_pin_ref();
+                        window_item.update_state_property(WindowItem::FIELD_OFFSETS.minimized, minimized);
+                        window_item.update_state_property(WindowItem::FIELD_OFFSETS.maximized, maximized);
+                        window_item.update_state_property(WindowItem::FIELD_OFFSETS.fullscreen, fullscreen);
+                    }
+                });
             }
             QWidget::changeEvent(event);
         }
@@ -236,8 +276,9 @@ struct SlintWidget : QWidget {
 
     // Helper function used for the TextInput layouting
     //
+    // If line_height > 0, all lines have that height.
     // if line_for_y_pos > 0, then the function will return the line at this y position
-    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, int line_for_y_pos = -1) {
+    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, qreal line_height = 0, int line_for_y_pos = -1) {
         QTextOption options;
         options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
         options.setFlags(QTextOption::IncludeTrailingSpaces);
@@ -253,9 +294,15 @@ struct SlintWidget : QWidget {
             if (!line.isValid())
                 break;
             line.setLineWidth(rect.width());
-            height += leading;
-            line.setPosition(QPointF(0, height));
-            height += line.height();
+            if (line_height > 0) {
+                // center the line vertically
+                line.setPosition(QPointF(0, height + (line_height - line.height()) / 2.));
+                height += line_height;
+            } else {
+                height += leading;
+                line.setPosition(QPointF(0, height));
+                height += line.height();
+            }
             if (line_for_y_pos >= 0 && height > line_for_y_pos) {
                 return count;
             }
@@ -269,6 +316,58 @@ struct SlintWidget : QWidget {
         }
         return -1;
     }
+
+    // Same as do_text_layout, but with the formats of the spans of a styled text and the lines
+    // aligned horizontally according to the flags.
+    // If line_height > 0, all lines have that height. If max_lines > 0, only that many lines are laid out.
+    // Returns true if there is text after the last line.
+    static bool do_styled_text_layout(QTextLayout &layout, const QVector<QTextLayout::FormatRange> &formats, int flags, const QRectF &rect, qreal line_height = 0, int max_lines = 0) {
+        layout.setFormats(formats);
+        QTextOption options;
+        options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
+        options.setFlags(QTextOption::IncludeTrailingSpaces);
+        layout.setTextOption(options);
+        layout.setCacheEnabled(true);
+        QFontMetrics fm(layout.font());
+        int leading = fm.leading();
+        qreal height = 0;
+        layout.beginLayout();
+        while (max_lines <= 0 || layout.lineCount() < max_lines) {
+            auto line = layout.createLine();
+            if (!line.isValid())
+                break;
+            line.setLineWidth(rect.width());
+            if (line_height > 0) {
+                // center the line vertically
+                line.setPosition(QPointF(0, height + (line_height - line.height()) / 2.));
+                height += line_height;
+            } else {
+                height += leading;
+                line.setPosition(QPointF(0, height));
+                height += line.height();
+            }
+        }
+        layout.endLayout();
+        if (flags & Qt::AlignVCenter) {
+            layout.setPosition(QPointF(0, (rect.height() - height) / 2.));
+        } else if (flags & Qt::AlignBottom) {
+            layout.setPosition(QPointF(0, rect.height() - height));
+        }
+        for (int i = 0; i < layout.lineCount(); ++i) {
+            auto line = layout.lineAt(i);
+            qreal x = 0;
+            if (flags & Qt::AlignHCenter) {
+                x = (rect.width() - line.naturalTextWidth()) / 2.;
+            } else if (flags & Qt::AlignRight) {
+                x = rect.width() - line.naturalTextWidth();
+            }
+            line.setPosition(QPointF(x, line.y()));
+        }
+        if (layout.lineCount() == 0)
+            return false;
+        auto last_line = layout.lineAt(layout.lineCount() - 1);
+        return last_line.textStart() + last_line.textLength() < layout.text().size();
+    }
 }}
 
 cpp_class!(
@@ -475,12 +574,13 @@ fn draw_image(&mut self, image: Pin<&items::ImageItem>, item_rc: &ItemRc) {
         let dest_rect: qttypes::QRectF = get_geometry!(items::ImageItem, image);
         self.draw_image_impl(
             item_rc,
-            items::ImageItem::FIELD_OFFSETS.source.apply_pin(image),
+            &|| image.current_frame(),
             dest_rect,
             None,
             items::ImageItem::FIELD_OFFSETS.width.apply_pin(image),
             items::ImageItem::FIELD_OFFSETS.height.apply_pin(image),
             image.image_fit(),
+            None,
             image.image_rendering(),
             None,
         );
@@ -496,12 +596,13 @@ fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>, item_rc: &Ite
         };
         self.draw_image_impl(
             item_rc,
-            items::ClippedImage::FIELD_OFFSETS.source.apply_pin(image),
+            &|| image.current_frame(),
             dest_rect,
             Some(source_rect),
             items::ClippedImage::FIELD_OFFSETS.width.apply_pin(image),
             items::ClippedImage::FIELD_OFFSETS.height.apply_pin(image),
             image.image_fit(),
+            image.slices(),
             image.image_rendering(),
             Some(items::ClippedImage::FIELD_OFFSETS.colorize.apply_pin(image)),
         );
@@ -511,7 +612,8 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
         let rect: qttypes::QRectF = get_geometry!(items::Text, text);
         let fill_brush: qttypes::QBrush = into_qbrush(text.color(), rect.width, rect.height);
         let mut string: qttypes::QString = text.text().as_str().into();
-        let font: QFont = get_font(text.font_request(WindowInner::from_pub(&self.window)));
+        let mut font: QFont = get_font(text.font_request(WindowInner::from_pub(&self.window)));
+        font.set_decorations(text.underline(), text.strikethrough());
         let flags = match text.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
@@ -524,8 +626,35 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
-        let elide = text.overflow() == TextOverflow::Elide;
         let painter: &mut QPainterPtr = &mut self.painter;
+        let elide = text.overflow() == TextOverflow::Elide;
+        let line_height = text.explicit_line_height();
+        let max_lines = text.max_line_count();
+        if text.text_format() == TextFormat::Markup || line_height.is_some() || max_lines.is_some()
+        {
+            // On this path, the text is only elided when it is cut by max-lines
+            let styled_text = text.styled_text();
+            let mut string: qttypes::QString = styled_text.text.as_str().into();
+            let formats = styled_text_formats(&styled_text);
+            let line_height: f64 = line_height.unwrap_or_default() as _;
+            let max_lines = max_lines.unwrap_or_default() as i32;
+            cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", formats as "QVector<QTextLayout::FormatRange>", line_height as "qreal", max_lines as "int", elide as "bool"] {
+                string.replace(QChar('\n'), QChar::LineSeparator);
+                QTextLayout layout(string, font);
+                if (do_styled_text_layout(layout, formats, flags, rect, line_height, max_lines) && elide) {
+                    // add the ellipsis on the last line, and layout again
+                    auto last_line = layout.lineAt(layout.lineCount() - 1);
+                    QFontMetrics fm(font);
+                    QString to_elide = QStringView(string).mid(last_line.textStart(), last_line.textLength()).trimmed() % QStringView(QT_UNICODE_LITERAL("…"));
+                    string = string.left(last_line.textStart()) + fm.elidedText(to_elide, Qt::ElideRight, rect.width());
+                    layout.setText(string);
+                    do_styled_text_layout(layout, formats, flags, rect, line_height, max_lines);
+                }
+                (*painter)->setPen(QPen(fill_brush, 0));
+                layout.draw(painter->get(), rect.topLeft());
+            }}
+            return;
+        }
         cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool"] {
             (*painter)->setFont(font);
             (*painter)->setPen(QPen(fill_brush, 0));
@@ -602,9 +731,10 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
             }}
         }
 
-        let font: QFont = get_font(
+        let mut font: QFont = get_font(
             text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
         );
+        font.set_decorations(text_input.underline(), text_input.strikethrough());
         let flags = match text_input.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
@@ -643,6 +773,8 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
             };
 
         let single_line: bool = text_input.single_line();
+        let line_height: f64 = text_input.explicit_line_height().unwrap_or_default() as _;
+        let preedit_string: qttypes::QString = text_input.preedit_text().as_str().into();
 
         let painter: &mut QPainterPtr = &mut self.painter;
         cpp! { unsafe [
@@ -654,15 +786,20 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                 mut string as "QString",
                 flags as "int",
                 single_line as "bool",
+                line_height as "qreal",
                 font as "QFont",
                 cursor_position as "int",
                 anchor_position as "int",
-                text_cursor_width as "float"] {
+                text_cursor_width as "float",
+                preedit_string as "QString"] {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(string, font);
-            do_text_layout(layout, flags, rect);
+            if (!preedit_string.isEmpty()) {
+                layout.setPreeditArea(cursor_position, preedit_string);
+            }
+            do_text_layout(layout, flags, rect, line_height);
             (*painter)->setPen(QPen(fill_brush, 0));
             QVector<QTextLayout::FormatRange> selections;
             if (anchor_position != cursor_position) {
@@ -675,9 +812,14 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                     fmt
                 };
             }
+            if (!preedit_string.isEmpty()) {
+                QTextCharFormat fmt;
+                fmt.setFontUnderline(true);
+                selections << QTextLayout::FormatRange{ cursor_position, int(preedit_string.size()), fmt };
+            }
             layout.draw(painter->get(), rect.topLeft(), selections);
             if (text_cursor_width > 0) {
-                layout.drawCursor(painter->get(), rect.topLeft(), cursor_position, text_cursor_width);
+                layout.drawCursor(painter->get(), rect.topLeft(), cursor_position + preedit_string.size(), text_cursor_width);
             }
         }}
     }
@@ -993,7 +1135,7 @@ fn adjust_to_image_fit(
     dest_rect: &mut qttypes::QRectF,
 ) {
     match image_fit {
-        i_slint_core::items::ImageFit::Fill => (),
+        i_slint_core::items::ImageFit::Fill | i_slint_core::items::ImageFit::Tile => (),
         i_slint_core::items::ImageFit::Cover => {
             let ratio = qttypes::qreal::max(
                 dest_rect.width / source_rect.width,
@@ -1029,12 +1171,13 @@ impl QtItemRenderer<'_> {
     fn draw_image_impl(
         &mut self,
         item_rc: &ItemRc,
-        source_property: Pin<&Property<Image>>,
+        source: &dyn Fn() -> Image,
         dest_rect: qttypes::QRectF,
         source_rect: Option<qttypes::QRectF>,
         target_width: std::pin::Pin<&Property<f32>>,
         target_height: std::pin::Pin<&Property<f32>>,
         image_fit: ImageFit,
+        slices: Option<Vec<items::ImageSlice>>,
         rendering: ImageRendering,
         colorize_property: Option<Pin<&Property<Brush>>>,
     ) {
@@ -1054,14 +1197,16 @@ fn draw_image_impl(
                         || !rect.width.approx_eq(&target_width)
                         || !rect.height.approx_eq(&target_height))
             });
-            let source_size = if !has_source_clipping {
-                Some(IntSize::new(target_width as u32, target_height as u32))
-            } else {
-                // Source size & clipping is not implemented yet
-                None
-            };
+            // Tiled images are repeated at their natural size
+            let source_size =
+                if !has_source_clipping && slices.is_none() && image_fit != ImageFit::Tile {
+                    Some(IntSize::new(target_width as u32, target_height as u32))
+                } else {
+                    // Source size & clipping is not implemented yet
+                    None
+                };
 
-            image_to_pixmap((&source_property.get()).into(), source_size).map_or_else(
+            image_to_pixmap((&source()).into(), source_size).map_or_else(
                 Default::default,
                 |mut pixmap: qttypes::QPixmap| {
                     let colorize = colorize_property.map_or(Brush::default(), |c| c.get());
@@ -1080,6 +1225,69 @@ fn draw_image_impl(
         });
 
         let image_size = pixmap.size();
+        let smooth: bool = rendering == ImageRendering::Smooth;
+
+        if let Some(slices) = slices {
+            // Scalable images may be rendered into a pixmap of a different size
+            let natural_size = source().size();
+            if natural_size.is_empty() {
+                return;
+            }
+            let texture_scale_x = image_size.width as f64 / natural_size.width as f64;
+            let texture_scale_y = image_size.height as f64 / natural_size.height as f64;
+            for slice in slices {
+                let source_rect = qttypes::QRectF {
+                    x: slice.source.min_x() as f64 * texture_scale_x,
+                    y: slice.source.min_y() as f64 * texture_scale_y,
+                    width: slice.source.width() as f64 * texture_scale_x,
+                    height: slice.source.height() as f64 * texture_scale_y,
+                };
+                let dest_rect = qttypes::QRectF {
+                    x: dest_rect.x + slice.target.min_x() as f64,
+                    y: dest_rect.y + slice.target.min_y() as f64,
+                    width: slice.target.width() as f64,
+                    height: slice.target.height() as f64,
+                };
+                let painter: &mut QPainterPtr = &mut self.painter;
+                cpp! { unsafe [
+                        painter as "QPainterPtr*",
+                        pixmap as "QPixmap",
+                        source_rect as "QRectF",
+                        dest_rect as "QRectF",
+                        smooth as "bool"] {
+                    (*painter)->save();
+                    (*painter)->setRenderHint(QPainter::SmoothPixmapTransform, smooth);
+                    (*painter)->drawPixmap(dest_rect, pixmap, source_rect);
+                    (*painter)->restore();
+                }};
+            }
+            return;
+        }
+
+        if image_fit == ImageFit::Tile {
+            let source_rect = source_rect.filter(|r| r.is_valid()).unwrap_or(qttypes::QRectF {
+                x: 0.,
+                y: 0.,
+                width: image_size.width as _,
+                height: image_size.height as _,
+            });
+            let painter: &mut QPainterPtr = &mut self.painter;
+            cpp! { unsafe [
+                    painter as "QPainterPtr*",
+                    pixmap as "QPixmap",
+                    source_rect as "QRectF",
+                    dest_rect as "QRectF",
+                    smooth as "bool"] {
+                const QPixmap tile = source_rect == QRectF(pixmap.rect())
+                    ? pixmap : pixmap.copy(source_rect.toAlignedRect());
+                (*painter)->save();
+                (*painter)->setRenderHint(QPainter::SmoothPixmapTransform, smooth);
+                (*painter)->drawTiledPixmap(dest_rect, tile);
+                (*painter)->restore();
+            }};
+            return;
+        }
+
         let mut source_rect = source_rect.filter(|r| r.is_valid()).unwrap_or(qttypes::QRectF {
             x: 0.,
             y: 0.,
@@ -1089,7 +1297,6 @@ fn draw_image_impl(
         let mut dest_rect = dest_rect;
         adjust_to_image_fit(image_fit, &mut source_rect, &mut dest_rect);
         let painter: &mut QPainterPtr = &mut self.painter;
-        let smooth: bool = rendering == ImageRendering::Smooth;
         cpp! { unsafe [
                 painter as "QPainterPtr*",
                 pixmap as "QPixmap",
@@ -1246,6 +1453,94 @@ fn widget_ptr(&self) -> NonNull<()> {
         unsafe { std::mem::transmute_copy::<QWidgetPtr, NonNull<_>>(&self.widget_ptr) }
     }
 
+    /// Replaces the QMenuBar of the window. Returns the height it takes in the window.
+    pub(crate) fn set_menu_bar(&self, menus: Option<&[MenuEntry]>) -> Option<f32> {
+        let widget_ptr = self.widget_ptr();
+        let menus = match menus {
+            Some(menus) => menus,
+            None => {
+                cpp! {unsafe [widget_ptr as "SlintWidget*"] {
+                    if (widget_ptr->menuBar) {
+                        widget_ptr->menuBar->hide();
+                        widget_ptr->menuBar->deleteLater();
+                    }
+                }};
+                return None;
+            }
+        };
+        // The menus are re-created when they change. The old menu bar is deleted later, because
+        // this may be called while one of its actions is being triggered.
+        let menu_bar = cpp! {unsafe [widget_ptr as "SlintWidget*"] -> NonNull<()> as "QMenuBar*" {
+            if (widget_ptr->menuBar) {
+                widget_ptr->menuBar->hide();
+                widget_ptr->menuBar->deleteLater();
+            }
+            widget_ptr->menuBar = new QMenuBar(widget_ptr);
+            return widget_ptr->menuBar;
+        }};
+        crate::qt_menus::add_menu_entries(menu_bar, menus);
+        Some(
+            cpp! {unsafe [widget_ptr as "SlintWidget*", menu_bar as "QMenuBar*"] -> f32 as "float" {
+                // On macOS, the menu bar is shown at the top of the screen
+                if (menu_bar->isNativeMenuBar())
+                    return 0;
+                int height = menu_bar->sizeHint().height();
+                menu_bar->setGeometry(0, 0, widget_ptr->width(), height);
+                menu_bar->show();
+                return height;
+            }},
+        )
+    }
+
+    /// Shows, updates, or removes the QSystemTrayIcon of the window
+    pub(crate) fn set_system_tray_icon(&self, tray_icon: Option<&SystemTrayIconData>) -> bool {
+        let widget_ptr = self.widget_ptr();
+        let tray_icon = match tray_icon {
+            Some(tray_icon) => tray_icon,
+            None => {
+                cpp! {unsafe [widget_ptr as "SlintWidget*"] {
+                    if (widget_ptr->trayIcon) {
+                        widget_ptr->trayIcon->hide();
+                        widget_ptr->trayIcon->deleteLater();
+                    }
+                }};
+                return false;
+            }
+        };
+        let pixmap: qttypes::QPixmap =
+            image_to_pixmap((&tray_icon.icon).into(), None).unwrap_or_default();
+        let tooltip: qttypes::QString = tray_icon.tooltip.as_str().into();
+        let qt_tray_icon = cpp! {unsafe [widget_ptr as "SlintWidget*", pixmap as "QPixmap", tooltip as "QString"] -> Option<NonNull<()>> as "QSystemTrayIcon*" {
+            if (!QSystemTrayIcon::isSystemTrayAvailable())
+                return nullptr;
+            if (!widget_ptr->trayIcon)
+                widget_ptr->trayIcon = new QSystemTrayIcon(widget_ptr);
+            widget_ptr->trayIcon->setIcon(QIcon(pixmap));
+            widget_ptr->trayIcon->setToolTip(tooltip);
+            return widget_ptr->trayIcon;
+        }};
+        let qt_tray_icon = match qt_tray_icon {
+            Some(qt_tray_icon) => qt_tray_icon,
+            None => return false,
+        };
+        let menu = cpp! {unsafe [widget_ptr as "SlintWidget*", qt_tray_icon as "QSystemTrayIcon*"] -> NonNull<()> as "QMenu*" {
+            if (auto old_menu = qt_tray_icon->contextMenu())
+                old_menu->deleteLater();
+            auto menu = new QMenu(widget_ptr);
+            qt_tray_icon->setContextMenu(menu);
+            return menu;
+        }};
+        crate::qt_menus::add_menu_entries(menu, &tray_icon.menu);
+        crate::qt_menus::set_tray_icon_callback(qt_tray_icon, {
+            let tray_icon = tray_icon.clone();
+            Box::new(move || tray_icon.activate())
+        });
+        cpp! {unsafe [qt_tray_icon as "QSystemTrayIcon*"] {
+            qt_tray_icon->show();
+        }};
+        true
+    }
+
     fn paint_event(&self, painter: QPainterPtr) {
         let runtime_window = WindowInner::from_pub(&self.window);
         runtime_window.draw_contents(|components| {
@@ -1326,6 +1621,33 @@ fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, release
         timer_event();
     }
 
+    fn input_method_event(
+        &self,
+        commit_string: qttypes::QString,
+        preedit_string: qttypes::QString,
+    ) {
+        i_slint_core::animations::update_animations();
+        let runtime_window = WindowInner::from_pub(&self.window);
+        let commit_string: String = commit_string.into();
+        if !commit_string.is_empty() {
+            let mut event = KeyEvent {
+                event_type: KeyEventType::KeyPressed,
+                text: commit_string.into(),
+                modifiers: Default::default(),
+            };
+            runtime_window.process_key_input(&event);
+            event.event_type = KeyEventType::KeyReleased;
+            runtime_window.process_key_input(&event);
+        }
+        let preedit_string: String = preedit_string.into();
+        runtime_window.process_key_input(&KeyEvent {
+            event_type: KeyEventType::UpdateComposition,
+            text: preedit_string.into(),
+            modifiers: Default::default(),
+        });
+        timer_event();
+    }
+
     fn close_popup(&self) {
         WindowInner::from_pub(&self.window).close_popup();
     }
@@ -1405,6 +1727,11 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
         let widget_ptr = self.widget_ptr();
         let title: qttypes::QString = window_item.title().as_str().into();
         let no_frame = window_item.no_frame();
+        let always_on_top = window_item.always_on_top();
+        let resizable = window_item.resizable();
+        let fullscreen = window_item.fullscreen();
+        let maximized = window_item.maximized();
+        let minimized = window_item.minimized();
         let mut size = qttypes::QSize {
             width: window_item.width().ceil() as _,
             height: window_item.height().ceil() as _,
@@ -1435,11 +1762,33 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
             }
         };
 
-        cpp! {unsafe [widget_ptr as "QWidget*",  title as "QString", size as "QSize", background as "QRgb", no_frame as "bool"] {
-            if (size != widget_ptr->size()) {
+        cpp! {unsafe [widget_ptr as "QWidget*",  title as "QString", size as "QSize", background as "QRgb", no_frame as "bool",
+                always_on_top as "bool", resizable as "bool", fullscreen as "bool", maximized as "bool", minimized as "bool"] {
+            Qt::WindowStates state = widget_ptr->windowState() & ~(Qt::WindowMinimized | Qt::WindowMaximized | Qt::WindowFullScreen);
+            if (minimized)
+                state |= Qt::WindowMinimized;
+            if (maximized)
+                state |= Qt::WindowMaximized;
+            if (fullscreen)
+                state |= Qt::WindowFullScreen;
+            if (state != widget_ptr->windowState()) {
+                widget_ptr->setWindowState(state);
+            }
+            if (size != widget_ptr->size() && !(state & (Qt::WindowMaximized | Qt::WindowFullScreen))) {
                 widget_ptr->resize(size.expandedTo({1, 1}));
             }
+            if (!resizable) {
+                // apply_geometry_constraint is called before this function and will restore the constraints
+                widget_ptr->setFixedSize(widget_ptr->size());
+            }
             widget_ptr->setWindowFlag(Qt::FramelessWindowHint, no_frame);
+            if (widget_ptr->windowFlags().testFlag(Qt::WindowStaysOnTopHint) != always_on_top) {
+                // Changing the flags hides the window
+                bool visible = widget_ptr->isVisible();
+                widget_ptr->setWindowFlag(Qt::WindowStaysOnTopHint, always_on_top);
+                if (visible)
+                    widget_ptr->show();
+            }
             widget_ptr->setWindowTitle(title);
             auto pal = widget_ptr->palette();
 
@@ -1549,6 +1898,39 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         }};
     }
 
+    fn show_virtual_keyboard(&self, it: i_slint_core::items::InputType) {
+        let widget_ptr = self.widget_ptr();
+        let digits_only = matches!(it, InputType::Number);
+        let formatted_numbers_only = matches!(it, InputType::Decimal);
+        cpp! {unsafe [widget_ptr as "QWidget*", digits_only as "bool", formatted_numbers_only as "bool"] {
+            Qt::InputMethodHints hints = Qt::ImhNone;
+            if (digits_only)
+                hints |= Qt::ImhDigitsOnly;
+            if (formatted_numbers_only)
+                hints |= Qt::ImhFormattedNumbersOnly;
+            widget_ptr->setInputMethodHints(hints);
+            widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, true);
+            QGuiApplication::inputMethod()->update(Qt::ImEnabled | Qt::ImHints);
+        }};
+    }
+
+    fn hide_virtual_keyboard(&self) {
+        let widget_ptr = self.widget_ptr();
+        cpp! {unsafe [widget_ptr as "QWidget*"] {
+            widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, false);
+            QGuiApplication::inputMethod()->update(Qt::ImEnabled);
+        }};
+    }
+
+    fn set_ime_position(&self, position: i_slint_core::api::LogicalPosition) {
+        let widget_ptr = self.widget_ptr();
+        let position = qttypes::QPointF { x: position.x as _, y: position.y as _ };
+        cpp! {unsafe [widget_ptr as "SlintWidget*", position as "QPointF"] {
+            widget_ptr->imeCursorRect = QRectF(position, QSizeF(1, 0));
+            QGuiApplication::inputMethod()->update(Qt::ImCursorRectangle);
+        }};
+    }
+
     fn renderer(&self) -> &dyn Renderer {
         self
     }
@@ -1615,6 +1997,104 @@ fn text_size(
         get_font(font_request).text_size(text, max_width)
     }
 
+    fn styled_text_size(
+        &self,
+        font_request: i_slint_core::graphics::FontRequest,
+        styled_text: &StyledText,
+        max_width: Option<f32>,
+        line_height: Option<f32>,
+        max_lines: Option<usize>,
+        _scale_factor: f32,
+    ) -> Size {
+        let font = get_font(font_request);
+        if styled_text.spans.len() <= 1
+            && !styled_text.has_font_runs()
+            && line_height.is_none()
+            && max_lines.is_none()
+        {
+            return font.text_size(styled_text.text.as_str(), max_width);
+        }
+        let line_height: f64 = line_height.unwrap_or_default() as _;
+        let max_lines = max_lines.unwrap_or_default() as i32;
+        let mut string = qttypes::QString::from(styled_text.text.as_str());
+        let formats = styled_text_formats(styled_text);
+        let rect = qttypes::QRectF {
+            width: max_width.unwrap_or(f32::MAX) as _,
+            height: f32::MAX as _,
+            ..Default::default()
+        };
+        let flags = match max_width {
+            Some(_) => key_generated::Qt_TextFlag_TextWordWrap,
+            None => 0,
+        };
+        let size = cpp! { unsafe [font as "QFont", mut string as "QString", formats as "QVector<QTextLayout::FormatRange>", flags as "int", rect as "QRectF", line_height as "qreal", max_lines as "int"]
+                -> qttypes::QSizeF as "QSizeF" {
+            string.replace(QChar('\n'), QChar::LineSeparator);
+            QTextLayout layout(string, font);
+            do_styled_text_layout(layout, formats, flags, rect, line_height, max_lines);
+            qreal width = 0;
+            qreal height = 0;
+            for (int i = 0; i < layout.lineCount(); ++i) {
+                auto line = layout.lineAt(i);
+                width = qMax(width, line.naturalTextWidth());
+                height = line_height > 0 ? (i + 1) * line_height : line.y() + line.height();
+            }
+            return QSizeF(width, height);
+        }};
+        Size::new(size.width as _, size.height as _)
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let rect: qttypes::QRectF = get_geometry!(items::Text, text);
+        let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
+        let font: QFont = get_font(text.font_request(WindowInner::from_pub(&self.window)));
+        let styled_text = text.styled_text();
+        let string = qttypes::QString::from(styled_text.text.as_str());
+        let formats = styled_text_formats(&styled_text);
+        let line_height: f64 = text.explicit_line_height().unwrap_or_default() as _;
+        let max_lines = text.max_line_count().unwrap_or_default() as i32;
+        let flags = match text.horizontal_alignment() {
+            TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
+            TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
+            TextHorizontalAlignment::Right => key_generated::Qt_AlignmentFlag_AlignRight,
+        } | match text.vertical_alignment() {
+            TextVerticalAlignment::Top => key_generated::Qt_AlignmentFlag_AlignTop,
+            TextVerticalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignVCenter,
+            TextVerticalAlignment::Bottom => key_generated::Qt_AlignmentFlag_AlignBottom,
+        } | match text.wrap() {
+            TextWrap::NoWrap => 0,
+            TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+        };
+        let offset = cpp! { unsafe [font as "QFont", string as "QString", formats as "QVector<QTextLayout::FormatRange>", pos as "QPointF", flags as "int", rect as "QRectF", line_height as "qreal", max_lines as "int"] -> i32 as "int" {
+            // we need to do the \n replacement in a copy because the original need to be kept to know the utf8 offset
+            auto copy = string;
+            copy.replace(QChar('\n'), QChar::LineSeparator);
+            QTextLayout layout(copy, font);
+            do_styled_text_layout(layout, formats, flags, rect, line_height, max_lines);
+            QPointF line_pos = pos - layout.position();
+            for (int i = 0; i < layout.lineCount(); ++i) {
+                auto line = layout.lineAt(i);
+                if (line_pos.y() < line.y() || line_pos.y() >= line.y() + line.height())
+                    continue;
+                if (line_pos.x() < line.x() || line_pos.x() >= line.x() + line.naturalTextWidth())
+                    return -1;
+                int cur = line.xToCursor(line_pos.x() - line.x(), QTextLine::CursorOnCharacter);
+                // convert to an utf8 pos;
+                return QStringView(string).left(cur).toUtf8().size();
+            }
+            return -1;
+        }};
+        if offset >= 0 {
+            Some(offset as usize)
+        } else {
+            None
+        }
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         text_input: Pin<&i_slint_core::items::TextInput>,
@@ -1642,9 +2122,10 @@ fn text_input_byte_offset_for_position(
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let single_line: bool = text_input.single_line();
+        let line_height: f64 = text_input.explicit_line_height().unwrap_or_default() as _;
         let is_password: bool = matches!(text_input.input_type(), InputType::Password);
         cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int",
-                rect as "QRectF", single_line as "bool", is_password as "bool"] -> usize as "size_t" {
+                rect as "QRectF", single_line as "bool", line_height as "qreal", is_password as "bool"] -> usize as "size_t" {
             // we need to do the \n replacement in a copy because the original need to be kept to know the utf8 offset
             auto copy = string;
             if (is_password) {
@@ -1654,7 +2135,7 @@ fn text_input_byte_offset_for_position(
                 copy.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(copy, font);
-            auto line = do_text_layout(layout, flags, rect, pos.y());
+            auto line = do_text_layout(layout, flags, rect, line_height, pos.y());
             if (line < 0 || layout.lineCount() <= line)
                 return string.toUtf8().size();
             QTextLine textLine = layout.lineAt(line);
@@ -1701,13 +2182,14 @@ fn text_input_cursor_rect_for_byte_offset(
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let single_line: bool = text_input.single_line();
-        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool"]
+        let line_height: f64 = text_input.explicit_line_height().unwrap_or_default() as _;
+        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool", line_height as "qreal"]
                 -> qttypes::QPointF as "QPointF" {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(string, font);
-            do_text_layout(layout, flags, rect);
+            do_text_layout(layout, flags, rect, line_height);
 
             QTextLine textLine = layout.lineForTextPosition(offset);
             if (!textLine.isValid())
@@ -1773,8 +2255,10 @@ fn get_font(request: FontRequest) -> QFont {
     let pixel_size: f32 = request.pixel_size.unwrap_or(0.);
     let weight: i32 = request.weight.unwrap_or(0);
     let letter_spacing: f32 = request.letter_spacing.unwrap_or_default();
-    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", letter_spacing as "float"] -> QFont as "QFont" {
+    let italic = request.italic;
+    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", letter_spacing as "float", italic as "bool"] -> QFont as "QFont" {
         QFont f;
+        f.setItalic(italic);
         if (!family.isEmpty())
             f.setFamily(family);
         if (pixel_size > 0)
@@ -1800,9 +2284,46 @@ fn get_font(request: FontRequest) -> QFont {
     })
 }
 
+cpp_class!(unsafe struct QTextFormatRanges as "QVector<QTextLayout::FormatRange>");
+
+/// Returns the text formats for the spans of the styled text
+fn styled_text_formats(styled_text: &StyledText) -> QTextFormatRanges {
+    let mut formats = QTextFormatRanges::default();
+    let formats_ptr = &mut formats;
+    let mut start: i32 = 0;
+    for span in &styled_text.spans {
+        let length = styled_text.text[span.range.clone()].encode_utf16().count() as i32;
+        if !span.has_default_font() || span.color.is_some() {
+            let bold = span.bold;
+            let italic = span.italic;
+            let has_color = span.color.is_some();
+            let color: u32 = span.color.map_or(0, |c| c.as_argb_encoded());
+            cpp! { unsafe [formats_ptr as "QVector<QTextLayout::FormatRange>*", start as "int", length as "int", bold as "bool", italic as "bool", has_color as "bool", color as "QRgb"] {
+                QTextCharFormat fmt;
+                if (bold)
+                    fmt.setFontWeight(QFont::Bold);
+                if (italic)
+                    fmt.setFontItalic(true);
+                if (has_color)
+                    fmt.setForeground(QColor::fromRgba(color));
+                formats_ptr->append(QTextLayout::FormatRange{ start, length, fmt });
+            }}
+        }
+        start += length;
+    }
+    formats
+}
+
 cpp_class! {pub unsafe struct QFont as "QFont"}
 
 impl QFont {
+    fn set_decorations(&mut self, underline: bool, strikethrough: bool) {
+        cpp!(unsafe [self as "QFont*", underline as "bool", strikethrough as "bool"] {
+            self->setUnderline(underline);
+            self->setStrikeOut(strikethrough);
+        });
+    }
+
     fn text_size(&self, text: &str, max_width: Option<f32>) -> i_slint_core::graphics::Size {
         let string = qttypes::QString::from(text);
         let mut r = qttypes::QRectF::default();
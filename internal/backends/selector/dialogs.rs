@@ -0,0 +1,139 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Shows the file and message dialogs with the platform.
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use core::cell::Cell;
+use i_slint_core::api::{
+    DialogError, FileDialogOptions, MessageDialogButtons, MessageDialogOptions, MessageDialogResult,
+};
+use i_slint_core::items::ItemRc;
+use i_slint_core::SharedString;
+
+/// Calls `native` with a callback that forwards the result to `callback`. If `native` returns an
+/// error without having invoked it, `callback` is passed to `fallback` instead.
+pub fn show_with_fallback<T: 'static>(
+    callback: Box<dyn FnOnce(T)>,
+    native: impl FnOnce(Box<dyn FnOnce(T)>) -> Result<(), DialogError>,
+    fallback: impl FnOnce(Box<dyn FnOnce(T)>),
+) {
+    let callback = Rc::new(Cell::new(Some(callback)));
+    let forward = {
+        let callback = callback.clone();
+        Box::new(move |result| {
+            if let Some(callback) = callback.take() {
+                callback(result)
+            }
+        })
+    };
+    if native(forward).is_err() {
+        if let Some(callback) = callback.take() {
+            fallback(callback);
+        }
+    }
+}
+
+/// The result of a message dialog that is closed without clicking one of its buttons
+pub fn dismissed_message_dialog_result(buttons: MessageDialogButtons) -> MessageDialogResult {
+    // This is like clicking "No" when there is no cancel button
+    match buttons {
+        MessageDialogButtons::YesNo => MessageDialogResult::No,
+        _ => MessageDialogResult::Cancel,
+    }
+}
+
+/// Shows the native dialog of the platform to choose an existing file. If the platform doesn't
+/// provide one, `callback` is invoked right away as if the user cancelled the dialog.
+pub fn open_file_dialog(
+    options: &FileDialogOptions,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) {
+    show_with_fallback(
+        callback,
+        |callback| crate::with_platform(|b| b.open_file_dialog(options, callback)),
+        |callback| callback(None),
+    )
+}
+
+/// Shows the native dialog of the platform to choose the path of a file to save. If the platform
+/// doesn't provide one, `callback` is invoked right away as if the user cancelled the dialog.
+pub fn save_file_dialog(
+    options: &FileDialogOptions,
+    callback: Box<dyn FnOnce(Option<SharedString>)>,
+) {
+    show_with_fallback(
+        callback,
+        |callback| crate::with_platform(|b| b.save_file_dialog(options, callback)),
+        |callback| callback(None),
+    )
+}
+
+/// Shows the native message box of the platform. If the platform doesn't provide one, `callback`
+/// is invoked right away as if the user closed the dialog.
+pub fn show_message_dialog(
+    options: &MessageDialogOptions,
+    callback: Box<dyn FnOnce(MessageDialogResult)>,
+) {
+    let buttons = options.buttons;
+    show_with_fallback(
+        callback,
+        |callback| crate::with_platform(|b| b.show_message_dialog(options, callback)),
+        |callback| callback(dismissed_message_dialog_result(buttons)),
+    )
+}
+
+/// Shows the dialog of a `FileDialog` element with [`open_file_dialog`] or [`save_file_dialog`]
+pub fn show_file_dialog_element(item_rc: &ItemRc) {
+    i_slint_core::items::show_file_dialog(item_rc, |options, save, callback| {
+        if save {
+            save_file_dialog(options, callback)
+        } else {
+            open_file_dialog(options, callback)
+        }
+    })
+}
+
+/// Shows the dialog of a `MessageDialog` element with [`show_message_dialog`]
+pub fn show_message_dialog_element(item_rc: &ItemRc) {
+    i_slint_core::items::show_message_dialog(item_rc, show_message_dialog)
+}
+
+#[test]
+fn test_show_with_fallback() {
+    let result = Rc::new(Cell::new(None));
+
+    // The platform shows the dialog: the fallback is not used
+    let r = result.clone();
+    show_with_fallback(
+        Box::new(move |value| r.set(Some(value))),
+        |callback| {
+            callback(1);
+            Ok(())
+        },
+        |_| panic!("the fallback must not be used"),
+    );
+    assert_eq!(result.take(), Some(1));
+
+    // The platform doesn't support dialogs: the fallback gets the callback
+    let r = result.clone();
+    show_with_fallback(
+        Box::new(move |value| r.set(Some(value))),
+        |_| Err(DialogError::Unsupported),
+        |callback| callback(2),
+    );
+    assert_eq!(result.take(), Some(2));
+
+    // The callback is invoked once, even if the platform reports an error after invoking it
+    let r = result.clone();
+    show_with_fallback(
+        Box::new(move |value| r.set(Some(value))),
+        |callback| {
+            callback(3);
+            Err(DialogError::Unsupported)
+        },
+        |_| panic!("the fallback must not be used"),
+    );
+    assert_eq!(result.take(), Some(3));
+}
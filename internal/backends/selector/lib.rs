@@ -11,6 +11,9 @@
 use core::pin::Pin;
 use i_slint_core::platform::Platform;
 
+mod dialogs;
+pub use dialogs::*;
+
 cfg_if::cfg_if! {
     if #[cfg(all(feature = "i-slint-backend-qt", not(no_qt)))] {
         use i_slint_backend_qt as default_backend;
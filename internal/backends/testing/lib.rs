@@ -81,6 +81,18 @@ fn text_size(
         Size::new(text.len() as f32 * 10., 10.)
     }
 
+    fn styled_text_size(
+        &self,
+        _font_request: i_slint_core::graphics::FontRequest,
+        styled_text: &i_slint_core::styled_text::StyledText,
+        _max_width: Option<f32>,
+        line_height: Option<f32>,
+        _max_lines: Option<usize>,
+        _scale_factor: f32,
+    ) -> Size {
+        Size::new(styled_text.text.len() as f32 * 10., line_height.unwrap_or(10.))
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         _text_input: Pin<&i_slint_core::items::TextInput>,
@@ -97,6 +109,22 @@ fn text_input_cursor_rect_for_byte_offset(
         Default::default()
     }
 
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        // Consistent with text_size: every byte is 10 pixels wide, on a single line
+        if pos.x < 0. || pos.y < 0. || pos.y >= 10. {
+            return None;
+        }
+        let styled_text = text.styled_text();
+        let byte_offset = (pos.x / 10.) as usize;
+        (byte_offset < styled_text.text.len()).then(|| {
+            (0..=byte_offset).rev().find(|i| styled_text.text.is_char_boundary(*i)).unwrap()
+        })
+    }
+
     fn register_font_from_memory(
         &self,
         _data: &'static [u8],
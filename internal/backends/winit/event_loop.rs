@@ -356,6 +356,16 @@ fn key_event(
             event.event_type = KeyEventType::KeyReleased;
             runtime_window.process_key_input(&event);
         }
+        WindowEvent::Ime(winit::event::Ime::Preedit(string, _)) => {
+            let modifiers = window.current_keyboard_modifiers().get();
+            let event = key_event(KeyEventType::UpdateComposition, string.into(), modifiers);
+            runtime_window.process_key_input(&event);
+        }
+        WindowEvent::Ime(winit::event::Ime::Disabled) => {
+            let modifiers = window.current_keyboard_modifiers().get();
+            let event = key_event(KeyEventType::UpdateComposition, Default::default(), modifiers);
+            runtime_window.process_key_input(&event);
+        }
         WindowEvent::ModifiersChanged(state) => {
             // To provide an easier cross-platform behavior, we map the command key to control
             // on macOS, and control to meta.
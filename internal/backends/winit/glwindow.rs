@@ -232,6 +232,15 @@ fn input_method_focused(&self) -> bool {
     fn resize_event(&self, size: winit::dpi::PhysicalSize<u32>) {
         if let Some(mapped_window) = self.borrow_mapped_window() {
             self.window().set_size(corelib::api::PhysicalSize::new(size.width, size.height));
+            // The window may have been maximized or restored by the user through the window manager
+            if let Some(window_item) = WindowInner::from_pub(self.window()).window_item() {
+                mapped_window.canvas.with_window_handle(|winit_window| {
+                    window_item.as_pin_ref().update_state_property(
+                        corelib::items::WindowItem::FIELD_OFFSETS.maximized,
+                        winit_window.is_maximized(),
+                    );
+                });
+            }
             mapped_window.canvas.resize_event()
         }
     }
@@ -287,11 +296,38 @@ fn apply_window_properties(&self, window_item: Pin<&i_slint_core::items::WindowI
 
         let mut must_resize = false;
 
-        self.with_window_handle(&mut |winit_window| {
+        let (constraints_horizontal, constraints_vertical) = self.constraints();
+        let mapped_window = self.borrow_mapped_window().unwrap();
+
+        mapped_window.canvas.with_window_handle(|winit_window| {
             winit_window.set_window_icon(icon_to_winit(window_item.icon()));
             winit_window.set_title(&window_item.title());
-            winit_window
-                .set_decorations(!window_item.no_frame() || winit_window.fullscreen().is_some());
+
+            let fullscreen = window_item.fullscreen();
+            if fullscreen != winit_window.fullscreen().is_some() {
+                winit_window.set_fullscreen(
+                    fullscreen.then(|| winit::window::Fullscreen::Borderless(None)),
+                );
+            }
+            winit_window.set_decorations(!window_item.no_frame() || fullscreen);
+
+            let maximized = window_item.maximized();
+            if maximized != winit_window.is_maximized() {
+                winit_window.set_maximized(maximized);
+            }
+            let minimized = window_item.minimized();
+            if mapped_window.minimized.replace(minimized) != minimized {
+                winit_window.set_minimized(minimized);
+            }
+            let always_on_top = window_item.always_on_top();
+            if mapped_window.always_on_top.replace(always_on_top) != always_on_top {
+                winit_window.set_always_on_top(always_on_top);
+            }
+            winit_window.set_resizable(
+                window_item.resizable()
+                    && (constraints_horizontal.min < constraints_horizontal.max
+                        || constraints_vertical.min < constraints_vertical.max),
+            );
 
             if width <= 0. || height <= 0. {
                 must_resize = true;
@@ -316,11 +352,13 @@ fn apply_window_properties(&self, window_item: Pin<&i_slint_core::items::WindowI
                 // If we're in fullscreen state, don't try to resize the window but maintain the surface
                 // size we've been assigned to from the windowing system. Weston/Wayland don't like it
                 // when we create a surface that's bigger than the screen due to constraints (#532).
-                if winit_window.fullscreen().is_none() {
+                // Same when maximized, as resizing would restore the window.
+                if winit_window.fullscreen().is_none() && !winit_window.is_maximized() {
                     winit_window.set_inner_size(winit::dpi::LogicalSize::new(width, height));
                 }
             }
         });
+        drop(mapped_window);
 
         if must_resize {
             let win = self.window();
@@ -411,6 +449,8 @@ fn show(&self) {
                     .with_resizable(window_item.height() <= 0 as _ || window_item.width() <= 0 as _)
                     .with_decorations(!window_item.no_frame())
                     .with_window_icon(icon_to_winit(window_item.icon()))
+                    .with_maximized(window_item.maximized())
+                    .with_always_on_top(window_item.always_on_top())
             } else {
                 window_builder.with_title("Slint Window".to_string())
             };
@@ -447,7 +487,19 @@ fn show(&self) {
                 layout_info_v.preferred_bounded(),
             );
 
-            let window_builder = if std::env::var("SLINT_FULLSCREEN").is_ok() {
+            let fullscreen = if let Some(window_item) = runtime_window.window_item() {
+                if std::env::var("SLINT_FULLSCREEN").is_ok() {
+                    window_item.as_pin_ref().update_state_property(
+                        corelib::items::WindowItem::FIELD_OFFSETS.fullscreen,
+                        true,
+                    );
+                }
+                window_item.as_pin_ref().fullscreen()
+            } else {
+                std::env::var("SLINT_FULLSCREEN").is_ok()
+            };
+
+            let window_builder = if fullscreen {
                 window_builder.with_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
             } else {
                 if layout_info_h.min >= 1. || layout_info_v.min >= 1. {
@@ -505,9 +557,16 @@ fn show(&self) {
                 winit_window.id()
             });
 
+            let always_on_top = runtime_window
+                .window_item()
+                .map_or(false, |item| item.as_pin_ref().always_on_top());
+
             self_.map_state.replace(GraphicsWindowBackendState::Mapped(MappedWindow {
                 canvas,
                 constraints: Default::default(),
+                // winit can't create a minimized window, apply_window_properties will minimize it
+                minimized: Cell::new(false),
+                always_on_top: Cell::new(always_on_top),
             }));
 
             crate::event_loop::register_window(id, self_.self_weak.upgrade().unwrap());
@@ -574,13 +633,13 @@ fn renderer(&self) -> &dyn i_slint_core::renderer::Renderer {
     }
 
     #[cfg(target_arch = "wasm32")]
-    fn show_virtual_keyboard(&self, _it: corelib::items::InputType) {
+    fn show_virtual_keyboard(&self, it: corelib::items::InputType) {
         let mut vkh = self.virtual_keyboard_helper.borrow_mut();
         let h = vkh.get_or_insert_with(|| {
             let canvas = self.borrow_mapped_window().unwrap().canvas.html_canvas_element().clone();
             super::wasm_input_helper::WasmInputHelper::new(self.self_weak.clone(), canvas)
         });
-        h.show();
+        h.show(it);
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -590,6 +649,23 @@ fn hide_virtual_keyboard(&self) {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_virtual_keyboard(&self, _it: corelib::items::InputType) {
+        // Receive the Ime events while a text input has the focus
+        self.with_window_handle(&mut |winit_window| winit_window.set_ime_allowed(true));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn hide_virtual_keyboard(&self) {
+        self.with_window_handle(&mut |winit_window| winit_window.set_ime_allowed(false));
+    }
+
+    fn set_ime_position(&self, position: corelib::api::LogicalPosition) {
+        self.with_window_handle(&mut |winit_window| {
+            winit_window.set_ime_position(winit::dpi::LogicalPosition::new(position.x, position.y))
+        });
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -650,6 +726,10 @@ fn drop(&mut self) {
 struct MappedWindow<Renderer: WinitCompatibleRenderer> {
     canvas: Renderer::Canvas,
     constraints: Cell<(corelib::layout::LayoutInfo, corelib::layout::LayoutInfo)>,
+    /// The last value of the minimized and always-on-top properties applied to the window,
+    /// as winit can't query them
+    minimized: Cell<bool>,
+    always_on_top: Cell<bool>,
 }
 
 enum GraphicsWindowBackendState<Renderer: WinitCompatibleRenderer> {
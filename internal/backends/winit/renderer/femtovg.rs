@@ -215,7 +215,33 @@ fn text_size(
         max_width: Option<Coord>,
         scale_factor: f32,
     ) -> Size {
-        crate::renderer::femtovg::fonts::text_size(&font_request, scale_factor, text, max_width)
+        crate::renderer::femtovg::fonts::text_size(
+            &font_request,
+            scale_factor,
+            text,
+            max_width,
+            None,
+            None,
+        )
+    }
+
+    fn styled_text_size(
+        &self,
+        font_request: i_slint_core::graphics::FontRequest,
+        styled_text: &i_slint_core::styled_text::StyledText,
+        max_width: Option<Coord>,
+        line_height: Option<Coord>,
+        max_lines: Option<usize>,
+        scale_factor: f32,
+    ) -> Size {
+        crate::renderer::femtovg::fonts::text_size(
+            &font_request,
+            scale_factor,
+            &styled_text.text,
+            max_width,
+            line_height,
+            max_lines,
+        )
     }
 
     fn text_input_byte_offset_for_position(
@@ -272,6 +298,7 @@ fn text_input_byte_offset_for_position(
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
             text_input.single_line(),
+            (text_input.explicit_line_height().map(|h| h * scale_factor), None),
             paint,
             |line_text, line_pos, start, metrics| {
                 if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
@@ -297,6 +324,72 @@ fn text_input_byte_offset_for_position(
         }
     }
 
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let window_adapter = self.window_adapter_weak.upgrade()?;
+        let window = WindowInner::from_pub(window_adapter.window());
+
+        let scale_factor = window.scale_factor();
+        let pos = pos * scale_factor;
+
+        let width = text.width() * scale_factor;
+        let height = text.height() * scale_factor;
+        if width <= 0. || height <= 0. {
+            return None;
+        }
+
+        let styled_text = text.styled_text();
+        let font_request = text.font_request(window);
+        let font = crate::renderer::femtovg::fonts::FONT_CACHE.with(|cache| {
+            cache.borrow_mut().font(font_request.clone(), scale_factor, &styled_text.text)
+        });
+        let span_fonts =
+            crate::renderer::femtovg::fonts::span_fonts(&styled_text, &font_request, scale_factor);
+
+        let paint = font.init_paint(text.letter_spacing() * scale_factor, Default::default());
+        let text_context = crate::renderer::femtovg::fonts::FONT_CACHE
+            .with(|cache| cache.borrow().text_context.clone());
+        let font_height = text_context.measure_font(paint).unwrap().height();
+
+        let mut result = None;
+        crate::renderer::femtovg::fonts::layout_text_lines(
+            &styled_text.text,
+            &font,
+            Size::new(width, height),
+            (text.horizontal_alignment(), text.vertical_alignment()),
+            text.wrap(),
+            text.overflow(),
+            false,
+            (text.explicit_line_height().map(|h| h * scale_factor), text.max_line_count()),
+            paint,
+            |line_text, line_pos, start, _| {
+                if !(line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
+                    return;
+                }
+                crate::renderer::femtovg::fonts::for_each_styled_line_part(
+                    &styled_text,
+                    &span_fonts,
+                    line_text.trim_end(),
+                    start,
+                    paint,
+                    |_, byte_offset, x, _, metrics| {
+                        let mut glyph_x = line_pos.x + x;
+                        for glyph in &metrics.glyphs {
+                            if (glyph_x..(glyph_x + glyph.advance_x)).contains(&pos.x) {
+                                result = Some(byte_offset + glyph.byte_index);
+                            }
+                            glyph_x += glyph.advance_x;
+                        }
+                    },
+                );
+            },
+        );
+        result
+    }
+
     fn text_input_cursor_rect_for_byte_offset(
         &self,
         text_input: Pin<&i_slint_core::items::TextInput>,
@@ -340,6 +433,7 @@ fn text_input_cursor_rect_for_byte_offset(
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
             text_input.single_line(),
+            (text_input.explicit_line_height().map(|h| h * scale_factor), None),
             paint,
             |line_text, line_pos, start, metrics| {
                 if (start..=(start + line_text.len())).contains(&byte_offset) {
@@ -7,6 +7,7 @@
 use i_slint_core::graphics::euclid;
 use i_slint_core::graphics::{FontRequest, Point, Size};
 use i_slint_core::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use i_slint_core::styled_text::StyledText;
 use i_slint_core::{SharedString, SharedVector};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -67,6 +68,7 @@ pub fn register_font_from_path(_path: &std::path::Path) -> Result<(), Box<dyn st
 struct FontCacheKey {
     family: SharedString,
     weight: i32,
+    italic: bool,
 }
 
 #[derive(Clone)]
@@ -85,14 +87,24 @@ pub fn init_paint(&self, letter_spacing: f32, mut paint: femtovg::Paint) -> femt
         paint
     }
 
-    pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>) -> Size {
+    /// Returns the size of the text. The lines are `line_height` high (the height of the font if None)
+    /// and only the first `max_lines` lines are measured.
+    pub fn text_size(
+        &self,
+        letter_spacing: f32,
+        text: &str,
+        max_width: Option<f32>,
+        line_height: Option<f32>,
+        max_lines: Option<usize>,
+    ) -> Size {
         let paint = self.init_paint(letter_spacing, femtovg::Paint::default());
         let font_metrics = self.text_context.measure_font(paint).unwrap();
+        let max_lines = max_lines.unwrap_or(usize::MAX);
         let mut lines = 0;
         let mut width = 0.;
         let mut start = 0;
         if let Some(max_width) = max_width {
-            while start < text.len() {
+            while start < text.len() && lines < max_lines {
                 let index = self.text_context.break_text(max_width, &text[start..], paint).unwrap();
                 if index == 0 {
                     break;
@@ -105,13 +117,13 @@ pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>)
                 width = measure.width().max(width);
             }
         } else {
-            for line in text.lines() {
+            for line in text.lines().take(max_lines) {
                 let measure = self.text_context.measure_text(0., 0., line, paint).unwrap();
                 lines += 1;
                 width = measure.width().max(width);
             }
         }
-        euclid::size2(width, lines as f32 * font_metrics.height())
+        euclid::size2(width, lines as f32 * line_height.unwrap_or_else(|| font_metrics.height()))
     }
 }
 
@@ -120,11 +132,19 @@ pub(crate) fn text_size(
     scale_factor: f32,
     text: &str,
     max_width: Option<f32>,
+    line_height: Option<f32>,
+    max_lines: Option<usize>,
 ) -> Size {
     let font =
         FONT_CACHE.with(|cache| cache.borrow_mut().font(font_request.clone(), scale_factor, text));
     let letter_spacing = font_request.letter_spacing.unwrap_or_default();
-    font.text_size(letter_spacing, text, max_width.map(|x| x * scale_factor)) / scale_factor
+    font.text_size(
+        letter_spacing,
+        text,
+        max_width.map(|x| x * scale_factor),
+        line_height.map(|x| x * scale_factor),
+        max_lines,
+    ) / scale_factor
 }
 
 #[derive(Copy, Clone)]
@@ -244,6 +264,7 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         let cache_key = FontCacheKey {
             family: request.family.clone().unwrap_or_default(),
             weight: request.weight.unwrap(),
+            italic: request.italic,
         };
 
         if let Some(loaded_font) = self.loaded_fonts.get(&cache_key) {
@@ -259,6 +280,7 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         let query = fontdb::Query {
             families: &[family],
             weight: fontdb::Weight(request.weight.unwrap() as u16),
+            style: if request.italic { fontdb::Style::Italic } else { fontdb::Style::Normal },
             ..Default::default()
         };
 
@@ -410,6 +432,7 @@ fn font_fallbacks_for_request(
             weight: _request.weight,
             pixel_size: _request.pixel_size,
             letter_spacing: _request.letter_spacing,
+            italic: _request.italic,
         })
         .filter(|request| self.is_known_family(request))
         .collect::<Vec<_>>()
@@ -486,6 +509,7 @@ fn get_paragraph_reading_direction(
                     weight: request.weight,
                     pixel_size: request.pixel_size,
                     letter_spacing: request.letter_spacing,
+                    italic: request.italic,
                 };
                 if self.is_known_family(&fallback) {
                     fallback_fonts.push(fallback)
@@ -514,6 +538,7 @@ fn font_fallbacks_for_request(
                 weight: _request.weight,
                 pixel_size: _request.pixel_size,
                 letter_spacing: _request.letter_spacing,
+                italic: _request.italic,
             })
             .filter(|request| self.is_known_family(request))
             .collect()
@@ -531,6 +556,7 @@ fn font_fallbacks_for_request(
             weight: _request.weight,
             pixel_size: _request.pixel_size,
             letter_spacing: _request.letter_spacing,
+            italic: _request.italic,
         }]
         .iter()
         .filter(|request| self.is_known_family(request))
@@ -623,6 +649,81 @@ fn check_and_update_script_coverage(
     }
 }
 
+/// Returns the font of each span of the styled text, or None for the spans that are rendered with
+/// the font of the element itself.
+pub(crate) fn span_fonts(
+    styled_text: &StyledText,
+    font_request: &FontRequest,
+    scale_factor: f32,
+) -> Vec<Option<Font>> {
+    FONT_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        styled_text
+            .spans
+            .iter()
+            .map(|span| {
+                (!span.has_default_font()).then(|| {
+                    cache.font(
+                        span.font_request(font_request),
+                        scale_factor,
+                        &styled_text.text[span.range.clone()],
+                    )
+                })
+            })
+            .collect()
+    })
+}
+
+/// Splits a line produced by [`layout_text_lines`] for the text of `styled_text` into the parts
+/// covered by each span, and call `part_callback` for each part with:
+/// `(text, byte_offset, x, paint, metrics)`, where `x` is relative to the start of the line.
+/// `paint` is the paint for the element's font and color, it is adjusted for each span.
+pub(crate) fn for_each_styled_line_part(
+    styled_text: &StyledText,
+    span_fonts: &[Option<Font>],
+    line_text: &str,
+    line_start: usize,
+    paint: femtovg::Paint,
+    mut part_callback: impl FnMut(&str, usize, f32, femtovg::Paint, &femtovg::TextMetrics),
+) {
+    let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
+    let string = styled_text.text.as_str();
+    // An elided line ends with an ellipsis that is not part of the string
+    let line_len = if string[line_start..].starts_with(line_text) {
+        line_text.len()
+    } else {
+        line_text.len() - '…'.len_utf8()
+    };
+
+    let mut x = 0.;
+    let mut process_part = |text: &str, byte_offset: usize, part_paint: femtovg::Paint| {
+        let metrics = text_context.measure_text(0., 0., text, part_paint).unwrap();
+        part_callback(text, byte_offset, x, part_paint, &metrics);
+        x += metrics.width();
+    };
+
+    let mut last_paint = paint;
+    for (span, span_font) in styled_text.spans.iter().zip(span_fonts) {
+        let begin = span.range.start.max(line_start);
+        let end = span.range.end.min(line_start + line_len);
+        if begin >= end {
+            continue;
+        }
+        let mut span_paint = paint;
+        if let Some(color) = span.color {
+            span_paint.set_color(super::itemrenderer::to_femtovg_color(&color));
+        }
+        if let Some(font) = span_font {
+            span_paint = font.init_paint(paint.letter_spacing(), span_paint);
+        }
+        process_part(&string[begin..end], begin, span_paint);
+        last_paint = span_paint;
+    }
+    if line_len < line_text.len() {
+        process_part(&line_text[line_len..], line_start + line_len, last_paint);
+    }
+}
+
 /// Layout the given string in lines, and call the `layout_line` callback with the line to draw at position y.
 /// The signature of the `layout_line` function is: `(text, pos, start_index, line_metrics)`.
 /// start index is the starting byte of the text in the string.
@@ -635,6 +736,7 @@ pub(crate) fn layout_text_lines(
     wrap: TextWrap,
     overflow: TextOverflow,
     single_line: bool,
+    (line_height, max_lines): (Option<f32>, Option<usize>),
     paint: femtovg::Paint,
     mut layout_line: impl FnMut(&str, Point, usize, &femtovg::TextMetrics),
 ) -> f32 {
@@ -644,16 +746,21 @@ pub(crate) fn layout_text_lines(
     let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
     let font_metrics = text_context.measure_font(paint).unwrap();
     let font_height = font_metrics.height();
+    let line_height = line_height.unwrap_or(font_height);
+    // The glyphs are vertically centered in their line
+    let half_leading = (line_height - font_height) / 2.;
 
     let text_height = || {
         if single_line {
-            font_height
+            line_height
         } else {
             // Note: this is kind of doing twice the layout because text_size also does it
             font.text_size(
                 paint.letter_spacing(),
                 string,
                 if wrap { Some(max_width) } else { None },
+                Some(line_height),
+                max_lines,
             )
             .height
         }
@@ -670,7 +777,7 @@ pub(crate) fn layout_text_lines(
             }
             TextHorizontalAlignment::Right => max_width - f32::min(max_width, line_metrics.width()),
         };
-        layout_line(text, Point::new(x, y), start, line_metrics);
+        layout_line(text, Point::new(x, y + half_leading), start, line_metrics);
     };
 
     let baseline_y = match vertical_alignment {
@@ -680,8 +787,12 @@ pub(crate) fn layout_text_lines(
     };
     let mut y = baseline_y;
     let mut start = 0;
-    'lines: while start < string.len() && y + font_height <= max_height {
-        if wrap && (!elide || y + 2. * font_height <= max_height) {
+    let max_lines = max_lines.unwrap_or(usize::MAX);
+    let mut line_count = 0;
+    'lines: while start < string.len() && y + line_height <= max_height && line_count < max_lines {
+        line_count += 1;
+        let last_line = y + 2. * line_height > max_height || line_count == max_lines;
+        if wrap && (!elide || !last_line) {
             let index = text_context.break_text(max_width, &string[start..], paint).unwrap();
             if index == 0 {
                 // FIXME the word is too big to be shown, but we should still break, ideally
@@ -691,7 +802,7 @@ pub(crate) fn layout_text_lines(
             let line = &string[start..index];
             let text_metrics = text_context.measure_text(0., 0., line, paint).unwrap();
             process_line(line, y, start, &text_metrics);
-            y += font_height;
+            y += line_height;
             start = index;
         } else {
             let index = if single_line {
@@ -701,8 +812,7 @@ pub(crate) fn layout_text_lines(
             };
             let line = &string[start..index];
             let text_metrics = text_context.measure_text(0., 0., line, paint).unwrap();
-            let elide_last_line =
-                elide && index < string.len() && y + 2. * font_height > max_height;
+            let elide_last_line = elide && index < string.len() && last_line;
             if text_metrics.width() > max_width || elide_last_line {
                 let w = max_width
                     - if elide {
@@ -721,7 +831,7 @@ pub(crate) fn layout_text_lines(
                         } else {
                             process_line(txt, y, start, &text_metrics);
                         }
-                        y += font_height;
+                        y += line_height;
                         start = index;
                         continue 'lines;
                     }
@@ -729,13 +839,13 @@ pub(crate) fn layout_text_lines(
                 if elide_last_line {
                     let elided = format!("{}…", line);
                     process_line(&elided, y, start, &text_metrics);
-                    y += font_height;
+                    y += line_height;
                     start = index;
                     continue 'lines;
                 }
             }
             process_line(line, y, start, &text_metrics);
-            y += font_height;
+            y += line_height;
             start = index;
         }
     }
@@ -24,6 +24,14 @@ pub fn size(&self) -> Option<IntSize> {
             .ok()
     }
 
+    /// Returns true if the texture repeats itself when painted beyond its size
+    pub fn is_repeated(&self) -> bool {
+        self.canvas
+            .borrow()
+            .image_info(self.id)
+            .map_or(false, |info| info.flags().contains(femtovg::ImageFlags::REPEAT_X))
+    }
+
     pub fn as_render_target(&self) -> femtovg::RenderTarget {
         femtovg::RenderTarget::Image(self.id)
     }
@@ -86,11 +94,15 @@ pub fn new_from_image(
         canvas: &CanvasRc,
         target_size_for_scalable_source: Option<euclid::default::Size2D<u32>>,
         scaling: ImageRendering,
+        repeat: bool,
     ) -> Option<Rc<Self>> {
-        let image_flags = match scaling {
+        let mut image_flags = match scaling {
             ImageRendering::Smooth => femtovg::ImageFlags::empty(),
             ImageRendering::Pixelated => femtovg::ImageFlags::NEAREST,
         };
+        if repeat {
+            image_flags |= femtovg::ImageFlags::REPEAT_X | femtovg::ImageFlags::REPEAT_Y;
+        }
 
         let image_id = match image {
             #[cfg(target_arch = "wasm32")]
@@ -131,6 +143,7 @@ pub struct TextureCacheKey {
     source_key: ImageCacheKey,
     target_size_for_scalable_source: Option<euclid::default::Size2D<u32>>,
     gpu_image_flags: ImageRendering,
+    repeat: bool,
 }
 
 impl TextureCacheKey {
@@ -138,11 +151,13 @@ pub fn new(
         resource: &ImageInner,
         target_size_for_scalable_source: Option<euclid::default::Size2D<u32>>,
         gpu_image_flags: ImageRendering,
+        repeat: bool,
     ) -> Option<Self> {
         ImageCacheKey::new(resource).map(|source_key| Self {
             source_key,
             target_size_for_scalable_source,
             gpu_image_flags,
+            repeat,
         })
     }
 }
@@ -173,7 +188,26 @@ pub(crate) fn lookup_image_in_cache_or_create(
     }
 
     pub(crate) fn drain(&mut self) {
-        self.0.retain(|_, cached_image| {
+        // The frames of an animated image are only shown one at a time, so keep all
+        // the frames of an animation as long as one of them is in use.
+        let animations_in_use = self
+            .0
+            .iter()
+            .filter_map(|(key, cached_image)| match key.source_key {
+                ImageCacheKey::AnimationFrame(animation_id, _)
+                    if Rc::strong_count(cached_image) > 1 =>
+                {
+                    Some(animation_id)
+                }
+                _ => None,
+            })
+            .collect::<std::collections::HashSet<_>>();
+        self.0.retain(|key, cached_image| {
+            if let ImageCacheKey::AnimationFrame(animation_id, _) = key.source_key {
+                if animations_in_use.contains(&animation_id) {
+                    return true;
+                }
+            }
             // * Retain images that are used by elements, so that they can be effectively
             // shared (one image element refers to foo.png, another element is created
             // and refers to the same -> share).
@@ -11,8 +11,8 @@
 use i_slint_core::graphics::{Image, IntRect, IntSize, Point, Rect, Size};
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::{
-    self, Clip, FillRule, ImageFit, ImageRendering, InputType, Item, ItemRc, Layer, Opacity,
-    RenderingResult,
+    self, Clip, Effect, FillRule, ImageFit, ImageRendering, InputType, Item, ItemRc, Layer,
+    Opacity, RenderingResult,
 };
 use i_slint_core::window::WindowInner;
 use i_slint_core::{Brush, Color, ImageInner, Property, SharedString};
@@ -95,6 +95,35 @@ fn rect_with_radius_to_path(rect: Rect, border_radius: f32) -> femtovg::Path {
     path
 }
 
+/// Draws the `(underline, strikethrough)` lines below and through a run of text of the given width,
+/// that was drawn at `pos` with `paint`
+fn draw_text_decorations(
+    canvas: &mut Canvas,
+    pos: Point,
+    width: f32,
+    paint: femtovg::Paint,
+    (underline, strikethrough): (bool, bool),
+) {
+    if !underline && !strikethrough {
+        return;
+    }
+    let font_metrics = canvas.measure_font(paint).unwrap();
+    let ascender = font_metrics.ascender();
+    let thickness = (paint.font_size() / 16.).max(1.);
+    let mut draw_line = |y: f32| {
+        canvas.fill_path(
+            &mut rect_to_path(Rect::new(pos + euclid::vec2(0., y), Size::new(width, thickness))),
+            paint,
+        );
+    };
+    if underline {
+        draw_line(ascender + thickness);
+    }
+    if strikethrough {
+        draw_line(ascender * 0.7);
+    }
+}
+
 fn rect_to_path(r: Rect) -> femtovg::Path {
     rect_with_radius_to_path(r, 0.)
 }
@@ -205,12 +234,13 @@ fn draw_border_rectangle(&mut self, rect: Pin<&items::BorderRectangle>, _: &Item
     fn draw_image(&mut self, image: Pin<&items::ImageItem>, item_rc: &ItemRc) {
         self.draw_image_impl(
             item_rc,
-            items::ImageItem::FIELD_OFFSETS.source.apply_pin(image),
+            &|| image.current_frame(),
             IntRect::default(),
             items::ImageItem::FIELD_OFFSETS.width.apply_pin(image),
             items::ImageItem::FIELD_OFFSETS.height.apply_pin(image),
             image.image_fit(),
             None,
+            None,
             image.image_rendering(),
         );
     }
@@ -223,11 +253,12 @@ fn draw_clipped_image(&mut self, clipped_image: Pin<&items::ClippedImage>, item_
 
         self.draw_image_impl(
             item_rc,
-            items::ClippedImage::FIELD_OFFSETS.source.apply_pin(clipped_image),
+            &|| clipped_image.current_frame(),
             source_clip_rect,
             items::ClippedImage::FIELD_OFFSETS.width.apply_pin(clipped_image),
             items::ClippedImage::FIELD_OFFSETS.height.apply_pin(clipped_image),
             clipped_image.image_fit(),
+            clipped_image.slices(),
             Some(items::ClippedImage::FIELD_OFFSETS.colorize.apply_pin(clipped_image)),
             clipped_image.image_rendering(),
         );
@@ -241,15 +272,11 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             return;
         }
 
-        let string = text.text();
-        let string = string.as_str();
-        let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text.font_request(WindowInner::from_pub(&self.window)),
-                self.scale_factor,
-                &text.text(),
-            )
-        });
+        let styled_text = text.styled_text();
+        let string = styled_text.text.as_str();
+        let font_request = text.font_request(WindowInner::from_pub(&self.window));
+        let font = fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request.clone(), self.scale_factor, string));
 
         let paint = match self
             .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
@@ -258,6 +285,13 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             None => return,
         };
 
+        let span_fonts = if styled_text.spans.len() > 1 || styled_text.has_font_runs() {
+            Some(fonts::span_fonts(&styled_text, &font_request, self.scale_factor))
+        } else {
+            None
+        };
+
+        let decorations = (text.underline(), text.strikethrough());
         let mut canvas = self.canvas.borrow_mut();
         fonts::layout_text_lines(
             string,
@@ -267,9 +301,31 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             text.wrap(),
             text.overflow(),
             false,
+            (text.explicit_line_height().map(|h| h * self.scale_factor), text.max_line_count()),
             paint,
-            |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+            |to_draw, pos, start, _| match &span_fonts {
+                Some(span_fonts) => fonts::for_each_styled_line_part(
+                    &styled_text,
+                    span_fonts,
+                    to_draw.trim_end(),
+                    start,
+                    paint,
+                    |part, _, x, part_paint, metrics| {
+                        canvas.fill_text(pos.x + x, pos.y, part, part_paint).unwrap();
+                        draw_text_decorations(
+                            &mut canvas,
+                            pos + euclid::vec2(x, 0.),
+                            metrics.width(),
+                            part_paint,
+                            decorations,
+                        );
+                    },
+                ),
+                None => {
+                    let metrics =
+                        canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+                    draw_text_decorations(&mut canvas, pos, metrics.width(), paint, decorations);
+                }
             },
         );
     }
@@ -281,11 +337,13 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             return;
         }
 
+        let visual_representation = text_input.visual_representation();
+
         let font = fonts::FONT_CACHE.with(|cache| {
             cache.borrow_mut().font(
                 text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
                 self.scale_factor,
-                &text_input.text(),
+                &visual_representation.text,
             )
         });
 
@@ -298,7 +356,7 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
         };
 
         let (mut min_select, mut max_select) = text_input.selection_anchor_and_cursor();
-        let cursor_pos = text_input.cursor_position();
+        let cursor_pos = visual_representation.cursor_position;
         let cursor_visible = cursor_pos >= 0
             && text_input.cursor_visible()
             && text_input.enabled()
@@ -306,15 +364,20 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
         let mut cursor_pos = cursor_pos as usize;
         let mut canvas = self.canvas.borrow_mut();
         let font_height = canvas.measure_font(paint).unwrap().height();
-        let mut text = text_input.text();
+        let mut text = visual_representation.text;
+        let mut preedit_range = visual_representation.preedit_range;
 
         if let InputType::Password = text_input.input_type() {
             min_select = text[..min_select].chars().count() * PASSWORD_CHARACTER.len();
             max_select = text[..max_select].chars().count() * PASSWORD_CHARACTER.len();
             cursor_pos = text[..cursor_pos].chars().count() * PASSWORD_CHARACTER.len();
+            preedit_range = text[..preedit_range.start].chars().count() * PASSWORD_CHARACTER.len()
+                ..text[..preedit_range.end].chars().count() * PASSWORD_CHARACTER.len();
             text = SharedString::from(PASSWORD_CHARACTER.repeat(text.chars().count()));
         };
 
+        let decorations = (text_input.underline(), text_input.strikethrough());
+        let line_height = text_input.explicit_line_height().map(|h| h * self.scale_factor);
         let mut cursor_point: Option<Point> = None;
 
         let next_y = fonts::layout_text_lines(
@@ -325,6 +388,7 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             text_input.wrap(),
             items::TextOverflow::Clip,
             text_input.single_line(),
+            (line_height, None),
             paint,
             |to_draw, pos, start, metrics| {
                 let range = start..(start + to_draw.len());
@@ -400,6 +464,30 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
                     // no selection on this line
                     canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
                 };
+                if decorations != (false, false) {
+                    let width =
+                        canvas.measure_text(0., 0., to_draw.trim_end(), paint).unwrap().width();
+                    draw_text_decorations(&mut canvas, pos, width, paint, decorations);
+                }
+                if preedit_range.start < range.end && range.start < preedit_range.end {
+                    // Underline the part of the text being composed by the input method
+                    let preedit_x = |byte_offset: usize| {
+                        metrics
+                            .glyphs
+                            .iter()
+                            .find(|glyph| glyph.byte_index >= byte_offset.saturating_sub(start))
+                            .map_or_else(|| metrics.width(), |glyph| glyph.x - glyph.bearing_x)
+                    };
+                    let preedit_start_x = preedit_x(preedit_range.start.max(start));
+                    let preedit_end_x = preedit_x(preedit_range.end);
+                    draw_text_decorations(
+                        &mut canvas,
+                        pos + euclid::vec2(preedit_start_x, 0.),
+                        preedit_end_x - preedit_start_x,
+                        paint,
+                        (true, false),
+                    );
+                }
                 if cursor_visible
                     && (range.contains(&cursor_pos)
                         || (cursor_pos == range.end
@@ -422,8 +510,10 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             },
         );
 
+        // The glyphs are vertically centered in their line, see `layout_text_lines`
+        let half_leading = line_height.map_or(0., |h| (h - font_height) / 2.);
         if let Some(cursor_point) =
-            cursor_point.or_else(|| cursor_visible.then(|| [0., next_y].into()))
+            cursor_point.or_else(|| cursor_visible.then(|| [0., next_y + half_leading].into()))
         {
             let mut cursor_rect = femtovg::Path::new();
             cursor_rect.rect(
@@ -692,6 +782,56 @@ fn visit_layer(&mut self, layer_item: Pin<&Layer>, self_rc: &ItemRc) -> Renderin
         }
     }
 
+    fn visit_effect(&mut self, effect_item: Pin<&Effect>, item_rc: &ItemRc) -> RenderingResult {
+        let backdrop_blur = effect_item.effect_backdrop_blur() * self.scale_factor;
+        if backdrop_blur > 0. {
+            self.draw_blurred_backdrop(effect_item.geometry().size, backdrop_blur);
+        }
+
+        if !effect_item.has_content_effect() {
+            self.graphics_cache.release(item_rc);
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let current_clip = self.get_current_clip();
+        let margin = (effect_item.margin() * self.scale_factor).ceil();
+        let cache_entry = self.graphics_cache.get_or_update_cache_entry(item_rc, || {
+            let children_rect = i_slint_core::properties::evaluate_no_tracking(|| {
+                let self_ref = item_rc.borrow();
+                self_ref.as_ref().geometry().union(
+                    &i_slint_core::item_rendering::item_children_bounding_rect(
+                        &item_rc.component(),
+                        item_rc.index() as isize,
+                        &current_clip,
+                    ),
+                )
+            });
+            // The blur and the shadow extend the content by the margin on each side
+            let size: IntSize = (children_rect.size * self.scale_factor
+                + Size::new(2. * margin, 2. * margin))
+            .ceil()
+            .cast();
+
+            *self.metrics.layers_created.as_mut().unwrap() += 1;
+            let layer = Texture::new_empty_on_gpu(&self.canvas, size.width, size.height)?;
+            self.render_children_to_texture(item_rc, &layer, margin);
+            ItemGraphicsCacheEntry::Texture(self.apply_effect_to_layer(effect_item, layer)).into()
+        });
+
+        if let Some(texture) = cache_entry.as_ref().map(|entry| entry.as_texture()) {
+            if let Some(size) = texture.size() {
+                let mut path = femtovg::Path::new();
+                path.rect(0., 0., size.width as f32, size.height as f32);
+                let paint = texture.as_paint().with_anti_alias(false);
+                self.canvas.borrow_mut().save_with(|canvas| {
+                    canvas.translate(-margin, -margin);
+                    canvas.fill_path(&mut path, paint);
+                });
+            }
+        }
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
     fn visit_clip(&mut self, clip_item: Pin<&Clip>, item_rc: &ItemRc) -> RenderingResult {
         if !clip_item.clip() {
             return RenderingResult::ContinueRenderingChildren;
@@ -1022,11 +1162,237 @@ fn render_and_blend_layer(&mut self, alpha_tint: f32, item_rc: &ItemRc) -> Rende
         RenderingResult::ContinueRenderingWithoutChildren
     }
 
+    /// Renders the children of the item into the texture, translated by `offset` physical pixels
+    fn render_children_to_texture(&mut self, item_rc: &ItemRc, texture: &Texture, offset: f32) {
+        let size = match texture.size() {
+            Some(size) => size,
+            None => return,
+        };
+        let previous_state = self.state.last().unwrap().clone();
+
+        {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.save();
+            canvas.set_render_target(texture.as_render_target());
+            canvas.reset();
+            canvas.clear_rect(0, 0, size.width, size.height, femtovg::Color::rgba(0, 0, 0, 0));
+            canvas.translate(offset, offset);
+        }
+
+        *self.state.last_mut().unwrap() = State {
+            scissor: Rect::new(
+                Point::new(-offset, -offset) / self.scale_factor,
+                Size::new(size.width as f32, size.height as f32) / self.scale_factor,
+            ),
+            global_alpha: 1.,
+            current_render_target: texture.as_render_target(),
+        };
+
+        i_slint_core::item_rendering::render_item_children(
+            self,
+            &item_rc.component(),
+            item_rc.index() as isize,
+        );
+
+        {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.restore();
+            canvas.set_render_target(previous_state.current_render_target);
+        }
+        *self.state.last_mut().unwrap() = previous_state;
+    }
+
+    /// Applies the color filters, the blur and the drop shadow of the effect to the layer with the
+    /// rendered children, and returns the resulting texture.
+    fn apply_effect_to_layer(&self, effect_item: Pin<&Effect>, layer: Rc<Texture>) -> Rc<Texture> {
+        let size = match layer.size() {
+            Some(size) => size,
+            None => return layer,
+        };
+        let (width, height) = (size.width as f32, size.height as f32);
+        let mut layer_rect = femtovg::Path::new();
+        layer_rect.rect(0., 0., width, height);
+        let image_paint = |image_id: femtovg::ImageId, x: f32, y: f32| {
+            femtovg::Paint::image(image_id, x, y, width, height, 0., 1.0).with_anti_alias(false)
+        };
+
+        // femtovg has no color filter, so the pixels are read back and processed on the CPU
+        if let Some(matrix) = effect_item.color_matrix() {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.set_render_target(layer.as_render_target());
+            if let Ok(mut pixels) = canvas.screenshot() {
+                for pixel in pixels.buf_mut().iter_mut() {
+                    *pixel = items::apply_color_matrix(&matrix, *pixel);
+                }
+                if let Ok(image_id) =
+                    canvas.create_image(pixels.as_ref(), femtovg::ImageFlags::PREMULTIPLIED)
+                {
+                    let filtered_image = Texture::adopt(&self.canvas, image_id);
+                    canvas.save();
+                    canvas.reset();
+                    canvas.global_composite_operation(femtovg::CompositeOperation::Copy);
+                    canvas
+                        .fill_path(&mut layer_rect.clone(), image_paint(filtered_image.id, 0., 0.));
+                    canvas.restore();
+                    self.layer_images_to_delete_after_flush.borrow_mut().push(filtered_image);
+                }
+            }
+            canvas.set_render_target(self.current_render_target());
+        }
+
+        let blur = effect_item.effect_blur() * self.scale_factor;
+        let mut layer = if blur > 0. {
+            let blurred_layer =
+                layer.filter(femtovg::ImageFilter::GaussianBlur { sigma: blur / 2. });
+            self.layer_images_to_delete_after_flush.borrow_mut().push(layer);
+            blurred_layer
+        } else {
+            layer
+        };
+
+        if effect_item.has_drop_shadow() {
+            // The shadow is the content filled with the shadow color, blurred, and drawn with
+            // an offset underneath the content.
+            let shadow = match Texture::new_empty_on_gpu(&self.canvas, size.width, size.height) {
+                Some(shadow) => shadow,
+                None => return layer,
+            };
+            {
+                let mut canvas = self.canvas.borrow_mut();
+                canvas.save();
+                canvas.set_render_target(shadow.as_render_target());
+                canvas.reset();
+                canvas.clear_rect(0, 0, size.width, size.height, femtovg::Color::rgba(0, 0, 0, 0));
+                canvas.fill_path(&mut layer_rect.clone(), image_paint(layer.id, 0., 0.));
+                canvas.global_composite_operation(femtovg::CompositeOperation::SourceIn);
+                canvas.fill_path(
+                    &mut layer_rect.clone(),
+                    femtovg::Paint::color(to_femtovg_color(&effect_item.drop_shadow_color())),
+                );
+                canvas.restore();
+            }
+
+            let shadow_blur = effect_item.drop_shadow_blur() * self.scale_factor;
+            let shadow = if shadow_blur > 0. {
+                let blurred_shadow =
+                    shadow.filter(femtovg::ImageFilter::GaussianBlur { sigma: shadow_blur / 2. });
+                self.layer_images_to_delete_after_flush.borrow_mut().push(shadow);
+                blurred_shadow
+            } else {
+                shadow
+            };
+
+            if let Some(result) = Texture::new_empty_on_gpu(&self.canvas, size.width, size.height) {
+                let offset_x = effect_item.drop_shadow_offset_x() * self.scale_factor;
+                let offset_y = effect_item.drop_shadow_offset_y() * self.scale_factor;
+                let mut shadow_rect = femtovg::Path::new();
+                shadow_rect.rect(offset_x, offset_y, width, height);
+
+                let mut canvas = self.canvas.borrow_mut();
+                canvas.save();
+                canvas.set_render_target(result.as_render_target());
+                canvas.reset();
+                canvas.clear_rect(0, 0, size.width, size.height, femtovg::Color::rgba(0, 0, 0, 0));
+                canvas.fill_path(&mut shadow_rect, image_paint(shadow.id, offset_x, offset_y));
+                canvas.fill_path(&mut layer_rect.clone(), image_paint(layer.id, 0., 0.));
+                canvas.restore();
+
+                let mut images_to_delete = self.layer_images_to_delete_after_flush.borrow_mut();
+                images_to_delete.push(std::mem::replace(&mut layer, result));
+            }
+            self.layer_images_to_delete_after_flush.borrow_mut().push(shadow);
+        }
+
+        self.canvas.borrow_mut().set_render_target(self.current_render_target());
+        layer
+    }
+
+    /// Draws the content underneath the rectangle of the given logical size, blurred.
+    /// femtovg has no backdrop filter, so the content is read back from the current render target.
+    fn draw_blurred_backdrop(&mut self, size: Size, blur: f32) {
+        let size = size * self.scale_factor;
+        // Include the surrounding of the rectangle, so that its edges are blurred with the content
+        // next to them.
+        let margin = (blur * 1.5).ceil();
+
+        let mut canvas = self.canvas.borrow_mut();
+        // Only the translation of the current transform is taken into account
+        let (x, y) = canvas.transform().transform_point(0., 0.);
+        let pixels = match canvas.screenshot() {
+            Ok(pixels) => pixels,
+            Err(_) => return,
+        };
+        let left = (x - margin).floor().max(0.) as usize;
+        let top = (y - margin).floor().max(0.) as usize;
+        let right = ((x + size.width + margin).ceil().max(0.) as usize).min(pixels.width());
+        let bottom = ((y + size.height + margin).ceil().max(0.) as usize).min(pixels.height());
+        if left >= right || top >= bottom {
+            return;
+        }
+        let (width, height) = (right - left, bottom - top);
+        let (buffer, _, _) = pixels.sub_image(left, top, width, height).to_contiguous_buf();
+        let image_id = match canvas.create_image(
+            imgref::Img::new(buffer.as_ref(), width, height),
+            femtovg::ImageFlags::PREMULTIPLIED,
+        ) {
+            Ok(image_id) => image_id,
+            Err(_) => return,
+        };
+        let backdrop_image = Texture::adopt(&self.canvas, image_id);
+        drop(canvas);
+
+        let backdrop = match Texture::new_empty_on_gpu(&self.canvas, width as u32, height as u32) {
+            Some(backdrop) => backdrop,
+            None => return,
+        };
+        let (width, height) = (width as f32, height as f32);
+        {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.save();
+            canvas.set_render_target(backdrop.as_render_target());
+            canvas.reset();
+            let mut backdrop_rect = femtovg::Path::new();
+            backdrop_rect.rect(0., 0., width, height);
+            canvas.global_composite_operation(femtovg::CompositeOperation::Copy);
+            canvas.fill_path(
+                &mut backdrop_rect,
+                femtovg::Paint::image(image_id, 0., 0., width, height, 0., 1.0),
+            );
+            canvas.restore();
+            canvas.set_render_target(self.current_render_target());
+        }
+        let blurred_backdrop =
+            backdrop.filter(femtovg::ImageFilter::GaussianBlur { sigma: blur / 2. });
+        self.canvas.borrow_mut().set_render_target(self.current_render_target());
+
+        let mut rect = femtovg::Path::new();
+        rect.rect(0., 0., size.width, size.height);
+        self.canvas.borrow_mut().fill_path(
+            &mut rect,
+            femtovg::Paint::image(
+                blurred_backdrop.id,
+                left as f32 - x,
+                top as f32 - y,
+                width,
+                height,
+                0.,
+                1.0,
+            )
+            .with_anti_alias(false),
+        );
+
+        let mut images_to_delete = self.layer_images_to_delete_after_flush.borrow_mut();
+        images_to_delete.push(backdrop_image);
+        images_to_delete.push(backdrop);
+        images_to_delete.push(blurred_backdrop);
+    }
+
     fn colorize_image(
         &self,
         original_cache_entry: ItemGraphicsCacheEntry,
         colorize_property: Option<Pin<&Property<Brush>>>,
         scaling: ImageRendering,
+        repeat: bool,
     ) -> ItemGraphicsCacheEntry {
         let colorize_brush = colorize_property.map_or(Brush::default(), |prop| prop.get());
         if colorize_brush.is_transparent() {
@@ -1039,12 +1405,15 @@ fn colorize_image(
             None => return original_cache_entry,
         };
 
-        let scaling_flags = match scaling {
+        let mut scaling_flags = match scaling {
             ImageRendering::Smooth => femtovg::ImageFlags::empty(),
             ImageRendering::Pixelated => {
                 femtovg::ImageFlags::empty() | femtovg::ImageFlags::NEAREST
             }
         };
+        if repeat {
+            scaling_flags |= femtovg::ImageFlags::REPEAT_X | femtovg::ImageFlags::REPEAT_Y;
+        }
 
         let image_id = original_image.id;
         let colorized_image = self
@@ -1101,11 +1470,12 @@ fn colorize_image(
     fn draw_image_impl(
         &mut self,
         item_rc: &ItemRc,
-        source_property: Pin<&Property<Image>>,
+        source: &dyn Fn() -> Image,
         source_clip_rect: IntRect,
         target_width: Pin<&Property<f32>>,
         target_height: Pin<&Property<f32>>,
         image_fit: ImageFit,
+        slices: Option<Vec<items::ImageSlice>>,
         colorize_property: Option<Pin<&Property<Brush>>>,
         image_rendering: ImageRendering,
     ) {
@@ -1116,48 +1486,75 @@ fn draw_image_impl(
             return;
         }
 
+        // femtovg can only repeat whole textures, so the repetitions of a part of the image are
+        // drawn one by one
+        let slices = slices.or_else(|| {
+            (image_fit == ImageFit::Tile
+                && !source_clip_rect.is_empty()
+                && source_clip_rect != IntRect::from_size(source().size().cast()))
+            .then(|| {
+                items::image_slices(
+                    source_clip_rect,
+                    [0; 4],
+                    Size::new(target_width.get(), target_height.get()),
+                    true,
+                )
+            })
+        });
+        let repeat = image_fit == ImageFit::Tile && slices.is_none();
+
         let cached_image = loop {
             let image_cache_entry = self.graphics_cache.get_or_update_cache_entry(item_rc, || {
-                let image = source_property.get();
+                let image = source();
                 let image_inner: &ImageInner = (&image).into();
 
                 let target_size_for_scalable_source = matches!(image_inner, ImageInner::Svg(..))
                     .then(|| {
                         // get the scale factor as a property again, to ensure the cache is invalidated when the scale factor changes
                         let scale_factor = self.window.scale_factor();
-                        [
-                            (target_width.get() * scale_factor) as u32,
-                            (target_height.get() * scale_factor) as u32,
-                        ]
-                        .into()
+                        // Tiled images are repeated at their natural size
+                        let size = if repeat {
+                            image.size().cast()
+                        } else {
+                            Size::new(target_width.get(), target_height.get())
+                        };
+                        [(size.width * scale_factor) as u32, (size.height * scale_factor) as u32]
+                            .into()
                     });
 
-                TextureCacheKey::new(image_inner, target_size_for_scalable_source, image_rendering)
-                    .and_then(|cache_key| {
-                        self.texture_cache.borrow_mut().lookup_image_in_cache_or_create(
-                            cache_key,
-                            || {
-                                Texture::new_from_image(
-                                    image_inner,
-                                    &self.canvas,
-                                    target_size_for_scalable_source,
-                                    image_rendering,
-                                )
-                            },
-                        )
-                    })
-                    .or_else(|| {
-                        Texture::new_from_image(
-                            image_inner,
-                            &self.canvas,
-                            target_size_for_scalable_source,
-                            image_rendering,
-                        )
-                    })
-                    .map(ItemGraphicsCacheEntry::Texture)
-                    .map(|cache_entry| {
-                        self.colorize_image(cache_entry, colorize_property, image_rendering)
-                    })
+                TextureCacheKey::new(
+                    image_inner,
+                    target_size_for_scalable_source,
+                    image_rendering,
+                    repeat,
+                )
+                .and_then(|cache_key| {
+                    self.texture_cache.borrow_mut().lookup_image_in_cache_or_create(
+                        cache_key,
+                        || {
+                            Texture::new_from_image(
+                                image_inner,
+                                &self.canvas,
+                                target_size_for_scalable_source,
+                                image_rendering,
+                                repeat,
+                            )
+                        },
+                    )
+                })
+                .or_else(|| {
+                    Texture::new_from_image(
+                        image_inner,
+                        &self.canvas,
+                        target_size_for_scalable_source,
+                        image_rendering,
+                        repeat,
+                    )
+                })
+                .map(ItemGraphicsCacheEntry::Texture)
+                .map(|cache_entry| {
+                    self.colorize_image(cache_entry, colorize_property, image_rendering, repeat)
+                })
             });
 
             // Check if the image in the cache is loaded. If not, don't draw any image and we'll return
@@ -1179,12 +1576,74 @@ fn draw_image_impl(
                 continue;
             }
 
+            // The item cache may also contain a texture that was created before the image-fit
+            // changed, which repeats itself or not.
+            if cached_image.as_texture().is_repeated() != repeat {
+                self.graphics_cache.release(item_rc);
+                continue;
+            }
+
             break cached_image.as_texture().clone();
         };
 
         let image_id = cached_image.id;
         let image_size = cached_image.size().unwrap_or_default().cast();
 
+        if let Some(slices) = slices {
+            // Scalable images are rendered into a texture of a different size
+            let natural_size = source().size().cast::<f32>();
+            if natural_size.is_empty() {
+                return;
+            }
+            let texture_scale_x = image_size.width / natural_size.width;
+            let texture_scale_y = image_size.height / natural_size.height;
+            let mut canvas = self.canvas.borrow_mut();
+            for slice in slices {
+                let source = slice.source.cast::<f32>().scale(texture_scale_x, texture_scale_y);
+                let target = slice.target.scale(self.scale_factor, self.scale_factor);
+                let fill_paint = femtovg::Paint::image(
+                    image_id,
+                    -source.min_x(),
+                    -source.min_y(),
+                    image_size.width,
+                    image_size.height,
+                    0.0,
+                    1.0,
+                )
+                .with_anti_alias(false);
+                let scale_x = target.width() / source.width();
+                let scale_y = target.height() / source.height();
+                let mut path = femtovg::Path::new();
+                path.rect(0., 0., source.width(), source.height());
+                canvas.save_with(|canvas| {
+                    canvas.translate(target.min_x(), target.min_y());
+                    canvas.scale(scale_x, scale_y);
+                    canvas.fill_path(&mut path, fill_paint);
+                });
+            }
+            return;
+        }
+
+        if repeat {
+            // The texture repeats itself, so the pattern of the paint has the natural size of
+            // the image
+            let pattern_size = source().size().cast::<f32>() * self.scale_factor;
+            let fill_paint = femtovg::Paint::image(
+                image_id,
+                0.,
+                0.,
+                pattern_size.width,
+                pattern_size.height,
+                0.0,
+                1.0,
+            )
+            .with_anti_alias(false);
+            let mut path = femtovg::Path::new();
+            path.rect(0., 0., target_w, target_h);
+            self.canvas.borrow_mut().fill_path(&mut path, fill_paint);
+            return;
+        }
+
         let (source_width, source_height) = if source_clip_rect.is_empty() {
             (image_size.width, image_size.height)
         } else {
@@ -1199,7 +1658,7 @@ fn draw_image_impl(
         // The source_to_target scale is applied to the paint that holds the image as well as path
         // begin rendered.
         let (source_to_target_scale_x, source_to_target_scale_y) = match image_fit {
-            ImageFit::Fill => (target_w / source_width, target_h / source_height),
+            ImageFit::Fill | ImageFit::Tile => (target_w / source_width, target_h / source_height),
             ImageFit::Cover => {
                 let ratio = f32::max(target_w / source_width, target_h / source_height);
 
@@ -146,15 +146,90 @@ fn text_size(
             scale_factor,
             text,
             None,
-            max_width.map(|w| w * scale_factor),
-            Default::default(),
-            Default::default(),
+            textlayout::LayoutOptions {
+                max_width: max_width.map(|w| w * scale_factor),
+                ..Default::default()
+            },
         );
 
         [layout.max_intrinsic_width().ceil() / scale_factor, layout.height().ceil() / scale_factor]
             .into()
     }
 
+    fn styled_text_size(
+        &self,
+        font_request: i_slint_core::graphics::FontRequest,
+        styled_text: &i_slint_core::styled_text::StyledText,
+        max_width: Option<i_slint_core::Coord>,
+        line_height: Option<i_slint_core::Coord>,
+        max_lines: Option<usize>,
+        scale_factor: f32,
+    ) -> i_slint_core::graphics::Size {
+        let layout = textlayout::create_styled_layout(
+            font_request,
+            scale_factor,
+            styled_text,
+            None,
+            textlayout::LayoutOptions {
+                max_width: max_width.map(|w| w * scale_factor),
+                line_height: line_height.map(|h| h * scale_factor),
+                max_lines,
+                ..Default::default()
+            },
+        );
+
+        [layout.max_intrinsic_width().ceil() / scale_factor, layout.height().ceil() / scale_factor]
+            .into()
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: std::pin::Pin<&i_slint_core::items::Text>,
+        pos: i_slint_core::graphics::Point,
+    ) -> Option<usize> {
+        let window_adapter = self.window_adapter_weak.upgrade()?;
+        let window = WindowInner::from_pub(window_adapter.window());
+
+        let scale_factor = window.scale_factor();
+
+        let max_width = text.width() * scale_factor;
+        let max_height = text.height() * scale_factor;
+        let pos = pos * scale_factor;
+
+        if max_width <= 0. || max_height <= 0. {
+            return None;
+        }
+
+        let styled_text = text.styled_text();
+        let layout = textlayout::create_styled_layout(
+            text.font_request(window),
+            scale_factor,
+            &styled_text,
+            None,
+            textlayout::LayoutOptions {
+                max_width: Some(max_width),
+                h_align: text.horizontal_alignment(),
+                overflow: text.overflow(),
+                line_height: text.explicit_line_height().map(|h| h * scale_factor),
+                max_lines: text.max_line_count(),
+            },
+        );
+
+        let layout_top_y = match text.vertical_alignment() {
+            i_slint_core::items::TextVerticalAlignment::Top => 0.,
+            i_slint_core::items::TextVerticalAlignment::Center => {
+                (max_height - layout.height()) / 2.
+            }
+            i_slint_core::items::TextVerticalAlignment::Bottom => max_height - layout.height(),
+        };
+
+        textlayout::byte_offset_for_position(
+            &styled_text.text,
+            (pos.x, pos.y - layout_top_y),
+            &layout,
+        )
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
@@ -186,9 +261,13 @@ fn text_input_byte_offset_for_position(
             scale_factor,
             string,
             None,
-            Some(max_width),
-            text_input.horizontal_alignment(),
-            i_slint_core::items::TextOverflow::Clip,
+            textlayout::LayoutOptions {
+                max_width: Some(max_width),
+                h_align: text_input.horizontal_alignment(),
+                overflow: i_slint_core::items::TextOverflow::Clip,
+                line_height: text_input.explicit_line_height().map(|h| h * scale_factor),
+                max_lines: None,
+            },
         );
 
         let layout_top_y = match text_input.vertical_alignment() {
@@ -243,9 +322,13 @@ fn text_input_cursor_rect_for_byte_offset(
             scale_factor,
             string,
             None,
-            Some(max_width),
-            text_input.horizontal_alignment(),
-            i_slint_core::items::TextOverflow::Clip,
+            textlayout::LayoutOptions {
+                max_width: Some(max_width),
+                h_align: text_input.horizontal_alignment(),
+                overflow: i_slint_core::items::TextOverflow::Clip,
+                line_height: text_input.explicit_line_height().map(|h| h * scale_factor),
+                max_lines: None,
+            },
         );
 
         let layout_top_y = match text_input.vertical_alignment() {
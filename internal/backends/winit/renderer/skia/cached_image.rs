@@ -34,6 +34,14 @@ pub(crate) fn as_skia_image(
     match image_inner {
         ImageInner::None => None,
         ImageInner::EmbeddedImage { buffer, cache_key } => {
+            // The frames of animated images are converted once and then looked up by key
+            if matches!(cache_key, ImageCacheKey::AnimationFrame(..)) {
+                if let Some(ImageInner::BackendStorage(x)) = core_cache::cached_image(cache_key) {
+                    if let Some(cached) = vtable::VRc::borrow(&x).downcast::<SkiaCachedImage>() {
+                        return Some(cached.image.clone());
+                    }
+                }
+            }
             let result = image_buffer_to_skia_image(buffer);
             if let Some(img) = result.as_ref() {
                 core_cache::replace_cached_image(
@@ -73,6 +81,9 @@ pub(crate) fn as_skia_image(
         ImageInner::BackendStorage(x) => {
             vtable::VRc::borrow(x).downcast::<SkiaCachedImage>().map(|x| x.image.clone())
         }
+        ImageInner::AnimatedImage(animated) => {
+            as_skia_image(animated.frames()[0].image.clone(), target_width, target_height)
+        }
     }
 }
 
@@ -5,7 +5,9 @@
 
 use i_slint_core::graphics::euclid;
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
-use i_slint_core::items::{ImageFit, ImageRendering, ItemRc, Layer, Opacity, RenderingResult};
+use i_slint_core::items::{
+    Effect, ImageFit, ImageRendering, ItemRc, Layer, Opacity, RenderingResult,
+};
 use i_slint_core::window::WindowInner;
 use i_slint_core::{items, Brush, Color, Property};
 
@@ -123,18 +125,19 @@ fn colorize_image(
     fn draw_image_impl(
         &mut self,
         item_rc: &ItemRc,
-        source_property: Pin<&Property<i_slint_core::graphics::Image>>,
+        source: &dyn Fn() -> i_slint_core::graphics::Image,
         mut dest_rect: skia_safe::Rect,
         source_rect: Option<skia_safe::Rect>,
         target_width: std::pin::Pin<&Property<f32>>,
         target_height: std::pin::Pin<&Property<f32>>,
         image_fit: ImageFit,
+        slices: Option<Vec<items::ImageSlice>>,
         rendering: ImageRendering,
         colorize_property: Option<Pin<&Property<Brush>>>,
     ) {
         // TODO: avoid doing creating an SkImage multiple times when the same source is used in multiple image elements
         let skia_image = self.image_cache.get_or_update_cache_entry(item_rc, || {
-            let image = source_property.get();
+            let image = source();
             super::cached_image::as_skia_image(image, target_width, target_height).and_then(
                 |skia_image| match colorize_property
                     .map(|p| p.get())
@@ -151,6 +154,73 @@ fn draw_image_impl(
             None => return,
         };
 
+        let filter_mode: skia_safe::sampling_options::SamplingOptions = match rendering {
+            ImageRendering::Smooth => skia_safe::sampling_options::FilterMode::Linear,
+            ImageRendering::Pixelated => skia_safe::sampling_options::FilterMode::Nearest,
+        }
+        .into();
+
+        if let Some(slices) = slices {
+            // Scalable images are rendered into an image of a different size
+            let natural_size = source().size().cast::<f32>();
+            if natural_size.is_empty() {
+                return;
+            }
+            let texture_scale_x = skia_image.width() as f32 / natural_size.width;
+            let texture_scale_y = skia_image.height() as f32 / natural_size.height;
+            for slice in slices {
+                let source = slice.source.cast::<f32>().scale(texture_scale_x, texture_scale_y);
+                let target = slice.target.scale(self.scale_factor, self.scale_factor);
+                self.canvas.draw_image_rect_with_sampling_options(
+                    &skia_image,
+                    Some((&to_skia_rect(&source), skia_safe::canvas::SrcRectConstraint::Strict)),
+                    to_skia_rect(&target),
+                    filter_mode,
+                    &skia_safe::Paint::default(),
+                );
+            }
+            return;
+        }
+
+        if image_fit == ImageFit::Tile {
+            // The shader repeats the image at its natural size
+            let natural_size = source().size().cast::<f32>();
+            if natural_size.is_empty() {
+                return;
+            }
+            let texture_scale_x = skia_image.width() as f32 / natural_size.width;
+            let texture_scale_y = skia_image.height() as f32 / natural_size.height;
+            let tile_image = match source_rect.filter(|r| !r.is_empty()) {
+                Some(source_rect) => {
+                    let subset = skia_safe::Rect::from_xywh(
+                        source_rect.x() * texture_scale_x,
+                        source_rect.y() * texture_scale_y,
+                        source_rect.width() * texture_scale_x,
+                        source_rect.height() * texture_scale_y,
+                    )
+                    .round();
+                    match skia_image.new_subset(subset) {
+                        Some(image) => image,
+                        None => return,
+                    }
+                }
+                None => skia_image,
+            };
+            let mut local_matrix = skia_safe::Matrix::translate((dest_rect.left, dest_rect.top));
+            local_matrix.pre_scale(
+                (self.scale_factor / texture_scale_x, self.scale_factor / texture_scale_y),
+                None,
+            );
+            let mut paint = skia_safe::Paint::default();
+            paint.set_shader(tile_image.to_shader(
+                (skia_safe::TileMode::Repeat, skia_safe::TileMode::Repeat),
+                filter_mode,
+                &local_matrix,
+            ));
+            self.canvas.draw_rect(dest_rect, &paint);
+            return;
+        }
+
         self.canvas.save();
 
         let mut source_rect = source_rect.filter(|r| !r.is_empty()).unwrap_or_else(|| {
@@ -164,12 +234,6 @@ fn draw_image_impl(
             skia_safe::Matrix::rect_to_rect(source_rect, dest_rect, None).unwrap_or_default();
         self.canvas.concat(&transform);
 
-        let filter_mode: skia_safe::sampling_options::SamplingOptions = match rendering {
-            ImageRendering::Smooth => skia_safe::sampling_options::FilterMode::Linear,
-            ImageRendering::Pixelated => skia_safe::sampling_options::FilterMode::Nearest,
-        }
-        .into();
-
         self.canvas.draw_image_with_sampling_options(
             skia_image,
             skia_safe::Point::default(),
@@ -304,12 +368,13 @@ fn draw_image(
 
         self.draw_image_impl(
             self_rc,
-            i_slint_core::items::ImageItem::FIELD_OFFSETS.source.apply_pin(image),
+            &|| image.current_frame(),
             geometry,
             None,
             items::ImageItem::FIELD_OFFSETS.width.apply_pin(image),
             items::ImageItem::FIELD_OFFSETS.height.apply_pin(image),
             image.image_fit(),
+            None,
             image.image_rendering(),
             None,
         );
@@ -334,12 +399,13 @@ fn draw_clipped_image(
 
         self.draw_image_impl(
             self_rc,
-            i_slint_core::items::ClippedImage::FIELD_OFFSETS.source.apply_pin(image),
+            &|| image.current_frame(),
             geometry,
             Some(source_rect),
             items::ClippedImage::FIELD_OFFSETS.width.apply_pin(image),
             items::ClippedImage::FIELD_OFFSETS.height.apply_pin(image),
             image.image_fit(),
+            image.slices(),
             image.image_rendering(),
             Some(items::ClippedImage::FIELD_OFFSETS.colorize.apply_pin(image)),
         );
@@ -357,8 +423,7 @@ fn draw_text(
             return;
         }
 
-        let string = text.text();
-        let string = string.as_str();
+        let styled_text = text.styled_text();
         let font_request = text.font_request(WindowInner::from_pub(&self.window));
 
         let paint = match self.brush_to_paint(text.color(), max_width, max_height) {
@@ -368,15 +433,20 @@ fn draw_text(
 
         let mut text_style = skia_safe::textlayout::TextStyle::new();
         text_style.set_foreground_color(paint);
+        set_text_decorations(&mut text_style, text.underline(), text.strikethrough(), text.color());
 
-        let layout = super::textlayout::create_layout(
+        let layout = super::textlayout::create_styled_layout(
             font_request,
             self.scale_factor,
-            string,
+            &styled_text,
             Some(text_style),
-            Some(max_width),
-            text.horizontal_alignment(),
-            text.overflow(),
+            super::textlayout::LayoutOptions {
+                max_width: Some(max_width),
+                h_align: text.horizontal_alignment(),
+                overflow: text.overflow(),
+                line_height: text.explicit_line_height().map(|h| h * self.scale_factor),
+                max_lines: text.max_line_count(),
+            },
         );
 
         let y = match text.vertical_alignment() {
@@ -400,8 +470,8 @@ fn draw_text_input(
             return;
         }
 
-        let string = text_input.text();
-        let string = string.as_str();
+        let visual_representation = text_input.visual_representation();
+        let string = visual_representation.text.as_str();
         let font_request =
             text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter());
 
@@ -412,15 +482,25 @@ fn draw_text_input(
 
         let mut text_style = skia_safe::textlayout::TextStyle::new();
         text_style.set_foreground_color(paint);
+        set_text_decorations(
+            &mut text_style,
+            text_input.underline(),
+            text_input.strikethrough(),
+            text_input.color(),
+        );
 
         let layout = super::textlayout::create_layout(
             font_request,
             self.scale_factor,
             string,
             Some(text_style),
-            Some(max_width),
-            text_input.horizontal_alignment(),
-            i_slint_core::items::TextOverflow::Clip,
+            super::textlayout::LayoutOptions {
+                max_width: Some(max_width),
+                h_align: text_input.horizontal_alignment(),
+                overflow: i_slint_core::items::TextOverflow::Clip,
+                line_height: text_input.explicit_line_height().map(|h| h * self.scale_factor),
+                max_lines: None,
+            },
         );
 
         let layout_top_y = match text_input.vertical_alignment() {
@@ -433,7 +513,30 @@ fn draw_text_input(
 
         layout.paint(&mut self.canvas, skia_safe::Point::new(0., layout_top_y));
 
-        let cursor_pos = text_input.cursor_position();
+        if !visual_representation.preedit_range.is_empty() {
+            if let Some(underline_paint) = self.brush_to_paint(text_input.color(), 0., 0.) {
+                // Underline the part of the text being composed by the input method
+                let thickness = (text_input.font_size() * self.scale_factor / 16.).max(1.);
+                for text_box in super::textlayout::range_rects(
+                    string,
+                    visual_representation.preedit_range.clone(),
+                    &layout,
+                ) {
+                    let rect = text_box.rect;
+                    self.canvas.draw_rect(
+                        skia_safe::Rect::from_xywh(
+                            rect.x(),
+                            rect.bottom() + layout_top_y - thickness,
+                            rect.width(),
+                            thickness,
+                        ),
+                        &underline_paint,
+                    );
+                }
+            }
+        }
+
+        let cursor_pos = visual_representation.cursor_position;
 
         let cursor_visible = cursor_pos >= 0
             && text_input.cursor_visible()
@@ -723,6 +826,76 @@ fn visit_layer(&mut self, layer_item: Pin<&Layer>, self_rc: &ItemRc) -> Renderin
             RenderingResult::ContinueRenderingChildren
         }
     }
+
+    fn visit_effect(&mut self, effect_item: Pin<&Effect>, item_rc: &ItemRc) -> RenderingResult {
+        let backdrop_blur = effect_item.effect_backdrop_blur() * self.scale_factor;
+        if backdrop_blur > 0. {
+            // A layer with a backdrop filter is initialized with the blurred content underneath,
+            // restoring it draws that content back, clipped to the bounds of the layer.
+            let rect = item_rect(effect_item, self.scale_factor);
+            if let Some(backdrop) = skia_safe::image_filters::blur(
+                (backdrop_blur / 2., backdrop_blur / 2.),
+                skia_safe::TileMode::Clamp,
+                None,
+                None,
+            ) {
+                self.canvas.save();
+                self.canvas.clip_rect(rect, None, true);
+                self.canvas.save_layer(
+                    &skia_safe::canvas::SaveLayerRec::default().bounds(&rect).backdrop(&backdrop),
+                );
+                self.canvas.restore();
+                self.canvas.restore();
+            }
+        }
+
+        if !effect_item.has_content_effect() {
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        let mut filter = None;
+        if let Some(matrix) = effect_item.color_matrix() {
+            filter = skia_safe::image_filters::color_filter(
+                skia_safe::color_filters::matrix_row_major(&matrix),
+                filter,
+                None,
+            );
+        }
+        let blur = effect_item.effect_blur() * self.scale_factor;
+        if blur > 0. {
+            filter = skia_safe::image_filters::blur((blur / 2., blur / 2.), None, filter, None);
+        }
+        if effect_item.has_drop_shadow() {
+            let shadow_blur = effect_item.drop_shadow_blur() * self.scale_factor;
+            filter = skia_safe::image_filters::drop_shadow(
+                (
+                    effect_item.drop_shadow_offset_x() * self.scale_factor,
+                    effect_item.drop_shadow_offset_y() * self.scale_factor,
+                ),
+                (shadow_blur / 2., shadow_blur / 2.),
+                to_skia_color(&effect_item.drop_shadow_color()),
+                filter,
+                None,
+            );
+        }
+
+        let mut paint = skia_safe::Paint::default();
+        paint.set_image_filter(filter);
+        paint.set_alpha_f(self.current_state.alpha);
+        self.canvas.save_layer(&skia_safe::canvas::SaveLayerRec::default().paint(&paint));
+        self.state_stack.push(self.current_state);
+        self.current_state.alpha = 1.0;
+
+        i_slint_core::item_rendering::render_item_children(
+            self,
+            &item_rc.component(),
+            item_rc.index() as isize,
+        );
+
+        self.current_state = self.state_stack.pop().unwrap();
+        self.canvas.restore();
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
 }
 
 pub fn from_skia_rect(rect: &skia_safe::Rect) -> i_slint_core::graphics::Rect {
@@ -753,6 +926,24 @@ pub fn to_skia_color(col: &Color) -> skia_safe::Color {
     skia_safe::Color::from_argb(col.alpha(), col.red(), col.green(), col.blue())
 }
 
+/// Enables the underline and strike-through decorations of the text style, in the color of the text
+fn set_text_decorations(
+    text_style: &mut skia_safe::textlayout::TextStyle,
+    underline: bool,
+    strikethrough: bool,
+    color: Brush,
+) {
+    let mut decoration = skia_safe::textlayout::TextDecoration::NO_DECORATION;
+    if underline {
+        decoration |= skia_safe::textlayout::TextDecoration::UNDERLINE;
+    }
+    if strikethrough {
+        decoration |= skia_safe::textlayout::TextDecoration::LINE_THROUGH;
+    }
+    text_style.set_decoration_type(decoration);
+    text_style.set_decoration_color(to_skia_color(&color.color()));
+}
+
 fn adjust_rect_and_border_for_inner_drawing(rect: &mut skia_safe::Rect, border_width: &mut f32) {
     // If the border width exceeds the width, just fill the rectangle.
     *border_width = border_width.min((rect.width() as f32) / 2.);
@@ -771,7 +962,7 @@ fn adjust_to_image_fit(
     dest_rect: &mut skia_safe::Rect,
 ) {
     match image_fit {
-        ImageFit::Fill => (),
+        ImageFit::Fill | ImageFit::Tile => (),
         ImageFit::Cover => {
             let ratio = (dest_rect.width() / source_rect.width())
                 .max(dest_rect.height() / source_rect.height());
@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 
 use i_slint_core::items;
+use i_slint_core::styled_text::{StyledText, StyledTextSpan};
 use i_slint_core::{graphics::FontRequest, Coord};
 
 pub const DEFAULT_FONT_SIZE: f32 = 12.;
@@ -37,18 +38,70 @@ struct FontCache {
     }
 }
 
+/// The options of the paragraph created by [`create_layout`] and [`create_styled_layout`]
+#[derive(Clone, Copy, Default)]
+pub struct LayoutOptions {
+    /// The width at which the lines are wrapped, in physical pixels
+    pub max_width: Option<Coord>,
+    pub h_align: items::TextHorizontalAlignment,
+    pub overflow: items::TextOverflow,
+    /// The height of the lines in physical pixels, or None to use the height of the font
+    pub line_height: Option<Coord>,
+    /// The maximum number of lines to show, or None if the number of lines is not limited
+    pub max_lines: Option<usize>,
+}
+
 pub fn create_layout(
     font_request: FontRequest,
     scale_factor: f32,
     text: &str,
     text_style: Option<skia_safe::textlayout::TextStyle>,
-    max_width: Option<Coord>,
-    h_align: items::TextHorizontalAlignment,
-    overflow: items::TextOverflow,
+    options: LayoutOptions,
+) -> skia_safe::textlayout::Paragraph {
+    create_layout_with_spans(font_request, scale_factor, text, &[], text_style, options)
+}
+
+/// Same as [`create_layout`], but each span of the styled text is added with its own style
+pub fn create_styled_layout(
+    font_request: FontRequest,
+    scale_factor: f32,
+    styled_text: &StyledText,
+    text_style: Option<skia_safe::textlayout::TextStyle>,
+    options: LayoutOptions,
+) -> skia_safe::textlayout::Paragraph {
+    create_layout_with_spans(
+        font_request,
+        scale_factor,
+        &styled_text.text,
+        &styled_text.spans,
+        text_style,
+        options,
+    )
+}
+
+fn font_style(font_request: &FontRequest) -> skia_safe::FontStyle {
+    skia_safe::FontStyle::new(
+        font_request.weight.map_or(skia_safe::font_style::Weight::NORMAL, |w| w.into()),
+        skia_safe::font_style::Width::NORMAL,
+        if font_request.italic {
+            skia_safe::font_style::Slant::Italic
+        } else {
+            skia_safe::font_style::Slant::Upright
+        },
+    )
+}
+
+fn create_layout_with_spans(
+    font_request: FontRequest,
+    scale_factor: f32,
+    text: &str,
+    spans: &[StyledTextSpan],
+    text_style: Option<skia_safe::textlayout::TextStyle>,
+    LayoutOptions { max_width, h_align, overflow, line_height, max_lines }: LayoutOptions,
 ) -> skia_safe::textlayout::Paragraph {
     let mut text_style = text_style.unwrap_or_default();
 
-    if let Some(family_name) = font_request.family {
+    if let Some(family_name) = &font_request.family {
         text_style.set_font_families(&[family_name.as_str()]);
     }
 
@@ -58,11 +111,7 @@ pub fn create_layout(
         text_style.set_letter_spacing(letter_spacing * scale_factor);
     }
     text_style.set_font_size(pixel_size);
-    text_style.set_font_style(skia_safe::FontStyle::new(
-        font_request.weight.map_or(skia_safe::font_style::Weight::NORMAL, |w| w.into()),
-        skia_safe::font_style::Width::NORMAL,
-        skia_safe::font_style::Slant::Upright,
-    ));
+    text_style.set_font_style(font_style(&font_request));
 
     let mut style = skia_safe::textlayout::ParagraphStyle::new();
 
@@ -70,6 +119,21 @@ pub fn create_layout(
         style.set_ellipsis("…");
     }
 
+    if let Some(max_lines) = max_lines {
+        style.set_max_lines(max_lines);
+    }
+
+    if let Some(line_height) = line_height {
+        // Force all the lines to have the same height
+        let mut strut_style = skia_safe::textlayout::StrutStyle::new();
+        strut_style.set_strut_enabled(true);
+        strut_style.set_force_strut_height(true);
+        strut_style.set_font_size(pixel_size);
+        strut_style.set_height(line_height / pixel_size);
+        strut_style.set_height_override(true);
+        style.set_strut_style(strut_style);
+    }
+
     style.set_text_align(match h_align {
         items::TextHorizontalAlignment::Left => skia_safe::textlayout::TextAlign::Left,
         items::TextHorizontalAlignment::Center => skia_safe::textlayout::TextAlign::Center,
@@ -80,7 +144,26 @@ pub fn create_layout(
         skia_safe::textlayout::ParagraphBuilder::new(&style, font_cache.font_collection.clone())
     });
     builder.push_style(&text_style);
-    builder.add_text(text);
+    if spans.is_empty() {
+        builder.add_text(text);
+    } else {
+        for span in spans {
+            let mut span_style = text_style.clone();
+            if !span.has_default_font() {
+                span_style.set_font_style(font_style(&span.font_request(&font_request)));
+            }
+            if let Some(color) = span.color {
+                let color = super::itemrenderer::to_skia_color(&color);
+                let mut paint = skia_safe::Paint::default();
+                paint.set_color(color);
+                span_style.set_foreground_color(paint);
+                span_style.set_decoration_color(color);
+            }
+            builder.push_style(&span_style);
+            builder.add_text(&text[span.range.clone()]);
+            builder.pop();
+        }
+    }
     let mut paragraph = builder.build();
     paragraph.layout(max_width.unwrap_or(core::f32::MAX));
     paragraph
@@ -144,3 +227,50 @@ pub fn cursor_rect(
     );
     boxes.into_iter().next()
 }
+
+/// Returns the boxes covering the given byte range of the string, one per line
+pub fn range_rects(
+    string: &str,
+    range: core::ops::Range<usize>,
+    layout: &skia_safe::textlayout::Paragraph,
+) -> Vec<skia_safe::textlayout::TextBox> {
+    let utf16_start = string[..range.start].chars().map(char::len_utf16).sum::<usize>();
+    let utf16_end = utf16_start + string[range].chars().map(char::len_utf16).sum::<usize>();
+    layout.get_rects_for_range(
+        utf16_start..utf16_end,
+        skia_safe::textlayout::RectHeightStyle::Max,
+        skia_safe::textlayout::RectWidthStyle::Tight,
+    )
+}
+
+/// Returns the byte offset in `string` of the character whose glyph contains the given position,
+/// if any.
+pub fn byte_offset_for_position(
+    string: &str,
+    (x, y): (f32, f32),
+    layout: &skia_safe::textlayout::Paragraph,
+) -> Option<usize> {
+    let utf16_offset = layout.get_glyph_position_at_coordinate((x, y)).position.max(0) as usize;
+    // The position is the nearest caret position, so the character can be on either side of it
+    let utf16_hit = [utf16_offset, utf16_offset.saturating_sub(1)].into_iter().find(|offset| {
+        layout
+            .get_rects_for_range(
+                *offset..*offset + 1,
+                skia_safe::textlayout::RectHeightStyle::Max,
+                skia_safe::textlayout::RectWidthStyle::Tight,
+            )
+            .iter()
+            .any(|text_box| {
+                let rect = text_box.rect;
+                (rect.left..rect.right).contains(&x) && (rect.top..rect.bottom).contains(&y)
+            })
+    })?;
+    let mut utf16_count = 0;
+    string
+        .char_indices()
+        .find(|(_, ch)| {
+            utf16_count += ch.len_utf16();
+            utf16_hit < utf16_count
+        })
+        .map(|(byte_offset, _)| byte_offset)
+}
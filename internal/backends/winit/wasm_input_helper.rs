@@ -21,6 +21,7 @@
 use std::rc::{Rc, Weak};
 
 use i_slint_core::input::{KeyEvent, KeyEventType, KeyboardModifiers};
+use i_slint_core::items::InputType;
 use i_slint_core::window::{WindowAdapter, WindowInner};
 use i_slint_core::SharedString;
 use wasm_bindgen::closure::Closure;
@@ -206,7 +207,14 @@ pub fn has_focus(&self) -> bool {
         self.input.matches(":focus").unwrap_or(false)
     }
 
-    pub fn show(&self) {
+    pub fn show(&self, input_type: InputType) {
+        // Hint for the virtual keyboard layout
+        let input_mode = match input_type {
+            InputType::Number => "numeric",
+            InputType::Decimal => "decimal",
+            _ => "text",
+        };
+        self.input.set_attribute("inputmode", input_mode).unwrap();
         self.input.style().set_property("visibility", "visible").unwrap();
         self.input.focus().unwrap();
     }
@@ -57,6 +57,26 @@ enum TextOverflow {
                 Elide,
             }
 
+            /// This enum describes how the `text` property of a Text element is interpreted.
+            enum TextFormat {
+                /// The text is displayed as is.
+                Plain,
+                /// The text is a markup with a subset of HTML tags: `<b>`, `<i>`, `<font color="...">`, `<a href="...">` and `<br>`.
+                Markup,
+            }
+
+            /// This enum describes in which direction an animation plays its iterations.
+            enum AnimationDirection {
+                /// Every iteration goes from the start value to the end value.
+                Normal,
+                /// Every iteration goes from the end value to the start value.
+                Reverse,
+                /// The direction is reversed every other iteration, starting with a normal one.
+                Alternate,
+                /// The direction is reversed every other iteration, starting with a reversed one.
+                AlternateReverse,
+            }
+
             /// This enum describes whether an event was rejected or accepted by an event handler.
             enum EventResult {
                 /// The event is rejected by this event handler and may then be handled by the parent item
@@ -194,6 +214,8 @@ enum ImageFit {
                 Fill,
                 Contain,
                 Cover,
+                /// The image is repeated at its natural size, starting at the top left corner
+                Tile,
             }
 
             enum ImageRendering {
@@ -201,14 +223,17 @@ enum ImageRendering {
                 Pixelated,
             }
 
-            /// This enum is used to define the type of the input field. Currently this only differentiates between
-            /// text and password inputs but in the future it could be expanded to also define what type of virtual keyboard
-            /// should be shown, for example.
+            /// This enum is used to define the type of the input field. It defines how the characters are shown,
+            /// which characters are accepted, and what type of virtual keyboard should be shown.
             enum InputType {
                 /// The default value. This will render all characters normally
                 Text,
                 /// This will render all characters with a character that defaults to "*"
                 Password,
+                /// Only accepts integer numbers, optionally limited by the `minimum` and `maximum` properties
+                Number,
+                /// Only accepts decimal numbers, optionally limited by the `minimum` and `maximum` properties
+                Decimal,
             }
 
             /// Enum representing the alignment property of a BoxLayout or HorizontalLayout
@@ -221,6 +246,19 @@ enum LayoutAlignment {
                 SpaceAround,
             }
 
+            /// This enum represents the value of the `stack-horizontal-alignment` and `stack-vertical-alignment`
+            /// properties which can be added to any element within a `StackLayout`
+            enum StackAlignment {
+                /// The element takes the whole size of the layout, within its minimum and maximum size
+                Stretch,
+                /// The element is placed at the beginning of the layout with its preferred size
+                Start,
+                /// The element is centered within the layout with its preferred size
+                Center,
+                /// The element is placed at the end of the layout with its preferred size
+                End,
+            }
+
             /// PathEvent is a low-level data structure describing the composition of a path. Typically it is
             /// generated at compile time from a higher-level description, such as SVG commands.
             enum PathEvent {
@@ -258,6 +296,42 @@ enum AccessibleRole {
                 /// The role for a Text element. It is automatically applied.
                 Text,
             }
+
+            /// This enum describes the icon and severity of a message dialog.
+            enum MessageDialogKind {
+                /// An informative message
+                Information,
+                /// A warning
+                Warning,
+                /// An error
+                Error,
+                /// A question to the user
+                Question,
+            }
+
+            /// This enum describes the set of buttons shown in a message dialog.
+            enum MessageDialogButtons {
+                /// A single "Ok" button
+                Ok,
+                /// "Ok" and "Cancel" buttons
+                OkCancel,
+                /// "Yes" and "No" buttons
+                YesNo,
+                /// "Yes", "No" and "Cancel" buttons
+                YesNoCancel,
+            }
+
+            /// This enum describes the button the user clicked to close a message dialog.
+            enum MessageDialogResult {
+                /// The "Ok" button
+                Ok,
+                /// The "Cancel" button, or the dialog was closed without clicking any button
+                Cancel,
+                /// The "Yes" button
+                Yes,
+                /// The "No" button
+                No,
+            }
         ];
     };
 }
@@ -6,7 +6,7 @@
 
 use crate::diagnostics::{BuildDiagnostics, Spanned};
 use crate::expression_tree::{
-    BuiltinFunction, BuiltinMacroFunction, EasingCurve, Expression, Unit,
+    ArrayIterationKind, BuiltinFunction, BuiltinMacroFunction, EasingCurve, Expression, Unit,
 };
 use crate::langtype::{EnumerationValue, Type};
 use crate::parser::NodeOrToken;
@@ -54,10 +54,70 @@ pub fn lower_macro(
 
             expr
         }
+        BuiltinMacroFunction::Spring => {
+            let args = number_literal_arguments(n.clone(), sub_expr.collect(), "spring", diag);
+            let [stiffness, damping, mass] = args.unwrap_or([1., 1., 1.]);
+            if args.is_some() && !(stiffness > 0. && damping > 0. && mass > 0.) {
+                diag.push_error(
+                    "The stiffness, damping and mass of a spring must be positive".into(),
+                    &n,
+                );
+            }
+            Expression::EasingCurve(EasingCurve::Spring(stiffness, damping, mass))
+        }
+        BuiltinMacroFunction::Steps => {
+            let args = number_literal_arguments(n.clone(), sub_expr.collect(), "steps", diag);
+            let [steps] = args.unwrap_or([1.]);
+            if args.is_some() && (steps < 1. || steps.fract() != 0.) {
+                diag.push_error("The number of steps must be a positive integer".into(), &n);
+            }
+            Expression::EasingCurve(EasingCurve::Steps(steps.max(1.) as u32))
+        }
         BuiltinMacroFunction::Rgb => rgb_macro(n, sub_expr.collect(), diag),
+        BuiltinMacroFunction::ArrayIndexOf => array_index_of_macro(n, sub_expr.collect(), diag),
+        BuiltinMacroFunction::ArrayContains => Expression::BinaryExpression {
+            lhs: Box::new(array_index_of_macro(n, sub_expr.collect(), diag)),
+            rhs: Box::new(Expression::NumberLiteral(-1., Unit::None)),
+            op: '!',
+        },
+        BuiltinMacroFunction::ArraySum => array_sum_macro(n, sub_expr.collect(), diag),
+        BuiltinMacroFunction::ArraySlice => array_slice_macro(n, sub_expr.collect(), diag),
+        BuiltinMacroFunction::ArrayFilter | BuiltinMacroFunction::ArrayMap => {
+            // The lambda argument is resolved by the resolving pass, which does not call this function
+            diag.push_error("Expected an argument of the form 'item => expression'".into(), &n);
+            Expression::Invalid
+        }
     }
 }
 
+/// Returns the value of the `N` arguments of an easing function, which must be number literals.
+/// Reports an error and returns None if the arguments are not valid.
+fn number_literal_arguments<const N: usize>(
+    node: Option<NodeOrToken>,
+    args: Vec<(Expression, Option<NodeOrToken>)>,
+    name: &str,
+    diag: &mut BuildDiagnostics,
+) -> Option<[f32; N]> {
+    if args.len() != N {
+        diag.push_error(
+            format!("Expected {} argument{} for {}", N, if N == 1 { "" } else { "s" }, name),
+            &node,
+        );
+        return None;
+    }
+    let mut result = [0.; N];
+    for (r, (expr, n)) in result.iter_mut().zip(args) {
+        match expr {
+            Expression::NumberLiteral(val, Unit::None) => *r = val as f32,
+            _ => {
+                diag.push_error(format!("Arguments to {} must be number literal", name), &n);
+                return None;
+            }
+        }
+    }
+    Some(result)
+}
+
 fn min_max_macro(
     node: Option<NodeOrToken>,
     op: char,
@@ -337,6 +397,108 @@ fn to_debug_string(
     }
 }
 
+/// Returns unique names for the `item` and `index` local variables of an [`Expression::ArrayIteration`]
+pub fn array_iteration_variables() -> (String, String) {
+    let id = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    (format!("array_item{}", id), format!("array_index{}", id))
+}
+
+/// Store the value in a local variable with an unique name, and return the expression to read it
+fn store_in_local_variable(
+    value: Expression,
+    prefix: &str,
+    statements: &mut Vec<Expression>,
+) -> Box<Expression> {
+    let name = format!("{}{}", prefix, COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+    let ty = value.ty();
+    statements.push(Expression::StoreLocalVariable { name: name.clone(), value: Box::new(value) });
+    Box::new(Expression::ReadLocalVariable { name, ty })
+}
+
+fn array_index_of_macro(
+    node: Option<NodeOrToken>,
+    args: Vec<(Expression, Option<NodeOrToken>)>,
+    diag: &mut BuildDiagnostics,
+) -> Expression {
+    if args.len() != 2 {
+        diag.push_error("Needs 1 argument".into(), &node);
+        return Expression::Invalid;
+    }
+    let mut args = args.into_iter();
+    let (array, _) = args.next().unwrap();
+    let (value, value_node) = args.next().unwrap();
+    let item_ty = match array.ty() {
+        Type::Array(ty) => *ty,
+        _ => return Expression::Invalid,
+    };
+    let mut statements = vec![];
+    let value = store_in_local_variable(
+        value.maybe_convert_to(item_ty.clone(), &value_node, diag),
+        "index_of_value",
+        &mut statements,
+    );
+    let (item, index) = array_iteration_variables();
+    statements.push(Expression::ArrayIteration {
+        kind: ArrayIterationKind::FindIndex,
+        array: Box::new(array),
+        expression: Box::new(Expression::BinaryExpression {
+            lhs: Box::new(Expression::ReadLocalVariable { name: item.clone(), ty: item_ty }),
+            rhs: value,
+            op: '=',
+        }),
+        item,
+        index,
+    });
+    Expression::CodeBlock(statements)
+}
+
+fn array_sum_macro(
+    node: Option<NodeOrToken>,
+    args: Vec<(Expression, Option<NodeOrToken>)>,
+    diag: &mut BuildDiagnostics,
+) -> Expression {
+    if args.len() != 1 {
+        diag.push_error("Needs no argument".into(), &node);
+        return Expression::Invalid;
+    }
+    let (array, array_node) = args.into_iter().next().unwrap();
+    let item_ty = match array.ty() {
+        Type::Array(ty) => *ty,
+        _ => return Expression::Invalid,
+    };
+    if !matches!(item_ty, Type::Int32 | Type::Float32) && item_ty.default_unit().is_none() {
+        diag.push_error(format!("Cannot compute the sum of an array of {}", item_ty), &array_node);
+        return Expression::Invalid;
+    }
+    let (item, index) = array_iteration_variables();
+    Expression::ArrayIteration {
+        kind: ArrayIterationKind::Sum,
+        array: Box::new(array),
+        expression: Box::new(Expression::ReadLocalVariable { name: item.clone(), ty: item_ty }),
+        item,
+        index,
+    }
+}
+
+fn array_slice_macro(
+    node: Option<NodeOrToken>,
+    args: Vec<(Expression, Option<NodeOrToken>)>,
+    diag: &mut BuildDiagnostics,
+) -> Expression {
+    if args.len() != 2 && args.len() != 3 {
+        diag.push_error("Needs 1 or 2 arguments".into(), &node);
+        return Expression::Invalid;
+    }
+    let mut args = args.into_iter();
+    let (array, _) = args.next().unwrap();
+    let mut bounds = args.map(|(value, value_node)| {
+        Box::new(value.maybe_convert_to(Type::Int32, &value_node, diag))
+    });
+    let start = bounds.next().unwrap();
+    let end = bounds.next();
+    Expression::ArraySlice { array: Box::new(array), start, end }
+}
+
 /// Generate an expression which is like `min(lhs, rhs)` if op is '<' or `max(lhs, rhs)` if op is '>'.
 /// counter is an unique id.
 /// The rhs and lhs of the expression must have the same numerical type
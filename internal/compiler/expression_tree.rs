@@ -36,10 +36,30 @@ pub enum BuiltinFunction {
     Pow,
     SetFocusItem,
     ShowPopupWindow,
+    ShowSecondaryWindow,
+    HideSecondaryWindow,
+    TextInputUndo,
+    TextInputRedo,
+    ShowFileDialog,
+    ShowMessageDialog,
     /// the "42".to_float()
     StringToFloat,
     /// the "42".is_float()
     StringIsFloat,
+    /// the "abc".to-uppercase()
+    StringToUppercase,
+    /// the "ABC".to-lowercase()
+    StringToLowercase,
+    /// the "abc".length
+    StringLength,
+    /// the "abc".substring(start, end)
+    StringSubstring,
+    /// the "abc".contains("b")
+    StringContains,
+    /// the 3.1415.to-fixed(2)
+    NumberToFixed,
+    /// the 3.1415.to-precision(3)
+    NumberToPrecision,
     ColorBrighter,
     ColorDarker,
     ImageSize,
@@ -65,11 +85,40 @@ pub enum BuiltinMacroFunction {
     /// Add the right conversion operations so that the return type is the same as the argument type
     Mod,
     CubicBezier,
+    /// `spring(stiffness, damping, mass)`: the arguments must be number literals
+    Spring,
+    /// `steps(n)`: the argument must be a positive integer literal
+    Steps,
     /// The argument can be r,g,b,a or r,g,b and they can be percentages or integer.
     /// transform the argument so it is always rgb(r, g, b, a) with r, g, b between 0 and 255.
     Rgb,
     /// transform `debug(a, b, c)` into debug `a + " " + b + " " + c`
     Debug,
+    /// `array.index-of(value)`: an [`ArrayIterationKind::FindIndex`] comparing each row with the value
+    ArrayIndexOf,
+    /// `array.contains(value)`: like `ArrayIndexOf`, but returns whether the index is valid
+    ArrayContains,
+    /// `array.sum()`: an [`ArrayIterationKind::Sum`] of the rows
+    ArraySum,
+    /// `array.slice(start, end)`: an [`Expression::ArraySlice`]
+    ArraySlice,
+    /// `array.filter(item => condition)`. The lambda argument is resolved by the resolving pass.
+    ArrayFilter,
+    /// `array.map(item => expression)`. The lambda argument is resolved by the resolving pass.
+    ArrayMap,
+}
+
+/// What an [`Expression::ArrayIteration`] computes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayIterationKind {
+    /// A model with the rows for which the expression is true. It follows the changes of the array.
+    Filter,
+    /// A model with the value of the expression for each row, evaluated when the row is read
+    Map,
+    /// The index of the first row for which the expression is true, or -1
+    FindIndex,
+    /// The sum of the value of the expression for each row
+    Sum,
 }
 
 impl BuiltinFunction {
@@ -113,12 +162,46 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
+            BuiltinFunction::ShowSecondaryWindow | BuiltinFunction::HideSecondaryWindow => {
+                Type::Function {
+                    return_type: Box::new(Type::Void),
+                    args: vec![Type::ElementReference],
+                }
+            }
+            BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => Type::Function {
+                return_type: Box::new(Type::Void),
+                args: vec![Type::ElementReference],
+            },
+            BuiltinFunction::ShowFileDialog | BuiltinFunction::ShowMessageDialog => {
+                Type::Function {
+                    return_type: Box::new(Type::Void),
+                    args: vec![Type::ElementReference],
+                }
+            }
             BuiltinFunction::StringToFloat => {
                 Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::String] }
             }
             BuiltinFunction::StringIsFloat => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![Type::String] }
             }
+            BuiltinFunction::StringToUppercase | BuiltinFunction::StringToLowercase => {
+                Type::Function { return_type: Box::new(Type::String), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringLength => {
+                Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringSubstring => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::Int32, Type::Int32],
+            },
+            BuiltinFunction::StringContains => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::NumberToFixed | BuiltinFunction::NumberToPrecision => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::Float32, Type::Int32],
+            },
             BuiltinFunction::ImplicitLayoutInfo(_) => Type::Function {
                 return_type: Box::new(crate::layout::layout_info_type()),
                 args: vec![Type::ElementReference],
@@ -162,8 +245,27 @@ pub fn ty(&self) -> Type {
         }
     }
 
+    /// Returns true if calling this function changes the state of the program (focus, open windows, ...),
+    /// which is not allowed from within a user defined function
+    pub fn has_side_effects(&self) -> bool {
+        matches!(
+            self,
+            BuiltinFunction::SetFocusItem
+                | BuiltinFunction::ShowPopupWindow
+                | BuiltinFunction::ShowSecondaryWindow
+                | BuiltinFunction::HideSecondaryWindow
+                | BuiltinFunction::TextInputUndo
+                | BuiltinFunction::TextInputRedo
+                | BuiltinFunction::ShowFileDialog
+                | BuiltinFunction::ShowMessageDialog
+                | BuiltinFunction::RegisterCustomFontByPath
+                | BuiltinFunction::RegisterCustomFontByMemory
+                | BuiltinFunction::RegisterBitmapFont
+        )
+    }
+
     /// It is pure if the return value only depends on its argument and has no side effect
-    fn is_pure(&self) -> bool {
+    pub(crate) fn is_pure(&self) -> bool {
         match self {
             BuiltinFunction::GetWindowScaleFactor => false,
             BuiltinFunction::AnimationTick => false,
@@ -185,7 +287,16 @@ fn is_pure(&self) -> bool {
             | BuiltinFunction::ATan => true,
             BuiltinFunction::SetFocusItem => false,
             BuiltinFunction::ShowPopupWindow => false,
+            BuiltinFunction::ShowSecondaryWindow | BuiltinFunction::HideSecondaryWindow => false,
+            BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => false,
+            BuiltinFunction::ShowFileDialog | BuiltinFunction::ShowMessageDialog => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
+            BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringLength
+            | BuiltinFunction::StringSubstring
+            | BuiltinFunction::StringContains => true,
+            BuiltinFunction::NumberToFixed | BuiltinFunction::NumberToPrecision => true,
             BuiltinFunction::ColorBrighter | BuiltinFunction::ColorDarker => true,
             // ImageSize is pure, except when loading images via the network. Then the initial size will be 0/0 and
             // we need to make sure that calls to this function stay within a binding, so that the property
@@ -331,9 +442,14 @@ pub enum Expression {
     /// Note: if we are to separate expression and statement, we probably do not need to have callback reference within expressions
     CallbackReference(NamedReference),
 
-    /// Reference to the callback <name> in the <element>
+    /// Reference to the property <name> in the <element>
     PropertyReference(NamedReference),
 
+    /// Reference to the function <name> in the <element>
+    ///
+    /// Only valid as the function of a FunctionCall
+    FunctionReference(NamedReference),
+
     /// Reference to a function built into the run-time, implemented natively
     BuiltinFunctionReference(BuiltinFunction, Option<SourceLocation>),
 
@@ -402,6 +518,26 @@ pub enum Expression {
         index: Box<Expression>,
     },
 
+    /// Evaluate the expression for each row of the array. While evaluating the expression, the data of
+    /// the current row is in the local variable named `item`, and its index in the local variable named `index`.
+    ArrayIteration {
+        kind: ArrayIterationKind,
+        /// This expression should have [`Type::Array`] type
+        array: Box<Expression>,
+        item: String,
+        index: String,
+        expression: Box<Expression>,
+    },
+
+    /// A model with the rows of the array from `start` up to, but not including, `end`
+    /// (or up to the last row if there is no end). It follows the changes of the array.
+    ArraySlice {
+        /// This expression should have [`Type::Array`] type
+        array: Box<Expression>,
+        start: Box<Expression>,
+        end: Option<Box<Expression>>,
+    },
+
     /// Cast an expression to the given type
     Cast {
         from: Box<Expression>,
@@ -507,6 +643,7 @@ pub fn ty(&self) -> Type {
             Expression::NumberLiteral(_, unit) => unit.ty(),
             Expression::BoolLiteral(_) => Type::Bool,
             Expression::CallbackReference(nr) => nr.ty(),
+            Expression::FunctionReference(nr) => nr.ty(),
             Expression::PropertyReference(nr) => nr.ty(),
             Expression::BuiltinFunctionReference(funcref, _) => funcref.ty(),
             Expression::MemberFunction { member, .. } => member.ty(),
@@ -623,6 +760,13 @@ pub fn ty(&self) -> Type {
             Expression::PathData { .. } => Type::PathData,
             Expression::StoreLocalVariable { .. } => Type::Void,
             Expression::ReadLocalVariable { ty, .. } => ty.clone(),
+            Expression::ArrayIteration { kind, array, expression, .. } => match kind {
+                ArrayIterationKind::Filter => array.ty(),
+                ArrayIterationKind::Map => Type::Array(Box::new(expression.ty())),
+                ArrayIterationKind::FindIndex => Type::Int32,
+                ArrayIterationKind::Sum => expression.ty(),
+            },
+            Expression::ArraySlice { array, .. } => array.ty(),
             Expression::EasingCurve(_) => Type::Easing,
             Expression::LinearGradient { .. } => Type::Brush,
             Expression::RadialGradient { .. } => Type::Brush,
@@ -644,6 +788,7 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
             Expression::NumberLiteral(_, _) => {}
             Expression::BoolLiteral(_) => {}
             Expression::CallbackReference { .. } => {}
+            Expression::FunctionReference { .. } => {}
             Expression::PropertyReference { .. } => {}
             Expression::FunctionParameterReference { .. } => {}
             Expression::BuiltinFunctionReference { .. } => {}
@@ -706,6 +851,17 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
             },
             Expression::StoreLocalVariable { value, .. } => visitor(&**value),
             Expression::ReadLocalVariable { .. } => {}
+            Expression::ArrayIteration { array, expression, .. } => {
+                visitor(&**array);
+                visitor(&**expression);
+            }
+            Expression::ArraySlice { array, start, end } => {
+                visitor(&**array);
+                visitor(&**start);
+                if let Some(end) = end {
+                    visitor(&**end);
+                }
+            }
             Expression::EasingCurve(_) => {}
             Expression::LinearGradient { angle, stops } => {
                 visitor(angle);
@@ -740,6 +896,7 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
             Expression::NumberLiteral(_, _) => {}
             Expression::BoolLiteral(_) => {}
             Expression::CallbackReference { .. } => {}
+            Expression::FunctionReference { .. } => {}
             Expression::PropertyReference { .. } => {}
             Expression::FunctionParameterReference { .. } => {}
             Expression::BuiltinFunctionReference { .. } => {}
@@ -805,6 +962,17 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
             },
             Expression::StoreLocalVariable { value, .. } => visitor(&mut **value),
             Expression::ReadLocalVariable { .. } => {}
+            Expression::ArrayIteration { array, expression, .. } => {
+                visitor(&mut **array);
+                visitor(&mut **expression);
+            }
+            Expression::ArraySlice { array, start, end } => {
+                visitor(&mut **array);
+                visitor(&mut **start);
+                if let Some(end) = end {
+                    visitor(&mut **end);
+                }
+            }
             Expression::EasingCurve(_) => {}
             Expression::LinearGradient { angle, stops } => {
                 visitor(&mut *angle);
@@ -851,6 +1019,7 @@ pub fn is_constant(&self) -> bool {
             Expression::NumberLiteral(_, _) => true,
             Expression::BoolLiteral(_) => true,
             Expression::CallbackReference { .. } => false,
+            Expression::FunctionReference { .. } => false,
             Expression::PropertyReference(nr) => nr.is_constant(),
             Expression::BuiltinFunctionReference(func, _) => func.is_pure(),
             Expression::MemberFunction { .. } => false,
@@ -886,6 +1055,8 @@ pub fn is_constant(&self) -> bool {
             Expression::StoreLocalVariable { .. } => false,
             // we should somehow find out if this is constant or not
             Expression::ReadLocalVariable { .. } => false,
+            Expression::ArrayIteration { .. } => false,
+            Expression::ArraySlice { .. } => false,
             Expression::EasingCurve(_) => true,
             Expression::LinearGradient { angle, stops } => {
                 angle.is_constant() && stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
@@ -1283,6 +1454,15 @@ pub struct PathElement {
 pub enum EasingCurve {
     Linear,
     CubicBezier(f32, f32, f32, f32),
+    /// stiffness, damping, mass
+    Spring(f32, f32, f32),
+    EaseInElastic,
+    EaseOutElastic,
+    EaseInOutElastic,
+    EaseInBounce,
+    EaseOutBounce,
+    EaseInOutBounce,
+    Steps(u32),
     // CubicBezierNonConst([Box<Expression>; 4]),
     // Custom(Box<dyn Fn(f32)->f32>),
 }
@@ -1312,6 +1492,7 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
         Expression::NumberLiteral(vl, unit) => write!(f, "{}{}", vl, unit),
         Expression::BoolLiteral(b) => write!(f, "{:?}", b),
         Expression::CallbackReference(a) => write!(f, "{:?}", a),
+        Expression::FunctionReference(a) => write!(f, "{:?}", a),
         Expression::PropertyReference(a) => write!(f, "{:?}", a),
         Expression::BuiltinFunctionReference(a, _) => write!(f, "{:?}", a),
         Expression::MemberFunction { base, base_node: _, member } => {
@@ -1334,6 +1515,22 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
             pretty_print(f, value)
         }
         Expression::ReadLocalVariable { name, ty: _ } => write!(f, "{}", name),
+        Expression::ArrayIteration { kind, array, item, index, expression } => {
+            pretty_print(f, array)?;
+            write!(f, ".@{:?}(({}, {}) => ", kind, item, index)?;
+            pretty_print(f, expression)?;
+            write!(f, ")")
+        }
+        Expression::ArraySlice { array, start, end } => {
+            pretty_print(f, array)?;
+            write!(f, ".@slice(")?;
+            pretty_print(f, start)?;
+            if let Some(end) = end {
+                write!(f, ", ")?;
+                pretty_print(f, end)?;
+            }
+            write!(f, ")")
+        }
         Expression::StructFieldAccess { base, name } => {
             pretty_print(f, base)?;
             write!(f, ".{}", name)
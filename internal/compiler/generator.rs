@@ -385,6 +385,10 @@ fn handle_property_inner(
     let mut processed = HashSet::new();
     crate::object_tree::recurse_elem(&component.root_element, &(), &mut |elem: &ElementRc, ()| {
         for (prop_name, binding_expression) in &elem.borrow().bindings {
+            if is_function(elem, prop_name) {
+                // The binding of a function is its body, not the value of a property
+                continue;
+            }
             handle_property_inner(
                 &Rc::downgrade(component),
                 elem,
@@ -397,6 +401,14 @@ fn handle_property_inner(
     });
 }
 
+/// Returns true if `prop_name` is a function declared with `function` in that element
+pub fn is_function(elem: &ElementRc, prop_name: &str) -> bool {
+    elem.borrow()
+        .property_declarations
+        .get(prop_name)
+        .map_or(false, |d| matches!(d.property_type, Type::Function { .. }))
+}
+
 /// Call the given function for each constant property in the Component so one can set
 /// `set_constant` on it.
 pub fn for_each_const_properties(component: &Rc<Component>, mut f: impl FnMut(&ElementRc, &str)) {
@@ -104,6 +104,7 @@ pub enum Declaration {
         Function(Function),
         Var(Var),
         TypeAlias(TypeAlias),
+        Enum(Enum),
     }
 
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -262,6 +263,25 @@ fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         }
     }
 
+    #[derive(Default, Debug)]
+    pub struct Enum {
+        pub name: String,
+        pub values: Vec<String>,
+    }
+
+    impl Display for Enum {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            indent(f)?;
+            writeln!(f, "enum class {} {{", self.name)?;
+            for value in &self.values {
+                indent(f)?;
+                writeln!(f, "    {},", value)?;
+            }
+            indent(f)?;
+            writeln!(f, "}};")
+        }
+    }
+
     pub trait CppType {
         fn cpp_type(&self) -> Option<String>;
     }
@@ -286,7 +306,7 @@ pub fn escape_string(str: &str) -> String {
     }
 }
 
-use crate::expression_tree::{BuiltinFunction, EasingCurve};
+use crate::expression_tree::{ArrayIterationKind, BuiltinFunction, EasingCurve};
 use crate::langtype::{NativeClass, Type};
 use crate::layout::Orientation;
 use crate::llr::{
@@ -333,6 +353,9 @@ fn cpp_type(&self) -> Option<String> {
             Type::Array(i) => Some(format!("std::shared_ptr<slint::Model<{}>>", i.cpp_type()?)),
             Type::Image => Some("slint::Image".to_owned()),
             Type::Builtin(elem) => elem.native_class.cpp_type.clone(),
+            Type::Enumeration(enumeration) if enumeration.node.is_some() => {
+                Some(ident(&enumeration.name))
+            }
             Type::Enumeration(enumeration) => {
                 Some(format!("slint::cbindgen_private::{}", ident(&enumeration.name)))
             }
@@ -389,13 +412,37 @@ fn property_set_value_code(
     ctx: &EvaluationContext,
 ) -> String {
     let prop = access_member(property, ctx);
-    if let Some(animation) = ctx.current_sub_component.and_then(|c| c.animations.get(property)) {
+    if let Some((animation, keyframes)) =
+        ctx.current_sub_component.and_then(|c| c.animations.get(property))
+    {
         let animation_code = compile_expression(animation, ctx);
-        return format!("{}.set_animated_value({}, {})", prop, value_expr, animation_code);
+        if keyframes.is_empty() {
+            return format!("{}.set_animated_value({}, {})", prop, value_expr, animation_code);
+        }
+        let keyframes = compile_keyframes(keyframes, ctx.property_ty(property), ctx);
+        return format!(
+            "{}.set_animated_value({}, {}, {})",
+            prop, value_expr, animation_code, keyframes
+        );
     }
     format!("{}.set({})", prop, value_expr)
 }
 
+/// Returns the code of the initializer list with the keyframes of an animation
+fn compile_keyframes(keyframes: &[llr::KeyFrame], ty: &Type, ctx: &EvaluationContext) -> String {
+    let ty = ty.cpp_type().unwrap_or_default();
+    let keyframes = keyframes.iter().map(|k| {
+        format!(
+            "slint::cbindgen_private::KeyFrame<{ty}>{{ {position}, {ty}({value}), {easing} }}",
+            ty = ty,
+            position = k.position,
+            value = compile_expression(&k.value, ctx),
+            easing = compile_expression(&k.easing, ctx),
+        )
+    });
+    format!("{{ {} }}", keyframes.join(", "))
+}
+
 fn handle_property_init(
     prop: &llr::PropertyReference,
     binding_expression: &llr::BindingExpression,
@@ -441,25 +488,48 @@ fn handle_property_init(
                 format!("slint::private_api::set_state_binding({}, {});", prop_access, binding_code)
             } else {
                 match &binding_expression.animation {
-                    Some(llr::Animation::Static(anim)) => {
+                    Some(llr::Animation::Static(anim, keyframes)) if keyframes.is_empty() => {
                         let anim = compile_expression(anim, ctx);
                         format!("{}.set_animated_binding({}, {});", prop_access, binding_code, anim)
                     }
+                    Some(llr::Animation::Static(anim, keyframes)) => {
+                        let anim = compile_expression(anim, ctx);
+                        let keyframes = compile_keyframes(keyframes, prop_type, ctx);
+                        format!(
+                            "{}.set_animated_binding({}, {}, {});",
+                            prop_access, binding_code, anim, keyframes
+                        )
+                    }
                     Some(llr::Animation::Transition (
-                        anim
+                        anim, keyframes
                     )) => {
                         let anim = compile_expression(anim, ctx);
+                        let keyframes_cases = keyframes
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, keyframes)| !keyframes.is_empty())
+                            .map(|(index, keyframes)| {
+                                format!(
+                                    "case {}: *keyframes = {}; break;",
+                                    index,
+                                    compile_keyframes(keyframes, prop_type, ctx)
+                                )
+                            })
+                            .join(" ");
                         format!(
                             "{}.set_animated_binding_for_transition({},
-                            [this](uint64_t *start_time) -> slint::cbindgen_private::PropertyAnimation {{
+                            [this](uint64_t *start_time, std::vector<slint::cbindgen_private::KeyFrame<{}>> *keyframes) -> slint::cbindgen_private::PropertyAnimation {{
                                 [[maybe_unused]] auto self = this;
-                                auto [anim, time] = {};
+                                [[maybe_unused]] auto [anim, time, index] = {};
                                 *start_time = time;
+                                switch (int(index)) {{ {} default: keyframes->clear(); }}
                                 return anim;
                             }});",
                             prop_access,
                             binding_code,
+                            prop_type.cpp_type().unwrap_or_default(),
                             anim,
+                            keyframes_cases,
                         )
                     }
                     None => format!("{}.set_binding({});", prop_access, binding_code),
@@ -513,6 +583,17 @@ pub fn generate(doc: &Document) -> impl std::fmt::Display {
         },
     ));
 
+    for enumeration in doc.root_component.used_types.borrow().enums.iter() {
+        file.declarations.push(Declaration::Enum(Enum {
+            name: ident(&enumeration.name),
+            values: enumeration
+                .values
+                .iter()
+                .map(|value| ident(&crate::generator::to_pascal_case(value)))
+                .collect(),
+        }));
+    }
+
     for ty in doc.root_component.used_types.borrow().structs.iter() {
         if let Type::Struct { fields, name: Some(name), node: Some(_) } = ty {
             generate_struct(&mut file, name, fields);
@@ -639,6 +720,7 @@ fn generate_public_component(file: &mut File, component: &llr::PublicComponent)
         None,
         component_id,
         Access::Private, // Hide properties and other fields from the C++ API
+        false,
         file,
     );
 
@@ -770,8 +852,22 @@ fn generate_item_tree(
     parent_ctx: Option<ParentCtx>,
     item_tree_class_name: String,
     field_access: Access,
+    is_secondary_window: bool,
     file: &mut File,
 ) {
+    if is_secondary_window {
+        // The window need to be the first member so it is destroyed last
+        target_struct.members.push((
+            Access::Public,
+            Declaration::Var(Var {
+                ty: "slint::Window".into(),
+                name: "m_window".into(),
+                init: Some("slint::Window{slint::private_api::WindowAdapterRc()}".into()),
+                ..Default::default()
+            }),
+        ));
+    }
+
     target_struct.friends.push(format!(
         "vtable::VRc<slint::private_api::ComponentVTable, {}>",
         item_tree_class_name
@@ -787,6 +883,12 @@ fn generate_item_tree(
     );
 
     let root_access = if parent_ctx.is_some() { "parent->root" } else { "self" };
+    // A SecondaryWindow has its own window, the other components use the window of their parent
+    let window_ptr = if parent_ctx.is_none() || is_secondary_window {
+        "&self->m_window"
+    } else {
+        "parent->window_ptr"
+    };
 
     let mut item_tree_array: Vec<String> = Default::default();
     let mut item_array: Vec<String> = Default::default();
@@ -1096,19 +1198,16 @@ fn generate_item_tree(
         "self->self_weak = vtable::VWeak(self_rc).into_dyn();".into(),
     ];
 
-    if parent_ctx.is_none() {
-        create_code.extend([format!(
-            "{}->m_window.window_handle().set_component(*self_rc);",
-            root_access
-        )]);
+    if parent_ctx.is_none() || is_secondary_window {
+        create_code.push("self->m_window.window_handle().set_component(*self_rc);".into());
     }
 
     create_code.extend([
+        format!("({})->window_handle().register_component(self, self->item_array());", window_ptr),
         format!(
-            "{}->m_window.window_handle().register_component(self, self->item_array());",
-            root_access
+            "self->init({}, {}, self->self_weak, 0, 1 {});",
+            root_access, window_ptr, init_parent_parameters
         ),
-        format!("self->init({}, self->self_weak, 0, 1 {});", root_access, init_parent_parameters),
         format!("return slint::ComponentHandle<{0}>{{ self_rc }};", target_struct.name),
     ]);
 
@@ -1129,10 +1228,8 @@ fn generate_item_tree(
 
     let mut destructor = vec!["auto self = this;".to_owned()];
 
-    destructor.push(format!(
-        "{}->m_window.window_handle().unregister_component(self, item_array());",
-        root_access
-    ));
+    destructor
+        .push("self->window_ptr->window_handle().unregister_component(self, item_array());".into());
 
     target_struct.members.push((
         Access::Public,
@@ -1158,6 +1255,7 @@ fn generate_sub_component(
 
     let mut init_parameters = vec![
         format!("{} root", root_ptr_type),
+        "slint::Window *window".into(),
         "slint::cbindgen_private::ComponentWeak enclosing_component".into(),
         "uintptr_t tree_index".into(),
         "uintptr_t tree_index_of_first_child".into(),
@@ -1181,6 +1279,17 @@ fn generate_sub_component(
     ));
     init.push("self->root = root;".into());
 
+    // Not necessarily the same as the root's window if this is within a SecondaryWindow
+    target_struct.members.push((
+        field_access,
+        Declaration::Var(Var {
+            ty: "slint::Window *".into(),
+            name: "window_ptr".to_owned(),
+            ..Default::default()
+        }),
+    ));
+    init.push("self->window_ptr = window;".into());
+
     target_struct.members.push((
         field_access,
         Declaration::Var(Var {
@@ -1234,12 +1343,40 @@ fn generate_sub_component(
             Some(ParentCtx::new(&ctx, None)),
             component_id,
             Access::Public,
+            false,
             file,
         );
         file.definitions.extend(popup_struct.extract_definitions().collect::<Vec<_>>());
         file.declarations.push(Declaration::Struct(popup_struct));
     });
 
+    component.secondary_windows.iter().enumerate().for_each(|(idx, c)| {
+        let component_id = ident(&c.root.name);
+        let mut window_struct = Struct { name: component_id.clone(), ..Default::default() };
+        generate_item_tree(
+            &mut window_struct,
+            c,
+            root,
+            Some(ParentCtx::new(&ctx, None)),
+            component_id.clone(),
+            Access::Public,
+            true,
+            file,
+        );
+        file.definitions.extend(window_struct.extract_definitions().collect::<Vec<_>>());
+        file.declarations.push(Declaration::Struct(window_struct));
+
+        // The window is only created the first time it is shown
+        target_struct.members.push((
+            field_access,
+            Declaration::Var(Var {
+                ty: format!("mutable std::optional<slint::ComponentHandle<{}>>", component_id),
+                name: format!("secondary_window{}", idx),
+                ..Default::default()
+            }),
+        ));
+    });
+
     for property in component.properties.iter().filter(|p| p.use_count.get() > 0) {
         let cpp_name = ident(&property.name);
 
@@ -1281,7 +1418,7 @@ fn generate_sub_component(
         };
 
         init.push(format!(
-            "this->{}.init(root, self_weak.into_dyn(), {}, {});",
+            "this->{}.init(root, window, self_weak.into_dyn(), {}, {});",
             field_name, global_index, global_children
         ));
 
@@ -1454,6 +1591,23 @@ fn generate_sub_component(
     init.extend(properties_init_code);
     init.extend(component.init_code.iter().map(|e| compile_expression(&e.borrow(), &ctx)));
 
+    for (idx, (prop, code)) in component.change_callbacks.iter().enumerate() {
+        let change_tracker = format!("change_tracker{}", idx);
+        init.push(format!(
+            "self->{change_tracker}.init(self, [](auto self) {{ return {prop}.get(); }}, []([[maybe_unused]] auto self, auto) {{ {code}; }});",
+            prop = access_member(prop, &ctx),
+            code = compile_expression(&code.borrow(), &ctx)
+        ));
+        target_struct.members.push((
+            field_access,
+            Declaration::Var(Var {
+                ty: "slint::private_api::ChangeTracker".into(),
+                name: change_tracker,
+                ..Default::default()
+            }),
+        ));
+    }
+
     target_struct.members.push((
         field_access,
         Declaration::Function(Function {
@@ -1482,6 +1636,10 @@ fn generate_sub_component(
         }),
     ));
 
+    target_struct
+        .members
+        .extend(generate_functions(&component.functions, &ctx).map(|x| (Access::Public, x)));
+
     let mut accessible_function = |name: &str,
                                    signature: &str,
                                    forward_args: &str,
@@ -1607,6 +1765,7 @@ fn generate_repeated_component(
         Some(parent_ctx.clone()),
         repeater_id.clone(),
         Access::Public,
+        false,
         file,
     );
 
@@ -1766,6 +1925,10 @@ fn generate_global(file: &mut File, global: &llr::GlobalComponent, root: &llr::P
         Declaration::Var(Var { ty: root_ptr_type, name: "root".to_owned(), ..Default::default() }),
     ));
 
+    global_struct
+        .members
+        .extend(generate_functions(&global.functions, &ctx).map(|x| (Access::Public, x)));
+
     let declarations = generate_public_api_for_properties(&global.public_properties, &ctx);
     global_struct.members.extend(declarations.into_iter().map(|decl| (Access::Public, decl)));
 
@@ -1773,12 +1936,47 @@ fn generate_global(file: &mut File, global: &llr::GlobalComponent, root: &llr::P
     file.declarations.push(Declaration::Struct(global_struct));
 }
 
+/// Generate the member functions for the functions declared in a sub component or a global
+fn generate_functions<'a>(
+    functions: &'a [llr::Function],
+    ctx: &'a EvaluationContext,
+) -> impl Iterator<Item = Declaration> + 'a {
+    functions.iter().map(|f| {
+        let mut ctx2 = ctx.clone();
+        ctx2.argument_types = &f.args;
+        let code = compile_expression_wrap_return(&f.code.borrow(), &ctx2);
+        let body = if f.ret_ty == Type::Void {
+            vec!["[[maybe_unused]] auto self = this;".into(), format!("{};", code)]
+        } else {
+            vec!["[[maybe_unused]] auto self = this;".into(), format!("return {};", code)]
+        };
+        Declaration::Function(Function {
+            name: ident(&format!("fn_{}", f.name)),
+            signature: format!(
+                "({}) const -> {}",
+                f.args
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| format!(
+                        "[[maybe_unused]] {} arg_{}",
+                        ty.cpp_type().unwrap_or_default(),
+                        i
+                    ))
+                    .join(", "),
+                f.ret_ty.cpp_type().unwrap_or_else(|| "void".into())
+            ),
+            statements: Some(body),
+            ..Default::default()
+        })
+    })
+}
+
 fn generate_public_api_for_properties(
     public_properties: &llr::PublicProperties,
     ctx: &EvaluationContext,
 ) -> Vec<Declaration> {
     let mut declarations = Vec::new();
-    for (p, (ty, r)) in public_properties.iter() {
+    for (p, llr::PublicProperty { ty, prop: r, read_only }) in public_properties.iter() {
         let prop_ident = ident(p);
 
         let access = access_member(r, ctx);
@@ -1818,6 +2016,31 @@ fn generate_public_api_for_properties(
                 ]),
                 ..Default::default()
             }));
+        } else if let Type::Function { args, return_type } = ty {
+            let param_types = args.iter().map(|t| t.cpp_type().unwrap()).collect::<Vec<_>>();
+            let return_type = return_type.cpp_type().unwrap_or_else(|| "void".into());
+            let function_call = vec![
+                "[[maybe_unused]] auto self = this;".into(),
+                format!(
+                    "return {}({});",
+                    access,
+                    (0..args.len()).map(|i| format!("arg_{}", i)).join(", ")
+                ),
+            ];
+            declarations.push(Declaration::Function(Function {
+                name: format!("invoke_{}", ident(p)),
+                signature: format!(
+                    "({}) const -> {}",
+                    param_types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, ty)| format!("{} arg_{}", ty, i))
+                        .join(", "),
+                    return_type
+                ),
+                statements: Some(function_call),
+                ..Default::default()
+            }));
         } else {
             let cpp_property_type = ty.cpp_type().expect("Invalid type in public properties");
             let prop_getter: Vec<String> = vec![
@@ -1831,16 +2054,18 @@ fn generate_public_api_for_properties(
                 ..Default::default()
             }));
 
-            let prop_setter: Vec<String> = vec![
-                "[[maybe_unused]] auto self = this;".into(),
-                property_set_value_code(r, "value", ctx) + ";",
-            ];
-            declarations.push(Declaration::Function(Function {
-                name: format!("set_{}", &prop_ident),
-                signature: format!("(const {} &value) const", &cpp_property_type),
-                statements: Some(prop_setter),
-                ..Default::default()
-            }));
+            if !read_only {
+                let prop_setter: Vec<String> = vec![
+                    "[[maybe_unused]] auto self = this;".into(),
+                    property_set_value_code(r, "value", ctx) + ";",
+                ];
+                declarations.push(Declaration::Function(Function {
+                    name: format!("set_{}", &prop_ident),
+                    signature: format!("(const {} &value) const", &cpp_property_type),
+                    statements: Some(prop_setter),
+                    ..Default::default()
+                }));
+            }
         }
     }
     declarations
@@ -1861,8 +2086,12 @@ fn follow_sub_component_path<'a>(
 }
 
 fn access_window_field(ctx: &EvaluationContext) -> String {
-    let root = &ctx.generator_state;
-    format!("{}->window().window_handle()", root)
+    if ctx.current_sub_component.is_some() {
+        "self->window_ptr->window_handle()".into()
+    } else {
+        let root = &ctx.generator_state;
+        format!("{}->window().window_handle()", root)
+    }
 }
 
 /// Returns the code that can access the given property (but without the set or get)
@@ -1933,7 +2162,16 @@ fn in_native_item(
                     item_index,
                     prop_name,
                 } => in_native_item(ctx, sub_component_path, *item_index, prop_name, &path),
-                llr::PropertyReference::InParent { .. } | llr::PropertyReference::Global { .. } => {
+                llr::PropertyReference::Function { sub_component_path, function_index } => {
+                    let sub_component = ctx.current_sub_component.unwrap();
+                    let (compo_path, sub_component) =
+                        follow_sub_component_path(sub_component, sub_component_path);
+                    let name = ident(&sub_component.functions[*function_index].name);
+                    format!("{}->{}fn_{}", path, compo_path, name)
+                }
+                llr::PropertyReference::InParent { .. }
+                | llr::PropertyReference::Global { .. }
+                | llr::PropertyReference::GlobalFunction { .. } => {
                     unreachable!()
                 }
             }
@@ -1947,6 +2185,25 @@ fn in_native_item(
             );
             format!("{}->{}->{}", root_access, global_id, property_name)
         }
+        llr::PropertyReference::Function { sub_component_path, function_index } => {
+            if let Some(sub_component) = ctx.current_sub_component {
+                let (compo_path, sub_component) =
+                    follow_sub_component_path(sub_component, sub_component_path);
+                let name = ident(&sub_component.functions[*function_index].name);
+                format!("self->{}fn_{}", compo_path, name)
+            } else if let Some(current_global) = ctx.current_global {
+                format!("this->fn_{}", ident(&current_global.functions[*function_index].name))
+            } else {
+                unreachable!()
+            }
+        }
+        llr::PropertyReference::GlobalFunction { global_index, function_index } => {
+            let root_access = &ctx.generator_state;
+            let global = &ctx.public_component.globals[*global_index];
+            let global_id = format!("global_{}", ident(&global.name));
+            let name = ident(&global.functions[*function_index].name);
+            format!("{}->{}->fn_{}", root_access, global_id, name)
+        }
     }
 }
 
@@ -1975,6 +2232,23 @@ fn native_item<'a>(
     }
 }
 
+/// Compile the expression of an array `filter` or `map` into a lambda that takes the row as `item`.
+/// The lambda outlives the binding, so it captures the local variables by value.
+fn compile_array_lambda(
+    item: &str,
+    item_ty: &str,
+    return_ty: &str,
+    expression: &llr::Expression,
+    ctx: &EvaluationContext,
+) -> String {
+    let capture = if ctx.current_global.is_some() { "[=, this]" } else { "[=]" };
+    format!(
+        "{capture}([[maybe_unused]] const {item_ty} &{item}) -> {return_ty} {{ return {return_ty}({expression}); }}",
+        item = ident(item),
+        expression = compile_expression(expression, ctx),
+    )
+}
+
 fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String {
     use llr::Expression;
     match expr {
@@ -2002,6 +2276,11 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
             let mut a = arguments.iter().map(|a| compile_expression(a, ctx));
             format!("{}.call({})", f, a.join(","))
         }
+        Expression::FunctionCall{ function, arguments } => {
+            let f = access_member(function, ctx);
+            let mut a = arguments.iter().map(|a| compile_expression(a, ctx));
+            format!("{}({})", f, a.join(","))
+        }
         Expression::ExtraBuiltinFunctionCall { function, arguments, return_ty: _ } => {
             let mut a = arguments.iter().map(|a| compile_expression(a, ctx));
             format!("slint::private_api::{}({})", ident(function), a.join(","))
@@ -2030,6 +2309,77 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 compile_expression(array, ctx), compile_expression(index, ctx)
             )
         },
+        Expression::ArrayIteration { kind: ArrayIterationKind::Filter, array, item, expression, .. } => {
+            let ty = match array.ty(ctx) {
+                Type::Array(ty) => ty.cpp_type().unwrap(),
+                _ => unreachable!(),
+            };
+            format!(
+                "std::shared_ptr<slint::Model<{ty}>>(std::make_shared<slint::private_api::FilterModel<{ty}>>({array}, {filter_fn}))",
+                array = compile_expression(array, ctx),
+                filter_fn = compile_array_lambda(item, &ty, "bool", expression, ctx),
+            )
+        }
+        Expression::ArrayIteration { kind: ArrayIterationKind::Map, array, item, expression, .. } => {
+            let source_ty = match array.ty(ctx) {
+                Type::Array(ty) => ty.cpp_type().unwrap(),
+                _ => unreachable!(),
+            };
+            let ty = expression.ty(ctx).cpp_type().unwrap();
+            format!(
+                "std::shared_ptr<slint::Model<{ty}>>(std::make_shared<slint::private_api::MapModel<{source_ty}, {ty}>>({array}, {map_fn}))",
+                array = compile_expression(array, ctx),
+                map_fn = compile_array_lambda(item, &source_ty, &ty, expression, ctx),
+            )
+        }
+        Expression::ArraySlice { array, start, end } => {
+            let ty = match array.ty(ctx) {
+                Type::Array(ty) => ty.cpp_type().unwrap(),
+                _ => unreachable!(),
+            };
+            let end = match end {
+                Some(end) => format!("std::optional<int>({})", compile_expression(end, ctx)),
+                None => "std::nullopt".into(),
+            };
+            format!(
+                "std::shared_ptr<slint::Model<{ty}>>(std::make_shared<slint::private_api::SliceModel<{ty}>>({array}, int({start}), {end}))",
+                array = compile_expression(array, ctx),
+                start = compile_expression(start, ctx),
+            )
+        }
+        Expression::ArrayIteration { kind, array, item, index, expression } => {
+            let item = ident(item);
+            let index = ident(index);
+            let (init, step, result) = match kind {
+                ArrayIterationKind::FindIndex => (
+                    String::new(),
+                    format!("if ({}) return {index};", compile_expression(expression, ctx)),
+                    "-1".into(),
+                ),
+                ArrayIterationKind::Sum => {
+                    let ty = expression.ty(ctx).cpp_type().unwrap();
+                    (
+                        format!("{ty} result = {{}};"),
+                        format!("result += {};", compile_expression(expression, ctx)),
+                        "result".into(),
+                    )
+                }
+                ArrayIterationKind::Filter | ArrayIterationKind::Map => unreachable!(),
+            };
+            format!(
+                r#"[&](const auto &model) {{
+                    model->track_row_count_changes();
+                    {init}
+                    for (int i = 0; i < model->row_count(); ++i) {{
+                        [[maybe_unused]] auto {item} = slint::private_api::access_array_index(model, i);
+                        [[maybe_unused]] int {index} = i;
+                        {step}
+                    }}
+                    return {result};
+                }}({array})"#,
+                array = compile_expression(array, ctx),
+            )
+        }
         Expression::Cast { from, to } => {
             let f = compile_expression(&*from, ctx);
             match (from.ty(ctx), to) {
@@ -2265,6 +2615,19 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
             "slint::cbindgen_private::EasingCurve(slint::cbindgen_private::EasingCurve::Tag::CubicBezier, {}, {}, {}, {})",
             a, b, c, d
         ),
+        Expression::EasingCurve(EasingCurve::Spring(stiffness, damping, mass)) => format!(
+            "slint::cbindgen_private::EasingCurve(slint::cbindgen_private::EasingCurve::Tag::Spring, {}, {}, {})",
+            stiffness, damping, mass
+        ),
+        Expression::EasingCurve(EasingCurve::Steps(steps)) => format!(
+            "slint::cbindgen_private::EasingCurve(slint::cbindgen_private::EasingCurve::Tag::Steps, uint32_t({}))",
+            steps
+        ),
+        // The curves without parameters have the same name in the runtime library
+        Expression::EasingCurve(curve) => format!(
+            "slint::cbindgen_private::EasingCurve(slint::cbindgen_private::EasingCurve::Tag::{:?})",
+            curve
+        ),
         Expression::LinearGradient{angle, stops} => {
             let angle = compile_expression(angle, ctx);
             let mut stops_it = stops.iter().map(|(color, stop)| {
@@ -2288,6 +2651,9 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 stops_it.join(", "), stops.len()
             )
         }
+        Expression::EnumerationValue(value) if value.enumeration.node.is_some() => {
+            format!("{}::{}", ident(&value.enumeration.name), ident(&value.to_pascal_case()))
+        }
         Expression::EnumerationValue(value) => {
             format!(
                 "slint::cbindgen_private::{}::{}",
@@ -2402,6 +2768,35 @@ fn compile_builtin_function_call(
         BuiltinFunction::StringToFloat => {
             format!("[](const auto &a){{ auto e1 = std::end(a); auto e2 = const_cast<char*>(e1); auto r = std::strtod(std::begin(a), &e2); return e1 == e2 ? r : 0; }}({})", a.next().unwrap())
         }
+        BuiltinFunction::StringToUppercase => {
+            format!("{}.to_uppercase()", a.next().unwrap())
+        }
+        BuiltinFunction::StringToLowercase => {
+            format!("{}.to_lowercase()", a.next().unwrap())
+        }
+        BuiltinFunction::StringLength => {
+            format!("{}.character_count()", a.next().unwrap())
+        }
+        BuiltinFunction::StringSubstring => {
+            format!("{}.substring({}, {})", a.next().unwrap(), a.next().unwrap(), a.next().unwrap())
+        }
+        BuiltinFunction::StringContains => {
+            format!("{}.contains({})", a.next().unwrap(), a.next().unwrap())
+        }
+        BuiltinFunction::NumberToFixed => {
+            format!(
+                "slint::SharedString::from_number_fixed({}, {})",
+                a.next().unwrap(),
+                a.next().unwrap()
+            )
+        }
+        BuiltinFunction::NumberToPrecision => {
+            format!(
+                "slint::SharedString::from_number_precision({}, {})",
+                a.next().unwrap(),
+                a.next().unwrap()
+            )
+        }
         BuiltinFunction::ColorBrighter => {
             format!("{}.brighter({})", a.next().unwrap(), a.next().unwrap())
         }
@@ -2451,6 +2846,42 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::ShowSecondaryWindow | BuiltinFunction::HideSecondaryWindow => {
+            if let [llr::Expression::NumberLiteral(window_index), llr::Expression::PropertyReference(parent_ref)] =
+                arguments
+            {
+                let mut parent_ctx = ctx;
+                let mut component_access = "self".into();
+
+                if let llr::PropertyReference::InParent { level, .. } = parent_ref {
+                    for _ in 0..level.get() {
+                        component_access = format!("{}->parent", component_access);
+                        parent_ctx = parent_ctx.parent.as_ref().unwrap().ctx;
+                    }
+                };
+
+                let current_sub_component = parent_ctx.current_sub_component.unwrap();
+                let window_index = *window_index as usize;
+                let window_id =
+                    ident(&current_sub_component.secondary_windows[window_index].root.name);
+                if matches!(function, BuiltinFunction::ShowSecondaryWindow) {
+                    format!(
+                        "[](auto component) {{ if (!component->secondary_window{idx}) component->secondary_window{idx} = {id}::create(component); (*component->secondary_window{idx})->m_window.show(); }}({access})",
+                        idx = window_index,
+                        id = window_id,
+                        access = component_access,
+                    )
+                } else {
+                    format!(
+                        "[](auto component) {{ if (component->secondary_window{idx}) (*component->secondary_window{idx})->m_window.hide(); }}({access})",
+                        idx = window_index,
+                        access = component_access,
+                    )
+                }
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
         BuiltinFunction::RegisterCustomFontByPath => {
             if let [llr::Expression::StringLiteral(path)] = arguments {
                 let window = access_window_field(ctx);
@@ -2474,6 +2905,37 @@ fn compile_builtin_function_call(
         BuiltinFunction::RegisterBitmapFont => {
             todo!()
         }
+        BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                format!(
+                    "slint::cbindgen_private::slint_text_input_{f}(&{i}, &{window});",
+                    f = if matches!(function, BuiltinFunction::TextInputUndo) {
+                        "undo"
+                    } else {
+                        "redo"
+                    },
+                    i = access_member(pr, ctx),
+                    window = access_window_field(ctx)
+                )
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
+        BuiltinFunction::ShowFileDialog | BuiltinFunction::ShowMessageDialog => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                format!(
+                    "slint::private_api::{f}({item_rc});",
+                    f = if matches!(function, BuiltinFunction::ShowFileDialog) {
+                        "show_file_dialog"
+                    } else {
+                        "show_message_dialog"
+                    },
+                    item_rc = access_item_rc(pr, ctx)
+                )
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
         BuiltinFunction::ImplicitLayoutInfo(orient) => {
             if let [llr::Expression::PropertyReference(pr)] = arguments {
                 let native = native_item(pr, ctx);
@@ -12,8 +12,8 @@
     this is usually a local variable to the init code that shouldn't rbe relied upon by the binding code.
 */
 
-use crate::expression_tree::{BuiltinFunction, EasingCurve, OperatorClass};
-use crate::langtype::Type;
+use crate::expression_tree::{ArrayIterationKind, BuiltinFunction, EasingCurve, OperatorClass};
+use crate::langtype::{Enumeration, Type};
 use crate::layout::Orientation;
 use crate::llr::{
     self, EvaluationContext as llr_EvaluationContext, Expression, ParentCtx as llr_ParentCtx,
@@ -23,7 +23,7 @@
 use itertools::Either;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroUsize;
 
 type EvaluationContext<'a> = llr_EvaluationContext<'a, TokenStream>;
@@ -89,6 +89,7 @@ fn rust_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
             let inner = rust_type(o)?;
             Some(quote!(slint::private_unstable_api::re_exports::ModelRc<#inner>))
         }
+        Type::Enumeration(e) if e.node.is_some() => Some(struct_name_to_tokens(&e.name)),
         Type::Enumeration(e) => {
             let e = ident(&e.name);
             Some(quote!(slint::private_unstable_api::re_exports::#e))
@@ -125,6 +126,15 @@ pub fn generate(doc: &Document) -> TokenStream {
         })
         .unzip();
 
+    let (enums_ids, enums): (Vec<_>, Vec<_>) = doc
+        .root_component
+        .used_types
+        .borrow()
+        .enums
+        .iter()
+        .map(|e| (ident(&e.name), generate_enum(e)))
+        .unzip();
+
     let llr = crate::llr::lower_to_item_tree::lower_to_item_tree(&doc.root_component);
 
     let sub_compos = llr
@@ -270,6 +280,7 @@ pub fn generate(doc: &Document) -> TokenStream {
         #[allow(clippy::approx_constant)] // We may get those from .slint inputs!
         mod #compo_module {
             use slint::private_unstable_api::re_exports::*;
+            #(#enums)*
             #(#structs)*
             #(#globals)*
             #(#sub_compos)*
@@ -277,7 +288,7 @@ mod #compo_module {
             #(#resource_symbols)*
             const _THE_SAME_VERSION_MUST_BE_USED_FOR_THE_COMPILER_AND_THE_RUNTIME : slint::#version_check = slint::#version_check;
         }
-        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#globals_ids)* };
+        pub use #compo_module::{#compo_id #(,#enums_ids)* #(,#structs_ids)* #(,#globals_ids)* };
         pub use slint::{ComponentHandle, Global, ModelExt as _};
     }
 }
@@ -287,8 +298,14 @@ fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
     let inner_component_id = inner_component_id(&llr.item_tree.root);
     let global_container_id = format_ident!("Globals_{}", public_component_id);
 
-    let component =
-        generate_item_tree(&llr.item_tree, llr, None, quote!(globals: #global_container_id), None);
+    let component = generate_item_tree(
+        &llr.item_tree,
+        llr,
+        None,
+        quote!(globals: #global_container_id),
+        None,
+        false,
+    );
 
     let ctx = EvaluationContext {
         public_component: llr,
@@ -390,6 +407,24 @@ pub struct #component_id {
     }
 }
 
+fn generate_enum(en: &Enumeration) -> TokenStream {
+    let enum_id = struct_name_to_tokens(&en.name);
+    let values = en.values.iter().map(|value| ident(&crate::generator::to_pascal_case(value)));
+    let default_value = ident(&crate::generator::to_pascal_case(&en.values[en.default_value]));
+
+    quote! {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+        pub enum #enum_id {
+            #(#values),*
+        }
+        impl Default for #enum_id {
+            fn default() -> Self {
+                Self::#default_value
+            }
+        }
+    }
+}
+
 fn handle_property_init(
     prop: &llr::PropertyReference,
     binding_expression: &llr::BindingExpression,
@@ -457,20 +492,41 @@ fn handle_property_init(
                 } }
             } else {
                 match &binding_expression.animation {
-                    Some(llr::Animation::Static(anim)) => {
+                    Some(llr::Animation::Static(anim, keyframes)) => {
                         let anim = compile_expression(anim, ctx);
+                        let keyframes = compile_keyframes(keyframes, ctx);
                         quote! { {
                             #init_self_pin_ref
-                            slint::private_unstable_api::set_animated_property_binding(#rust_property, &self_rc, #binding_tokens, #anim);
+                            slint::private_unstable_api::set_animated_property_binding(#rust_property, &self_rc, #binding_tokens, #anim, #keyframes);
                         } }
                     }
-                    Some(llr::Animation::Transition(anim)) => {
+                    Some(llr::Animation::Transition(anim, keyframes)) => {
                         let anim = compile_expression(anim, ctx);
+                        let keyframes_arms = keyframes
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, keyframes)| !keyframes.is_empty())
+                            .map(|(index, keyframes)| {
+                                let index = index as i32;
+                                let keyframes = compile_keyframes(keyframes, ctx);
+                                quote!(#index => #keyframes,)
+                            })
+                            .collect::<Vec<_>>();
+                        let (index, keyframes) = if keyframes_arms.is_empty() {
+                            (quote!(_), quote!(slint::private_unstable_api::re_exports::vec![]))
+                        } else {
+                            let keyframes = quote!(match index {
+                                #(#keyframes_arms)*
+                                _ => slint::private_unstable_api::re_exports::vec![],
+                            });
+                            (quote!(index), keyframes)
+                        };
                         quote! {
                             slint::private_unstable_api::set_animated_property_binding_for_transition(
                                 #rust_property, &self_rc, #binding_tokens, move |self_rc| {
                                     #init_self_pin_ref
-                                    #anim
+                                    let (animation, start_time, #index) = #anim;
+                                    (animation, start_time, #keyframes)
                                 }
                             );
                         }
@@ -493,7 +549,7 @@ fn public_api(
     ctx: &EvaluationContext,
 ) -> TokenStream {
     let mut property_and_callback_accessors: Vec<TokenStream> = vec![];
-    for (p, (ty, r)) in public_properties {
+    for (p, llr::PublicProperty { ty, prop: r, read_only }) in public_properties {
         let prop_ident = ident(p);
         let prop = access_member(r, ctx);
 
@@ -522,6 +578,18 @@ pub fn #on_ident(&self, mut f: impl FnMut(#(#callback_args),*) -> #return_type +
                     )
                 }
             ));
+        } else if let Type::Function { return_type, args } = ty {
+            let function_args = args.iter().map(|a| rust_type(a).unwrap()).collect::<Vec<_>>();
+            let return_type = rust_type(return_type).unwrap_or(quote!(()));
+            let args_name = (0..args.len()).map(|i| format_ident!("arg_{}", i)).collect::<Vec<_>>();
+            let caller_ident = format_ident!("invoke_{}", prop_ident);
+            property_and_callback_accessors.push(quote!(
+                #[allow(dead_code)]
+                pub fn #caller_ident(&self, #(#args_name : #function_args,)*) -> #return_type {
+                    let _self = #self_init;
+                    #prop(#(#args_name,)*)
+                }
+            ));
         } else {
             let rust_property_type = rust_type(ty).unwrap();
 
@@ -538,22 +606,51 @@ pub fn #getter_ident(&self) -> #rust_property_type {
                 }
             ));
 
-            let set_value = property_set_value_tokens(r, quote!(value), ctx);
-            property_and_callback_accessors.push(quote!(
-                #[allow(dead_code)]
-                pub fn #setter_ident(&self, value: #rust_property_type) {
-                    #[allow(unused_imports)]
-                    use slint::private_unstable_api::re_exports::*;
-                    let _self = #self_init;
-                    #set_value
-                }
-            ));
+            if !read_only {
+                let set_value = property_set_value_tokens(r, quote!(value), ctx);
+                property_and_callback_accessors.push(quote!(
+                    #[allow(dead_code)]
+                    pub fn #setter_ident(&self, value: #rust_property_type) {
+                        #[allow(unused_imports)]
+                        use slint::private_unstable_api::re_exports::*;
+                        let _self = #self_init;
+                        #set_value
+                    }
+                ));
+            }
         }
     }
 
     quote!(#(#property_and_callback_accessors)*)
 }
 
+/// Generate the methods for the functions declared in a sub component or a global
+fn generate_functions(functions: &[llr::Function], ctx: &EvaluationContext) -> Vec<TokenStream> {
+    functions
+        .iter()
+        .map(|f| {
+            let mut ctx2 = ctx.clone();
+            ctx2.argument_types = &f.args;
+            let tokens_for_code = compile_expression(&f.code.borrow(), &ctx2);
+            let as_ = if f.ret_ty == Type::Void { quote!(;) } else { quote!(as _) };
+            let fn_id = ident(&format!("fn_{}", f.name));
+            let args_ty = f.args.iter().map(|a| rust_type(a).unwrap());
+            let return_type = rust_type(&f.ret_ty).unwrap_or(quote!(()));
+            let args_name =
+                (0..f.args.len()).map(|i| format_ident!("arg_{}", i)).collect::<Vec<_>>();
+            quote! {
+                #[allow(dead_code, unused, unreachable_code)]
+                fn #fn_id(self: ::core::pin::Pin<&Self>, #(#args_name : #args_ty,)*) -> #return_type {
+                    use slint::private_unstable_api::re_exports::*;
+                    let _self = self;
+                    let args = (#(#args_name,)*);
+                    (#tokens_for_code) #as_
+                }
+            }
+        })
+        .collect()
+}
+
 /// Generate the rust code for the given component.
 fn generate_sub_component(
     component: &llr::SubComponent,
@@ -574,8 +671,18 @@ fn generate_sub_component(
     let mut extra_components = component
         .popup_windows
         .iter()
-        .map(|c| generate_item_tree(c, root, Some(ParentCtx::new(&ctx, None)), quote!(), None))
+        .map(|c| {
+            generate_item_tree(c, root, Some(ParentCtx::new(&ctx, None)), quote!(), None, false)
+        })
+        .collect::<Vec<_>>();
+    extra_components.extend(component.secondary_windows.iter().map(|c| {
+        generate_item_tree(c, root, Some(ParentCtx::new(&ctx, None)), quote!(), None, true)
+    }));
+    let secondary_window_names = (0..component.secondary_windows.len())
+        .map(|idx| format_ident!("secondary_window{}", idx))
         .collect::<Vec<_>>();
+    let secondary_window_types =
+        component.secondary_windows.iter().map(|c| self::inner_component_id(&c.root));
 
     let mut declared_property_vars = vec![];
     let mut declared_property_types = vec![];
@@ -747,6 +854,11 @@ fn generate_sub_component(
 
         let sub_compo_field = access_component_field_offset(&format_ident!("Self"), &field_name);
 
+        // The sub component is shown in the same window as this component, which is not
+        // the window of the root if this is within a SecondaryWindow
+        init.push(quote!(
+            #sub_compo_field.apply_pin(_self).window_adapter.set(_self.window_adapter.get().unwrap().clone());
+        ));
         init.push(quote!(#sub_component_id::init(
             VRcMapped::map(self_rc.clone(), |x| #sub_compo_field.apply_pin(x)),
             &#root_ref_tokens,
@@ -833,9 +945,34 @@ fn generate_sub_component(
 
     init.extend(component.init_code.iter().map(|e| compile_expression(&e.borrow(), &ctx)));
 
+    let mut change_tracker_names = vec![];
+    for (idx, (prop, code)) in component.change_callbacks.iter().enumerate() {
+        let prop = access_member(prop, &ctx);
+        let code = compile_expression(&code.borrow(), &ctx);
+        let change_tracker = format_ident!("change_tracker{}", idx);
+        init.push(quote! {
+            _self.#change_tracker.init(
+                VRcMapped::downgrade(&self_rc),
+                |self_weak| {
+                    let self_rc = self_weak.upgrade().unwrap();
+                    let _self = self_rc.as_pin_ref();
+                    #prop.get()
+                },
+                |self_weak, _| {
+                    let self_rc = self_weak.upgrade().unwrap();
+                    let _self = self_rc.as_pin_ref();
+                    #code ;
+                }
+            );
+        });
+        change_tracker_names.push(change_tracker);
+    }
+
     let layout_info_h = compile_expression(&component.layout_info_h.borrow(), &ctx);
     let layout_info_v = compile_expression(&component.layout_info_v.borrow(), &ctx);
 
+    let declared_functions = generate_functions(&component.functions, &ctx);
+
     // FIXME! this is only public because of the ComponentHandle::Inner. we should find another way
     let visibility =
         core::ptr::eq(&root.item_tree.root as *const _, component as *const _).then(|| quote!(pub));
@@ -864,6 +1001,8 @@ struct #inner_component_id {
             #(#declared_property_vars : slint::private_unstable_api::re_exports::Property<#declared_property_types>,)*
             #(#declared_callbacks : slint::private_unstable_api::re_exports::Callback<(#(#declared_callbacks_types,)*), #declared_callbacks_ret>,)*
             #(#repeated_element_names : slint::private_unstable_api::re_exports::Repeater<#repeated_element_components>,)*
+            #(#secondary_window_names : slint::private_unstable_api::re_exports::OnceCell<slint::private_unstable_api::re_exports::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #secondary_window_types>>,)*
+            #(#change_tracker_names : slint::private_unstable_api::re_exports::ChangeTracker,)*
             self_weak : slint::private_unstable_api::re_exports::OnceCell<slint::private_unstable_api::re_exports::VWeakMapped<slint::private_unstable_api::re_exports::ComponentVTable, #inner_component_id>>,
             #(parent : #parent_component_type,)*
             // FIXME: Do we really need a window all the time?
@@ -964,6 +1103,8 @@ fn accessible_string_property(
                     _ => Default::default(),
                 }
             }
+
+            #(#declared_functions)*
         }
 
         #(#extra_components)*
@@ -1033,6 +1174,8 @@ fn generate_global(global: &llr::GlobalComponent, root: &llr::PublicComponent) -
         }
     }
 
+    let declared_functions = generate_functions(&global.functions, &ctx);
+
     let public_interface = global.exported.then(|| {
         let property_and_callback_accessors = public_api(&global.public_properties, quote!(self.0.as_ref()), &ctx);
         let public_component_id = ident(&global.name);
@@ -1080,6 +1223,8 @@ fn init(self: ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>
                 let _self = self_rc.as_ref();
                 #(#init)*
             }
+
+            #(#declared_functions)*
         }
 
         #public_interface
@@ -1092,6 +1237,7 @@ fn generate_item_tree(
     parent_ctx: Option<ParentCtx>,
     extra_fields: TokenStream,
     index_property: Option<llr::PropertyIndex>,
+    is_secondary_window: bool,
 ) -> TokenStream {
     let sub_comp = generate_sub_component(
         &sub_tree.root,
@@ -1111,18 +1257,21 @@ fn generate_item_tree(
     } else {
         quote!(&self_rc)
     };
-    let (create_window_adapter, init_window) = if parent_ctx.is_none() {
+    let (create_window_adapter, init_window) = if parent_ctx.is_none() || is_secondary_window {
         (
             Some(
                 quote!(let window_adapter = slint::private_unstable_api::create_window_adapter();),
             ),
-            Some(quote! {
+            quote! {
                 _self.window_adapter.set(window_adapter);
                 slint::private_unstable_api::re_exports::WindowInner::from_pub(_self.window_adapter.get().unwrap().window()).set_component(&VRc::into_dyn(self_rc.clone()));
-            }),
+            },
         )
     } else {
-        (None, None)
+        (
+            None,
+            quote!(_self.window_adapter.set(parent.upgrade().unwrap().window_adapter.get().unwrap().clone());),
+        )
     };
 
     let parent_item_expression = parent_ctx.and_then(|parent| {
@@ -1205,7 +1354,7 @@ pub fn new(#(parent: #parent_component_type)*)
                 let self_rc = VRc::new(_self);
                 let _self = self_rc.as_pin_ref();
                 #init_window
-                slint::private_unstable_api::re_exports::register_component(_self, Self::item_array(), #root_token.window_adapter.get().unwrap());
+                slint::private_unstable_api::re_exports::register_component(_self, Self::item_array(), _self.window_adapter.get().unwrap());
                 Self::init(slint::private_unstable_api::re_exports::VRc::map(self_rc.clone(), |x| x), #root_token, 0, 1);
                 self_rc
             }
@@ -1317,6 +1466,7 @@ fn generate_repeated_component(
         Some(parent_ctx.clone()),
         quote!(),
         repeated.index_prop,
+        false,
     );
 
     let ctx = EvaluationContext {
@@ -1421,13 +1571,34 @@ fn property_set_value_tokens(
     ctx: &EvaluationContext,
 ) -> TokenStream {
     let prop = access_member(property, ctx);
-    if let Some(animation) = ctx.current_sub_component.and_then(|c| c.animations.get(property)) {
+    if let Some((animation, keyframes)) =
+        ctx.current_sub_component.and_then(|c| c.animations.get(property))
+    {
         let animation_tokens = compile_expression(animation, ctx);
-        return quote!(#prop.set_animated_value(#value_tokens as _, #animation_tokens));
+        if keyframes.is_empty() {
+            return quote!(#prop.set_animated_value(#value_tokens as _, #animation_tokens));
+        }
+        let keyframes = compile_keyframes(keyframes, ctx);
+        return quote!(#prop.set_animated_value_with_keyframes(#value_tokens as _, #animation_tokens, #keyframes));
     }
     quote!(#prop.set(#value_tokens as _))
 }
 
+/// Returns the code creating the Vec of the keyframes of an animation
+fn compile_keyframes(keyframes: &[llr::KeyFrame], ctx: &EvaluationContext) -> TokenStream {
+    let keyframes = keyframes.iter().map(|k| {
+        let position = k.position;
+        let value = compile_expression(&k.value, ctx);
+        let easing = compile_expression(&k.easing, ctx);
+        quote!(slint::private_unstable_api::re_exports::KeyFrame {
+            position: #position,
+            value: (#value) as _,
+            easing: #easing,
+        })
+    });
+    quote!(slint::private_unstable_api::re_exports::vec![#(#keyframes),*])
+}
+
 /// Returns the code that can access the given property or callback (but without the set or get)
 ///
 /// to be used like:
@@ -1461,6 +1632,25 @@ fn in_native_item(
         }
     }
 
+    fn in_function(
+        sub_component: &llr::SubComponent,
+        sub_component_path: &[usize],
+        function_index: usize,
+        path: TokenStream,
+    ) -> TokenStream {
+        let mut compo_path = path;
+        let mut sub_component = sub_component;
+        for i in sub_component_path {
+            let component_id = inner_component_id(sub_component);
+            let sub_component_name = ident(&sub_component.sub_components[*i].name);
+            compo_path =
+                quote!(#component_id::FIELD_OFFSETS.#sub_component_name.apply_pin(#compo_path));
+            sub_component = &sub_component.sub_components[*i].ty;
+        }
+        let fn_id = ident(&format!("fn_{}", sub_component.functions[function_index].name));
+        quote!(#compo_path.#fn_id)
+    }
+
     match reference {
         llr::PropertyReference::Local { sub_component_path, property_index } => {
             if let Some(sub_component) = ctx.current_sub_component {
@@ -1504,7 +1694,17 @@ fn in_native_item(
                     item_index,
                     prop_name,
                 } => in_native_item(ctx, sub_component_path, *item_index, prop_name, path),
-                llr::PropertyReference::InParent { .. } | llr::PropertyReference::Global { .. } => {
+                llr::PropertyReference::Function { sub_component_path, function_index } => {
+                    in_function(
+                        ctx.current_sub_component.unwrap(),
+                        sub_component_path,
+                        *function_index,
+                        path,
+                    )
+                }
+                llr::PropertyReference::InParent { .. }
+                | llr::PropertyReference::Global { .. }
+                | llr::PropertyReference::GlobalFunction { .. } => {
                     unreachable!()
                 }
             }
@@ -1519,6 +1719,24 @@ fn in_native_item(
             );
             quote!(#global_name::FIELD_OFFSETS.#property_name.apply_pin(#root_access.globals.#global_id.as_ref()))
         }
+        llr::PropertyReference::Function { sub_component_path, function_index } => {
+            if let Some(sub_component) = ctx.current_sub_component {
+                in_function(sub_component, sub_component_path, *function_index, quote!(_self))
+            } else if let Some(current_global) = ctx.current_global {
+                let fn_id =
+                    ident(&format!("fn_{}", current_global.functions[*function_index].name));
+                quote!(_self.#fn_id)
+            } else {
+                unreachable!()
+            }
+        }
+        llr::PropertyReference::GlobalFunction { global_index, function_index } => {
+            let root_access = &ctx.generator_state;
+            let global = &ctx.public_component.globals[*global_index];
+            let global_id = format_ident!("global_{}", ident(&global.name));
+            let fn_id = ident(&format!("fn_{}", global.functions[*function_index].name));
+            quote!(#root_access.globals.#global_id.as_ref().#fn_id)
+        }
     }
 }
 
@@ -1538,8 +1756,13 @@ fn follow_sub_component_path<'a>(
 }
 
 fn access_window_adapter_field(ctx: &EvaluationContext) -> TokenStream {
-    let root = &ctx.generator_state;
-    quote!(#root.window_adapter.get().unwrap())
+    if ctx.current_sub_component.is_some() {
+        // Not necessarily the same as the root's window if this is within a SecondaryWindow
+        quote!(_self.window_adapter.get().unwrap())
+    } else {
+        let root = &ctx.generator_state;
+        quote!(#root.window_adapter.get().unwrap())
+    }
 }
 
 /// Given a property reference to a native item (eg, the property name is empty)
@@ -1580,6 +1803,76 @@ fn access_item_rc(pr: &llr::PropertyReference, ctx: &EvaluationContext) -> Token
     }
 }
 
+/// Compile the expression of an array `filter` or `map` into a closure that takes the row as `item`.
+/// The closure outlives the binding, so it holds a weak reference to the component and a clone of the
+/// local variables it reads.
+fn compile_array_lambda(
+    item: &str,
+    item_ty: TokenStream,
+    return_ty: TokenStream,
+    expression: &Expression,
+    ctx: &EvaluationContext,
+) -> TokenStream {
+    let mut read_variables = BTreeSet::new();
+    let mut stored_variables = BTreeSet::new();
+    let mut uses_arguments = false;
+    expression.visit_recursive(&mut |e| match e {
+        Expression::ReadLocalVariable { name, .. } => {
+            read_variables.insert(name.clone());
+        }
+        Expression::StoreLocalVariable { name, .. } => {
+            stored_variables.insert(name.clone());
+        }
+        Expression::FunctionParameterReference { .. } => uses_arguments = true,
+        _ => (),
+    });
+    let captured_variables = read_variables
+        .iter()
+        .filter(|name| name.as_str() != item && !stored_variables.contains(*name))
+        .map(|name| ident(name))
+        .collect::<Vec<_>>();
+    let capture_arguments = uses_arguments.then(|| quote!(let args = args.clone();));
+
+    let (weak, upgrade) = if let Some(global) = ctx.current_global {
+        let global_id = format_ident!("global_{}", ident(&global.name));
+        (
+            quote!(let root_weak = _self.root.get().unwrap().clone();),
+            quote!(
+                let root = match root_weak.upgrade() {
+                    Some(root) => root,
+                    None => return ::core::default::Default::default(),
+                };
+                let _self = root.globals.#global_id.as_ref();
+            ),
+        )
+    } else {
+        (
+            quote!(let self_weak = _self.self_weak.get().unwrap().clone();),
+            quote!(
+                let self_rc = match self_weak.upgrade() {
+                    Some(self_rc) => self_rc,
+                    None => return ::core::default::Default::default(),
+                };
+                let _self = self_rc.as_pin_ref();
+            ),
+        )
+    };
+
+    let item = ident(item);
+    let expression = compile_expression(expression, ctx);
+    quote!({
+        #weak
+        #(let #captured_variables = #captured_variables.clone();)*
+        #capture_arguments
+        move |#item: #item_ty| -> #return_ty {
+            #[allow(unused_variables)]
+            let #item = #item.clone();
+            #upgrade
+            (#expression) as _
+        }
+    })
+}
+
 fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream {
     match expr {
         Expression::StringLiteral(s) => {
@@ -1684,6 +1977,11 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
             let a = arguments.iter().map(|a| compile_expression(a, ctx));
             quote! { #f.call(&(#(#a as _,)*).into())}
         }
+        Expression::FunctionCall { function, arguments } => {
+            let f = access_member(function, ctx);
+            let a = arguments.iter().map(|a| compile_expression(a, ctx));
+            quote! { #f(#(#a as _,)*) }
+        }
         Expression::ExtraBuiltinFunctionCall { function, arguments, return_ty: _ } => {
             let f = ident(function);
             let a = arguments.iter().map(|a| {
@@ -1726,6 +2024,98 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 x.row_data_tracked(index).unwrap_or_default()
             }})
         }
+        Expression::ArrayIteration {
+            kind: ArrayIterationKind::Filter,
+            array,
+            item,
+            expression,
+            ..
+        } => {
+            let item_ty = match array.ty(ctx) {
+                Type::Array(ty) => rust_type(&ty).unwrap(),
+                _ => unreachable!(),
+            };
+            let array = compile_expression(array, ctx);
+            let filter_function =
+                compile_array_lambda(item, quote!(&#item_ty), quote!(bool), expression, ctx);
+            quote!(slint::private_unstable_api::re_exports::ModelRc::new(
+                slint::private_unstable_api::re_exports::FilterModel::new(#array, #filter_function)
+            ))
+        }
+        Expression::ArrayIteration {
+            kind: ArrayIterationKind::Map,
+            array,
+            item,
+            expression,
+            ..
+        } => {
+            let item_ty = match array.ty(ctx) {
+                Type::Array(ty) => rust_type(&ty).unwrap(),
+                _ => unreachable!(),
+            };
+            let rust_element_ty = rust_type(&expression.ty(ctx)).unwrap();
+            let array = compile_expression(array, ctx);
+            let map_function =
+                compile_array_lambda(item, item_ty, rust_element_ty, expression, ctx);
+            quote!(slint::private_unstable_api::re_exports::ModelRc::new(
+                slint::private_unstable_api::re_exports::MapModel::new(#array, #map_function)
+            ))
+        }
+        Expression::ArraySlice { array, start, end } => {
+            let array = compile_expression(array, ctx);
+            let start = compile_expression(start, ctx);
+            let end = match end {
+                Some(end) => {
+                    let end = compile_expression(end, ctx);
+                    quote!(Some(((#end) as i32).max(0) as usize))
+                }
+                None => quote!(None),
+            };
+            quote!(slint::private_unstable_api::re_exports::ModelRc::new(
+                slint::private_unstable_api::re_exports::SliceModel::new(
+                    #array,
+                    ((#start) as i32).max(0) as usize,
+                    #end,
+                )
+            ))
+        }
+        Expression::ArrayIteration { kind, array, item, index, expression } => {
+            let array = compile_expression(array, ctx);
+            let item = ident(item);
+            let index = ident(index);
+            let (init, step, result) = match kind {
+                ArrayIterationKind::FindIndex => {
+                    let expression = compile_expression(expression, ctx);
+                    (
+                        quote!(let mut result = -1;),
+                        quote!(if #expression { result = #index; break; }),
+                        quote!(result),
+                    )
+                }
+                ArrayIterationKind::Sum => {
+                    let rust_ty = rust_type(&expression.ty(ctx)).unwrap();
+                    let expression = compile_expression(expression, ctx);
+                    (
+                        quote!(let mut result: #rust_ty = ::core::default::Default::default();),
+                        quote!(result += (#expression) as #rust_ty;),
+                        quote!(result),
+                    )
+                }
+                ArrayIterationKind::Filter | ArrayIterationKind::Map => unreachable!(),
+            };
+            quote!(match &#array { model => {
+                model.model_tracker().track_row_count_changes();
+                #init
+                for i in 0..model.row_count() {
+                    #[allow(unused_variables)]
+                    let #item = model.row_data_tracked(i).unwrap_or_default();
+                    #[allow(unused_variables)]
+                    let #index = i as i32;
+                    #step
+                }
+                #result
+            }})
+        }
         Expression::CodeBlock(sub) => {
             let map = sub.iter().map(|e| compile_expression(e, ctx));
             quote!({ #(#map);* })
@@ -1908,6 +2298,17 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
         Expression::EasingCurve(EasingCurve::CubicBezier(a, b, c, d)) => {
             quote!(slint::private_unstable_api::re_exports::EasingCurve::CubicBezier([#a, #b, #c, #d]))
         }
+        Expression::EasingCurve(EasingCurve::Spring(stiffness, damping, mass)) => {
+            quote!(slint::private_unstable_api::re_exports::EasingCurve::Spring([#stiffness, #damping, #mass]))
+        }
+        Expression::EasingCurve(EasingCurve::Steps(steps)) => {
+            quote!(slint::private_unstable_api::re_exports::EasingCurve::Steps(#steps))
+        }
+        Expression::EasingCurve(curve) => {
+            // The curves without parameters have the same name in the runtime library
+            let name = format_ident!("{:?}", curve);
+            quote!(slint::private_unstable_api::re_exports::EasingCurve::#name)
+        }
         Expression::LinearGradient { angle, stops } => {
             let angle = compile_expression(angle, ctx);
             let stops = stops.iter().map(|(color, stop)| {
@@ -1929,6 +2330,11 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 slint::private_unstable_api::re_exports::RadialGradientBrush::new_circle([#(#stops),*])
             ))
         }
+        Expression::EnumerationValue(value) if value.enumeration.node.is_some() => {
+            let base_ident = struct_name_to_tokens(&value.enumeration.name);
+            let value_ident = ident(&value.to_pascal_case());
+            quote!(#base_ident::#value_ident)
+        }
         Expression::EnumerationValue(value) => {
             let base_ident = ident(&value.enumeration.name);
             let value_ident = ident(&value.to_pascal_case());
@@ -2032,6 +2438,66 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::ShowSecondaryWindow | BuiltinFunction::HideSecondaryWindow => {
+            if let [Expression::NumberLiteral(window_index), Expression::PropertyReference(parent_ref)] =
+                arguments
+            {
+                let mut parent_ctx = ctx;
+                let mut component_access_tokens = quote!(_self);
+                if let llr::PropertyReference::InParent { level, .. } = parent_ref {
+                    for _ in 0..level.get() {
+                        component_access_tokens =
+                            quote!(#component_access_tokens.parent.upgrade().unwrap().as_pin_ref());
+                        parent_ctx = parent_ctx.parent.as_ref().unwrap().ctx;
+                    }
+                }
+                let current_sub_component = parent_ctx.current_sub_component.unwrap();
+                let window_index = *window_index as usize;
+                let window_id =
+                    inner_component_id(&current_sub_component.secondary_windows[window_index].root);
+                let window_field = format_ident!("secondary_window{}", window_index);
+                if matches!(function, BuiltinFunction::ShowSecondaryWindow) {
+                    quote!(
+                        #component_access_tokens.#window_field.get_or_init(
+                            || #window_id::new(#component_access_tokens.self_weak.get().unwrap().clone())
+                        ).window_adapter.get().unwrap().window().show();
+                    )
+                } else {
+                    quote!(
+                        if let Some(window_component) = #component_access_tokens.#window_field.get() {
+                            window_component.window_adapter.get().unwrap().window().hide();
+                        }
+                    )
+                }
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
+        BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item = access_member(pr, ctx);
+                let window_adapter_tokens = access_window_adapter_field(ctx);
+                if matches!(function, BuiltinFunction::TextInputUndo) {
+                    quote!(#item.undo(#window_adapter_tokens);)
+                } else {
+                    quote!(#item.redo(#window_adapter_tokens);)
+                }
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
+        BuiltinFunction::ShowFileDialog | BuiltinFunction::ShowMessageDialog => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item_rc = access_item_rc(pr, ctx);
+                if matches!(function, BuiltinFunction::ShowFileDialog) {
+                    quote!(slint::private_unstable_api::show_file_dialog(#item_rc);)
+                } else {
+                    quote!(slint::private_unstable_api::show_message_dialog(#item_rc);)
+                }
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
         BuiltinFunction::ImplicitLayoutInfo(orient) => {
             if let [Expression::PropertyReference(pr)] = arguments {
                 let item = access_member(pr, ctx);
@@ -2103,6 +2569,29 @@ fn compile_builtin_function_call(
             quote!(#(#a)*.as_str().parse::<f64>().unwrap_or_default())
         }
         BuiltinFunction::StringIsFloat => quote!(#(#a)*.as_str().parse::<f64>().is_ok()),
+        BuiltinFunction::StringToUppercase => {
+            quote!(slint::private_unstable_api::re_exports::SharedString::from(#(#a)*.to_uppercase()))
+        }
+        BuiltinFunction::StringToLowercase => {
+            quote!(slint::private_unstable_api::re_exports::SharedString::from(#(#a)*.to_lowercase()))
+        }
+        BuiltinFunction::StringLength => quote!(#(#a)*.chars().count() as i32),
+        BuiltinFunction::StringSubstring => {
+            let (s, start, end) = (a.next().unwrap(), a.next().unwrap(), a.next().unwrap());
+            quote!(slint::private_unstable_api::re_exports::substring(&#s, #start as i32, #end as i32))
+        }
+        BuiltinFunction::StringContains => {
+            let (s, needle) = (a.next().unwrap(), a.next().unwrap());
+            quote!(#s.contains(#needle.as_str()))
+        }
+        BuiltinFunction::NumberToFixed => {
+            let (n, digits) = (a.next().unwrap(), a.next().unwrap());
+            quote!(slint::private_unstable_api::re_exports::number_to_fixed(#n as f64, #digits as i32))
+        }
+        BuiltinFunction::NumberToPrecision => {
+            let (n, precision) = (a.next().unwrap(), a.next().unwrap());
+            quote!(slint::private_unstable_api::re_exports::number_to_precision(#n as f64, #precision as i32))
+        }
         BuiltinFunction::ColorBrighter => {
             let x = a.next().unwrap();
             let factor = a.next().unwrap();
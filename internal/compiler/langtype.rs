@@ -236,7 +236,11 @@ pub fn ok_for_public_api(&self) -> bool {
 
     pub fn lookup_property<'a>(&self, name: &'a str) -> PropertyLookupResult<'a> {
         match self {
-            Type::Component(c) => c.root_element.borrow().lookup_property(name),
+            Type::Component(c) => {
+                let mut r = c.root_element.borrow().lookup_property(name);
+                r.is_local_to_component = false;
+                r
+            }
             Type::Builtin(b) => {
                 let resolved_name =
                     if let Some(alias_name) = b.native_class.lookup_alias(name.as_ref()) {
@@ -247,12 +251,22 @@ pub fn lookup_property<'a>(&self, name: &'a str) -> PropertyLookupResult<'a> {
                 match b.properties.get(resolved_name.as_ref()) {
                     None => {
                         if b.is_non_item_type {
-                            PropertyLookupResult { resolved_name, property_type: Type::Invalid }
+                            PropertyLookupResult {
+                                resolved_name,
+                                property_type: Type::Invalid,
+                                property_visibility: PropertyVisibility::InOut,
+                                is_local_to_component: false,
+                            }
                         } else {
                             crate::typeregister::reserved_property(name)
                         }
                     }
-                    Some(p) => PropertyLookupResult { resolved_name, property_type: p.ty.clone() },
+                    Some(p) => PropertyLookupResult {
+                        resolved_name,
+                        property_type: p.ty.clone(),
+                        property_visibility: p.property_visibility,
+                        is_local_to_component: false,
+                    },
                 }
             }
             Type::Native(n) => {
@@ -263,11 +277,18 @@ pub fn lookup_property<'a>(&self, name: &'a str) -> PropertyLookupResult<'a> {
                 };
                 let property_type =
                     n.lookup_property(resolved_name.as_ref()).cloned().unwrap_or_default();
-                PropertyLookupResult { resolved_name, property_type }
+                PropertyLookupResult {
+                    resolved_name,
+                    property_type,
+                    property_visibility: PropertyVisibility::InOut,
+                    is_local_to_component: false,
+                }
             }
             _ => PropertyLookupResult {
                 resolved_name: Cow::Borrowed(name),
                 property_type: Type::Invalid,
+                property_visibility: PropertyVisibility::InOut,
+                is_local_to_component: false,
             },
         }
     }
@@ -286,9 +307,12 @@ pub fn property_list(&self) -> Vec<(String, Type)> {
                 );
                 r
             }
-            Type::Builtin(b) => {
-                b.properties.iter().map(|(k, t)| (k.clone(), t.ty.clone())).collect()
-            }
+            Type::Builtin(b) => b
+                .properties
+                .iter()
+                .filter(|(_, t)| t.property_visibility != PropertyVisibility::Private)
+                .map(|(k, t)| (k.clone(), t.ty.clone()))
+                .collect(),
             Type::Native(n) => {
                 n.properties.iter().map(|(k, t)| (k.clone(), t.ty.clone())).collect()
             }
@@ -523,11 +547,18 @@ pub struct BuiltinPropertyInfo {
     /// are output properties which are meant to be read by the .slint.
     /// `is_native_output` is true if the native item can modify the property.
     pub is_native_output: bool,
+    /// Properties set by the compiler, such as the `key` of a `Shortcut`, are private
+    pub property_visibility: PropertyVisibility,
 }
 
 impl BuiltinPropertyInfo {
     pub fn new(ty: Type) -> Self {
-        Self { ty, default_value: None, is_native_output: false }
+        Self {
+            ty,
+            default_value: None,
+            is_native_output: false,
+            property_visibility: PropertyVisibility::InOut,
+        }
     }
 }
 
@@ -628,10 +659,32 @@ pub fn new(native_class: Rc<NativeClass>) -> Self {
     }
 }
 
+/// The access qualifier of a property declaration (`in`, `out`, `in-out` or `private`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyVisibility {
+    /// `private`: can only be accessed from within the component that declares it
+    Private,
+    /// `in`: can be set from outside, the component itself can only provide a default binding
+    Input,
+    /// `out`: can be read from outside, but only set by the component itself
+    Output,
+    /// `in-out`: can be read and set from anywhere. This is the default
+    InOut,
+}
+
+impl Default for PropertyVisibility {
+    fn default() -> Self {
+        Self::InOut
+    }
+}
+
 #[derive(PartialEq, Debug)]
 pub struct PropertyLookupResult<'a> {
     pub resolved_name: std::borrow::Cow<'a, str>,
     pub property_type: Type,
+    pub property_visibility: PropertyVisibility,
+    /// True if the property is declared in the element itself, false if it comes from its base type
+    pub is_local_to_component: bool,
 }
 
 impl<'a> PropertyLookupResult<'a> {
@@ -645,6 +698,8 @@ pub struct Enumeration {
     pub name: String,
     pub values: Vec<String>,
     pub default_value: usize, // index in values
+    /// When declared in .slint, this is the node of the declaration. None for builtin enums.
+    pub node: Option<syntax_nodes::EnumDeclaration>,
 }
 
 impl PartialEq for Enumeration {
@@ -5,7 +5,7 @@
 
 use crate::diagnostics::BuildDiagnostics;
 use crate::expression_tree::*;
-use crate::langtype::{PropertyLookupResult, Type};
+use crate::langtype::{EnumerationValue, PropertyLookupResult, Type};
 use crate::object_tree::{Component, ElementRc};
 
 use std::cell::RefCell;
@@ -22,6 +22,8 @@ pub enum Layout {
     GridLayout(GridLayout),
     PathLayout(PathLayout),
     BoxLayout(BoxLayout),
+    FlowLayout(FlowLayout),
+    StackLayout(StackLayout),
 }
 
 impl Layout {
@@ -29,6 +31,8 @@ pub fn rect(&self) -> &LayoutRect {
         match self {
             Layout::GridLayout(g) => &g.geometry.rect,
             Layout::BoxLayout(g) => &g.geometry.rect,
+            Layout::FlowLayout(g) => &g.geometry.rect,
+            Layout::StackLayout(g) => &g.geometry.rect,
             Layout::PathLayout(p) => &p.rect,
         }
     }
@@ -36,6 +40,8 @@ pub fn rect_mut(&mut self) -> &mut LayoutRect {
         match self {
             Layout::GridLayout(g) => &mut g.geometry.rect,
             Layout::BoxLayout(g) => &mut g.geometry.rect,
+            Layout::FlowLayout(g) => &mut g.geometry.rect,
+            Layout::StackLayout(g) => &mut g.geometry.rect,
             Layout::PathLayout(p) => &mut p.rect,
         }
     }
@@ -43,6 +49,8 @@ pub fn geometry(&self) -> Option<&LayoutGeometry> {
         match self {
             Layout::GridLayout(l) => Some(&l.geometry),
             Layout::BoxLayout(l) => Some(&l.geometry),
+            Layout::FlowLayout(l) => Some(&l.geometry),
+            Layout::StackLayout(l) => Some(&l.geometry),
             Layout::PathLayout(_) => None,
         }
     }
@@ -54,6 +62,8 @@ pub fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedRefe
         match self {
             Layout::GridLayout(grid) => grid.visit_named_references(visitor),
             Layout::BoxLayout(l) => l.visit_named_references(visitor),
+            Layout::FlowLayout(l) => l.visit_named_references(visitor),
+            Layout::StackLayout(l) => l.visit_named_references(visitor),
             Layout::PathLayout(path) => path.visit_named_references(visitor),
         }
     }
@@ -69,7 +79,7 @@ pub struct LayoutItem {
 impl LayoutItem {
     pub fn rect(&self) -> LayoutRect {
         let p = |unresolved_name: &str| {
-            let PropertyLookupResult { resolved_name, property_type } =
+            let PropertyLookupResult { resolved_name, property_type, .. } =
                 self.element.borrow().lookup_property(unresolved_name);
             if property_type == Type::LogicalLength {
                 Some(NamedReference::new(&self.element, resolved_name.as_ref()))
@@ -386,7 +396,7 @@ fn find_binding<R>(
 }
 
 /// Return a named reference to a property if a binding is set on that property
-fn binding_reference(element: &ElementRc, name: &str) -> Option<NamedReference> {
+pub fn binding_reference(element: &ElementRc, name: &str) -> Option<NamedReference> {
     find_binding(element, name, |_, _, _| NamedReference::new(element, name))
 }
 
@@ -451,6 +461,76 @@ fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedReferenc
     }
 }
 
+/// Internal representation of a FlowLayout
+#[derive(Debug, Clone)]
+pub struct FlowLayout {
+    pub elems: Vec<LayoutItem>,
+    pub geometry: LayoutGeometry,
+    /// The alignment of the lines. (`geometry.alignment` is the alignment of the items within a line)
+    pub line_alignment: Option<NamedReference>,
+}
+
+impl FlowLayout {
+    fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedReference)) {
+        for cell in &mut self.elems {
+            cell.constraints.visit_named_references(visitor);
+        }
+        self.geometry.visit_named_references(visitor);
+        if let Some(e) = self.line_alignment.as_mut() {
+            visitor(&mut *e)
+        }
+    }
+}
+
+/// The alignment of an element in a StackLayout
+#[derive(Debug, Clone)]
+pub enum StackAlignment {
+    /// The alignment is known at compile time: one of the values of the StackAlignment enum
+    Constant(EnumerationValue),
+    /// The alignment is the value of this property
+    Property(NamedReference),
+}
+
+/// An element in a StackLayout, with its alignment in both orientations
+#[derive(Debug, Clone)]
+pub struct StackLayoutElement {
+    pub item: LayoutItem,
+    /// The value of `stack-horizontal-alignment`
+    pub horizontal_alignment: StackAlignment,
+    /// The value of `stack-vertical-alignment`
+    pub vertical_alignment: StackAlignment,
+}
+
+impl StackLayoutElement {
+    pub fn alignment(&self, orientation: Orientation) -> &StackAlignment {
+        match orientation {
+            Orientation::Horizontal => &self.horizontal_alignment,
+            Orientation::Vertical => &self.vertical_alignment,
+        }
+    }
+}
+
+/// Internal representation of a StackLayout
+#[derive(Debug, Clone)]
+pub struct StackLayout {
+    pub elems: Vec<StackLayoutElement>,
+    pub geometry: LayoutGeometry,
+}
+
+impl StackLayout {
+    fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedReference)) {
+        for cell in &mut self.elems {
+            cell.item.constraints.visit_named_references(visitor);
+            for alignment in [&mut cell.horizontal_alignment, &mut cell.vertical_alignment] {
+                if let StackAlignment::Property(nr) = alignment {
+                    visitor(nr)
+                }
+            }
+        }
+        self.geometry.visit_named_references(visitor);
+    }
+}
+
 /// Internal representation of a path layout
 #[derive(Debug, Clone)]
 pub struct PathLayout {
@@ -169,6 +169,10 @@ pub async fn compile_syntax_node(
         diagnostics
             .push_error("@children placeholder not allowed in the final component".into(), node)
     }
+    for (_, node) in doc.root_component.named_child_insertion_points.borrow().values() {
+        diagnostics
+            .push_error("@children placeholder not allowed in the final component".into(), node)
+    }
 
     if !diagnostics.has_error() {
         // FIXME: ideally we would be able to run more passes, but currently we panic because invariant are not met.
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use super::PropertyReference;
-use crate::expression_tree::{BuiltinFunction, OperatorClass};
+use crate::expression_tree::{ArrayIterationKind, BuiltinFunction, OperatorClass};
 use crate::langtype::Type;
 use crate::layout::Orientation;
 use itertools::Either;
@@ -53,6 +53,26 @@ pub enum Expression {
         index: Box<Expression>,
     },
 
+    /// Evaluate the expression for each row of the array. While evaluating the expression, the data of
+    /// the current row is in the local variable named `item`, and its index in the local variable named `index`.
+    ArrayIteration {
+        kind: ArrayIterationKind,
+        /// This expression should have [`Type::Array`] type
+        array: Box<Expression>,
+        item: String,
+        index: String,
+        expression: Box<Expression>,
+    },
+
+    /// A model with the rows of the array from `start` up to, but not including, `end`
+    /// (or up to the last row if there is no end)
+    ArraySlice {
+        /// This expression should have [`Type::Array`] type
+        array: Box<Expression>,
+        start: Box<Expression>,
+        end: Option<Box<Expression>>,
+    },
+
     /// Cast an expression to the given type
     Cast {
         from: Box<Expression>,
@@ -71,6 +91,11 @@ pub enum Expression {
         callback: PropertyReference,
         arguments: Vec<Expression>,
     },
+    /// Call of a function declared with `function` in a .slint file
+    FunctionCall {
+        function: PropertyReference,
+        arguments: Vec<Expression>,
+    },
 
     /// A BuiltinFunctionCall, but the function is not yet in the `BuiltinFunction` enum
     /// TODO: merge in BuiltinFunctionCall
@@ -250,6 +275,13 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
                 Type::Array(ty) => *ty,
                 _ => unreachable!(),
             },
+            Self::ArrayIteration { kind, array, expression, .. } => match kind {
+                ArrayIterationKind::Filter => array.ty(ctx),
+                ArrayIterationKind::Map => Type::Array(expression.ty(ctx).into()),
+                ArrayIterationKind::FindIndex => Type::Int32,
+                ArrayIterationKind::Sum => expression.ty(ctx),
+            },
+            Self::ArraySlice { array, .. } => array.ty(ctx),
             Self::Cast { to, .. } => to.clone(),
             Self::CodeBlock(sub) => sub.last().map_or(Type::Void, |e| e.ty(ctx)),
             Self::BuiltinFunctionCall { function, .. } => match function.ty() {
@@ -263,6 +295,7 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
                     Type::Invalid
                 }
             }
+            Self::FunctionCall { function, .. } => ctx.property_ty(function).clone(),
             Self::ExtraBuiltinFunctionCall { return_ty, .. } => return_ty.clone(),
             Self::PropertyAssignment { .. } => Type::Void,
             Self::ModelDataAssignment { .. } => Type::Void,
@@ -308,12 +341,24 @@ macro_rules! visit_impl {
                 $visitor(array);
                 $visitor(index);
             }
+            Expression::ArrayIteration { array, expression, .. } => {
+                $visitor(array);
+                $visitor(expression);
+            }
+            Expression::ArraySlice { array, start, end } => {
+                $visitor(array);
+                $visitor(start);
+                if let Some(end) = end {
+                    $visitor(end);
+                }
+            }
             Expression::Cast { from, .. } => $visitor(from),
             Expression::CodeBlock(b) => b.$iter().for_each($visitor),
             Expression::BuiltinFunctionCall { arguments, .. } => {
                 arguments.$iter().for_each($visitor)
             }
             Expression::CallBackCall { arguments, .. } => arguments.$iter().for_each($visitor),
+            Expression::FunctionCall { arguments, .. } => arguments.$iter().for_each($visitor),
             Expression::ExtraBuiltinFunctionCall { arguments, .. } => {
                 arguments.$iter().for_each($visitor)
             }
@@ -391,6 +436,7 @@ pub fn visit_recursive(&self, visitor: &mut dyn FnMut(&Self)) {
 }
 
 pub trait TypeResolutionContext {
+    /// The type of the property. For a function, this is its return type
     fn property_ty(&self, _: &PropertyReference) -> &Type;
     // The type of the specified argument when evaluating a callback
     fn arg_type(&self, _index: usize) -> &Type {
@@ -502,6 +548,21 @@ fn property_ty(&self, prop: &PropertyReference) -> &Type {
             PropertyReference::Global { global_index, property_index } => {
                 &self.public_component.globals[*global_index].properties[*property_index].ty
             }
+            PropertyReference::Function { sub_component_path, function_index } => {
+                if let Some(mut sub_component) = self.current_sub_component {
+                    for i in sub_component_path {
+                        sub_component = &sub_component.sub_components[*i].ty;
+                    }
+                    &sub_component.functions[*function_index].ret_ty
+                } else if let Some(current_global) = self.current_global {
+                    &current_global.functions[*function_index].ret_ty
+                } else {
+                    unreachable!()
+                }
+            }
+            PropertyReference::GlobalFunction { global_index, function_index } => {
+                &self.public_component.globals[*global_index].functions[*function_index].ret_ty
+            }
         }
     }
 
@@ -29,11 +29,22 @@ pub fn ty(&self, ctx: &dyn super::TypeResolutionContext) -> Type {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct KeyFrame {
+    /// Between 0 and 1
+    pub position: f32,
+    pub value: Expression,
+    /// The easing from this keyframe to the next one
+    pub easing: Expression,
+}
+
 #[derive(Debug, Clone)]
 pub enum Animation {
-    /// The expression is a Struct with the animation fields
-    Static(Expression),
-    Transition(Expression),
+    /// The expression is a Struct with the animation fields, followed by the keyframes
+    Static(Expression, Vec<KeyFrame>),
+    /// The expression is a tuple with the animation, the time of the state change, and the index
+    /// of the transition that applies (or -1). It is followed by the keyframes of each transition.
+    Transition(Expression, Vec<Vec<KeyFrame>>),
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +80,8 @@ pub struct GlobalComponent {
 
     /// Analysis for each properties
     pub prop_analysis: Vec<crate::object_tree::PropertyAnalysis>,
+
+    pub functions: Vec<Function>,
 }
 
 /// a Reference to a property, in the context of a SubComponent
@@ -82,6 +95,10 @@ pub enum PropertyReference {
     InParent { level: NonZeroUsize, parent_reference: Box<PropertyReference> },
     /// The property within a GlobalComponent
     Global { global_index: usize, property_index: usize },
+    /// A function relative to this SubComponent (or GlobalComponent)
+    Function { sub_component_path: Vec<usize>, function_index: usize },
+    /// A function within a GlobalComponent
+    GlobalFunction { global_index: usize, function_index: usize },
 }
 
 #[derive(Debug, Default)]
@@ -93,6 +110,15 @@ pub struct Property {
     pub use_count: Cell<usize>,
 }
 
+/// A function declared with `function` in a .slint file
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub ret_ty: Type,
+    pub args: Vec<Type>,
+    pub code: MutExpression,
+}
+
 #[derive(Debug, Clone)]
 /// The property references might be either in the parent context, or in the
 /// repeated's component context
@@ -209,16 +235,21 @@ pub struct SubComponent {
     pub items: Vec<Item>,
     pub repeated: Vec<RepeatedElement>,
     pub popup_windows: Vec<ItemTree>,
+    /// The `SecondaryWindow`s, each one is shown in its own top-level window
+    pub secondary_windows: Vec<ItemTree>,
+    pub functions: Vec<Function>,
     pub sub_components: Vec<SubComponentInstance>,
     /// The initial value or binding for properties.
     /// This is ordered in the order they must be set.
     pub property_init: Vec<(PropertyReference, BindingExpression)>,
     /// The animation for properties which are animated
-    pub animations: HashMap<PropertyReference, Expression>,
+    pub animations: HashMap<PropertyReference, (Expression, Vec<KeyFrame>)>,
     pub two_way_bindings: Vec<(PropertyReference, PropertyReference)>,
     pub const_properties: Vec<PropertyReference>,
     /// Code that is run in the sub component constructor, after property initializations
     pub init_code: Vec<MutExpression>,
+    /// The code of the `changed <property> => {...}` callbacks, run when the property changes
+    pub change_callbacks: Vec<(PropertyReference, MutExpression)>,
 
     pub layout_info_h: MutExpression,
     pub layout_info_v: MutExpression,
@@ -316,7 +347,7 @@ fn visit_component<'a>(
                     Some(ParentCtx::new(&ctx, Some(idx))),
                 );
             }
-            for x in &c.popup_windows {
+            for x in c.popup_windows.iter().chain(c.secondary_windows.iter()) {
                 visit_component(root, &x.root, visitor, Some(ParentCtx::new(&ctx, None)));
             }
         }
@@ -331,6 +362,9 @@ pub fn for_each_expression<'a>(
         visitor: &mut dyn FnMut(&'a super::MutExpression, &EvaluationContext<'_>),
     ) {
         self.for_each_sub_components(&mut |sc, ctx| {
+            for (_, e) in &sc.change_callbacks {
+                visitor(e, ctx);
+            }
             for e in &sc.init_code {
                 visitor(e, ctx);
             }
@@ -342,14 +376,28 @@ pub fn for_each_expression<'a>(
             for (_, e) in &sc.accessible_prop {
                 visitor(e, ctx);
             }
+            for f in &sc.functions {
+                visitor(&f.code, &EvaluationContext { argument_types: &f.args, ..ctx.clone() });
+            }
         });
         for g in &self.globals {
             let ctx = EvaluationContext::new_global(self, g, ());
             for e in g.init_values.iter().filter_map(|x| x.as_ref()) {
                 visitor(&e.expression, &ctx)
             }
+            for f in &g.functions {
+                visitor(&f.code, &EvaluationContext { argument_types: &f.args, ..ctx.clone() });
+            }
         }
     }
 }
 
-pub type PublicProperties = BTreeMap<String, (Type, PropertyReference)>;
+#[derive(Debug, Clone)]
+pub struct PublicProperty {
+    pub ty: Type,
+    pub prop: PropertyReference,
+    /// True for `out` properties: only a getter is generated
+    pub read_only: bool,
+}
+
+pub type PublicProperties = BTreeMap<String, PublicProperty>;
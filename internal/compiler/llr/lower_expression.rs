@@ -9,13 +9,13 @@
 use itertools::Either;
 
 use super::lower_to_item_tree::{LoweredElement, LoweredSubComponentMapping, LoweringState};
-use super::{Animation, PropertyReference};
+use super::{Animation, KeyFrame, PropertyReference};
 use crate::expression_tree::{BuiltinFunction, Expression as tree_Expression};
 use crate::langtype::{EnumerationValue, Type};
 use crate::layout::Orientation;
 use crate::llr::Expression as llr_Expression;
 use crate::namedreference::NamedReference;
-use crate::object_tree::{Element, ElementRc, PropertyAnimation};
+use crate::object_tree::{Element, ElementRc, KeyFrameProperties, PropertyAnimation};
 
 pub struct ExpressionContext<'a> {
     pub component: &'a Rc<crate::object_tree::Component>,
@@ -74,6 +74,7 @@ pub fn lower_expression(
         tree_Expression::PropertyReference(nr) => {
             llr_Expression::PropertyReference(ctx.map_property_reference(nr))
         }
+        tree_Expression::FunctionReference(_) => panic!(),
         tree_Expression::BuiltinFunctionReference(_, _) => panic!(),
         tree_Expression::MemberFunction { .. } => panic!(),
         tree_Expression::BuiltinMacroReference(_, _) => panic!(),
@@ -107,6 +108,20 @@ pub fn lower_expression(
             array: Box::new(lower_expression(array, ctx)),
             index: Box::new(lower_expression(index, ctx)),
         },
+        tree_Expression::ArrayIteration { kind, array, item, index, expression } => {
+            llr_Expression::ArrayIteration {
+                kind: *kind,
+                array: Box::new(lower_expression(array, ctx)),
+                item: item.clone(),
+                index: index.clone(),
+                expression: Box::new(lower_expression(expression, ctx)),
+            }
+        }
+        tree_Expression::ArraySlice { array, start, end } => llr_Expression::ArraySlice {
+            array: Box::new(lower_expression(array, ctx)),
+            start: Box::new(lower_expression(start, ctx)),
+            end: end.as_ref().map(|end| Box::new(lower_expression(end, ctx))),
+        },
         tree_Expression::Cast { from, to } => {
             llr_Expression::Cast { from: Box::new(lower_expression(from, ctx)), to: to.clone() }
         }
@@ -117,6 +132,10 @@ pub fn lower_expression(
             tree_Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 lower_show_popup(arguments, ctx)
             }
+            tree_Expression::BuiltinFunctionReference(
+                f @ (BuiltinFunction::ShowSecondaryWindow | BuiltinFunction::HideSecondaryWindow),
+                _,
+            ) => lower_secondary_window_function(*f, arguments, ctx),
             tree_Expression::BuiltinFunctionReference(f, _) => {
                 let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
                 llr_Expression::BuiltinFunctionCall { function: *f, arguments }
@@ -125,6 +144,10 @@ pub fn lower_expression(
                 let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
                 llr_Expression::CallBackCall { callback: ctx.map_property_reference(nr), arguments }
             }
+            tree_Expression::FunctionReference(nr) => {
+                let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
+                llr_Expression::FunctionCall { function: ctx.map_property_reference(nr), arguments }
+            }
             _ => panic!("not calling a function"),
         },
         tree_Expression::SelfAssignment { lhs, rhs, op } => lower_assignment(lhs, rhs, *op, ctx),
@@ -357,6 +380,35 @@ fn lower_show_popup(args: &[tree_Expression], ctx: &ExpressionContext) -> llr_Ex
     }
 }
 
+fn lower_secondary_window_function(
+    function: BuiltinFunction,
+    args: &[tree_Expression],
+    ctx: &ExpressionContext,
+) -> llr_Expression {
+    if let [tree_Expression::ElementReference(e)] = args {
+        let window = e.upgrade().unwrap();
+        let window_comp = window.borrow().enclosing_component.upgrade().unwrap();
+        let parent_element = window_comp.parent_element.upgrade().unwrap();
+        let parent_component = parent_element.borrow().enclosing_component.upgrade().unwrap();
+        let window_index = parent_component
+            .secondary_windows
+            .borrow()
+            .iter()
+            .position(|w| Rc::ptr_eq(w, &window_comp))
+            .unwrap();
+        let item_ref = lower_expression(
+            &tree_Expression::ElementReference(Rc::downgrade(&parent_element)),
+            ctx,
+        );
+        llr_Expression::BuiltinFunctionCall {
+            function,
+            arguments: vec![llr_Expression::NumberLiteral(window_index as _), item_ref],
+        }
+    } else {
+        panic!("invalid arguments to {:?}", function);
+    }
+}
+
 pub fn lower_animation(a: &PropertyAnimation, ctx: &ExpressionContext<'_>) -> Animation {
     fn lower_animation_element(a: &ElementRc, ctx: &ExpressionContext<'_>) -> llr_Expression {
         llr_Expression::Struct {
@@ -379,9 +431,35 @@ fn animation_fields() -> impl Iterator<Item = (String, Type)> {
             ("iteration-count".to_string(), Type::Float32),
             ("easing".to_string(), Type::Easing),
             ("delay".to_string(), Type::Int32),
+            (
+                "direction".to_string(),
+                Type::Enumeration(
+                    crate::typeregister::BUILTIN_ENUMS.with(|e| e.AnimationDirection.clone()),
+                ),
+            ),
         ])
     }
 
+    fn lower_keyframes(a: &ElementRc, ctx: &ExpressionContext<'_>) -> Vec<KeyFrame> {
+        let a = a.borrow();
+        KeyFrameProperties::for_animation(&a)
+            .into_iter()
+            .filter_map(|k| {
+                let position = match a.bindings.get(&k.position)?.borrow().expression {
+                    tree_Expression::NumberLiteral(position, _) => position as f32,
+                    _ => return None,
+                };
+                let value = lower_expression(&a.bindings.get(&k.value)?.borrow().expression, ctx);
+                // Without easing, the keyframe uses the easing of the animation
+                let easing = a.bindings.get(&k.easing).or_else(|| a.bindings.get("easing")).map_or(
+                    llr_Expression::EasingCurve(crate::expression_tree::EasingCurve::default()),
+                    |e| lower_expression(&e.borrow().expression, ctx),
+                );
+                Some(KeyFrame { position, value, easing })
+            })
+            .collect()
+    }
+
     fn animation_ty() -> Type {
         Type::Struct {
             fields: animation_fields().collect(),
@@ -391,7 +469,9 @@ fn animation_ty() -> Type {
     }
 
     match a {
-        PropertyAnimation::Static(a) => Animation::Static(lower_animation_element(a, ctx)),
+        PropertyAnimation::Static(a) => {
+            Animation::Static(lower_animation_element(a, ctx), lower_keyframes(a, ctx))
+        }
         PropertyAnimation::Transition { state_ref, animations } => {
             let set_state = llr_Expression::StoreLocalVariable {
                 name: "state".into(),
@@ -399,7 +479,8 @@ fn animation_ty() -> Type {
             };
             let animation_ty = animation_ty();
             let mut get_anim = llr_Expression::default_value_for_type(&animation_ty).unwrap();
-            for tr in animations.iter().rev() {
+            let mut get_index = llr_Expression::NumberLiteral(-1.);
+            for (index, tr) in animations.iter().enumerate().rev() {
                 let condition = lower_expression(
                     &tr.condition(tree_Expression::ReadLocalVariable {
                         name: "state".into(),
@@ -408,10 +489,15 @@ fn animation_ty() -> Type {
                     ctx,
                 );
                 get_anim = llr_Expression::Condition {
-                    condition: Box::new(condition),
+                    condition: Box::new(condition.clone()),
                     true_expr: Box::new(lower_animation_element(&tr.animation, ctx)),
                     false_expr: Box::new(get_anim),
-                }
+                };
+                get_index = llr_Expression::Condition {
+                    condition: Box::new(condition),
+                    true_expr: Box::new(llr_Expression::NumberLiteral(index as _)),
+                    false_expr: Box::new(get_index),
+                };
             }
             let result = llr_Expression::Struct {
                 // This is going to be a tuple
@@ -420,6 +506,7 @@ fn animation_ty() -> Type {
                         ("0".to_string(), animation_ty),
                         // The type is an instant, which does not exist in our type system
                         ("1".to_string(), Type::Invalid),
+                        ("2".to_string(), Type::Int32),
                     ])
                     .collect(),
                     name: None,
@@ -438,10 +525,14 @@ fn animation_ty() -> Type {
                             name: "change_time".into(),
                         },
                     ),
+                    ("2".to_string(), get_index),
                 ])
                 .collect(),
             };
-            Animation::Transition(llr_Expression::CodeBlock(vec![set_state, result]))
+            Animation::Transition(
+                llr_Expression::CodeBlock(vec![set_state, result]),
+                animations.iter().map(|tr| lower_keyframes(&tr.animation, ctx)).collect(),
+            )
         }
     }
 }
@@ -488,6 +579,44 @@ fn compute_layout_info(
                 None => sub_expression,
             }
         }
+        crate::layout::Layout::FlowLayout(layout) => match o {
+            Orientation::Horizontal => {
+                let (padding, spacing) =
+                    generate_layout_padding_and_spacing(&layout.geometry, o, ctx);
+                let (cells, compute_cells) = box_layout_cells(&layout.elems, o, "cells", ctx);
+                let sub_expression = llr_Expression::ExtraBuiltinFunctionCall {
+                    function: "flow_layout_info_horizontal".into(),
+                    arguments: vec![cells, spacing, padding],
+                    return_ty: crate::layout::layout_info_type(),
+                };
+                match compute_cells {
+                    Some((cells_variable, elements)) => llr_Expression::BoxLayoutFunction {
+                        cells_variable,
+                        repeater_indices: None,
+                        elements,
+                        orientation: o,
+                        sub_expression: Box::new(sub_expression),
+                    },
+                    None => sub_expression,
+                }
+            }
+            Orientation::Vertical => flow_layout_function(layout, None, ctx, |data, _| {
+                llr_Expression::ExtraBuiltinFunctionCall {
+                    function: "flow_layout_info_vertical".into(),
+                    arguments: vec![data],
+                    return_ty: crate::layout::layout_info_type(),
+                }
+            }),
+        },
+        crate::layout::Layout::StackLayout(layout) => {
+            stack_layout_function(layout, o, false, ctx, |data, repeater_indices| {
+                llr_Expression::ExtraBuiltinFunctionCall {
+                    function: "stack_layout_info".into(),
+                    arguments: vec![data, repeater_indices],
+                    return_ty: crate::layout::layout_info_type(),
+                }
+            })
+        }
         crate::layout::Layout::PathLayout(_) => unimplemented!(),
     }
 }
@@ -614,6 +743,28 @@ fn solve_layout(
                 },
             }
         }
+        crate::layout::Layout::FlowLayout(layout) => {
+            let function = match o {
+                Orientation::Horizontal => "solve_flow_layout_horizontal",
+                Orientation::Vertical => "solve_flow_layout_vertical",
+            };
+            flow_layout_function(layout, Some(o), ctx, |data, repeater_indices| {
+                llr_Expression::ExtraBuiltinFunctionCall {
+                    function: function.into(),
+                    arguments: vec![data, repeater_indices],
+                    return_ty: Type::LayoutCache,
+                }
+            })
+        }
+        crate::layout::Layout::StackLayout(layout) => {
+            stack_layout_function(layout, o, true, ctx, |data, repeater_indices| {
+                llr_Expression::ExtraBuiltinFunctionCall {
+                    function: "solve_stack_layout".into(),
+                    arguments: vec![data, repeater_indices],
+                    return_ty: Type::LayoutCache,
+                }
+            })
+        }
         crate::layout::Layout::PathLayout(layout) => {
             let width = layout_geometry_size(&layout.rect, Orientation::Horizontal, ctx);
             let height = layout_geometry_size(&layout.rect, Orientation::Vertical, ctx);
@@ -664,18 +815,209 @@ fn box_layout_data(
     orientation: Orientation,
     ctx: &ExpressionContext,
 ) -> BoxLayoutDataResult {
-    let alignment = if let Some(expr) = &layout.geometry.alignment {
+    let alignment = layout_alignment(layout.geometry.alignment.as_ref(), None, ctx);
+    let (cells, compute_cells) = box_layout_cells(&layout.elems, orientation, "cells", ctx);
+    BoxLayoutDataResult { alignment, cells, compute_cells }
+}
+
+/// Build the FlowLayoutData of the layout and pass it to `f`, with the repeater indices.
+///
+/// `solve` is the orientation being solved, or None when computing the vertical layout info.
+/// The vertical cells are not computed when solving the horizontal orientation, as the
+/// horizontal geometry of the layout must not depend on the vertical geometry of the items.
+/// The height of the layout is only read when solving the vertical orientation, as the layout
+/// info must not depend on the size of the layout itself.
+fn flow_layout_function(
+    layout: &crate::layout::FlowLayout,
+    solve: Option<Orientation>,
+    ctx: &ExpressionContext,
+    f: impl FnOnce(llr_Expression, llr_Expression) -> llr_Expression,
+) -> llr_Expression {
+    let (padding_h, spacing) =
+        generate_layout_padding_and_spacing(&layout.geometry, Orientation::Horizontal, ctx);
+    let (padding_v, _) =
+        generate_layout_padding_and_spacing(&layout.geometry, Orientation::Vertical, ctx);
+    let (cells_h, compute_cells_h) =
+        box_layout_cells(&layout.elems, Orientation::Horizontal, "cells_h", ctx);
+    let (cells_v, compute_cells_v) = if solve != Some(Orientation::Horizontal) {
+        box_layout_cells(&layout.elems, Orientation::Vertical, "cells_v", ctx)
+    } else {
+        let element_ty = Type::Struct {
+            fields: IntoIterator::into_iter([(
+                "constraint".to_string(),
+                crate::layout::layout_info_type(),
+            )])
+            .collect(),
+            name: Some("BoxLayoutCellData".into()),
+            node: None,
+        };
+        (llr_Expression::Array { element_ty, values: vec![], as_model: false }, None)
+    };
+    let alignment_ty =
+        crate::typeregister::BUILTIN_ENUMS.with(|e| Type::Enumeration(e.LayoutAlignment.clone()));
+    let data = make_struct(
+        "FlowLayoutData".into(),
+        [
+            (
+                "width",
+                Type::Float32,
+                layout_geometry_size(&layout.geometry.rect, Orientation::Horizontal, ctx),
+            ),
+            (
+                "height",
+                Type::Float32,
+                if solve == Some(Orientation::Vertical) {
+                    layout_geometry_size(&layout.geometry.rect, Orientation::Vertical, ctx)
+                } else {
+                    llr_Expression::NumberLiteral(0.)
+                },
+            ),
+            ("spacing", Type::Float32, spacing),
+            ("padding_h", padding_h.ty(ctx), padding_h),
+            ("padding_v", padding_v.ty(ctx), padding_v),
+            (
+                "alignment",
+                alignment_ty.clone(),
+                layout_alignment(layout.geometry.alignment.as_ref(), Some("start"), ctx),
+            ),
+            (
+                "line_alignment",
+                alignment_ty,
+                layout_alignment(layout.line_alignment.as_ref(), Some("start"), ctx),
+            ),
+            ("cells_h", cells_h.ty(ctx), cells_h),
+            ("cells_v", cells_v.ty(ctx), cells_v),
+        ],
+    );
+    let repeater_indices = if compute_cells_h.is_some() {
+        llr_Expression::ReadLocalVariable {
+            name: "repeated_indices".into(),
+            ty: Type::Array(Type::Int32.into()),
+        }
+    } else {
+        llr_Expression::Array { element_ty: Type::Int32, values: vec![], as_model: false }
+    };
+    let mut expression = f(data, repeater_indices);
+    if let Some((cells_variable, elements)) = compute_cells_v {
+        expression = llr_Expression::BoxLayoutFunction {
+            cells_variable,
+            repeater_indices: None,
+            elements,
+            orientation: Orientation::Vertical,
+            sub_expression: Box::new(expression),
+        };
+    }
+    if let Some((cells_variable, elements)) = compute_cells_h {
+        expression = llr_Expression::BoxLayoutFunction {
+            cells_variable,
+            repeater_indices: Some("repeated_indices".into()),
+            elements,
+            orientation: Orientation::Horizontal,
+            sub_expression: Box::new(expression),
+        };
+    }
+    expression
+}
+
+/// Build the StackLayoutData for the given orientation and call `f` with it and the repeater indices.
+///
+/// The size of the layout is only read when `solve` is true, as the layout info must not depend
+/// on the size of the layout itself.
+fn stack_layout_function(
+    layout: &crate::layout::StackLayout,
+    o: Orientation,
+    solve: bool,
+    ctx: &ExpressionContext,
+    f: impl FnOnce(llr_Expression, llr_Expression) -> llr_Expression,
+) -> llr_Expression {
+    let (padding, _) = generate_layout_padding_and_spacing(&layout.geometry, o, ctx);
+    let items = layout.elems.iter().map(|e| e.item.clone()).collect::<Vec<_>>();
+    let (cells, compute_cells) = box_layout_cells(&items, o, "cells", ctx);
+    let alignment_ty =
+        crate::typeregister::BUILTIN_ENUMS.with(|e| Type::Enumeration(e.StackAlignment.clone()));
+    let alignments = llr_Expression::Array {
+        element_ty: alignment_ty,
+        values: layout
+            .elems
+            .iter()
+            .map(|e| match e.alignment(o) {
+                crate::layout::StackAlignment::Constant(value) => {
+                    llr_Expression::EnumerationValue(value.clone())
+                }
+                crate::layout::StackAlignment::Property(nr) => {
+                    llr_Expression::PropertyReference(ctx.map_property_reference(nr))
+                }
+            })
+            .collect(),
+        as_model: false,
+    };
+    let data = make_struct(
+        "StackLayoutData".into(),
+        [
+            (
+                "size",
+                Type::Float32,
+                if solve {
+                    layout_geometry_size(&layout.geometry.rect, o, ctx)
+                } else {
+                    llr_Expression::NumberLiteral(0.)
+                },
+            ),
+            ("padding", padding.ty(ctx), padding),
+            ("alignments", alignments.ty(ctx), alignments),
+            ("cells", cells.ty(ctx), cells),
+        ],
+    );
+    match compute_cells {
+        Some((cells_variable, elements)) => llr_Expression::BoxLayoutFunction {
+            cells_variable,
+            repeater_indices: Some("repeated_indices".into()),
+            elements,
+            orientation: o,
+            sub_expression: Box::new(f(
+                data,
+                llr_Expression::ReadLocalVariable {
+                    name: "repeated_indices".into(),
+                    ty: Type::Array(Type::Int32.into()),
+                },
+            )),
+        },
+        None => f(
+            data,
+            llr_Expression::Array { element_ty: Type::Int32, values: vec![], as_model: false },
+        ),
+    }
+}
+
+/// The value of a LayoutAlignment property, or of `default` if there is no binding to that property.
+/// (The default value of the enumeration if `default` is None)
+fn layout_alignment(
+    nr: Option<&NamedReference>,
+    default: Option<&str>,
+    ctx: &ExpressionContext,
+) -> llr_Expression {
+    if let Some(expr) = nr {
         llr_Expression::PropertyReference(ctx.map_property_reference(expr))
     } else {
         let e = crate::typeregister::BUILTIN_ENUMS.with(|e| e.LayoutAlignment.clone());
         llr_Expression::EnumerationValue(EnumerationValue {
-            value: e.default_value,
+            value: default
+                .and_then(|d| e.values.iter().position(|x| x == d))
+                .unwrap_or(e.default_value),
             enumeration: e,
         })
-    };
+    }
+}
 
-    let repeater_count =
-        layout.elems.iter().filter(|i| i.element.borrow().repeated.is_some()).count();
+/// Returns the array of BoxLayoutCellData of the items, and when there are repeater involved,
+/// the variable and elements of the BoxLayoutFunction that computes them
+fn box_layout_cells(
+    elems: &[crate::layout::LayoutItem],
+    orientation: Orientation,
+    cells_variable: &str,
+    ctx: &ExpressionContext,
+) -> (llr_Expression, Option<(String, Vec<Either<llr_Expression, usize>>)>) {
+    let repeater_count = elems.iter().filter(|i| i.element.borrow().repeated.is_some()).count();
 
     let element_ty = Type::Struct {
         fields: IntoIterator::into_iter([(
@@ -689,8 +1031,7 @@ fn box_layout_data(
 
     if repeater_count == 0 {
         let cells = llr_Expression::Array {
-            values: layout
-                .elems
+            values: elems
                 .iter()
                 .map(|li| {
                     let layout_info =
@@ -704,10 +1045,10 @@ fn box_layout_data(
             element_ty,
             as_model: false,
         };
-        BoxLayoutDataResult { alignment, cells, compute_cells: None }
+        (cells, None)
     } else {
         let mut elements = vec![];
-        for item in &layout.elems {
+        for item in elems {
             if item.element.borrow().repeated.is_some() {
                 let repeater_index =
                     match ctx.mapping.element_mapping.get(&item.element.clone().into()).unwrap() {
@@ -725,10 +1066,10 @@ fn box_layout_data(
             }
         }
         let cells = llr_Expression::ReadLocalVariable {
-            name: "cells".into(),
+            name: cells_variable.into(),
             ty: Type::Array(Box::new(crate::layout::layout_info_type())),
         };
-        BoxLayoutDataResult { alignment, cells, compute_cells: Some(("cells".into(), elements)) }
+        (cells, Some((cells_variable.into(), elements)))
     }
 }
 
@@ -4,7 +4,7 @@
 use by_address::ByAddress;
 
 use crate::expression_tree::Expression as tree_Expression;
-use crate::langtype::Type;
+use crate::langtype::{PropertyVisibility, Type};
 use crate::llr::item_tree::*;
 use crate::namedreference::NamedReference;
 use crate::object_tree::{Component, ElementRc};
@@ -144,11 +144,12 @@ fn property_reference_within_sub_component(
 ) -> PropertyReference {
     match &mut prop_ref {
         PropertyReference::Local { sub_component_path, .. }
-        | PropertyReference::InNativeItem { sub_component_path, .. } => {
+        | PropertyReference::InNativeItem { sub_component_path, .. }
+        | PropertyReference::Function { sub_component_path, .. } => {
             sub_component_path.insert(0, sub_component);
         }
         PropertyReference::InParent { .. } => panic!("the sub-component had no parents"),
-        PropertyReference::Global { .. } => (),
+        PropertyReference::Global { .. } | PropertyReference::GlobalFunction { .. } => (),
     }
     prop_ref
 }
@@ -182,12 +183,15 @@ fn lower_sub_component(
         items: Default::default(),
         repeated: Default::default(),
         popup_windows: Default::default(),
+        secondary_windows: Default::default(),
+        functions: Default::default(),
         sub_components: Default::default(),
         property_init: Default::default(),
         animations: Default::default(),
         two_way_bindings: Default::default(),
         const_properties: Default::default(),
         init_code: Default::default(),
+        change_callbacks: Default::default(),
         // just initialize to dummy expression right now and it will be set later
         layout_info_h: super::Expression::BoolLiteral(false).into(),
         layout_info_v: super::Expression::BoolLiteral(false).into(),
@@ -197,6 +201,8 @@ fn lower_sub_component(
     let mut mapping = LoweredSubComponentMapping::default();
     let mut repeated = vec![];
     let mut accessible_prop = Vec::new();
+    let mut functions = vec![];
+    let mut change_callbacks = vec![];
 
     if let Some(parent) = component.parent_element.upgrade() {
         // Add properties for the model data and index
@@ -221,10 +227,26 @@ fn lower_sub_component(
     let mut repeater_offset = 0;
     crate::object_tree::recurse_elem(&component.root_element, &s, &mut |element, parent| {
         let elem = element.borrow();
+        for (p, handlers) in &elem.change_callbacks {
+            change_callbacks.extend(
+                handlers.borrow().iter().map(|e| (NamedReference::new(element, p), e.clone())),
+            );
+        }
         for (p, x) in &elem.property_declarations {
             if x.is_alias.is_some() {
                 continue;
             }
+            if let Type::Function { .. } = &x.property_type {
+                mapping.property_mapping.insert(
+                    NamedReference::new(element, p),
+                    PropertyReference::Function {
+                        sub_component_path: vec![],
+                        function_index: functions.len(),
+                    },
+                );
+                functions.push((element.clone(), p.clone()));
+                continue;
+            }
             let property_index = sub_component.properties.len();
             mapping.property_mapping.insert(
                 NamedReference::new(element, p),
@@ -340,16 +362,23 @@ fn lower_sub_component(
         {
             if let Some(anim) = binding.animation.as_ref() {
                 match super::lower_expression::lower_animation(anim, &ctx) {
-                    Animation::Static(anim) => {
-                        sub_component.animations.insert(prop, anim);
+                    Animation::Static(anim, keyframes) => {
+                        sub_component.animations.insert(prop, (anim, keyframes));
                     }
-                    Animation::Transition(_) => {
+                    Animation::Transition(..) => {
                         // Cannot set a property with a transition anyway
                     }
                 }
             }
         }
     });
+    sub_component.functions = functions
+        .into_iter()
+        .map(|(e, p)| {
+            let name = format!("{}_{}", e.borrow().id, p);
+            lower_function(name, &e, &p, &ctx)
+        })
+        .collect();
     sub_component.repeated =
         repeated.into_iter().map(|elem| lower_repeated_component(&elem, &ctx)).collect();
     for s in &mut sub_component.sub_components {
@@ -361,12 +390,28 @@ fn lower_sub_component(
         .iter()
         .map(|popup| lower_popup_component(&popup.component, &ctx))
         .collect();
+    sub_component.secondary_windows = component
+        .secondary_windows
+        .borrow()
+        .iter()
+        .map(|window| lower_popup_component(window, &ctx))
+        .collect();
 
     crate::generator::for_each_const_properties(component, |elem, n| {
         let x = ctx.map_property_reference(&NamedReference::new(elem, n));
         sub_component.const_properties.push(x);
     });
 
+    sub_component.change_callbacks = change_callbacks
+        .into_iter()
+        .map(|(nr, e)| {
+            (
+                ctx.map_property_reference(&nr),
+                super::lower_expression::lower_expression(&e, &ctx).into(),
+            )
+        })
+        .collect();
+
     sub_component.init_code = component
         .setup_code
         .borrow()
@@ -507,10 +552,23 @@ fn lower_global(
     let mut properties = vec![];
     let mut const_properties = vec![];
     let mut prop_analysis = vec![];
+    let mut function_names = vec![];
 
     for (p, x) in &global.root_element.borrow().property_declarations {
-        let property_index = properties.len();
         let nr = NamedReference::new(&global.root_element, p);
+        if let Type::Function { .. } = &x.property_type {
+            let function_index = function_names.len();
+            mapping.property_mapping.insert(
+                nr.clone(),
+                PropertyReference::Function { sub_component_path: vec![], function_index },
+            );
+            state
+                .global_properties
+                .insert(nr, PropertyReference::GlobalFunction { global_index, function_index });
+            function_names.push(p.clone());
+            continue;
+        }
+        let property_index = properties.len();
         mapping.property_mapping.insert(
             nr.clone(),
             PropertyReference::Local { sub_component_path: vec![], property_index },
@@ -545,6 +603,9 @@ fn lower_global(
 
     let ctx = ExpressionContext { mapping: &mapping, state, parent: None, component: global };
     for (prop, binding) in &global.root_element.borrow().bindings {
+        if crate::generator::is_function(&global.root_element, prop) {
+            continue;
+        }
         assert!(binding.borrow().two_way_bindings.is_empty());
         assert!(binding.borrow().animation.is_none());
         let expression =
@@ -565,6 +626,11 @@ fn lower_global(
         });
     }
 
+    let functions = function_names
+        .into_iter()
+        .map(|p| lower_function(p.clone(), &global.root_element, &p, &ctx))
+        .collect();
+
     let is_builtin = if let Some(builtin) = global.root_element.borrow().native_class() {
         // We just generate the property so we know how to address them
         for (p, x) in &builtin.properties {
@@ -601,9 +667,28 @@ fn lower_global(
         aliases: global.global_aliases(),
         is_builtin,
         prop_analysis,
+        functions,
     }
 }
 
+fn lower_function(
+    name: String,
+    element: &ElementRc,
+    function_name: &str,
+    ctx: &ExpressionContext,
+) -> Function {
+    let (ret_ty, args) = match element.borrow().lookup_property(function_name).property_type {
+        Type::Function { return_type, args } => (*return_type, args),
+        _ => unreachable!(),
+    };
+    let code = super::lower_expression::lower_expression(
+        &element.borrow().bindings[function_name].borrow().expression,
+        ctx,
+    )
+    .into();
+    Function { name, ret_ty, args, code }
+}
+
 fn make_tree(
     state: &LoweringState,
     element: &ElementRc,
@@ -661,7 +746,14 @@ fn public_properties(
         .map(|(p, c)| {
             let property_reference = mapping
                 .map_property_reference(&NamedReference::new(&component.root_element, p), state);
-            (p.clone(), (c.property_type.clone(), property_reference))
+            (
+                p.clone(),
+                PublicProperty {
+                    ty: c.property_type.clone(),
+                    prop: property_reference,
+                    read_only: c.visibility == PropertyVisibility::Output,
+                },
+            )
         })
         .collect()
 }
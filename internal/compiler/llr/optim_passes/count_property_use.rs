@@ -11,13 +11,13 @@ pub fn count_property_use(root: &PublicComponent) {
     // Visit the root properties that are used.
     // 1. the public properties
     let root_ctx = EvaluationContext::new_sub_component(root, &root.item_tree.root, (), None);
-    for (_, pr) in root.public_properties.values() {
-        visit_property(pr, &root_ctx);
+    for p in root.public_properties.values() {
+        visit_property(&p.prop, &root_ctx);
     }
     for g in root.globals.iter().filter(|g| g.exported) {
         let ctx = EvaluationContext::new_global(root, g, ());
-        for (_, pr) in g.public_properties.values() {
-            visit_property(pr, &ctx);
+        for p in g.public_properties.values() {
+            visit_property(&p.prop, &ctx);
         }
     }
 
@@ -89,7 +89,26 @@ pub fn count_property_use(root: &PublicComponent) {
             visit_property(a, ctx);
             visit_property(b, ctx);
         }
-    })
+
+        // 8. functions (TODO: only visit used function)
+        for f in &sc.functions {
+            f.code.borrow().visit_recursive(&mut |e| visit_expression(e, ctx));
+        }
+
+        // 9. change callbacks
+        for (p, e) in &sc.change_callbacks {
+            visit_property(p, ctx);
+            e.borrow().visit_recursive(&mut |e| visit_expression(e, ctx));
+        }
+    });
+
+    // 10. functions in globals
+    for g in root.globals.iter() {
+        let ctx = EvaluationContext::new_global(root, g, ());
+        for f in &g.functions {
+            f.code.borrow().visit_recursive(&mut |e| visit_expression(e, &ctx));
+        }
+    }
 }
 
 fn visit_property(pr: &PropertyReference, ctx: &EvaluationContext) {
@@ -31,10 +31,13 @@ fn expression_cost(exp: &Expression, ctx: &EvaluationContext) -> isize {
         Expression::ReadLocalVariable { .. } => 1,
         Expression::StructFieldAccess { .. } => 1,
         Expression::ArrayIndex { .. } => ARRAY_INDEX_COST,
+        Expression::ArrayIteration { .. } => return isize::MAX,
+        Expression::ArraySlice { .. } => return isize::MAX,
         Expression::Cast { .. } => 0,
         Expression::CodeBlock(_) => 0,
         Expression::BuiltinFunctionCall { function, .. } => builtin_function_cost(*function),
         Expression::CallBackCall { callback, .. } => callback_cost(callback, ctx),
+        Expression::FunctionCall { .. } => return isize::MAX,
         Expression::ExtraBuiltinFunctionCall { .. } => return isize::MAX,
         Expression::PropertyAssignment { .. } => return isize::MAX,
         Expression::ModelDataAssignment { .. } => return isize::MAX,
@@ -86,8 +89,19 @@ fn builtin_function_cost(function: BuiltinFunction) -> isize {
         BuiltinFunction::Pow => 10,
         BuiltinFunction::SetFocusItem => isize::MAX,
         BuiltinFunction::ShowPopupWindow => isize::MAX,
+        BuiltinFunction::ShowSecondaryWindow => isize::MAX,
+        BuiltinFunction::HideSecondaryWindow => isize::MAX,
+        BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => isize::MAX,
+        BuiltinFunction::ShowFileDialog | BuiltinFunction::ShowMessageDialog => isize::MAX,
         BuiltinFunction::StringToFloat => 50,
         BuiltinFunction::StringIsFloat => 50,
+        BuiltinFunction::StringToUppercase => 50,
+        BuiltinFunction::StringToLowercase => 50,
+        BuiltinFunction::StringLength => 50,
+        BuiltinFunction::StringSubstring => 50,
+        BuiltinFunction::StringContains => 50,
+        BuiltinFunction::NumberToFixed => 50,
+        BuiltinFunction::NumberToPrecision => 50,
         BuiltinFunction::ColorBrighter => 50,
         BuiltinFunction::ColorDarker => 50,
         BuiltinFunction::ImageSize => 50,
@@ -245,6 +259,9 @@ fn match_in_sub_component<'a>(
             }
             ret
         }
+        PropertyReference::Function { .. } | PropertyReference::GlobalFunction { .. } => {
+            return PropertyInfoResult::default();
+        }
     }
 }
 
@@ -304,7 +321,19 @@ pub fn map_property_reference(&self, p: &PropertyReference) -> PropertyReference
                             parent_reference: parent_reference.clone(),
                         }
                     }
-                    PropertyReference::Global { .. } => return p.clone(),
+                    PropertyReference::Global { .. } | PropertyReference::GlobalFunction { .. } => {
+                        return p.clone()
+                    }
+                    PropertyReference::Function { sub_component_path, function_index } => {
+                        PropertyReference::Function {
+                            sub_component_path: path
+                                .iter()
+                                .chain(sub_component_path.iter())
+                                .copied()
+                                .collect(),
+                            function_index: *function_index,
+                        }
+                    }
                 };
                 if let Some(level) = NonZeroUsize::new(*parent) {
                     PropertyReference::InParent { level, parent_reference: p2.into() }
@@ -320,7 +349,15 @@ pub fn map_property_reference(&self, p: &PropertyReference) -> PropertyReference
                         property_index: *property_index,
                     }
                 }
-                g @ PropertyReference::Global { .. } => g.clone(),
+                PropertyReference::Function { sub_component_path, function_index } => {
+                    assert!(sub_component_path.is_empty());
+                    PropertyReference::GlobalFunction {
+                        global_index: *global_index,
+                        function_index: *function_index,
+                    }
+                }
+                g @ (PropertyReference::Global { .. }
+                | PropertyReference::GlobalFunction { .. }) => g.clone(),
                 _ => unreachable!(),
             },
         }
@@ -330,6 +367,7 @@ fn map_expression(&self, e: &mut Expression) {
         match e {
             Expression::PropertyReference(p)
             | Expression::CallBackCall { callback: p, .. }
+            | Expression::FunctionCall { function: p, .. }
             | Expression::PropertyAssignment { property: p, .. }
             | Expression::LayoutCacheAccess { layout_cache_prop: p, .. } => {
                 *p = self.map_property_reference(p);
@@ -56,6 +56,27 @@ fn print_component(
                 DisplayExpression(&init.expression.borrow(), &ctx)
             )?
         }
+        for func in &sc.functions {
+            let ctx = EvaluationContext { argument_types: &func.args, ..ctx.clone() };
+            self.indent()?;
+            writeln!(
+                self.writer,
+                "function {} ({}) -> {} {{ {} }};",
+                func.name,
+                func.args.iter().join(", "),
+                func.ret_ty,
+                DisplayExpression(&func.code.borrow(), &ctx)
+            )?;
+        }
+        for (p, code) in &sc.change_callbacks {
+            self.indent()?;
+            writeln!(
+                self.writer,
+                "changed {} => {};",
+                DisplayPropertyRef(p, &ctx),
+                DisplayExpression(&code.borrow(), &ctx)
+            )?;
+        }
         for ssc in &sc.sub_components {
             self.indent()?;
             writeln!(self.writer, "{} := {} {{}};", ssc.name, ssc.ty.name)?;
@@ -69,7 +90,7 @@ fn print_component(
             write!(self.writer, "for in {} : ", DisplayExpression(&r.model.borrow(), &ctx))?;
             self.print_component(root, &r.sub_tree.root, Some(ParentCtx::new(&ctx, Some(idx))))?
         }
-        for w in &sc.popup_windows {
+        for w in sc.popup_windows.iter().chain(sc.secondary_windows.iter()) {
             self.indent()?;
             self.print_component(root, &w.root, Some(ParentCtx::new(&ctx, None)))?
         }
@@ -122,6 +143,22 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
                 let g = &ctx.public_component.globals[*global_index];
                 write!(f, "{}.{}", g.name, g.properties[*property_index].name)
             }
+            PropertyReference::Function { sub_component_path, function_index } => {
+                if let Some(g) = ctx.current_global {
+                    write!(f, "{}.{}", g.name, g.functions[*function_index].name)
+                } else {
+                    let mut sc = ctx.current_sub_component.unwrap();
+                    for i in sub_component_path {
+                        write!(f, "{}.", sc.sub_components[*i].name)?;
+                        sc = &sc.sub_components[*i].ty;
+                    }
+                    write!(f, "{}", sc.functions[*function_index].name)
+                }
+            }
+            PropertyReference::GlobalFunction { global_index, function_index } => {
+                let g = &ctx.public_component.globals[*global_index];
+                write!(f, "{}.{}", g.name, g.functions[*function_index].name)
+            }
         }
     }
 }
@@ -143,6 +180,13 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
             Expression::ReadLocalVariable { name, .. } => write!(f, "{}", name),
             Expression::StructFieldAccess { base, name } => write!(f, "{}.{}", e(base), name),
             Expression::ArrayIndex { array, index } => write!(f, "{}[{}]", e(array), e(index)),
+            Expression::ArrayIteration { kind, array, item, index, expression } => {
+                write!(f, "{}.@{:?}(({}, {}) => {})", e(array), kind, item, index, e(expression))
+            }
+            Expression::ArraySlice { array, start, end } => match end {
+                Some(end) => write!(f, "{}.@slice({}, {})", e(array), e(start), e(end)),
+                None => write!(f, "{}.@slice({})", e(array), e(start)),
+            },
             Expression::Cast { from, to } => write!(f, "{} /*as {:?}*/", e(from), to),
             Expression::CodeBlock(v) => {
                 write!(f, "{{ {} }}", v.iter().map(e).join("; "))
@@ -158,6 +202,14 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
                     arguments.iter().map(e).join(", ")
                 )
             }
+            Expression::FunctionCall { function, arguments } => {
+                write!(
+                    f,
+                    "{}({})",
+                    DisplayPropertyRef(function, ctx),
+                    arguments.iter().map(e).join(", ")
+                )
+            }
             Expression::ExtraBuiltinFunctionCall { function, arguments, .. } => {
                 write!(f, "{}({})", function, arguments.iter().map(e).join(", "))
             }
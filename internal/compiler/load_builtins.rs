@@ -81,6 +81,7 @@ pub fn load_builtins(register: &mut TypeRegister) {
                         *diag.borrow_mut(),
                         register,
                     ));
+                    info.property_visibility = object_tree::property_visibility(&p);
 
                     if let Some(e) = p.BindingExpression() {
                         if e.Expression()
@@ -9,7 +9,7 @@
 use crate::expression_tree::{
     BuiltinFunction, BuiltinMacroFunction, EasingCurve, Expression, Unit,
 };
-use crate::langtype::{Enumeration, EnumerationValue, Type};
+use crate::langtype::{Enumeration, EnumerationValue, PropertyVisibility, Type};
 use crate::namedreference::NamedReference;
 use crate::object_tree::{find_parent_element, ElementRc};
 use crate::parser::NodeOrToken;
@@ -33,6 +33,10 @@ pub struct LookupCtx<'a> {
     /// The name of the arguments of the callback or function
     pub arguments: Vec<String>,
 
+    /// The local variables in scope (such as the argument of a lambda), and the expression that reads them.
+    /// The innermost variable is last.
+    pub local_variables: Vec<(String, Expression)>,
+
     /// The type register in which to look for Globals
     pub type_register: &'a TypeRegister,
 
@@ -53,6 +57,7 @@ pub fn empty_context(type_register: &'a TypeRegister, diag: &'a mut BuildDiagnos
             component_scope: Default::default(),
             diag,
             arguments: Default::default(),
+            local_variables: Default::default(),
             type_register,
             type_loader: None,
             current_token: None,
@@ -60,12 +65,26 @@ pub fn empty_context(type_register: &'a TypeRegister, diag: &'a mut BuildDiagnos
     }
 
     pub fn return_type(&self) -> &Type {
-        if let Type::Callback { return_type, .. } = &self.property_type {
-            return_type.as_ref().map_or(&Type::Void, |b| &(**b))
-        } else {
-            &self.property_type
+        match &self.property_type {
+            Type::Callback { return_type, .. } => {
+                return_type.as_ref().map_or(&Type::Void, |b| &(**b))
+            }
+            Type::Function { return_type, .. } => return_type,
+            _ => &self.property_type,
         }
     }
+
+    /// Returns true if `elem` belongs to the component in which the expression is being resolved.
+    /// (Properties declared in such an element are accessible regardless of their visibility)
+    pub fn is_local_element(&self, elem: &ElementRc) -> bool {
+        let enclosing = |e: &ElementRc| e.borrow().enclosing_component.upgrade();
+        self.component_scope.first().map_or(true, |scope| {
+            match (enclosing(scope), enclosing(elem)) {
+                (Some(a), Some(b)) => Rc::ptr_eq(&a, &b),
+                _ => true,
+            }
+        })
+    }
 }
 
 pub enum LookupResult {
@@ -160,6 +179,22 @@ fn lookup(&self, ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
     }
 }
 
+struct LocalVariableLookup;
+impl LookupObject for LocalVariableLookup {
+    fn for_each_entry<R>(
+        &self,
+        ctx: &LookupCtx,
+        f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
+    ) -> Option<R> {
+        for (name, expression) in ctx.local_variables.iter().rev() {
+            if let Some(r) = f(name, expression.clone().into()) {
+                return Some(r);
+            }
+        }
+        None
+    }
+}
+
 struct ArgumentsLookup;
 impl LookupObject for ArgumentsLookup {
     fn for_each_entry<R>(
@@ -328,8 +363,15 @@ fn for_each_entry<R>(
     }
 
     fn lookup(&self, _ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
-        let crate::langtype::PropertyLookupResult { resolved_name, property_type } =
-            self.borrow().lookup_property(name);
+        let crate::langtype::PropertyLookupResult {
+            resolved_name,
+            property_type,
+            property_visibility,
+            is_local_to_component,
+        } = self.borrow().lookup_property(name);
+        if !is_local_to_component && property_visibility == PropertyVisibility::Private {
+            return None;
+        }
         (property_type != Type::Invalid).then(|| LookupResult::Expression {
             expression: expression_from_reference(
                 NamedReference::new(self, &resolved_name),
@@ -341,13 +383,19 @@ fn lookup(&self, _ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
 }
 
 fn expression_from_reference(n: NamedReference, ty: &Type) -> Expression {
-    if matches!(ty, Type::Callback { .. }) {
-        Expression::CallbackReference(n)
-    } else {
-        Expression::PropertyReference(n)
+    match ty {
+        Type::Callback { .. } => Expression::CallbackReference(n),
+        Type::Function { .. } if is_user_function(&n) => Expression::FunctionReference(n),
+        _ => Expression::PropertyReference(n),
     }
 }
 
+/// Returns true if the function referenced by `n` is declared with `function` in a .slint file,
+/// as opposed to a builtin member function such as `focus`
+fn is_user_function(n: &NamedReference) -> bool {
+    matches!(n.element().borrow().base_type.lookup_member_function(n.name()), Expression::Invalid)
+}
+
 /// Lookup for Globals and Enum.
 /// Note: for enums, the expression's value is `usize::MAX`
 struct LookupType;
@@ -466,7 +514,10 @@ fn for_each_entry<R>(
         ctx: &LookupCtx,
         f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
     ) -> Option<R> {
-        use EasingCurve::CubicBezier;
+        use EasingCurve::{
+            CubicBezier, EaseInBounce, EaseInElastic, EaseInOutBounce, EaseInOutElastic,
+            EaseOutBounce, EaseOutElastic,
+        };
         None.or_else(|| f("linear", Expression::EasingCurve(EasingCurve::Linear).into()))
             .or_else(|| {
                 f("ease", Expression::EasingCurve(CubicBezier(0.25, 0.1, 0.25, 1.0)).into())
@@ -480,6 +531,12 @@ fn for_each_entry<R>(
             .or_else(|| {
                 f("ease-out", Expression::EasingCurve(CubicBezier(0.0, 0.0, 0.58, 1.0)).into())
             })
+            .or_else(|| f("ease-in-elastic", Expression::EasingCurve(EaseInElastic).into()))
+            .or_else(|| f("ease-out-elastic", Expression::EasingCurve(EaseOutElastic).into()))
+            .or_else(|| f("ease-in-out-elastic", Expression::EasingCurve(EaseInOutElastic).into()))
+            .or_else(|| f("ease-in-bounce", Expression::EasingCurve(EaseInBounce).into()))
+            .or_else(|| f("ease-out-bounce", Expression::EasingCurve(EaseOutBounce).into()))
+            .or_else(|| f("ease-in-out-bounce", Expression::EasingCurve(EaseInOutBounce).into()))
             .or_else(|| {
                 f(
                     "cubic-bezier",
@@ -490,6 +547,26 @@ fn for_each_entry<R>(
                     .into(),
                 )
             })
+            .or_else(|| {
+                f(
+                    "spring",
+                    Expression::BuiltinMacroReference(
+                        BuiltinMacroFunction::Spring,
+                        ctx.current_token.clone(),
+                    )
+                    .into(),
+                )
+            })
+            .or_else(|| {
+                f(
+                    "steps",
+                    Expression::BuiltinMacroReference(
+                        BuiltinMacroFunction::Steps,
+                        ctx.current_token.clone(),
+                    )
+                    .into(),
+                )
+            })
     }
 }
 
@@ -604,16 +681,22 @@ fn for_each_entry<R>(
 
 pub fn global_lookup() -> impl LookupObject {
     (
-        ArgumentsLookup,
+        LocalVariableLookup,
         (
-            SpecialIdLookup,
+            ArgumentsLookup,
             (
-                IdLookup,
+                SpecialIdLookup,
                 (
-                    InScopeLookup,
+                    IdLookup,
                     (
-                        LookupType,
-                        (BuiltinNamespaceLookup, (ReturnTypeSpecificLookup, BuiltinFunctionLookup)),
+                        InScopeLookup,
+                        (
+                            LookupType,
+                            (
+                                BuiltinNamespaceLookup,
+                                (ReturnTypeSpecificLookup, BuiltinFunctionLookup),
+                            ),
+                        ),
                     ),
                 ),
             ),
@@ -647,6 +730,7 @@ fn for_each_entry<R>(
                 }
                 Type::Component(c) => c.root_element.for_each_entry(ctx, f),
                 Type::String => StringExpression(self).for_each_entry(ctx, f),
+                Type::Int32 | Type::Float32 => NumberExpression(self).for_each_entry(ctx, f),
                 Type::Brush | Type::Color => ColorExpression(self).for_each_entry(ctx, f),
                 Type::Image => ImageExpression(self).for_each_entry(ctx, f),
                 Type::Array(_) => ArrayExpression(self).for_each_entry(ctx, f),
@@ -667,6 +751,7 @@ fn lookup(&self, ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
                 }),
                 Type::Component(c) => c.root_element.lookup(ctx, name),
                 Type::String => StringExpression(self).lookup(ctx, name),
+                Type::Int32 | Type::Float32 => NumberExpression(self).lookup(ctx, name),
                 Type::Brush | Type::Color => ColorExpression(self).lookup(ctx, name),
                 Type::Image => ImageExpression(self).lookup(ctx, name),
                 Type::Array(_) => ArrayExpression(self).lookup(ctx, name),
@@ -693,8 +778,45 @@ fn for_each_entry<R>(
                 )),
             })
         };
+        let function_call = |f: BuiltinFunction| {
+            LookupResult::from(Expression::FunctionCall {
+                function: Box::new(Expression::BuiltinFunctionReference(
+                    f,
+                    ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                )),
+                source_location: ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                arguments: vec![self.0.clone()],
+            })
+        };
         None.or_else(|| f("is-float", member_function(BuiltinFunction::StringIsFloat)))
             .or_else(|| f("to-float", member_function(BuiltinFunction::StringToFloat)))
+            .or_else(|| f("to-uppercase", member_function(BuiltinFunction::StringToUppercase)))
+            .or_else(|| f("to-lowercase", member_function(BuiltinFunction::StringToLowercase)))
+            .or_else(|| f("substring", member_function(BuiltinFunction::StringSubstring)))
+            .or_else(|| f("contains", member_function(BuiltinFunction::StringContains)))
+            .or_else(|| f("length", function_call(BuiltinFunction::StringLength)))
+    }
+}
+
+struct NumberExpression<'a>(&'a Expression);
+impl<'a> LookupObject for NumberExpression<'a> {
+    fn for_each_entry<R>(
+        &self,
+        ctx: &LookupCtx,
+        f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
+    ) -> Option<R> {
+        let member_function = |f: BuiltinFunction| {
+            LookupResult::from(Expression::MemberFunction {
+                base: Box::new(self.0.clone()),
+                base_node: ctx.current_token.clone(), // Note that this is not the base_node, but the function's node
+                member: Box::new(Expression::BuiltinFunctionReference(
+                    f,
+                    ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                )),
+            })
+        };
+        None.or_else(|| f("to-fixed", member_function(BuiltinFunction::NumberToFixed)))
+            .or_else(|| f("to-precision", member_function(BuiltinFunction::NumberToPrecision)))
     }
 }
 struct ColorExpression<'a>(&'a Expression);
@@ -761,6 +883,34 @@ fn for_each_entry<R>(
                 arguments: vec![self.0.clone()],
             })
         };
+        let member_macro = |m: BuiltinMacroFunction| {
+            LookupResult::from(Expression::MemberFunction {
+                base: Box::new(self.0.clone()),
+                base_node: ctx.current_token.clone(), // Note that this is not the base_node, but the function's node
+                member: Box::new(Expression::BuiltinMacroReference(m, ctx.current_token.clone())),
+            })
+        };
+        let is_empty = || {
+            LookupResult::from(Expression::BinaryExpression {
+                lhs: Box::new(Expression::FunctionCall {
+                    function: Box::new(Expression::BuiltinFunctionReference(
+                        BuiltinFunction::ArrayLength,
+                        ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                    )),
+                    source_location: ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                    arguments: vec![self.0.clone()],
+                }),
+                rhs: Box::new(Expression::NumberLiteral(0., Unit::None)),
+                op: '=',
+            })
+        };
         None.or_else(|| f("length", member_function(BuiltinFunction::ArrayLength)))
+            .or_else(|| f("is-empty", is_empty()))
+            .or_else(|| f("index-of", member_macro(BuiltinMacroFunction::ArrayIndexOf)))
+            .or_else(|| f("contains", member_macro(BuiltinMacroFunction::ArrayContains)))
+            .or_else(|| f("sum", member_macro(BuiltinMacroFunction::ArraySum)))
+            .or_else(|| f("slice", member_macro(BuiltinMacroFunction::ArraySlice)))
+            .or_else(|| f("filter", member_macro(BuiltinMacroFunction::ArrayFilter)))
+            .or_else(|| f("map", member_macro(BuiltinMacroFunction::ArrayMap)))
     }
 }
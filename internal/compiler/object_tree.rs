@@ -7,12 +7,10 @@
 
 // cSpell: ignore qualname
 
-use itertools::Either;
-
 use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
-use crate::langtype::PropertyLookupResult;
-use crate::langtype::{BuiltinElement, NativeClass, Type};
+use crate::langtype::{BuiltinElement, Enumeration, NativeClass, Type};
+use crate::langtype::{PropertyLookupResult, PropertyVisibility};
 use crate::layout::{LayoutConstraints, Orientation};
 use crate::namedreference::NamedReference;
 use crate::parser;
@@ -42,6 +40,7 @@ pub struct Document {
     pub node: Option<syntax_nodes::Document>,
     pub inner_components: Vec<Rc<Component>>,
     pub inner_structs: Vec<Type>,
+    pub inner_enums: Vec<Rc<Enumeration>>,
     pub root_component: Rc<Component>,
     pub local_registry: TypeRegister,
     /// A list of paths to .ttf/.ttc files that are supposed to be registered on
@@ -62,6 +61,7 @@ pub fn from_node(
         let mut local_registry = TypeRegister::new(parent_registry);
         let mut inner_components = vec![];
         let mut inner_structs = vec![];
+        let mut inner_enums = vec![];
 
         let mut process_component =
             |n: syntax_nodes::Component,
@@ -85,6 +85,38 @@ pub fn from_node(
                 local_registry.insert_type(ty.clone());
                 inner_structs.push(ty);
             };
+        let mut process_enum = |n: syntax_nodes::EnumDeclaration,
+                                diag: &mut BuildDiagnostics,
+                                local_registry: &mut TypeRegister| {
+            let name = match parser::identifier_text(&n.DeclaredIdentifier()) {
+                Some(name) => name,
+                None => {
+                    assert!(diag.has_error());
+                    return;
+                }
+            };
+            let mut values = Vec::<String>::new();
+            for value in n.EnumValue() {
+                let value_name = parser::identifier_text(&value).unwrap_or_default();
+                if values.contains(&value_name) {
+                    diag.push_error(format!("Duplicated enum value '{}'", value_name), &value);
+                } else {
+                    values.push(value_name);
+                }
+            }
+            if values.is_empty() {
+                debug_assert!(diag.has_error()); // the parser requires at least one value
+                return;
+            }
+            let enumeration = Rc::new(Enumeration {
+                name: name.clone(),
+                values,
+                default_value: 0,
+                node: Some(n),
+            });
+            local_registry.insert_type_with_name(Type::Enumeration(enumeration.clone()), name);
+            inner_enums.push(enumeration);
+        };
 
         for n in node.children() {
             match n.kind() {
@@ -92,6 +124,7 @@ pub fn from_node(
                 SyntaxKind::StructDeclaration => {
                     process_struct(n.into(), diag, &mut local_registry)
                 }
+                SyntaxKind::EnumDeclaration => process_enum(n.into(), diag, &mut local_registry),
                 SyntaxKind::ExportsList => {
                     for n in n.children() {
                         match n.kind() {
@@ -101,6 +134,9 @@ pub fn from_node(
                             SyntaxKind::StructDeclaration => {
                                 process_struct(n.into(), diag, &mut local_registry)
                             }
+                            SyntaxKind::EnumDeclaration => {
+                                process_enum(n.into(), diag, &mut local_registry)
+                            }
                             _ => {}
                         }
                     }
@@ -164,6 +200,7 @@ pub fn from_node(
             root_component,
             inner_components,
             inner_structs,
+            inner_enums,
             local_registry,
             custom_fonts,
             exports,
@@ -185,6 +222,15 @@ pub struct PopupWindow {
 
 type ChildrenInsertionPoint = (ElementRc, syntax_nodes::ChildrenPlaceholder);
 
+/// The `@children` placeholders found while creating the elements of a component
+#[derive(Default)]
+pub struct ChildrenInsertionPoints {
+    /// The unnamed `@children` placeholder
+    unnamed: Option<ChildrenInsertionPoint>,
+    /// The `@children(name)` placeholders, by name
+    named: BTreeMap<String, ChildrenInsertionPoint>,
+}
+
 /// Used sub types for a root component
 #[derive(Debug, Default)]
 pub struct UsedSubTypes {
@@ -192,6 +238,8 @@ pub struct UsedSubTypes {
     pub globals: Vec<Rc<Component>>,
     /// All the structs used by the component and its children.
     pub structs: Vec<Type>,
+    /// All the enums declared in .slint files and used by the component and its children.
+    pub enums: Vec<Rc<Enumeration>>,
     /// All the sub components use by this components and its children,
     /// and the amount of time it is used
     pub sub_components: Vec<Rc<Component>>,
@@ -224,6 +272,10 @@ pub struct Component {
     /// the element pointer to by this field.
     pub child_insertion_point: RefCell<Option<ChildrenInsertionPoint>>,
 
+    /// Same as `child_insertion_point`, for the named `@children(name)` placeholders.
+    /// The children declared with `@slot(name)` are appended to the element of the matching entry.
+    pub named_child_insertion_points: RefCell<BTreeMap<String, ChildrenInsertionPoint>>,
+
     /// Code to be inserted into the constructor
     pub setup_code: RefCell<Vec<Expression>>,
 
@@ -232,6 +284,10 @@ pub struct Component {
     pub used_types: RefCell<UsedSubTypes>,
     pub popup_windows: RefCell<Vec<PopupWindow>>,
 
+    /// The `SecondaryWindow` elements that were lowered into their own component. Each of these
+    /// is shown in its own top-level window, but share the globals with this component.
+    pub secondary_windows: RefCell<Vec<Rc<Component>>>,
+
     /// The names under which this component should be accessible
     /// if it is a global singleton and exported.
     pub exported_global_names: RefCell<Vec<ExportedName>>,
@@ -247,18 +303,19 @@ pub fn from_node(
         diag: &mut BuildDiagnostics,
         tr: &TypeRegister,
     ) -> Rc<Self> {
-        let mut child_insertion_point = None;
+        let mut child_insertion_points = ChildrenInsertionPoints::default();
         let c = Component {
             id: parser::identifier_text(&node.DeclaredIdentifier()).unwrap_or_default(),
             root_element: Element::from_node(
                 node.Element(),
                 "root".into(),
                 Type::Invalid,
-                &mut child_insertion_point,
+                &mut child_insertion_points,
                 diag,
                 tr,
             ),
-            child_insertion_point: RefCell::new(child_insertion_point),
+            child_insertion_point: RefCell::new(child_insertion_points.unnamed),
+            named_child_insertion_points: RefCell::new(child_insertion_points.named),
             ..Default::default()
         };
         let c = Rc::new(c);
@@ -327,21 +384,23 @@ pub fn repeater_count(&self) -> u32 {
 #[derive(Clone, Debug, Default)]
 pub struct PropertyDeclaration {
     pub property_type: Type,
-    pub node: Option<Either<syntax_nodes::PropertyDeclaration, syntax_nodes::CallbackDeclaration>>,
+    /// The PropertyDeclaration, CallbackDeclaration or Function node
+    pub node: Option<SyntaxNode>,
     /// Tells if getter and setter will be added to expose in the native language API
     pub expose_in_public_api: bool,
     /// Public API property exposed as an alias: it shouldn't be generated but instead forward to the alias.
     pub is_alias: Option<NamedReference>,
+    /// The access qualifier (`in`, `out`, `in-out` or `private`) of the declaration
+    pub visibility: PropertyVisibility,
 }
 
 impl PropertyDeclaration {
     // For diagnostics: return a node pointing to the type
     pub fn type_node(&self) -> Option<SyntaxNode> {
-        self.node.as_ref().map(|x| -> crate::parser::SyntaxNode {
-            x.as_ref().either(
-                |x| x.Type().map_or_else(|| x.clone().into(), |x| x.into()),
-                |x| x.clone().into(),
-            )
+        self.node.as_ref().map(|x| {
+            syntax_nodes::PropertyDeclaration::new(x.clone())
+                .and_then(|x| x.Type())
+                .map_or_else(|| x.clone(), |x| x.into())
         })
     }
 }
@@ -388,11 +447,12 @@ fn clone(&self) -> Self {
         fn deep_clone(e: &ElementRc) -> ElementRc {
             let e = e.borrow();
             debug_assert!(e.children.is_empty());
-            debug_assert!(e.property_declarations.is_empty());
             debug_assert!(e.states.is_empty() && e.transitions.is_empty());
             Rc::new(RefCell::new(Element {
                 id: e.id.clone(),
                 base_type: e.base_type.clone(),
+                // The keyframes of the animation are stored as declared properties
+                property_declarations: e.property_declarations.clone(),
                 bindings: e.bindings.clone(),
                 property_analysis: e.property_analysis.clone(),
                 enclosing_component: e.enclosing_component.clone(),
@@ -479,6 +539,14 @@ pub struct Element {
     /// How many times the element was inlined
     pub inline_depth: i32,
 
+    /// The code of the `changed <property> => { ... }` callbacks, by property name.
+    /// There can be several for the same property after inlining.
+    pub change_callbacks: BTreeMap<String, RefCell<Vec<Expression>>>,
+
+    /// The name of the `@children(name)` placeholder of the parent component in which this
+    /// element is placed, as given with `@slot(name)`
+    pub slot: Option<String>,
+
     /// The AST node, if available
     pub node: Option<syntax_nodes::Element>,
 }
@@ -545,6 +613,14 @@ macro_rules! indent {
             writeln!(f, "{} <=> {:?};", name, nr)?;
         }
     }
+    for (name, handlers) in &e.change_callbacks {
+        for expr in handlers.borrow().iter() {
+            indent!();
+            write!(f, "changed {} => ", name)?;
+            expression_tree::pretty_print(f, expr)?;
+            writeln!(f)?;
+        }
+    }
     if !e.states.is_empty() {
         indent!();
         writeln!(f, "states {:?}", e.states)?;
@@ -638,7 +714,7 @@ pub fn from_node(
         node: syntax_nodes::Element,
         id: String,
         parent_type: Type,
-        component_child_insertion_point: &mut Option<ChildrenInsertionPoint>,
+        component_child_insertion_points: &mut ChildrenInsertionPoints,
         diag: &mut BuildDiagnostics,
         tr: &TypeRegister,
     ) -> ElementRc {
@@ -682,6 +758,7 @@ pub fn from_node(
             node.PropertyAnimation().for_each(|n| error_on(&n, "animations"));
             node.States().for_each(|n| error_on(&n, "states"));
             node.Transitions().for_each(|n| error_on(&n, "transitions"));
+            node.ChangeCallback().for_each(|n| error_on(&n, "change callbacks"));
             Type::Void
         };
         let mut r = Element { id, base_type, node: Some(node.clone()), ..Default::default() };
@@ -693,11 +770,14 @@ pub fn from_node(
                 // Type::Void is used for two way bindings without type specified
                 .unwrap_or(Type::InferredProperty);
 
+            let visibility = property_visibility(&prop_decl);
+
             let unresolved_prop_name =
                 unwrap_or_continue!(parser::identifier_text(&prop_decl.DeclaredIdentifier()); diag);
             let PropertyLookupResult {
                 resolved_name: prop_name,
                 property_type: maybe_existing_prop_type,
+                ..
             } = r.lookup_property(&unresolved_prop_name);
             if !matches!(maybe_existing_prop_type, Type::Invalid) {
                 diag.push_error(
@@ -710,7 +790,8 @@ pub fn from_node(
                 prop_name.to_string(),
                 PropertyDeclaration {
                     property_type: prop_type,
-                    node: Some(Either::Left(prop_decl.clone())),
+                    node: Some(prop_decl.clone().into()),
+                    visibility,
                     ..Default::default()
                 },
             );
@@ -742,6 +823,43 @@ pub fn from_node(
             }
         }
 
+        for func in node.Function() {
+            let name =
+                unwrap_or_continue!(parser::identifier_text(&func.DeclaredIdentifier()); diag);
+            if !matches!(r.lookup_property(&name).property_type, Type::Invalid)
+                || node.CallbackDeclaration().any(|c| {
+                    parser::identifier_text(&c.DeclaredIdentifier()).as_ref() == Some(&name)
+                })
+            {
+                diag.push_error(
+                    format!("Cannot declare function '{}' because a property or callback with that name already exists", name),
+                    &func.DeclaredIdentifier(),
+                );
+                continue;
+            }
+
+            let args = func
+                .ArgumentDeclaration()
+                .map(|arg| type_from_node(arg.Type(), diag, tr))
+                .collect();
+            let return_type = Box::new(
+                func.ReturnType()
+                    .map_or(Type::Void, |ret_ty| type_from_node(ret_ty.Type(), diag, tr)),
+            );
+            r.bindings.insert(
+                name.clone(),
+                BindingExpression::new_uncompiled(func.clone().into()).into(),
+            );
+            r.property_declarations.insert(
+                name,
+                PropertyDeclaration {
+                    property_type: Type::Function { return_type, args },
+                    node: Some(func.into()),
+                    ..Default::default()
+                },
+            );
+        }
+
         r.parse_bindings(
             node.Binding().filter_map(|b| {
                 Some((b.child_token(SyntaxKind::Identifier)?, b.BindingExpression().into()))
@@ -767,7 +885,7 @@ pub fn from_node(
                     name,
                     PropertyDeclaration {
                         property_type: Type::InferredCallback,
-                        node: Some(Either::Right(sig_decl)),
+                        node: Some(sig_decl.into()),
                         ..Default::default()
                     },
                 );
@@ -782,7 +900,7 @@ pub fn from_node(
                 name,
                 PropertyDeclaration {
                     property_type: Type::Callback { return_type, args },
-                    node: Some(Either::Right(sig_decl)),
+                    node: Some(sig_decl.into()),
                     ..Default::default()
                 },
             );
@@ -790,7 +908,7 @@ pub fn from_node(
 
         for con_node in node.CallbackConnection() {
             let unresolved_name = unwrap_or_continue!(parser::identifier_text(&con_node); diag);
-            let PropertyLookupResult { resolved_name, property_type } =
+            let PropertyLookupResult { resolved_name, property_type, .. } =
                 r.lookup_property(&unresolved_name);
             if let Type::Callback { args, .. } = &property_type {
                 let num_arg = con_node.DeclaredIdentifier().count();
@@ -838,7 +956,7 @@ pub fn from_node(
             for prop_name_token in anim.QualifiedName() {
                 match QualifiedTypeName::from_node(prop_name_token.clone()).members.as_slice() {
                     [unresolved_prop_name] => {
-                        let PropertyLookupResult { resolved_name, property_type } =
+                        let PropertyLookupResult { resolved_name, property_type, .. } =
                             r.lookup_property(unresolved_prop_name);
                         if let Some(anim_element) = animation_element_from_node(
                             &anim,
@@ -880,6 +998,39 @@ pub fn from_node(
             }
         }
 
+        for ch in node.ChangeCallback() {
+            let unresolved_name =
+                unwrap_or_continue!(parser::identifier_text(&ch.DeclaredIdentifier()); diag);
+            let PropertyLookupResult { resolved_name, property_type, .. } =
+                r.lookup_property(&unresolved_name);
+            if !property_type.is_property_type() {
+                if property_type != Type::Invalid {
+                    diag.push_error(
+                        format!("Change callback can only be set on properties, and '{}' is not a property", unresolved_name),
+                        &ch.DeclaredIdentifier(),
+                    );
+                } else if r.base_type != Type::Invalid {
+                    diag.push_error(
+                        format!("Unknown property {} in {}", unresolved_name, r.base_type),
+                        &ch.DeclaredIdentifier(),
+                    );
+                }
+                continue;
+            }
+            if r.change_callbacks
+                .insert(
+                    resolved_name.into_owned(),
+                    RefCell::new(vec![Expression::Uncompiled(ch.clone().into())]),
+                )
+                .is_some()
+            {
+                diag.push_error(
+                    format!("Duplicated change callback on '{}'", unresolved_name),
+                    &ch.DeclaredIdentifier(),
+                );
+            }
+        }
+
         let mut children_placeholder = None;
         let r = ElementRc::new(RefCell::new(r));
 
@@ -889,7 +1040,7 @@ pub fn from_node(
                 r.borrow_mut().children.push(Element::from_sub_element_node(
                     se.into(),
                     parent_type,
-                    component_child_insertion_point,
+                    component_child_insertion_points,
                     diag,
                     tr,
                 ));
@@ -897,7 +1048,7 @@ pub fn from_node(
                 let rep = Element::from_repeated_node(
                     se.into(),
                     &r,
-                    component_child_insertion_point,
+                    component_child_insertion_points,
                     diag,
                     tr,
                 );
@@ -906,7 +1057,7 @@ pub fn from_node(
                 let rep = Element::from_conditional_node(
                     se.into(),
                     r.borrow().base_type.clone(),
-                    component_child_insertion_point,
+                    component_child_insertion_points,
                     diag,
                     tr,
                 );
@@ -918,19 +1069,45 @@ pub fn from_node(
                         &se,
                     )
                 } else {
-                    children_placeholder = Some(se.clone().into());
+                    children_placeholder = Some(syntax_nodes::ChildrenPlaceholder::from(se));
                 }
             }
         }
 
         if let Some(children_placeholder) = children_placeholder {
-            if component_child_insertion_point.is_some() {
-                diag.push_error(
+            match children_placeholder.DeclaredIdentifier().and_then(|n| parser::identifier_text(&n))
+            {
+                None if component_child_insertion_points.unnamed.is_some() => diag.push_error(
                     "The @children placeholder can only appear once in an element hierarchy".into(),
                     &children_placeholder,
-                )
-            } else {
-                *component_child_insertion_point = Some((r.clone(), children_placeholder));
+                ),
+                None => {
+                    component_child_insertion_points.unnamed =
+                        Some((r.clone(), children_placeholder))
+                }
+                Some(name) => match component_child_insertion_points.named.entry(name) {
+                    Entry::Occupied(e) => diag.push_error(
+                        format!(
+                            "The @children({}) placeholder can only appear once in an element hierarchy",
+                            e.key()
+                        ),
+                        &children_placeholder,
+                    ),
+                    Entry::Vacant(e) => {
+                        e.insert((r.clone(), children_placeholder));
+                    }
+                },
+            }
+        }
+
+        if let Type::Component(c) = &r.borrow().base_type {
+            for name in c.named_child_insertion_points.borrow().keys() {
+                if !r.borrow().children.iter().any(|ch| ch.borrow().slot.as_ref() == Some(name)) {
+                    diag.push_warning(
+                        format!("The slot '{}' of '{}' is not filled", name, c.id),
+                        &*r.borrow(),
+                    );
+                }
             }
         }
 
@@ -954,21 +1131,21 @@ pub fn from_node(
             if let Some(star) = trs.child_token(SyntaxKind::Star) {
                 diag.push_error("TODO: catch-all not yet implemented".into(), &star);
             };
+            let mut property_animations = vec![];
+            for pa in trs.PropertyAnimation() {
+                transition_animations_from_node(&pa, &r, &mut property_animations, diag, tr);
+            }
+            let animation_groups = trs
+                .AnimationGroup()
+                .map(|g| {
+                    TransitionAnimationGroup::from_node(g, &r, &mut property_animations, diag, tr)
+                })
+                .collect();
             let trans = Transition {
                 is_out: parser::identifier_text(&trs).unwrap_or_default() == "out",
                 state_id: parser::identifier_text(&trs.DeclaredIdentifier()).unwrap_or_default(),
-                property_animations: trs
-                    .PropertyAnimation()
-                    .flat_map(|pa| pa.QualifiedName().map(move |qn| (pa.clone(), qn)))
-                    .filter_map(|(pa, qn)| {
-                        lookup_property_from_qualified_name(qn.clone(), &r, diag).and_then(
-                            |(ne, prop_type)| {
-                                animation_element_from_node(&pa, &qn, prop_type, diag, tr)
-                                    .map(|anim_element| (ne, qn.to_source_location(), anim_element))
-                            },
-                        )
-                    })
-                    .collect(),
+                property_animations,
+                animation_groups,
                 node: trs.DeclaredIdentifier().into(),
             };
             r.borrow_mut().transitions.push(trans);
@@ -980,7 +1157,7 @@ pub fn from_node(
     fn from_sub_element_node(
         node: syntax_nodes::SubElement,
         parent_type: Type,
-        component_child_insertion_point: &mut Option<ChildrenInsertionPoint>,
+        component_child_insertion_points: &mut ChildrenInsertionPoints,
         diag: &mut BuildDiagnostics,
         tr: &TypeRegister,
     ) -> ElementRc {
@@ -991,20 +1168,34 @@ fn from_sub_element_node(
                 &node.child_token(SyntaxKind::Identifier).unwrap(),
             )
         }
-        Element::from_node(
+        let slot = node.SlotMarker().map(|marker| {
+            let name = parser::identifier_text(&marker.DeclaredIdentifier()).unwrap_or_default();
+            match &parent_type {
+                Type::Component(c)
+                    if c.named_child_insertion_points.borrow().contains_key(&name) => {}
+                Type::Invalid => {}
+                _ => {
+                    diag.push_error(format!("Unknown slot '{}' in {}", name, parent_type), &marker)
+                }
+            }
+            name
+        });
+        let e = Element::from_node(
             node.Element(),
             id,
             parent_type,
-            component_child_insertion_point,
+            component_child_insertion_points,
             diag,
             tr,
-        )
+        );
+        e.borrow_mut().slot = slot;
+        e
     }
 
     fn from_repeated_node(
         node: syntax_nodes::RepeatedElement,
         parent: &ElementRc,
-        component_child_insertion_point: &mut Option<ChildrenInsertionPoint>,
+        component_child_insertion_points: &mut ChildrenInsertionPoints,
         diag: &mut BuildDiagnostics,
         tr: &TypeRegister,
     ) -> ElementRc {
@@ -1035,7 +1226,7 @@ fn from_repeated_node(
         let e = Element::from_sub_element_node(
             node.SubElement(),
             parent.borrow().base_type.clone(),
-            component_child_insertion_point,
+            component_child_insertion_points,
             diag,
             tr,
         );
@@ -1046,7 +1237,7 @@ fn from_repeated_node(
     fn from_conditional_node(
         node: syntax_nodes::ConditionalElement,
         parent_type: Type,
-        component_child_insertion_point: &mut Option<ChildrenInsertionPoint>,
+        component_child_insertion_points: &mut ChildrenInsertionPoints,
         diag: &mut BuildDiagnostics,
         tr: &TypeRegister,
     ) -> ElementRc {
@@ -1060,7 +1251,7 @@ fn from_conditional_node(
         let e = Element::from_sub_element_node(
             node.SubElement(),
             parent_type,
-            component_child_insertion_point,
+            component_child_insertion_points,
             diag,
             tr,
         );
@@ -1072,9 +1263,14 @@ fn from_conditional_node(
     /// the provided name points towards a property alias. Type::Invalid is returned if the property does
     /// not exist.
     pub fn lookup_property<'a>(&self, name: &'a str) -> PropertyLookupResult<'a> {
-        self.property_declarations.get(name).cloned().map(|decl| decl.property_type).map_or_else(
+        self.property_declarations.get(name).map_or_else(
             || self.base_type.lookup_property(name),
-            |property_type| PropertyLookupResult { resolved_name: name.into(), property_type },
+            |decl| PropertyLookupResult {
+                resolved_name: name.into(),
+                property_type: decl.property_type.clone(),
+                property_visibility: decl.visibility,
+                is_local_to_component: true,
+            },
         )
     }
 
@@ -1090,8 +1286,38 @@ fn parse_bindings(
     ) {
         for (name_token, b) in bindings {
             let unresolved_name = crate::parser::normalize_identifier(name_token.text());
-            let PropertyLookupResult { resolved_name, property_type } =
-                self.lookup_property(&unresolved_name);
+            let PropertyLookupResult {
+                resolved_name,
+                property_type,
+                property_visibility,
+                is_local_to_component,
+            } = self.lookup_property(&unresolved_name);
+            if let Type::Function { .. } = property_type {
+                diag.push_error(
+                    format!("Cannot assign to '{}' because it is a function", unresolved_name),
+                    &name_token,
+                );
+                continue;
+            }
+            if !is_local_to_component {
+                match property_visibility {
+                    PropertyVisibility::Private => {
+                        diag.push_error(
+                            format!("Cannot access private property '{}'", unresolved_name),
+                            &name_token,
+                        );
+                        continue;
+                    }
+                    PropertyVisibility::Output => {
+                        diag.push_error(
+                            format!("Cannot assign to output property '{}'", unresolved_name),
+                            &name_token,
+                        );
+                        continue;
+                    }
+                    PropertyVisibility::Input | PropertyVisibility::InOut => {}
+                }
+            }
             if !property_type.is_property_type() {
                 diag.push_error(
                     match property_type {
@@ -1231,7 +1457,7 @@ pub fn sub_component(&self) -> Option<&Rc<Component>> {
 }
 
 /// Apply default property values defined in `builtins.slint` to the element.
-fn apply_default_type_properties(element: &mut Element) {
+pub(crate) fn apply_default_type_properties(element: &mut Element) {
     // Apply default property values on top:
     if let Type::Builtin(builtin_base) = &element.base_type {
         for (prop, info) in &builtin_base.properties {
@@ -1246,6 +1472,16 @@ fn apply_default_type_properties(element: &mut Element) {
     }
 }
 
+/// Returns the access qualifier of a property declaration
+pub fn property_visibility(prop_decl: &syntax_nodes::PropertyDeclaration) -> PropertyVisibility {
+    match prop_decl.child_token(SyntaxKind::Identifier) {
+        Some(t) if t.text() == "in" => PropertyVisibility::Input,
+        Some(t) if t.text() == "out" => PropertyVisibility::Output,
+        Some(t) if t.text() == "private" => PropertyVisibility::Private,
+        _ => PropertyVisibility::InOut,
+    }
+}
+
 /// Create a Type for this node
 pub fn type_from_node(
     node: syntax_nodes::Type,
@@ -1318,12 +1554,125 @@ fn animation_element_from_node(
             diag,
         );
 
+        for (index, keyframe) in anim.KeyFrame().enumerate() {
+            let position_token = keyframe.child_token(SyntaxKind::NumberLiteral).unwrap();
+            let position =
+                match crate::literals::parse_number_literal(position_token.text().to_string()) {
+                    Ok(Expression::NumberLiteral(val, Unit::Percent))
+                        if (0. ..=100.).contains(&val) =>
+                    {
+                        val / 100.
+                    }
+                    _ => {
+                        diag.push_error(
+                            "The position of a keyframe must be a percentage between 0% and 100%"
+                                .into(),
+                            &position_token,
+                        );
+                        continue;
+                    }
+                };
+            let names = KeyFrameProperties::new(index);
+            for (name, ty) in [
+                (&names.position, Type::Float32),
+                (&names.value, prop_type.clone()),
+                (&names.easing, Type::Easing),
+            ] {
+                anim_element.property_declarations.insert(name.clone(), ty.into());
+            }
+            anim_element.bindings.insert(
+                names.position,
+                RefCell::new(Expression::NumberLiteral(position, Unit::None).into()),
+            );
+            for b in keyframe.Binding() {
+                let name_token = match b.child_token(SyntaxKind::Identifier) {
+                    Some(name_token) => name_token,
+                    None => continue,
+                };
+                let name = match crate::parser::normalize_identifier(name_token.text()).as_str() {
+                    "value" => names.value.clone(),
+                    "easing" => names.easing.clone(),
+                    unknown => {
+                        diag.push_error(
+                            format!("Unknown property '{}' in keyframe. Only 'value' and 'easing' can be set", unknown),
+                            &name_token,
+                        );
+                        continue;
+                    }
+                };
+                if anim_element
+                    .bindings
+                    .insert(
+                        name,
+                        BindingExpression::new_uncompiled(b.BindingExpression().into()).into(),
+                    )
+                    .is_some()
+                {
+                    diag.push_error("Duplicated property binding".into(), &name_token);
+                }
+            }
+            if !anim_element.bindings.contains_key(&names.value) {
+                diag.push_error("The keyframe must set a value".into(), &keyframe);
+            }
+        }
+
         apply_default_type_properties(&mut anim_element);
 
         Some(Rc::new(RefCell::new(anim_element)))
     }
 }
 
+/// Create the animation elements for an `animate` within a transition, and add them to
+/// `property_animations`. Returns the indices of the added animations.
+fn transition_animations_from_node(
+    pa: &syntax_nodes::PropertyAnimation,
+    r: &ElementRc,
+    property_animations: &mut Vec<(NamedReference, SourceLocation, ElementRc)>,
+    diag: &mut BuildDiagnostics,
+    tr: &TypeRegister,
+) -> Vec<usize> {
+    let mut indices = vec![];
+    for qn in pa.QualifiedName() {
+        if let Some((ne, prop_type)) = lookup_property_from_qualified_name(qn.clone(), r, diag) {
+            if let Some(anim_element) = animation_element_from_node(pa, &qn, prop_type, diag, tr) {
+                indices.push(property_animations.len());
+                property_animations.push((ne, qn.to_source_location(), anim_element));
+            }
+        }
+    }
+    indices
+}
+
+/// The names of the properties of an animation element holding the keyframe at a given index.
+/// These properties are declared on the animation element when parsing a keyframe.
+pub struct KeyFrameProperties {
+    /// The position of the keyframe, a float between 0 and 1
+    pub position: String,
+    /// The value of the animated property at that position
+    pub value: String,
+    /// The easing used from this keyframe to the next one. If there is no binding for it,
+    /// the easing of the animation is used.
+    pub easing: String,
+}
+
+impl KeyFrameProperties {
+    fn new(index: usize) -> Self {
+        Self {
+            position: format!("keyframe-{}-position", index),
+            value: format!("keyframe-{}-value", index),
+            easing: format!("keyframe-{}-easing", index),
+        }
+    }
+
+    /// Returns the properties of all the keyframes of an animation element, in declaration order
+    pub fn for_animation(anim_element: &Element) -> Vec<Self> {
+        (0..)
+            .map(Self::new)
+            .take_while(|k| anim_element.property_declarations.contains_key(&k.position))
+            .collect()
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct QualifiedTypeName {
     pub members: Vec<String>,
@@ -1356,7 +1705,7 @@ fn lookup_property_from_qualified_name(
     let qualname = QualifiedTypeName::from_node(node.clone());
     match qualname.members.as_slice() {
         [unresolved_prop_name] => {
-            let PropertyLookupResult { resolved_name, property_type } =
+            let PropertyLookupResult { resolved_name, property_type, .. } =
                 r.borrow().lookup_property(unresolved_prop_name.as_ref());
             if !property_type.is_property_type() {
                 diag.push_error(format!("'{}' is not a valid property", qualname), &node);
@@ -1365,7 +1714,7 @@ fn lookup_property_from_qualified_name(
         }
         [elem_id, unresolved_prop_name] => {
             if let Some(element) = find_element_by_id(r, elem_id.as_ref()) {
-                let PropertyLookupResult { resolved_name, property_type } =
+                let PropertyLookupResult { resolved_name, property_type, .. } =
                     element.borrow().lookup_property(unresolved_prop_name.as_ref());
                 if !property_type.is_property_type() {
                     diag.push_error(
@@ -1463,7 +1812,12 @@ pub fn recurse_elem_including_sub_components<State>(
         .popup_windows
         .borrow()
         .iter()
-        .for_each(|p| recurse_elem_including_sub_components(&p.component, state, vis))
+        .for_each(|p| recurse_elem_including_sub_components(&p.component, state, vis));
+    component
+        .secondary_windows
+        .borrow()
+        .iter()
+        .for_each(|w| recurse_elem_including_sub_components(w, state, vis))
 }
 
 /// Same as recurse_elem, but will take the children from the element as to not keep the element borrow
@@ -1505,6 +1859,11 @@ pub fn recurse_elem_including_sub_components_no_borrow<State>(
         .borrow()
         .iter()
         .for_each(|p| recurse_elem_including_sub_components_no_borrow(&p.component, state, vis));
+    component
+        .secondary_windows
+        .borrow()
+        .iter()
+        .for_each(|w| recurse_elem_including_sub_components_no_borrow(w, state, vis));
     component
         .used_types
         .borrow()
@@ -1571,6 +1930,14 @@ fn visit_element_expressions_simple(
         }
     }
     elem.borrow_mut().transitions = transitions;
+
+    let change_callbacks = std::mem::take(&mut elem.borrow_mut().change_callbacks);
+    for e in change_callbacks.values() {
+        for e in e.borrow_mut().iter_mut() {
+            vis(e, None, &|| Type::Callback { return_type: None, args: vec![] });
+        }
+    }
+    elem.borrow_mut().change_callbacks = change_callbacks;
 }
 
 /// Visit all the named reference in an element
@@ -1582,7 +1949,9 @@ pub fn visit_all_named_references_in_element(
     fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedReference)) {
         expr.visit_mut(|sub| recurse_expression(sub, vis));
         match expr {
-            Expression::PropertyReference(r) | Expression::CallbackReference(r) => vis(r),
+            Expression::PropertyReference(r)
+            | Expression::CallbackReference(r)
+            | Expression::FunctionReference(r) => vis(r),
             Expression::LayoutCacheAccess { layout_cache_prop, .. } => vis(layout_cache_prop),
             Expression::SolveLayout(l, _) => l.visit_named_references(vis),
             Expression::ComputeLayoutInfo(l, _) => l.visit_named_references(vis),
@@ -1690,12 +2059,68 @@ pub struct State {
     pub property_changes: Vec<(NamedReference, Expression, syntax_nodes::StatePropertyChange)>,
 }
 
+/// A `sequence { ... }` or `parallel { ... }` group of animations within a transition
+#[derive(Debug, Clone)]
+pub enum TransitionAnimationGroup {
+    /// Index in [`Transition::property_animations`]
+    Animation(usize),
+    /// The animations run one after the other
+    Sequence(Vec<TransitionAnimationGroup>),
+    /// The animations run at the same time
+    Parallel(Vec<TransitionAnimationGroup>),
+}
+
+impl TransitionAnimationGroup {
+    fn from_node(
+        node: syntax_nodes::AnimationGroup,
+        r: &ElementRc,
+        property_animations: &mut Vec<(NamedReference, SourceLocation, ElementRc)>,
+        diag: &mut BuildDiagnostics,
+        tr: &TypeRegister,
+    ) -> Self {
+        let mut items = vec![];
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::PropertyAnimation => {
+                    let indices = transition_animations_from_node(
+                        &child.into(),
+                        r,
+                        property_animations,
+                        diag,
+                        tr,
+                    );
+                    // An `animate` for several properties animates them at the same time
+                    let mut animations =
+                        indices.into_iter().map(Self::Animation).collect::<Vec<_>>();
+                    items.push(if animations.len() == 1 {
+                        animations.remove(0)
+                    } else {
+                        Self::Parallel(animations)
+                    });
+                }
+                SyntaxKind::AnimationGroup => {
+                    items.push(Self::from_node(child.into(), r, property_animations, diag, tr))
+                }
+                _ => {}
+            }
+        }
+        if parser::identifier_text(&node).unwrap_or_default() == "sequence" {
+            Self::Sequence(items)
+        } else {
+            Self::Parallel(items)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Transition {
     /// false for 'to', true for 'out'
     pub is_out: bool,
     pub state_id: String,
     pub property_animations: Vec<(NamedReference, SourceLocation, ElementRc)>,
+    /// The `sequence` and `parallel` groups. The animations that are not part of a group start
+    /// at the beginning of the transition.
+    pub animation_groups: Vec<TransitionAnimationGroup>,
     /// Node pointing to the state name
     pub node: SyntaxNode,
 }
@@ -1791,6 +2216,21 @@ struct NamedExport {
                 }
             }),
         );
+        let exports_it = exports_it.chain(
+            doc.ExportsList().flat_map(|exports| exports.EnumDeclaration()).map(|en| {
+                let name_location: SyntaxNode = en.DeclaredIdentifier().into();
+                let name = parser::identifier_text(&en.DeclaredIdentifier()).unwrap_or_else(|| {
+                    debug_assert!(diag.has_error());
+                    String::new()
+                });
+                NamedExport {
+                    internal_name_ident: name_location.clone(),
+                    internal_name: name.clone(),
+                    external_name_ident: name_location,
+                    exported_name: name,
+                }
+            }),
+        );
 
         struct SeenExport {
             name_location: SyntaxNode,
@@ -1840,7 +2280,9 @@ struct SeenExport {
 
         let mut resolve_export_to_inner_component_or_import =
             |export: &NamedExport| match type_registry.lookup(export.internal_name.as_str()) {
-                ty @ Type::Component(_) | ty @ Type::Struct { .. } => Some(ty),
+                ty @ Type::Component(_) | ty @ Type::Struct { .. } | ty @ Type::Enumeration(_) => {
+                    Some(ty)
+                }
                 Type::Invalid => {
                     diag.push_error(
                         format!("'{}' not found", export.internal_name),
@@ -320,30 +320,42 @@ fn source_file(&self) -> Option<&SourceFile> {
     }
     // syntax kind
     {
-        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration ],
+        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration, *EnumDeclaration ],
         /// `DeclaredIdentifier := Element { ... }`
         Component -> [ DeclaredIdentifier, Element ],
         /// `id := Element { ... }`
-        SubElement -> [ Element ],
+        SubElement -> [ ?SlotMarker, Element ],
+        /// `@slot(name)` before a sub element, to place it in a named `@children(name)` placeholder
+        SlotMarker -> [ DeclaredIdentifier ],
         Element -> [ ?QualifiedName, *PropertyDeclaration, *Binding, *CallbackConnection,
                      *CallbackDeclaration, *SubElement, *RepeatedElement, *PropertyAnimation,
-                     *TwoWayBinding, *States, *Transitions, ?ChildrenPlaceholder ],
+                     *TwoWayBinding, *Function, *ChangeCallback, *States, *Transitions,
+                     ?ChildrenPlaceholder ],
         RepeatedElement -> [ ?DeclaredIdentifier, ?RepeatedIndex, Expression , SubElement],
         RepeatedIndex -> [],
         ConditionalElement -> [ Expression , SubElement],
         CallbackDeclaration -> [ DeclaredIdentifier, *Type, ?ReturnType, ?TwoWayBinding ],
         /// `-> type`  (but without the ->)
         ReturnType -> [Type],
+        /// `[public] function name(arg: type, ...) -> type { ... }`
+        Function -> [DeclaredIdentifier, *ArgumentDeclaration, ?ReturnType, CodeBlock ],
+        /// `name: type` in the argument list of a function
+        ArgumentDeclaration -> [DeclaredIdentifier, Type],
         CallbackConnection -> [ *DeclaredIdentifier,  CodeBlock ],
+        /// `changed <property> => { ... }`
+        ChangeCallback -> [ DeclaredIdentifier, CodeBlock ],
         /// Declaration of a property.
         PropertyDeclaration-> [ ?Type , DeclaredIdentifier, ?BindingExpression, ?TwoWayBinding ],
         /// QualifiedName are the properties name
-        PropertyAnimation-> [ *QualifiedName, *Binding ],
+        PropertyAnimation-> [ *QualifiedName, *Binding, *KeyFrame ],
+        /// `50% { value: ...; easing: ...; }` within an animation. The NumberLiteral is the position
+        KeyFrame -> [ *Binding ],
         /// wraps Identifiers, like `Rectangle` or `SomeModule.SomeType`
         QualifiedName-> [],
         /// Wraps single identifier (to disambiguate when there are other identifier in the production)
         DeclaredIdentifier -> [],
-        ChildrenPlaceholder -> [],
+        /// `@children` or `@children(name)`
+        ChildrenPlaceholder -> [ ?DeclaredIdentifier ],
         Binding-> [ BindingExpression ],
         /// `xxx <=> something`
         TwoWayBinding -> [ Expression ],
@@ -356,7 +368,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         Expression-> [ ?Expression, ?FunctionCallExpression, ?IndexExpression, ?SelfAssignment,
                        ?ConditionalExpression, ?QualifiedName, ?BinaryExpression, ?Array, ?ObjectLiteral,
                        ?UnaryOpExpression, ?CodeBlock, ?StringTemplate, ?AtImageUrl, ?AtGradient,
-                       ?MemberAccess ],
+                       ?MemberAccess, ?Lambda ],
         /// Concatenate the Expressions to make a string (usually expended from a template string)
         StringTemplate -> [*Expression],
         /// `@image-url("foo.png")`
@@ -365,6 +377,8 @@ fn source_file(&self) -> Option<&SourceFile> {
         AtGradient -> [*Expression],
         /// expression()
         FunctionCallExpression -> [*Expression],
+        /// `item => expression`, as the argument of `filter` or `map`
+        Lambda -> [DeclaredIdentifier, Expression],
         /// `expression[index]`
         IndexExpression -> [2 Expression],
         /// `expression += expression`
@@ -392,9 +406,11 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// `transitions: [...]`
         Transitions -> [*Transition],
         /// There is an identifier "in" or "out", the DeclaredIdentifier is the state name
-        Transition -> [DeclaredIdentifier, *PropertyAnimation],
+        Transition -> [DeclaredIdentifier, *PropertyAnimation, *AnimationGroup],
+        /// `sequence { ... }` or `parallel { ... }` within a transition
+        AnimationGroup -> [*PropertyAnimation, *AnimationGroup],
         /// Export a set of declared components by name
-        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration ],
+        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration, *EnumDeclaration ],
         /// Declare the first identifier to be exported, either under its name or instead
         /// under the name of the second identifier.
         ExportSpecifier -> [ ExportIdentifier, ?ExportName ],
@@ -417,6 +433,10 @@ fn source_file(&self) -> Option<&SourceFile> {
         ArrayType -> [ Type ],
         /// `struct Foo := { ... }
         StructDeclaration -> [DeclaredIdentifier, ObjectType],
+        /// `enum Foo { bli, bla, blu }`
+        EnumDeclaration -> [DeclaredIdentifier, *EnumValue],
+        /// The value is a Identifier
+        EnumValue -> [],
 
     }
 }
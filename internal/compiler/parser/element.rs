@@ -34,9 +34,14 @@ pub fn parse_element(p: &mut impl Parser) -> bool {
 /// clicked => {}
 /// callback foobar;
 /// property<int> width;
+/// out property<int> height;
+/// function foo() {}
+/// changed width => {}
 /// animate someProp { }
 /// animate * { }
 /// @children
+/// @children(header)
+/// @slot(header) Sub {}
 /// double_binding <=> element.property;
 /// ```
 pub fn parse_element_content(p: &mut impl Parser) {
@@ -58,6 +63,18 @@ pub fn parse_element_content(p: &mut impl Parser) {
                 SyntaxKind::Identifier if p.peek().as_str() == "callback" => {
                     parse_callback_declaration(&mut *p);
                 }
+                SyntaxKind::Identifier
+                    if p.peek().as_str() == "function"
+                        || (p.peek().as_str() == "public" && p.nth(1).as_str() == "function") =>
+                {
+                    parse_function(&mut *p);
+                }
+                SyntaxKind::Identifier
+                    if p.peek().as_str() == "changed"
+                        && p.nth(2).kind() == SyntaxKind::FatArrow =>
+                {
+                    parse_change_callback(&mut *p);
+                }
                 SyntaxKind::Identifier | SyntaxKind::Star if p.peek().as_str() == "animate" => {
                     parse_property_animation(&mut *p);
                 }
@@ -67,6 +84,12 @@ pub fn parse_element_content(p: &mut impl Parser) {
                 SyntaxKind::Identifier if p.peek().as_str() == "property" => {
                     parse_property_declaration(&mut *p);
                 }
+                SyntaxKind::Identifier
+                    if p.nth(1).as_str() == "property"
+                        && matches!(p.peek().as_str(), "in" | "out" | "in-out" | "private") =>
+                {
+                    parse_property_declaration(&mut *p);
+                }
                 _ if p.peek().as_str() == "if" => {
                     parse_if_element(&mut *p);
                 }
@@ -84,13 +107,23 @@ pub fn parse_element_content(p: &mut impl Parser) {
                     }
                 }
             },
+            SyntaxKind::At if p.nth(1).as_str() == "slot" => {
+                parse_sub_element(&mut *p);
+            }
             SyntaxKind::At => {
                 let checkpoint = p.checkpoint();
                 p.consume();
                 if p.peek().as_str() == "children" {
                     let mut p =
                         p.start_node_at(checkpoint.clone(), SyntaxKind::ChildrenPlaceholder);
-                    p.consume()
+                    p.consume();
+                    if p.test(SyntaxKind::LParent) {
+                        {
+                            let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+                            p.expect(SyntaxKind::Identifier);
+                        }
+                        p.expect(SyntaxKind::RParent);
+                    }
                 } else {
                     p.test(SyntaxKind::Identifier);
                     p.error("Parse error: Expected @children")
@@ -112,10 +145,15 @@ pub fn parse_element_content(p: &mut impl Parser) {
 /// Bar {}
 /// foo := Bar {}
 /// Bar { x : y ; }
+/// @slot(header) Bar {}
+/// @slot(footer) foo := Bar {}
 /// ```
 /// Must consume at least one token
 fn parse_sub_element(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::SubElement);
+    if p.nth(0).kind() == SyntaxKind::At {
+        parse_slot_marker(&mut *p);
+    }
     if p.nth(1).kind() == SyntaxKind::ColonEqual {
         p.expect(SyntaxKind::Identifier);
         p.expect(SyntaxKind::ColonEqual);
@@ -123,12 +161,35 @@ fn parse_sub_element(p: &mut impl Parser) {
     parse_element(&mut *p);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,SlotMarker
+/// @slot(header)
+/// @slot(some-slot)
+/// ```
+fn parse_slot_marker(p: &mut impl Parser) {
+    debug_assert_eq!(p.peek().kind(), SyntaxKind::At);
+    let mut p = p.start_node(SyntaxKind::SlotMarker);
+    p.consume(); // "@"
+    if p.peek().as_str() != "slot" {
+        p.error("Expected @slot");
+        return;
+    }
+    p.consume(); // "slot"
+    p.expect(SyntaxKind::LParent);
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    p.expect(SyntaxKind::RParent);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,RepeatedElement
 /// for xx in mm: Elem { }
 /// for [idx] in mm: Elem { }
 /// for xx [idx] in foo.bar: Elem { }
 /// for _ in (xxx()): blah := Elem { Elem{} }
+/// for xx in mm: @slot(items) Elem { }
 /// ```
 /// Must consume at least one token
 fn parse_repeated_element(p: &mut impl Parser) {
@@ -256,6 +317,23 @@ fn parse_callback_connection(p: &mut impl Parser) {
     parse_code_block(&mut *p);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,ChangeCallback
+/// changed foo => {}
+/// changed foo-bar => { debug(foo-bar); }
+/// ```
+fn parse_change_callback(p: &mut impl Parser) {
+    debug_assert_eq!(p.peek().as_str(), "changed");
+    let mut p = p.start_node(SyntaxKind::ChangeCallback);
+    p.consume(); // "changed"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    p.expect(SyntaxKind::FatArrow);
+    parse_code_block(&mut *p);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,TwoWayBinding
 /// foo <=> bar;
@@ -320,16 +398,65 @@ fn parse_callback_declaration(p: &mut impl Parser) {
     p.expect(SyntaxKind::Semicolon);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,Function
+/// function foo() {}
+/// function foo(a: int) -> int { a + 1 }
+/// function bar(a: string, b: { x: int },) -> string { return a; }
+/// public function baz() { }
+/// ```
+fn parse_function(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::Function);
+    if p.peek().as_str() == "public" {
+        p.consume();
+    }
+    debug_assert_eq!(p.peek().as_str(), "function");
+    p.consume(); // "function"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    if p.expect(SyntaxKind::LParent) {
+        while p.peek().kind() != SyntaxKind::RParent {
+            {
+                let mut p = p.start_node(SyntaxKind::ArgumentDeclaration);
+                {
+                    let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+                    p.expect(SyntaxKind::Identifier);
+                }
+                p.expect(SyntaxKind::Colon);
+                parse_type(&mut *p);
+            }
+            if !p.test(SyntaxKind::Comma) {
+                break;
+            }
+        }
+        p.expect(SyntaxKind::RParent);
+        if p.test(SyntaxKind::Arrow) {
+            let mut p = p.start_node(SyntaxKind::ReturnType);
+            parse_type(&mut *p);
+        }
+    }
+    parse_code_block(&mut *p);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,PropertyDeclaration
 /// property<int> foobar;
 /// property<string> text: "Something";
 /// property<string> text <=> two.way;
 /// property alias <=> two.way;
+/// in property<int> input;
+/// out property<int> output: 42;
+/// in-out property<string> both;
+/// private property<bool> hidden;
 /// ```
 fn parse_property_declaration(p: &mut impl Parser) {
-    debug_assert_eq!(p.peek().as_str(), "property");
     let mut p = p.start_node(SyntaxKind::PropertyDeclaration);
+    if p.peek().as_str() != "property" {
+        p.consume(); // in, out, in-out or private
+    }
+    debug_assert_eq!(p.peek().as_str(), "property");
     p.consume(); // property
 
     if p.test(SyntaxKind::LAngle) {
@@ -368,6 +495,7 @@ fn parse_property_declaration(p: &mut impl Parser) {
 /// animate x { duration: 1000; }
 /// animate x, foo.y {  }
 /// animate * {  }
+/// animate x { duration: 1s; 50% { value: 20px; easing: ease-in; } }
 /// ```
 fn parse_property_animation(p: &mut impl Parser) {
     debug_assert_eq!(p.peek().as_str(), "animate");
@@ -395,12 +523,42 @@ fn parse_property_animation(p: &mut impl Parser) {
                 SyntaxKind::Colon => parse_property_binding(&mut *p),
                 _ => {
                     p.consume();
-                    p.error("Only bindings are allowed in animations");
+                    p.error("Only bindings and keyframes are allowed in animations");
                 }
             },
+            SyntaxKind::NumberLiteral => parse_keyframe(&mut *p),
             _ => {
                 p.consume();
-                p.error("Only bindings are allowed in animations");
+                p.error("Only bindings and keyframes are allowed in animations");
+            }
+        }
+    }
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,KeyFrame
+/// 50% { value: 180deg; }
+/// 25% { value: 10px; easing: ease-in; }
+/// ```
+fn parse_keyframe(p: &mut impl Parser) {
+    debug_assert_eq!(p.nth(0).kind(), SyntaxKind::NumberLiteral);
+    let mut p = p.start_node(SyntaxKind::KeyFrame);
+    p.consume(); // the position
+    p.expect(SyntaxKind::LBrace);
+
+    loop {
+        match p.nth(0).kind() {
+            SyntaxKind::RBrace => {
+                p.consume();
+                return;
+            }
+            SyntaxKind::Eof => return,
+            SyntaxKind::Identifier if p.nth(1).kind() == SyntaxKind::Colon => {
+                parse_property_binding(&mut *p)
+            }
+            _ => {
+                p.consume();
+                p.error("Only bindings are allowed in keyframes");
             }
         }
     }
@@ -484,6 +642,7 @@ fn parse_transitions(p: &mut impl Parser) {
 /// in pressed : {}
 /// in pressed: { animate x { duration: 88ms; } }
 /// out pressed: { animate x { duration: 88ms; } }
+/// in pressed: { animate x { duration: 88ms; } sequence { animate y { duration: 1s; } } }
 /// ```
 fn parse_transition(p: &mut impl Parser) -> bool {
     if !matches!(p.peek().as_str(), "in" | "out") {
@@ -511,9 +670,45 @@ fn parse_transition(p: &mut impl Parser) -> bool {
             SyntaxKind::Identifier if p.peek().as_str() == "animate" => {
                 parse_property_animation(&mut *p);
             }
+            SyntaxKind::Identifier if matches!(p.peek().as_str(), "sequence" | "parallel") => {
+                parse_animation_group(&mut *p);
+            }
+            _ => {
+                p.consume();
+                p.error("Expected 'animate', 'sequence' or 'parallel'");
+            }
+        }
+    }
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,AnimationGroup
+/// sequence { }
+/// sequence { animate x { duration: 88ms; } animate y { duration: 88ms; } }
+/// parallel { animate x { duration: 1s; } sequence { animate y { duration: 1s; } animate z { } } }
+/// ```
+fn parse_animation_group(p: &mut impl Parser) {
+    debug_assert!(matches!(p.peek().as_str(), "sequence" | "parallel"));
+    let mut p = p.start_node(SyntaxKind::AnimationGroup);
+    p.consume(); // "sequence" or "parallel"
+    p.expect(SyntaxKind::LBrace);
+
+    loop {
+        match p.nth(0).kind() {
+            SyntaxKind::RBrace => {
+                p.consume();
+                return;
+            }
+            SyntaxKind::Eof => return,
+            SyntaxKind::Identifier if p.peek().as_str() == "animate" => {
+                parse_property_animation(&mut *p);
+            }
+            SyntaxKind::Identifier if matches!(p.peek().as_str(), "sequence" | "parallel") => {
+                parse_animation_group(&mut *p);
+            }
             _ => {
                 p.consume();
-                p.error("Expected 'animate'");
+                p.error("Expected 'animate', 'sequence' or 'parallel'");
             }
         }
     }
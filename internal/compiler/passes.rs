@@ -26,9 +26,11 @@
 mod inlining;
 mod lower_accessibility;
 mod lower_layout;
+mod lower_menus;
 mod lower_popups;
 mod lower_property_to_element;
 mod lower_shadows;
+mod lower_shortcuts;
 mod lower_states;
 mod lower_tabwidget;
 mod materialize_fake_properties;
@@ -39,6 +41,7 @@
 mod repeater_component;
 mod resolve_native_classes;
 mod resolving;
+mod track_map_dependencies;
 mod unique_id;
 mod visible;
 mod z_order;
@@ -88,6 +91,8 @@ pub async fn run_passes(
     {
         compile_paths::compile_paths(component, &doc.local_registry, diag);
         lower_tabwidget::lower_tabwidget(component, type_loader, diag).await;
+        lower_menus::lower_menus(component, type_loader, diag).await;
+        lower_shortcuts::lower_shortcuts(component, diag);
         apply_default_properties_from_style::apply_default_properties_from_style(
             component,
             &style_metrics,
@@ -147,6 +152,20 @@ pub async fn run_passes(
             diag,
         );
         lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
+        // The drop shadow properties that were not lowered to a box shadow are handled as an effect
+        let effect_properties = (crate::typeregister::RESERVED_EFFECT_PROPERTIES.iter())
+            .chain(crate::typeregister::RESERVED_DROP_SHADOW_PROPERTIES.iter())
+            .map(|(prop_name, _)| *prop_name)
+            .collect::<Vec<_>>();
+        lower_property_to_element::lower_properties_to_element(
+            component,
+            &effect_properties,
+            core::iter::empty(),
+            None,
+            "Effect",
+            &global_type_registry.borrow(),
+            diag,
+        );
         lower_property_to_element::lower_property_to_element(
             component,
             crate::typeregister::RESERVED_ROTATION_PROPERTIES[0].0,
@@ -178,8 +197,17 @@ pub async fn run_passes(
         }
         materialize_fake_properties::materialize_fake_properties(component);
     }
+    // After lower_popups, so that the shortcuts of the popups are checked with their own window
+    lower_shortcuts::check_duplicated_shortcuts(root_component, diag);
     collect_globals::collect_globals(doc, diag);
 
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(root_component.used_types.borrow().globals.iter())
+        .chain(std::iter::once(root_component))
+    {
+        track_map_dependencies::track_map_dependencies(component);
+    }
+
     if compiler_config.inline_all_elements {
         inlining::inline(doc, inlining::InlineSelection::InlineAllComponents);
         root_component.used_types.borrow_mut().sub_components.clear();
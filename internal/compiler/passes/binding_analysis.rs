@@ -149,6 +149,19 @@ fn analyze_element(
     for (_, nr) in &elem.borrow().accessibility_props.0 {
         process_property(&PropertyPath::from(nr.clone()), context, reverse_aliases, diag);
     }
+    for (name, handlers) in &elem.borrow().change_callbacks {
+        process_property(
+            &PropertyPath::from(NamedReference::new(elem, name)),
+            context,
+            reverse_aliases,
+            diag,
+        );
+        for e in handlers.borrow().iter() {
+            recurse_expression(e, &mut |prop| {
+                process_property(&PropertyPath::from(prop.clone()), context, reverse_aliases, diag);
+            });
+        }
+    }
 }
 
 #[derive(Copy, Clone, dm::BitAnd, dm::BitOr, dm::BitAndAssign, dm::BitOrAssign)]
@@ -187,10 +200,13 @@ fn analyse_binding(
 
             let span =
                 binding.span.clone().or_else(|| elem.node.as_ref().map(|n| n.to_source_location()));
-            diag.push_error(
-                format!("The binding for the property '{}' is part of a binding loop", p.name()),
-                &span,
-            );
+            let message =
+                if matches!(elem.lookup_property(p.name()).property_type, Type::Function { .. }) {
+                    format!("The function '{}' is part of a recursion or binding loop", p.name())
+                } else {
+                    format!("The binding for the property '{}' is part of a binding loop", p.name())
+                };
+            diag.push_error(message, &span);
 
             if it == current {
                 break;
@@ -314,7 +330,9 @@ fn process_property(
 fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&NamedReference)) {
     expr.visit(|sub| recurse_expression(sub, vis));
     match expr {
-        Expression::PropertyReference(r) | Expression::CallbackReference(r) => vis(r),
+        Expression::PropertyReference(r)
+        | Expression::CallbackReference(r)
+        | Expression::FunctionReference(r) => vis(r),
         Expression::LayoutCacheAccess { layout_cache_prop, .. } => vis(layout_cache_prop),
         Expression::SolveLayout(l, o) | Expression::ComputeLayoutInfo(l, o) => {
             // we should only visit the layout geometry for the orientation
@@ -328,6 +346,24 @@ fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&NamedReference))
                 crate::layout::Layout::BoxLayout(l) => {
                     visit_layout_items_dependencies(l.elems.iter(), *o, vis)
                 }
+                crate::layout::Layout::FlowLayout(l) => {
+                    visit_layout_items_dependencies(l.elems.iter(), *o, vis);
+                    if *o == Orientation::Vertical {
+                        // The lines depend on the width of the layout and of the items
+                        let horizontal = Orientation::Horizontal;
+                        visit_layout_items_dependencies(l.elems.iter(), horizontal, vis);
+                        l.geometry.rect.size_reference(horizontal).map(&mut |nr| vis(nr));
+                    }
+                    l.line_alignment.as_ref().map(&mut |nr| vis(nr));
+                }
+                crate::layout::Layout::StackLayout(l) => {
+                    visit_layout_items_dependencies(l.elems.iter().map(|it| &it.item), *o, vis);
+                    for it in &l.elems {
+                        if let crate::layout::StackAlignment::Property(nr) = it.alignment(*o) {
+                            vis(nr)
+                        }
+                    }
+                }
                 crate::layout::Layout::PathLayout(l) => {
                     for it in &l.elements {
                         vis(&NamedReference::new(it, "width"));
@@ -6,8 +6,9 @@
 use std::rc::Rc;
 
 use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel};
-use crate::langtype::Type;
+use crate::langtype::{PropertyVisibility, Type};
 use crate::object_tree::{Component, Document};
+use crate::parser::SyntaxKind;
 
 pub fn check_public_api(doc: &Document, diag: &mut BuildDiagnostics) {
     check_public_api_component(&doc.root_component, diag);
@@ -27,9 +28,20 @@ fn check_public_api_component(root_component: &Rc<Component>, diag: &mut BuildDi
     let root_elem = &mut *root_elem;
     let mut pa = root_elem.property_analysis.borrow_mut();
     root_elem.property_declarations.iter_mut().for_each(|(n, d)| {
-        if d.property_type.ok_for_public_api() {
+        if let Type::Function { .. } = &d.property_type {
+            // Functions are only exposed when declared with `public function`
+            d.expose_in_public_api = d.node.as_ref().map_or(false, |n| {
+                n.kind() == SyntaxKind::Function
+                    && n.child_token(SyntaxKind::Identifier).map_or(false, |t| t.text() == "public")
+            });
+        } else if d.visibility == PropertyVisibility::Private {
+            // Private properties are not part of the public API
+        } else if d.property_type.ok_for_public_api() {
             d.expose_in_public_api = true;
-            pa.entry(n.to_string()).or_default().is_set = true;
+            if d.visibility != PropertyVisibility::Output {
+                // Output properties are read-only in the public API
+                pa.entry(n.to_string()).or_default().is_set = true;
+            }
         } else {
             diag.push_diagnostic(
                  format!("Properties of type {} are not supported yet for public API. The property will not be exposed", d.property_type),
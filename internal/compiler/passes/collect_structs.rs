@@ -1,25 +1,27 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-//! Passes that fills the root component used_types.structs
+//! Passes that fills the root component used_types.structs and used_types.enums
 
 use crate::expression_tree::Expression;
-use crate::langtype::Type;
+use crate::langtype::{Enumeration, Type};
 use crate::object_tree::*;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
-/// Fill the root_component's used_types.structs
+/// Fill the root_component's used_types.structs and used_types.enums
 pub fn collect_structs(doc: &Document) {
     let mut hash = BTreeMap::new();
+    let mut enums = BTreeMap::new();
 
     for component in (doc.root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(&doc.root_component))
     {
-        collect_structs_in_component(component, &mut hash)
+        collect_structs_in_component(component, &mut hash, &mut enums)
     }
 
     let mut used_types = doc.root_component.used_types.borrow_mut();
+    used_types.enums = enums.into_values().collect();
     let used_struct = &mut used_types.structs;
     *used_struct = Vec::with_capacity(hash.len());
     while let Some(next) = hash.iter().next() {
@@ -29,11 +31,18 @@ pub fn collect_structs(doc: &Document) {
     }
 }
 
-fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTreeMap<String, Type>) {
+fn collect_structs_in_component(
+    root_component: &Rc<Component>,
+    hash: &mut BTreeMap<String, Type>,
+    enums: &mut BTreeMap<String, Rc<Enumeration>>,
+) {
     let mut maybe_collect_object = |ty: &Type| {
         visit_named_object(ty, &mut |name, sub_ty| {
             hash.entry(name.clone()).or_insert_with(|| sub_ty.clone());
         });
+        visit_declared_enums(ty, &mut |e| {
+            enums.entry(e.name.clone()).or_insert_with(|| e.clone());
+        });
     };
 
     recurse_elem_including_sub_components_no_borrow(root_component, &(), &mut |elem, _| {
@@ -43,10 +52,12 @@ fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTree
     });
 
     visit_all_expressions(root_component, |expr, _| {
-        expr.visit_recursive(&mut |expr| {
-            if let Expression::Struct { ty, .. } = expr {
-                maybe_collect_object(ty)
+        expr.visit_recursive(&mut |expr| match expr {
+            Expression::Struct { ty, .. } => maybe_collect_object(ty),
+            Expression::EnumerationValue(value) => {
+                maybe_collect_object(&Type::Enumeration(value.enumeration.clone()))
             }
+            _ => {}
         })
     });
 }
@@ -88,6 +99,40 @@ fn visit_named_object(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
                 visit_named_object(a, visitor);
             }
         }
+        Type::Function { return_type, args } => {
+            visit_named_object(return_type, visitor);
+            for a in args {
+                visit_named_object(a, visitor);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Call the visitor for each enum declared in a .slint file that is used by the type
+fn visit_declared_enums(ty: &Type, visitor: &mut impl FnMut(&Rc<Enumeration>)) {
+    match ty {
+        Type::Enumeration(e) if e.node.is_some() => visitor(e),
+        Type::Struct { fields, .. } => {
+            for sub_ty in fields.values() {
+                visit_declared_enums(sub_ty, visitor);
+            }
+        }
+        Type::Array(x) => visit_declared_enums(x, visitor),
+        Type::Callback { return_type, args } => {
+            if let Some(rt) = return_type {
+                visit_declared_enums(rt, visitor);
+            }
+            for a in args {
+                visit_declared_enums(a, visitor);
+            }
+        }
+        Type::Function { return_type, args } => {
+            visit_declared_enums(return_type, visitor);
+            for a in args {
+                visit_declared_enums(a, visitor);
+            }
+        }
         _ => {}
     }
 }
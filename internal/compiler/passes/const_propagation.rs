@@ -116,6 +116,7 @@ fn simplify_expression(expr: &mut Expression) -> bool {
             can_inline
         }
         Expression::CallbackReference { .. } => false,
+        Expression::FunctionReference { .. } => false,
         Expression::ElementReference { .. } => false,
         // FIXME
         Expression::LayoutCacheAccess { .. } => false,
@@ -42,8 +42,9 @@ pub fn default_geometry(root_component: &Rc<Component>, diag: &mut BuildDiagnost
                     DefaultSizeBinding::ImplicitSize => {
                         let has_length_property_binding = |elem: &ElementRc, property: &str| {
                             debug_assert!({
-                                let PropertyLookupResult { resolved_name: _, property_type } =
-                                    elem.borrow().lookup_property(property);
+                                let PropertyLookupResult {
+                                    resolved_name: _, property_type, ..
+                                } = elem.borrow().lookup_property(property);
                                 property_type == Type::LogicalLength
                             });
 
@@ -76,6 +77,7 @@ pub fn default_geometry(root_component: &Rc<Component>, diag: &mut BuildDiagnost
                                 let PropertyLookupResult {
                                     resolved_name: image_fit_prop_name,
                                     property_type: image_fit_prop_type,
+                                    ..
                                 } = elem.borrow().lookup_property("image-fit");
 
                                 elem.borrow_mut().set_binding_if_not_set(
@@ -175,11 +177,8 @@ fn fix_percent_size(
     let mut b = binding.borrow_mut();
     if let Some(parent) = parent {
         debug_assert_eq!(
-            parent.borrow().lookup_property(property),
-            PropertyLookupResult {
-                resolved_name: property.into(),
-                property_type: Type::LogicalLength
-            }
+            parent.borrow().lookup_property(property).property_type,
+            Type::LogicalLength
         );
         b.expression = Expression::BinaryExpression {
             lhs: Box::new(std::mem::take(&mut b.expression).maybe_convert_to(
@@ -196,7 +195,7 @@ fn fix_percent_size(
 }
 
 fn make_default_100(elem: &ElementRc, parent_element: &ElementRc, property: &str) {
-    let PropertyLookupResult { resolved_name, property_type } =
+    let PropertyLookupResult { resolved_name, property_type, .. } =
         parent_element.borrow().lookup_property(property);
     if property_type != Type::LogicalLength {
         return;
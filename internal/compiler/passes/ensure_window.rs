@@ -17,7 +17,10 @@ pub fn ensure_window(
     style_metrics: &Rc<Component>,
 ) {
     if component.root_element.borrow().builtin_type().map_or(true, |b| {
-        matches!(b.name.as_str(), "Window" | "Dialog" | "WindowItem" | "PopupWindow")
+        matches!(
+            b.name.as_str(),
+            "Window" | "Dialog" | "WindowItem" | "PopupWindow" | "SecondaryWindow"
+        )
     }) {
         return; // already a window, nothing to do
     }
@@ -40,6 +43,7 @@ pub fn ensure_window(
         repeated: Default::default(),
         states: Default::default(),
         transitions: Default::default(),
+        change_callbacks: Default::default(),
         child_of_layout: false,
         has_popup_child: false,
         layout_info_prop: Default::default(),
@@ -87,6 +91,9 @@ pub fn ensure_window(
         }
     }
 
+    // The default values from builtins.slint were not applied since the element was not a Window
+    crate::object_tree::apply_default_type_properties(&mut win_elem.borrow_mut());
+
     crate::object_tree::visit_all_named_references(component, &mut |nr| {
         if must_update.contains(nr) {
             *nr = NamedReference::new(&new_root, nr.name());
@@ -145,7 +145,9 @@ fn fixup_geometry(flickable_elem: &ElementRc) {
 fn is_layout(base_type: &Type) -> bool {
     if let Type::Builtin(be) = base_type {
         match be.name.as_str() {
-            "GridLayout" | "HorizontalLayout" | "VerticalLayout" => true,
+            "GridLayout" | "HorizontalLayout" | "VerticalLayout" | "FlowLayout" | "StackLayout" => {
+                true
+            }
             "PathLayout" => false,
             _ => false,
         }
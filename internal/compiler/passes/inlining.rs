@@ -46,7 +46,12 @@ fn inline_components_recursively(component: &Rc<Component>, inline_selection: In
             .popup_windows
             .borrow()
             .iter()
-            .for_each(|p| inline_components_recursively(&p.component, inline_selection))
+            .for_each(|p| inline_components_recursively(&p.component, inline_selection));
+        component
+            .secondary_windows
+            .borrow()
+            .iter()
+            .for_each(|w| inline_components_recursively(w, inline_selection))
     }
     inline_components_recursively(&doc.root_component, inline_selection);
 }
@@ -103,22 +108,42 @@ fn inline_element(
         }),
     );
 
-    match inlined_component
+    let unnamed_insertion_point = inlined_component
         .child_insertion_point
         .borrow()
         .as_ref()
-        .and_then(|(elem, node)| Some((mapping.get(&element_key(elem.clone()))?, node)))
-    {
-        Some((insertion_element, cip_node)) if !Rc::ptr_eq(elem, insertion_element) => {
-            insertion_element.borrow_mut().children.append(&mut elem_mut.children);
-            if let Some(cip) = root_component.child_insertion_point.borrow_mut().as_mut() {
-                if Rc::ptr_eq(&cip.0, elem) {
-                    *cip = (insertion_element.clone(), cip_node.clone());
-                }
-            };
+        .and_then(|(elem, node)| {
+            Some((mapping.get(&element_key(elem.clone()))?.clone(), node.clone()))
+        })
+        .filter(|(insertion_element, _)| !Rc::ptr_eq(elem, insertion_element));
+
+    for child in std::mem::take(&mut elem_mut.children) {
+        let insertion_element = match &child.borrow().slot {
+            None => unnamed_insertion_point.as_ref().map(|(e, _)| e.clone()),
+            Some(name) => inlined_component
+                .named_child_insertion_points
+                .borrow()
+                .get(name)
+                .and_then(|(e, _)| mapping.get(&element_key(e.clone())).cloned()),
+        };
+        match insertion_element {
+            Some(insertion_element) if !Rc::ptr_eq(elem, &insertion_element) => {
+                insertion_element.borrow_mut().children.push(child)
+            }
+            _ => new_children.push(child),
         }
-        _ => {
-            new_children.append(&mut elem_mut.children);
+    }
+
+    if let Some((insertion_element, cip_node)) = &unnamed_insertion_point {
+        if let Some(cip) = root_component.child_insertion_point.borrow_mut().as_mut() {
+            if Rc::ptr_eq(&cip.0, elem) {
+                *cip = (insertion_element.clone(), cip_node.clone());
+            }
+        };
+        for cip in root_component.named_child_insertion_points.borrow_mut().values_mut() {
+            if Rc::ptr_eq(&cip.0, elem) {
+                cip.0 = insertion_element.clone();
+            }
         }
     }
 
@@ -143,6 +168,13 @@ fn inline_element(
             .iter()
             .map(|p| duplicate_popup(p, &mut mapping, priority_delta)),
     );
+    root_component.secondary_windows.borrow_mut().extend(
+        inlined_component
+            .secondary_windows
+            .borrow()
+            .iter()
+            .map(|w| duplicate_secondary_window(w, &mut mapping, priority_delta)),
+    );
     for (k, val) in inlined_component.root_element.borrow().bindings.iter() {
         match elem_mut.bindings.entry(k.clone()) {
             std::collections::btree_map::Entry::Vacant(entry) => {
@@ -158,6 +190,15 @@ fn inline_element(
         }
     }
 
+    for (k, val) in inlined_component.root_element.borrow().change_callbacks.iter() {
+        elem_mut
+            .change_callbacks
+            .entry(k.clone())
+            .or_default()
+            .borrow_mut()
+            .extend(val.borrow().iter().cloned());
+    }
+
     if let Some(orig) = &inlined_component.root_element.borrow().layout_info_prop {
         if let Some(_new) = &mut elem_mut.layout_info_prop {
             todo!("Merge layout infos");
@@ -212,6 +253,7 @@ fn duplicate_element_with_mapping(
             .iter()
             .map(|t| duplicate_transition(t, mapping, root_component, priority_delta))
             .collect(),
+        change_callbacks: elem.change_callbacks.clone(),
         child_of_layout: elem.child_of_layout,
         layout_info_prop: elem.layout_info_prop.clone(),
         accessibility_props: elem.accessibility_props.clone(),
@@ -221,6 +263,7 @@ fn duplicate_element_with_mapping(
         is_flickable_viewport: elem.is_flickable_viewport,
         has_popup_child: elem.has_popup_child,
         inline_depth: elem.inline_depth + 1,
+        slot: elem.slot.clone(),
     }));
     mapping.insert(element_key(element.clone()), new.clone());
     if let Type::Component(c) = &mut new.borrow_mut().base_type {
@@ -268,9 +311,11 @@ fn duplicate_sub_component(
         embedded_file_resources: component_to_duplicate.embedded_file_resources.clone(),
         root_constraints: component_to_duplicate.root_constraints.clone(),
         child_insertion_point: component_to_duplicate.child_insertion_point.clone(),
+        named_child_insertion_points: component_to_duplicate.named_child_insertion_points.clone(),
         setup_code: component_to_duplicate.setup_code.clone(),
         used_types: Default::default(),
         popup_windows: Default::default(),
+        secondary_windows: Default::default(),
         exported_global_names: component_to_duplicate.exported_global_names.clone(),
         is_root_component: Default::default(),
     };
@@ -290,6 +335,12 @@ fn duplicate_sub_component(
         fixup_reference(&mut p.x, mapping);
         fixup_reference(&mut p.y, mapping);
     }
+    *new_component.secondary_windows.borrow_mut() = component_to_duplicate
+        .secondary_windows
+        .borrow()
+        .iter()
+        .map(|w| duplicate_secondary_window(w, mapping, priority_delta))
+        .collect();
     new_component
         .root_constraints
         .borrow_mut()
@@ -317,6 +368,18 @@ fn duplicate_popup(
     }
 }
 
+fn duplicate_secondary_window(
+    w: &Rc<Component>,
+    mapping: &mut HashMap<ByAddress<ElementRc>, ElementRc>,
+    priority_delta: i32,
+) -> Rc<Component> {
+    let parent = mapping
+        .get(&element_key(w.parent_element.upgrade().expect("must have a parent")))
+        .expect("Parent must be in the mapping")
+        .clone();
+    duplicate_sub_component(w, &parent, mapping, priority_delta)
+}
+
 /// Clone and increase the priority of a binding
 /// and duplicate its animation
 fn duplicate_binding(
@@ -410,6 +473,16 @@ fn fixup_element_references(
                     fxe(&mut e.element);
                 }
             }
+            crate::layout::Layout::FlowLayout(l) => {
+                for e in &mut l.elems {
+                    fxe(&mut e.element);
+                }
+            }
+            crate::layout::Layout::StackLayout(l) => {
+                for e in &mut l.elems {
+                    fxe(&mut e.item.element);
+                }
+            }
         },
         Expression::RepeaterModelReference { element }
         | Expression::RepeaterIndexReference { element } => fx(element),
@@ -437,6 +510,7 @@ fn duplicate_transition(
                 )
             })
             .collect(),
+        animation_groups: t.animation_groups.clone(),
         node: t.node.clone(),
     }
 }
@@ -444,7 +518,9 @@ fn duplicate_transition(
 // Some components need to be inlined to avoid increased complexity in handling them
 // in the code generators and subsequent passes.
 fn component_requires_inlining(component: &Rc<Component>) -> bool {
-    if component.child_insertion_point.borrow().is_some() {
+    if component.child_insertion_point.borrow().is_some()
+        || !component.named_child_insertion_points.borrow().is_empty()
+    {
         return true;
     }
 
@@ -456,6 +532,11 @@ fn component_requires_inlining(component: &Rc<Component>) -> bool {
         return true;
     }
 
+    if root_element.borrow().builtin_type().map_or(false, |b| b.name == "SecondaryWindow") {
+        // The lower_popups pass needs to see the SecondaryWindow element in its parent
+        return true;
+    }
+
     for (prop, binding) in &root_element.borrow().bindings {
         let binding = binding.borrow();
         // The passes that dp the drop shadow or the opacity currently won't allow this property
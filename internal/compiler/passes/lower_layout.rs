@@ -8,7 +8,7 @@
 use crate::diagnostics::BuildDiagnostics;
 use crate::diagnostics::Spanned;
 use crate::expression_tree::*;
-use crate::langtype::Type;
+use crate::langtype::{EnumerationValue, Type};
 use crate::layout::*;
 use crate::object_tree::*;
 use crate::typeloader::TypeLoader;
@@ -63,6 +63,8 @@ fn lower_element_layout(
         "GridLayout" => lower_grid_layout(component, elem, diag),
         "HorizontalLayout" => lower_box_layout(elem, diag, Orientation::Horizontal),
         "VerticalLayout" => lower_box_layout(elem, diag, Orientation::Vertical),
+        "FlowLayout" => lower_flow_layout(elem, diag),
+        "StackLayout" => lower_stack_layout(elem, diag),
         "PathLayout" => lower_path_layout(elem, diag),
         "Dialog" => {
             lower_dialog_layout(elem, style_metrics, diag);
@@ -90,7 +92,7 @@ fn lower_element_layout(
 }
 
 pub fn is_layout_element(element: &ElementRc) -> bool {
-    matches!(&element.borrow().base_type, Type::Builtin(n) if n.name == "GridLayout" || n.name == "HorizontalLayout" || n.name == "VerticalLayout" || n.name == "PathLayout")
+    matches!(&element.borrow().base_type, Type::Builtin(n) if n.name == "GridLayout" || n.name == "HorizontalLayout" || n.name == "VerticalLayout" || n.name == "FlowLayout" || n.name == "StackLayout" || n.name == "PathLayout")
 }
 
 fn lower_grid_layout(
@@ -386,6 +388,183 @@ fn lower_box_layout(
     layout_element.borrow_mut().layout_info_prop = Some((layout_info_prop_h, layout_info_prop_v));
 }
 
+fn lower_flow_layout(layout_element: &ElementRc, diag: &mut BuildDiagnostics) {
+    let mut layout = FlowLayout {
+        elems: Default::default(),
+        geometry: LayoutGeometry::new(layout_element),
+        line_alignment: binding_reference(layout_element, "line-alignment"),
+    };
+
+    let layout_cache_prop_h = create_new_prop(layout_element, "layout-cache-h", Type::LayoutCache);
+    let layout_cache_prop_v = create_new_prop(layout_element, "layout-cache-v", Type::LayoutCache);
+    let layout_info_prop_h = create_new_prop(layout_element, "layoutinfo-h", layout_info_type());
+    let layout_info_prop_v = create_new_prop(layout_element, "layoutinfo-v", layout_info_type());
+
+    let layout_children = std::mem::take(&mut layout_element.borrow_mut().children);
+    for layout_child in &layout_children {
+        if let Some(item) = create_layout_item(layout_child, diag) {
+            let index = layout.elems.len() * 2;
+            let rep_idx = &item.repeater_index;
+            let e = &item.elem;
+            set_prop_from_cache(e, "x", &layout_cache_prop_h, index, rep_idx, diag);
+            if !item.item.constraints.fixed_width {
+                set_prop_from_cache(e, "width", &layout_cache_prop_h, index + 1, rep_idx, diag);
+            }
+            set_prop_from_cache(e, "y", &layout_cache_prop_v, index, rep_idx, diag);
+            if !item.item.constraints.fixed_height {
+                set_prop_from_cache(e, "height", &layout_cache_prop_v, index + 1, rep_idx, diag);
+            }
+            layout.elems.push(item.item);
+        }
+    }
+    layout_element.borrow_mut().children = layout_children;
+    let span = layout_element.borrow().to_source_location();
+    for (prop, expression) in [
+        (
+            &layout_cache_prop_h,
+            Expression::SolveLayout(Layout::FlowLayout(layout.clone()), Orientation::Horizontal),
+        ),
+        (
+            &layout_cache_prop_v,
+            Expression::SolveLayout(Layout::FlowLayout(layout.clone()), Orientation::Vertical),
+        ),
+        (
+            &layout_info_prop_h,
+            Expression::ComputeLayoutInfo(
+                Layout::FlowLayout(layout.clone()),
+                Orientation::Horizontal,
+            ),
+        ),
+        (
+            &layout_info_prop_v,
+            Expression::ComputeLayoutInfo(Layout::FlowLayout(layout), Orientation::Vertical),
+        ),
+    ] {
+        prop.element().borrow_mut().bindings.insert(
+            prop.name().into(),
+            BindingExpression::new_with_span(expression, span.clone()).into(),
+        );
+    }
+    layout_element.borrow_mut().layout_info_prop = Some((layout_info_prop_h, layout_info_prop_v));
+}
+
+fn lower_stack_layout(layout_element: &ElementRc, diag: &mut BuildDiagnostics) {
+    let mut layout =
+        StackLayout { elems: Default::default(), geometry: LayoutGeometry::new(layout_element) };
+
+    let layout_cache_prop_h = create_new_prop(layout_element, "layout-cache-h", Type::LayoutCache);
+    let layout_cache_prop_v = create_new_prop(layout_element, "layout-cache-v", Type::LayoutCache);
+    let layout_info_prop_h = create_new_prop(layout_element, "layoutinfo-h", layout_info_type());
+    let layout_info_prop_v = create_new_prop(layout_element, "layoutinfo-v", layout_info_type());
+
+    let layout_children = std::mem::take(&mut layout_element.borrow_mut().children);
+    for layout_child in &layout_children {
+        if let Some(item) = create_layout_item(layout_child, diag) {
+            let index = layout.elems.len() * 2;
+            let rep_idx = &item.repeater_index;
+            let e = &item.elem;
+            set_prop_from_cache(e, "x", &layout_cache_prop_h, index, rep_idx, diag);
+            if !item.item.constraints.fixed_width {
+                set_prop_from_cache(e, "width", &layout_cache_prop_h, index + 1, rep_idx, diag);
+            }
+            set_prop_from_cache(e, "y", &layout_cache_prop_v, index, rep_idx, diag);
+            if !item.item.constraints.fixed_height {
+                set_prop_from_cache(e, "height", &layout_cache_prop_v, index + 1, rep_idx, diag);
+            }
+            layout.elems.push(StackLayoutElement {
+                horizontal_alignment: stack_alignment(
+                    e,
+                    "stack-horizontal-alignment",
+                    rep_idx.is_some(),
+                    diag,
+                ),
+                vertical_alignment: stack_alignment(
+                    e,
+                    "stack-vertical-alignment",
+                    rep_idx.is_some(),
+                    diag,
+                ),
+                item: item.item,
+            });
+        }
+    }
+    layout_element.borrow_mut().children = layout_children;
+    let span = layout_element.borrow().to_source_location();
+    for (prop, expression) in [
+        (
+            &layout_cache_prop_h,
+            Expression::SolveLayout(Layout::StackLayout(layout.clone()), Orientation::Horizontal),
+        ),
+        (
+            &layout_cache_prop_v,
+            Expression::SolveLayout(Layout::StackLayout(layout.clone()), Orientation::Vertical),
+        ),
+        (
+            &layout_info_prop_h,
+            Expression::ComputeLayoutInfo(
+                Layout::StackLayout(layout.clone()),
+                Orientation::Horizontal,
+            ),
+        ),
+        (
+            &layout_info_prop_v,
+            Expression::ComputeLayoutInfo(Layout::StackLayout(layout), Orientation::Vertical),
+        ),
+    ] {
+        prop.element().borrow_mut().bindings.insert(
+            prop.name().into(),
+            BindingExpression::new_with_span(expression, span.clone()).into(),
+        );
+    }
+    layout_element.borrow_mut().layout_info_prop = Some((layout_info_prop_h, layout_info_prop_v));
+}
+
+/// Take the binding of the `stack-horizontal-alignment` or `stack-vertical-alignment` property
+/// of an element in a StackLayout. Defaults to `stretch`.
+///
+/// When the value is not known at compile time, the binding is moved to a new property of the
+/// element that is read by the layout. This is not possible for repeated elements, as all the
+/// items of a repeater share the same alignment.
+fn stack_alignment(
+    elem: &ElementRc,
+    name: &str,
+    is_repeated: bool,
+    diag: &mut BuildDiagnostics,
+) -> StackAlignment {
+    let enumeration = crate::typeregister::BUILTIN_ENUMS.with(|e| e.StackAlignment.clone());
+    let default = || {
+        StackAlignment::Constant(EnumerationValue {
+            value: enumeration.default_value,
+            enumeration: enumeration.clone(),
+        })
+    };
+    let binding = match elem.borrow_mut().bindings.remove(name).map(RefCell::into_inner) {
+        None => return default(),
+        Some(binding) => binding,
+    };
+    match &binding.expression {
+        Expression::EnumerationValue(val) => {
+            debug_assert_eq!(val.enumeration.name, "StackAlignment");
+            StackAlignment::Constant(val.clone())
+        }
+        _ if is_repeated => {
+            diag.push_error(
+                format!(
+                    "The `{}` property of a repeated element must be known at compile-time",
+                    name
+                ),
+                &binding,
+            );
+            default()
+        }
+        _ => {
+            let nr = create_new_prop(elem, name, Type::Enumeration(enumeration.clone()));
+            elem.borrow_mut().bindings.insert(nr.name().into(), binding.into());
+            StackAlignment::Property(nr)
+        }
+    }
+}
+
 fn lower_dialog_layout(
     dialog_element: &ElementRc,
     style_metrics: &Option<Rc<Component>>,
@@ -792,6 +971,9 @@ fn check_no_layout_properties(item: &ElementRc, diag: &mut BuildDiagnostics) {
         if matches!(prop.as_ref(), "col" | "row" | "colspan" | "rowspan") {
             diag.push_error(format!("{} used outside of a GridLayout", prop), &*expr.borrow());
         }
+        if matches!(prop.as_ref(), "stack-horizontal-alignment" | "stack-vertical-alignment") {
+            diag.push_error(format!("{} used outside of a StackLayout", prop), &*expr.borrow());
+        }
         if matches!(prop.as_ref(), "dialog-button-role") {
             diag.push_error(format!("{} used outside of a Dialog", prop), &*expr.borrow());
         }
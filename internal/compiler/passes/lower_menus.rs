@@ -0,0 +1,276 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Passe lower the MenuBar to the MenuBarImpl of the style, and check that the Menu and MenuItem
+//! elements are only placed within a MenuBar, a Menu, or a SystemTrayIcon.
+//!
+//! The Menu and MenuItem elements stay in the tree so that the runtime can hand them to a native
+//! menu bar. The MenuBarImpl gets a model describing them, to draw the menus when there is no native
+//! menu bar, and a handler that forwards the activation to the MenuItem. The MenuItems with a
+//! shortcut also get a Shortcut element that activates them when there is no native menu bar.
+//!
+//! Must be done before inlining, like the lower_tabwidget pass.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{Expression, NamedReference, Unit};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub async fn lower_menus(
+    component: &Rc<Component>,
+    type_loader: &mut crate::typeloader::TypeLoader,
+    diag: &mut BuildDiagnostics,
+) {
+    // Ignore import errors
+    let mut build_diags_to_ignore = BuildDiagnostics::default();
+    let menubar_impl = type_loader
+        .import_type("std-widgets.slint", "MenuBarImpl", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load MenuBarImpl from std-widgets.slint");
+    let shortcut_type =
+        type_loader.global_type_registry.borrow().lookup_element("Shortcut").unwrap();
+
+    let mut menubars = Vec::new();
+    recurse_elem_including_sub_components_no_borrow(
+        component,
+        &String::new(),
+        &mut |elem, parent_type| {
+            let base_type = elem.borrow().base_type.to_string();
+            let parent_holds_menus =
+                matches!(parent_type.as_str(), "MenuBar" | "Menu" | "SystemTrayIcon");
+            if elem.borrow().repeated.is_some() {
+                if parent_holds_menus {
+                    diag.push_error(
+                        "dynamic menus ('if' or 'for') are currently not supported".into(),
+                        &*elem.borrow(),
+                    );
+                }
+            } else if parent_type == "MenuItem" {
+                diag.push_error("MenuItem cannot have children".into(), &*elem.borrow());
+            } else if parent_type == "MenuBar" && base_type == "MenuItem" {
+                diag.push_error(
+                    "MenuItem must be placed within a Menu in a MenuBar".into(),
+                    &*elem.borrow(),
+                );
+            } else if parent_holds_menus && base_type != "Menu" && base_type != "MenuItem" {
+                diag.push_error(
+                    format!("{} can only contain Menu and MenuItem elements", parent_type),
+                    &*elem.borrow(),
+                );
+            } else if !parent_holds_menus && (base_type == "Menu" || base_type == "MenuItem") {
+                diag.push_error(
+                    format!(
+                        "{} can only be used within a MenuBar, a Menu, or a SystemTrayIcon",
+                        base_type
+                    ),
+                    &*elem.borrow(),
+                );
+            }
+
+            if base_type == "MenuBar" {
+                menubars.push(elem.clone());
+            }
+            base_type
+        },
+    );
+
+    // Processed after the checks, which would otherwise report the added Shortcut elements
+    for menubar in menubars {
+        process_menubar(&menubar, &menubar_impl, &shortcut_type);
+    }
+}
+
+fn process_menubar(elem: &ElementRc, menubar_impl: &Type, shortcut_type: &Type) {
+    elem.borrow_mut().base_type = menubar_impl.clone();
+
+    let menu_ty = match elem.borrow().lookup_property("menus").property_type {
+        Type::Array(ty) => *ty,
+        _ => panic!("MenuBarImpl's menus property must be an array"),
+    };
+    let item_ty = match &menu_ty {
+        Type::Struct { fields, .. } => match fields.get("items") {
+            Some(Type::Array(ty)) => (**ty).clone(),
+            _ => panic!("MenuBarImpl's menus must have an items array"),
+        },
+        _ => panic!("MenuBarImpl's menus property must be an array of struct"),
+    };
+
+    let mut menus = Vec::new();
+    let mut handlers = Vec::new();
+    let mut shortcuts = Vec::new();
+    for menu in elem.borrow().children.iter() {
+        if menu.borrow().repeated.is_some() || menu.borrow().base_type.to_string() != "Menu" {
+            // The error was reported already
+            continue;
+        }
+        let menu_index = menus.len();
+        let mut items = Vec::new();
+        collect_items(menu, 0, &item_ty, &mut items, &mut |item_index, item| {
+            handlers.push(activation_handler(menu_index, item_index, item));
+            shortcuts.extend(shortcut_element(elem, item, shortcut_type));
+        });
+        menus.push(Expression::Struct {
+            ty: menu_ty.clone(),
+            values: HashMap::from([
+                ("title".to_owned(), property_reference(menu, "title")),
+                ("enabled".to_owned(), property_reference(menu, "enabled")),
+                (
+                    "items".to_owned(),
+                    Expression::Array { element_ty: item_ty.clone(), values: items },
+                ),
+            ]),
+        });
+    }
+
+    let mut elem = elem.borrow_mut();
+    elem.children.extend(shortcuts);
+    elem.bindings.insert(
+        "menus".into(),
+        RefCell::new(Expression::Array { element_ty: menu_ty, values: menus }.into()),
+    );
+    elem.bindings
+        .insert("item-activated".into(), RefCell::new(Expression::CodeBlock(handlers).into()));
+}
+
+/// Append the entries of the menu to `items`. The sub menus are flattened: their title is shown
+/// as a header and their items are indented.
+fn collect_items(
+    menu: &ElementRc,
+    indent: usize,
+    item_ty: &Type,
+    items: &mut Vec<Expression>,
+    on_item: &mut dyn FnMut(usize, &ElementRc),
+) {
+    for child in menu.borrow().children.iter() {
+        if child.borrow().repeated.is_some() {
+            continue;
+        }
+        let submenu = match child.borrow().base_type.to_string().as_str() {
+            "Menu" => true,
+            "MenuItem" => false,
+            _ => continue,
+        };
+        let (shortcut, checkable, checked) = if submenu {
+            (
+                Expression::StringLiteral(String::new()),
+                Expression::BoolLiteral(false),
+                Expression::BoolLiteral(false),
+            )
+        } else {
+            (
+                property_reference(child, "shortcut"),
+                property_reference(child, "checkable"),
+                property_reference(child, "checked"),
+            )
+        };
+        if !submenu {
+            on_item(items.len(), child);
+        }
+        items.push(Expression::Struct {
+            ty: item_ty.clone(),
+            values: HashMap::from([
+                ("title".to_owned(), property_reference(child, "title")),
+                ("shortcut".to_owned(), shortcut),
+                ("enabled".to_owned(), property_reference(child, "enabled")),
+                ("checkable".to_owned(), checkable),
+                ("checked".to_owned(), checked),
+                ("submenu".to_owned(), Expression::BoolLiteral(submenu)),
+                (
+                    "indent".to_owned(),
+                    Expression::Cast {
+                        from: Expression::NumberLiteral(indent as _, Unit::None).into(),
+                        to: Type::Int32,
+                    },
+                ),
+            ]),
+        });
+        if submenu {
+            collect_items(child, indent + 1, item_ty, items, on_item);
+        }
+    }
+}
+
+/// `if (menu-index == <menu_index> && item-index == <item_index>) { <activate the item> }`
+fn activation_handler(menu_index: usize, item_index: usize, item: &ElementRc) -> Expression {
+    let index_matches = |parameter: usize, value: usize| Expression::BinaryExpression {
+        lhs: Expression::FunctionParameterReference { index: parameter, ty: Type::Int32 }.into(),
+        rhs: Expression::NumberLiteral(value as _, Unit::None).into(),
+        op: '=',
+    };
+    Expression::Condition {
+        condition: Expression::BinaryExpression {
+            lhs: index_matches(0, menu_index).into(),
+            rhs: index_matches(1, item_index).into(),
+            op: '&',
+        }
+        .into(),
+        true_expr: activate_item(item).into(),
+        false_expr: Expression::CodeBlock(vec![]).into(),
+    }
+}
+
+/// `{ if (item.checkable) { item.checked = !item.checked; } item.activated() }`
+fn activate_item(item: &ElementRc) -> Expression {
+    let toggle_checked = Expression::Condition {
+        condition: property_reference(item, "checkable").into(),
+        true_expr: Expression::SelfAssignment {
+            lhs: property_reference(item, "checked").into(),
+            rhs: Expression::UnaryOp { sub: property_reference(item, "checked").into(), op: '!' }
+                .into(),
+            op: '=',
+        }
+        .into(),
+        false_expr: Expression::CodeBlock(vec![]).into(),
+    };
+    let activated = Expression::FunctionCall {
+        function: Expression::CallbackReference(NamedReference::new(item, "activated")).into(),
+        arguments: vec![],
+        source_location: None,
+    };
+    Expression::CodeBlock(vec![toggle_checked, activated])
+}
+
+/// Returns a Shortcut element activating the item with its shortcut when the menu bar is not
+/// native (the native menu bar handles the shortcuts itself).
+/// Like the keys of a Shortcut, only a shortcut set to a string literal can be handled.
+fn shortcut_element(
+    menubar: &ElementRc,
+    item: &ElementRc,
+    shortcut_type: &Type,
+) -> Option<ElementRc> {
+    let keys = item.borrow().bindings.get("shortcut")?.borrow().clone();
+    if !matches!(&keys.expression, Expression::StringLiteral(keys) if !keys.is_empty()) {
+        return None;
+    }
+    let mut shortcut = Element {
+        id: format!("{}-shortcut", item.borrow().id),
+        base_type: shortcut_type.clone(),
+        enclosing_component: menubar.borrow().enclosing_component.clone(),
+        ..Default::default()
+    };
+    shortcut.bindings.insert("keys".into(), RefCell::new(keys));
+    shortcut.bindings.insert(
+        "enabled".into(),
+        RefCell::new(
+            Expression::BinaryExpression {
+                lhs: property_reference(item, "enabled").into(),
+                rhs: Expression::UnaryOp {
+                    sub: property_reference(menubar, "native").into(),
+                    op: '!',
+                }
+                .into(),
+                op: '&',
+            }
+            .into(),
+        ),
+    );
+    shortcut.bindings.insert("activated".into(), RefCell::new(activate_item(item).into()));
+    Some(Rc::new(RefCell::new(shortcut)))
+}
+
+fn property_reference(elem: &ElementRc, name: &str) -> Expression {
+    Expression::PropertyReference(NamedReference::new(elem, name))
+}
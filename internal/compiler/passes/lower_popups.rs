@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-//! Passe that transform the PopupWindow element into a component
+//! Passe that transform the PopupWindow and SecondaryWindow elements into a component
 
 use crate::diagnostics::BuildDiagnostics;
 use crate::expression_tree::{Expression, NamedReference};
@@ -22,9 +22,11 @@ pub fn lower_popups(
         component,
         &None,
         &mut |elem, parent_element: &Option<ElementRc>| {
-            let is_popup = elem.borrow().base_type.to_string() == "PopupWindow";
-            if is_popup {
+            let base_type = elem.borrow().base_type.to_string();
+            if base_type == "PopupWindow" {
                 lower_popup_window(elem, parent_element.as_ref(), &window_type, diag);
+            } else if base_type == "SecondaryWindow" {
+                lower_secondary_window(elem, parent_element.as_ref(), &window_type, diag);
             }
             Some(elem.clone())
         },
@@ -109,6 +111,80 @@ fn lower_popup_window(
     });
 }
 
+fn lower_secondary_window(
+    window_element: &ElementRc,
+    parent_element: Option<&ElementRc>,
+    window_type: &Type,
+    diag: &mut BuildDiagnostics,
+) {
+    let parent_element = match parent_element {
+        None => {
+            diag.push_error(
+                "SecondaryWindow cannot be the top level".into(),
+                &*window_element.borrow(),
+            );
+            return;
+        }
+        Some(parent_element) => parent_element,
+    };
+
+    let parent_component = window_element.borrow().enclosing_component.upgrade().unwrap();
+    if Rc::ptr_eq(&parent_component.root_element, window_element) {
+        diag.push_error(
+            "SecondaryWindow cannot be directly repeated or conditional".into(),
+            &*window_element.borrow(),
+        );
+        return;
+    }
+
+    for coord in ["x", "y"] {
+        if let Some(binding) = window_element.borrow().bindings.get(coord) {
+            diag.push_error(
+                format!("The {} property of a SecondaryWindow cannot be set", coord),
+                &*binding.borrow(),
+            );
+        }
+    }
+
+    // Remove the window_element from its parent
+    let old_size = parent_element.borrow().children.len();
+    parent_element.borrow_mut().children.retain(|child| !Rc::ptr_eq(child, window_element));
+    debug_assert_eq!(
+        parent_element.borrow().children.len() + 1,
+        old_size,
+        "Exactly one child must be removed (the window itself)"
+    );
+    parent_element.borrow_mut().has_popup_child = true;
+
+    window_element.borrow_mut().base_type = window_type.clone();
+
+    let window_comp = Rc::new(Component {
+        root_element: window_element.clone(),
+        parent_element: Rc::downgrade(parent_element),
+        ..Component::default()
+    });
+
+    let weak = Rc::downgrade(&window_comp);
+    recurse_elem(&window_comp.root_element, &(), &mut |e, _| {
+        e.borrow_mut().enclosing_component = weak.clone()
+    });
+
+    // Same restriction as for the PopupWindow: the elements inside the window are only reachable
+    // from the inside, but the window can access the properties of the enclosing component.
+    visit_all_named_references(&parent_component, &mut |nr| {
+        if std::rc::Weak::ptr_eq(&nr.element().borrow().enclosing_component, &weak) {
+            diag.push_error(
+                "Cannot access the inside of a SecondaryWindow from enclosing component".into(),
+                &*window_element.borrow(),
+            );
+            // just set it to whatever is a valid NamedReference, otherwise we'll panic later
+            *nr = NamedReference::new(parent_element, "width");
+        }
+    });
+
+    parent_component.secondary_windows.borrow_mut().push(window_comp);
+}
+
 fn create_coordinate(
     popup_comp: &Rc<Component>,
     parent_element: &ElementRc,
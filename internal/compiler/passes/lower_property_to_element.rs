@@ -26,14 +26,38 @@ pub(crate) fn lower_property_to_element(
     type_register: &TypeRegister,
     diag: &mut BuildDiagnostics,
 ) {
-    if let Some(b) = component.root_element.borrow().bindings.get(property_name) {
-        diag.push_warning(
-            format!(
-                "The {} property cannot be used on the root element, it will not be applied",
-                property_name
-            ),
-            &*b.borrow(),
-        );
+    lower_properties_to_element(
+        component,
+        &[property_name],
+        extra_properties,
+        default_value_for_extra_properties,
+        element_name,
+        type_register,
+        diag,
+    )
+}
+
+/// Same as [`lower_property_to_element`], but the element is injected if any of the properties
+/// in `property_names` has a binding.
+pub(crate) fn lower_properties_to_element(
+    component: &Rc<Component>,
+    property_names: &[&'static str],
+    extra_properties: impl Iterator<Item = &'static str> + Clone,
+    default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Expression>,
+    element_name: &str,
+    type_register: &TypeRegister,
+    diag: &mut BuildDiagnostics,
+) {
+    for property_name in property_names {
+        if let Some(b) = component.root_element.borrow().bindings.get(*property_name) {
+            diag.push_warning(
+                format!(
+                    "The {} property cannot be used on the root element, it will not be applied",
+                    property_name
+                ),
+                &*b.borrow(),
+            );
+        }
     }
 
     object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
@@ -47,37 +71,42 @@ pub(crate) fn lower_property_to_element(
             std::mem::replace(&mut elem.children, new_children)
         };
 
-        let has_property_binding = |e: &ElementRc| {
-            e.borrow().base_type.lookup_property(property_name).property_type != Type::Invalid
-                && (e.borrow().bindings.contains_key(property_name)
-                    || e.borrow()
-                        .property_analysis
-                        .borrow()
-                        .get(property_name)
-                        .map_or(false, |a| a.is_set))
+        // Returns the first of the properties that is set on the element
+        let property_binding = |e: &ElementRc| {
+            property_names.iter().copied().find(|property_name| {
+                let e = e.borrow();
+                e.base_type.lookup_property(property_name).property_type != Type::Invalid
+                    // A property of the native item with the same name is not the synthetic property
+                    && e.native_class().map_or(true, |n| n.lookup_property(property_name).is_none())
+                    && (e.bindings.contains_key(*property_name)
+                        || e.property_analysis
+                            .borrow()
+                            .get(*property_name)
+                            .map_or(false, |a| a.is_set))
+            })
         };
 
         for mut child in old_children {
             if child.borrow().repeated.is_some() {
                 let root_elem = child.borrow().base_type.as_component().root_element.clone();
-                if has_property_binding(&root_elem) {
+                if let Some(property_name) = property_binding(&root_elem) {
                     object_tree::inject_element_as_repeated_element(
                         &child,
                         create_property_element(
                             &root_elem,
                             property_name,
-                            extra_properties.clone(),
+                            property_names.iter().copied().chain(extra_properties.clone()),
                             default_value_for_extra_properties,
                             element_name,
                             type_register,
                         ),
                     )
                 }
-            } else if has_property_binding(&child) {
+            } else if let Some(property_name) = property_binding(&child) {
                 let new_child = create_property_element(
                     &child,
                     property_name,
-                    extra_properties.clone(),
+                    property_names.iter().copied().chain(extra_properties.clone()),
                     default_value_for_extra_properties,
                     element_name,
                     type_register,
@@ -92,16 +121,17 @@ pub(crate) fn lower_property_to_element(
     });
 }
 
+/// Creates the element named `element_name` with bindings to the `properties` of the child.
+/// The `property_name` that caused the creation of the element is used for its id.
 fn create_property_element(
     child: &ElementRc,
     property_name: &'static str,
-    extra_properties: impl Iterator<Item = &'static str>,
+    properties: impl Iterator<Item = &'static str>,
     default_value_for_extra_properties: Option<&dyn Fn(&ElementRc, &str) -> Expression>,
     element_name: &str,
     type_register: &TypeRegister,
 ) -> ElementRc {
-    let bindings = core::iter::once(property_name)
-        .chain(extra_properties)
+    let bindings = properties
         .filter_map(|property_name| {
             if child.borrow().bindings.contains_key(property_name) {
                 Some((
@@ -0,0 +1,313 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! This pass parses the `keys` property of the Shortcut elements, such as `"Ctrl+Shift+S"`,
+//! into the `key` and `modifiers` properties that the runtime compares with the key events.
+//!
+//! It also reports the Shortcut elements of a window that use the same keys.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{BindingExpression, Expression, Unit};
+use crate::langtype::{EnumerationValue, Type};
+use crate::namedreference::NamedReference;
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub fn lower_shortcuts(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
+    recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
+        if !matches!(&elem.borrow().base_type, Type::Builtin(b) if b.name == "Shortcut") {
+            return;
+        }
+
+        let mut elem = elem.borrow_mut();
+        let keys_binding = match elem.bindings.remove("keys").map(RefCell::into_inner) {
+            Some(keys_binding) => keys_binding,
+            None => {
+                diag.push_error("Shortcut must have a 'keys' property".into(), &*elem);
+                return;
+            }
+        };
+        let keys = match &keys_binding.expression {
+            Expression::StringLiteral(keys) => keys.clone(),
+            _ => {
+                diag.push_error(
+                    "The keys of a Shortcut must be a string literal".into(),
+                    &keys_binding,
+                );
+                return;
+            }
+        };
+        let (key, modifiers) = match parse_keys(&keys) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                diag.push_error(
+                    format!("Error parsing the keys of the Shortcut ({})", e),
+                    &keys_binding,
+                );
+                return;
+            }
+        };
+
+        let modifiers_ty = elem.lookup_property("modifiers").property_type;
+        let modifiers = Expression::Struct {
+            ty: modifiers_ty,
+            values: HashMap::from([
+                ("alt".to_owned(), Expression::BoolLiteral(modifiers.alt)),
+                ("control".to_owned(), Expression::BoolLiteral(modifiers.control)),
+                ("shift".to_owned(), Expression::BoolLiteral(modifiers.shift)),
+                ("meta".to_owned(), Expression::BoolLiteral(modifiers.meta)),
+            ]),
+        };
+        // Keep the location of the keys to report the shortcuts using the same keys
+        let key = BindingExpression {
+            span: keys_binding.span.clone(),
+            ..Expression::StringLiteral(key).into()
+        };
+        elem.bindings.insert("key".into(), RefCell::new(key));
+        elem.bindings.insert("modifiers".into(), RefCell::new(modifiers.into()));
+    })
+}
+
+/// Reports the Shortcut elements that use the same keys in a window, including the Shortcut
+/// elements of the sub-components. Two Shortcut elements in `if` elements whose conditions can't
+/// be true at the same time may use the same keys.
+/// Must be run after [`lower_shortcuts`] was run on all the components.
+pub fn check_duplicated_shortcuts(root_component: &Rc<Component>, diag: &mut BuildDiagnostics) {
+    let mut windows = vec![root_component.clone()];
+    while let Some(window) = windows.pop() {
+        let mut shortcuts = vec![];
+        collect_window_shortcuts(&window, &[], &mut shortcuts, &mut windows);
+        let mut reported = vec![];
+        for (index, shortcut) in shortcuts.iter().enumerate() {
+            let conflicts = shortcuts[..index].iter().any(|other| {
+                other.keys == shortcut.keys
+                    && !conditions_are_exclusive(&other.conditions, &shortcut.conditions)
+            });
+            if conflicts && !reported.iter().any(|e| Rc::ptr_eq(e, &shortcut.element)) {
+                reported.push(shortcut.element.clone());
+                diag.push_error(
+                    "These keys are already used by another Shortcut in the same window".into(),
+                    &*shortcut.element.borrow().bindings["key"].borrow(),
+                );
+            }
+        }
+    }
+}
+
+struct ShortcutInfo {
+    element: ElementRc,
+    keys: (String, Modifiers),
+    /// The conditions of the `if` elements containing the Shortcut
+    conditions: Vec<Condition>,
+}
+
+/// Collects the Shortcut elements of the window in `shortcuts`, and the popup and secondary
+/// windows, which have their own shortcuts, in `windows`.
+fn collect_window_shortcuts(
+    component: &Rc<Component>,
+    conditions: &[Condition],
+    shortcuts: &mut Vec<ShortcutInfo>,
+    windows: &mut Vec<Rc<Component>>,
+) {
+    recurse_elem(&component.root_element, &conditions.to_vec(), &mut |elem, conditions| {
+        let elem_ref = elem.borrow();
+        let mut conditions = conditions.clone();
+        if let Some(repeated) = &elem_ref.repeated {
+            if repeated.is_conditional_element {
+                conditions.extend(Condition::from_expression(&repeated.model));
+            }
+        }
+        match &elem_ref.base_type {
+            Type::Component(c) => collect_window_shortcuts(c, &conditions, shortcuts, windows),
+            Type::Builtin(b) if b.name == "Shortcut" => {
+                if let Some(keys) = lowered_keys(&elem_ref) {
+                    shortcuts.push(ShortcutInfo {
+                        element: elem.clone(),
+                        keys,
+                        conditions: conditions.clone(),
+                    });
+                }
+            }
+            _ => (),
+        }
+        conditions
+    });
+    windows.extend(component.popup_windows.borrow().iter().map(|p| p.component.clone()));
+    windows.extend(component.secondary_windows.borrow().iter().cloned());
+}
+
+/// Returns the key and modifiers set by [`lower_shortcuts`]
+fn lowered_keys(elem: &Element) -> Option<(String, Modifiers)> {
+    let key = match &elem.bindings.get("key")?.borrow().expression {
+        Expression::StringLiteral(key) => key.clone(),
+        _ => return None,
+    };
+    let modifiers = match &elem.bindings.get("modifiers")?.borrow().expression {
+        Expression::Struct { values, .. } => {
+            let modifier =
+                |name: &str| matches!(values.get(name), Some(Expression::BoolLiteral(true)));
+            Modifiers {
+                alt: modifier("alt"),
+                control: modifier("control"),
+                shift: modifier("shift"),
+                meta: modifier("meta"),
+            }
+        }
+        _ => return None,
+    };
+    Some((key, modifiers))
+}
+
+/// A part of the condition of an `if` element
+#[derive(Clone)]
+enum Condition {
+    /// The boolean property has the given value
+    Bool(NamedReference, bool),
+    /// The property is equal to the literal
+    Equals(NamedReference, Literal),
+}
+
+#[derive(Clone, PartialEq)]
+enum Literal {
+    Number(f64, Unit),
+    String(String),
+    Enumeration(EnumerationValue),
+}
+
+impl Condition {
+    /// Returns the conditions that must all be true for the expression to be true. The parts of
+    /// the expression that are not understood are ignored.
+    fn from_expression(expression: &Expression) -> Vec<Self> {
+        match expression {
+            Expression::PropertyReference(nr) => vec![Self::Bool(nr.clone(), true)],
+            Expression::UnaryOp { sub, op: '!' } => match &**sub {
+                Expression::PropertyReference(nr) => vec![Self::Bool(nr.clone(), false)],
+                _ => vec![],
+            },
+            Expression::BinaryExpression { lhs, rhs, op: '&' } => {
+                let mut conditions = Self::from_expression(lhs);
+                conditions.extend(Self::from_expression(rhs));
+                conditions
+            }
+            Expression::BinaryExpression { lhs, rhs, op: '=' } => {
+                match (without_cast(lhs), without_cast(rhs)) {
+                    (Expression::PropertyReference(nr), literal)
+                    | (literal, Expression::PropertyReference(nr)) => {
+                        Literal::from_expression(literal)
+                            .map(|literal| Self::Equals(nr.clone(), literal))
+                            .into_iter()
+                            .collect()
+                    }
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+}
+
+impl Literal {
+    fn from_expression(expression: &Expression) -> Option<Self> {
+        match expression {
+            Expression::NumberLiteral(value, unit) => Some(Self::Number(*value, *unit)),
+            Expression::StringLiteral(value) => Some(Self::String(value.clone())),
+            Expression::EnumerationValue(value) => Some(Self::Enumeration(value.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// The operands of a comparison may be converted to a common type
+fn without_cast(expression: &Expression) -> &Expression {
+    match expression {
+        Expression::Cast { from, .. } => without_cast(from),
+        _ => expression,
+    }
+}
+
+/// Returns true if the two sets of conditions can't be all true at the same time
+fn conditions_are_exclusive(a: &[Condition], b: &[Condition]) -> bool {
+    a.iter().any(|a| {
+        b.iter().any(|b| match (a, b) {
+            (Condition::Bool(nr_a, value_a), Condition::Bool(nr_b, value_b)) => {
+                nr_a == nr_b && value_a != value_b
+            }
+            (Condition::Equals(nr_a, value_a), Condition::Equals(nr_b, value_b)) => {
+                nr_a == nr_b && value_a != value_b
+            }
+            _ => false,
+        })
+    })
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+struct Modifiers {
+    alt: bool,
+    control: bool,
+    shift: bool,
+    meta: bool,
+}
+
+/// Parses keys like `"Ctrl+Shift+S"` or `"F5"`. Returns the text of the key, as received in the
+/// KeyEvent, and the modifiers.
+fn parse_keys(keys: &str) -> Result<(String, Modifiers), String> {
+    let (modifier_names, key) = if keys == "+" {
+        ("", "+")
+    } else if let Some(modifier_names) = keys.strip_suffix("++") {
+        (modifier_names, "+")
+    } else {
+        keys.rsplit_once('+').unwrap_or(("", keys))
+    };
+
+    let mut modifiers = Modifiers::default();
+    if !modifier_names.is_empty() {
+        for name in modifier_names.split('+') {
+            match name.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.control = true,
+                "shift" => modifiers.shift = true,
+                "alt" => modifiers.alt = true,
+                "meta" => modifiers.meta = true,
+                _ => return Err(format!("unknown modifier '{}'", name.trim())),
+            }
+        }
+    }
+
+    let key = key.trim();
+    let mut chars = key.chars();
+    let key = match (chars.next(), chars.next()) {
+        (None, _) => return Err("missing key".into()),
+        (Some(c), None) => {
+            // The runtime ignores Shift for the symbols, as whether it is needed to type them
+            // depends on the keyboard layout
+            if modifiers.shift && !c.is_alphabetic() && !c.is_whitespace() {
+                return Err(format!(
+                    "Shift can't be combined with '{}', use the character typed with Shift instead",
+                    c
+                ));
+            }
+            c.to_lowercase().collect()
+        }
+        _ => match special_key(key) {
+            Some(c) => c.into(),
+            None => return Err(format!("unknown key '{}'", key)),
+        },
+    };
+    Ok((key, modifiers))
+}
+
+macro_rules! special_key_lookup {
+    ($($char:literal # $name:ident # $($qt:ident)|* # $($winit:ident)|* ;)*) => {
+        /// Returns the character of a named key, such as `Escape` or `F1`
+        fn special_key(name: &str) -> Option<char> {
+            match name {
+                "Space" => Some(' '),
+                $(stringify!($name) => Some($char),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+i_slint_common::for_each_special_keys!(special_key_lookup);
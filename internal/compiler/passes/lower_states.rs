@@ -140,6 +140,14 @@ fn lower_transitions_in_element(
             &0
         });
 
+        for group in &transition.animation_groups {
+            lower_animation_group(
+                group,
+                &transition.property_animations,
+                Expression::NumberLiteral(0., Unit::Ms),
+            );
+        }
+
         for (p, span, animation) in transition.property_animations {
             if !affected_properties.contains(&p) {
                 diag.push_error(
@@ -175,6 +183,49 @@ fn lower_transitions_in_element(
     }
 }
 
+/// Make the animations of the group start at `start`, by adding it to their delay.
+/// Returns an expression for the time at which all the animations of the group are finished.
+fn lower_animation_group(
+    group: &TransitionAnimationGroup,
+    animations: &[(NamedReference, SourceLocation, ElementRc)],
+    start: Expression,
+) -> Expression {
+    match group {
+        TransitionAnimationGroup::Animation(index) => {
+            let anim = &animations[*index].2;
+            let binding_or = |name: &str, default: Expression| {
+                anim.borrow().bindings.get(name).map_or(default, |b| b.borrow().expression.clone())
+            };
+            let delay = Expression::BinaryExpression {
+                lhs: Box::new(start),
+                rhs: Box::new(binding_or("delay", Expression::NumberLiteral(0., Unit::Ms))),
+                op: '+',
+            };
+            // Animations with an infinite iteration count are never finished, but we don't
+            // want to delay the following animations forever.
+            let duration = Expression::BinaryExpression {
+                lhs: Box::new(binding_or("duration", Expression::NumberLiteral(0., Unit::Ms))),
+                rhs: Box::new(crate::builtin_macros::min_max_expression(
+                    binding_or("iteration-count", Expression::NumberLiteral(1., Unit::None)),
+                    Expression::NumberLiteral(0., Unit::None),
+                    '>',
+                )),
+                op: '*',
+            };
+            anim.borrow_mut().bindings.insert("delay".into(), RefCell::new(delay.clone().into()));
+            Expression::BinaryExpression { lhs: Box::new(delay), rhs: Box::new(duration), op: '+' }
+        }
+        TransitionAnimationGroup::Sequence(items) => {
+            items.iter().fold(start, |start, item| lower_animation_group(item, animations, start))
+        }
+        TransitionAnimationGroup::Parallel(items) => items
+            .iter()
+            .map(|item| lower_animation_group(item, animations, start.clone()))
+            .reduce(|a, b| crate::builtin_macros::min_max_expression(a, b, '>'))
+            .unwrap_or(start),
+    }
+}
+
 /// Returns a suitable unique name for the "state" property
 fn compute_state_property_name(root_element: &ElementRc) -> String {
     let mut property_name = "state".to_owned();
@@ -208,7 +259,9 @@ fn expression_for_property(element: &ElementRc, name: &str) -> ExpressionForProp
                     // Check that the expresison is valid in the new scope
                     let mut has_invalid = false;
                     e.expression.visit_recursive(&mut |ex| match ex {
-                        Expression::CallbackReference(nr) | Expression::PropertyReference(nr) => {
+                        Expression::CallbackReference(nr)
+                        | Expression::PropertyReference(nr)
+                        | Expression::FunctionReference(nr) => {
                             let e = nr.element();
                             if !Rc::ptr_eq(&e, &element)
                                 && Weak::ptr_eq(
@@ -134,6 +134,7 @@ pub fn initialize(elem: &ElementRc, name: &str) -> Option<Expression> {
         "horizontal-stretch" => layout_constraint_prop(elem, "stretch", Orientation::Horizontal),
         "vertical-stretch" => layout_constraint_prop(elem, "stretch", Orientation::Vertical),
         "opacity" => Expression::NumberLiteral(1., Unit::None),
+        "effect-brightness" | "effect-saturation" => Expression::NumberLiteral(1., Unit::None),
         "visible" => Expression::BoolLiteral(true),
         _ => return None,
     };
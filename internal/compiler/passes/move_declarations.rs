@@ -28,8 +28,10 @@ pub fn move_declarations(component: &Rc<Component>) {
 fn do_move_declarations(component: &Rc<Component>) {
     let mut decl = Declarations::take_from_element(&mut *component.root_element.borrow_mut());
     component.popup_windows.borrow().iter().for_each(|f| do_move_declarations(&f.component));
+    component.secondary_windows.borrow().iter().for_each(do_move_declarations);
 
     let mut new_root_bindings = HashMap::new();
+    let mut new_root_change_callbacks = HashMap::new();
     let mut new_root_property_analysis = HashMap::new();
 
     let move_bindings_and_animations = &mut |elem: &ElementRc| {
@@ -61,6 +63,18 @@ fn do_move_declarations(component: &Rc<Component>) {
         }
         elem.borrow_mut().bindings = new_bindings;
 
+        let change_callbacks = core::mem::take(&mut elem.borrow_mut().change_callbacks);
+        let mut new_change_callbacks = BTreeMap::<String, _>::default();
+        for (k, e) in change_callbacks {
+            let will_be_moved = elem.borrow().property_declarations.contains_key(&k);
+            if will_be_moved {
+                new_root_change_callbacks.insert(map_name(elem, k.as_str()), e);
+            } else {
+                new_change_callbacks.insert(k, e);
+            }
+        }
+        elem.borrow_mut().change_callbacks = new_change_callbacks;
+
         let property_analysis = elem.borrow().property_analysis.take();
         let mut new_property_analysis = HashMap::with_capacity(property_analysis.len());
         for (prop, a) in property_analysis {
@@ -83,6 +97,11 @@ fn do_move_declarations(component: &Rc<Component>) {
         fixup_reference(&mut p.y);
         visit_all_named_references(&p.component, &mut fixup_reference)
     });
+    component
+        .secondary_windows
+        .borrow()
+        .iter()
+        .for_each(|w| visit_all_named_references(w, &mut fixup_reference));
     for pd in decl.property_declarations.values_mut() {
         pd.is_alias.as_mut().map(fixup_reference);
     }
@@ -102,6 +121,7 @@ fn do_move_declarations(component: &Rc<Component>) {
         let mut r = component.root_element.borrow_mut();
         r.property_declarations = decl.property_declarations;
         r.bindings.extend(new_root_bindings.into_iter());
+        r.change_callbacks.extend(new_root_change_callbacks.into_iter());
         r.property_analysis.borrow_mut().extend(new_root_property_analysis.into_iter());
     }
 
@@ -132,6 +152,7 @@ fn simplify_optimized_items_recursive(component: &Rc<Component>) {
         .borrow()
         .iter()
         .for_each(|f| simplify_optimized_items_recursive(&f.component));
+    component.secondary_windows.borrow().iter().for_each(simplify_optimized_items_recursive);
     recurse_elem(&component.root_element, &(), &mut |elem, _| {
         if elem.borrow().repeated.is_some() {
             if let Type::Component(base) = &elem.borrow().base_type {
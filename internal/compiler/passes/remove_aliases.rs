@@ -8,7 +8,7 @@
 use crate::object_tree::*;
 use std::cell::RefCell;
 use std::collections::{btree_map::Entry, HashMap, HashSet};
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 type Mapping = HashMap<NamedReference, NamedReference>;
 
@@ -153,6 +153,31 @@ pub fn remove_aliases(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
             }
         }
 
+        // Move the change callbacks to the property that is kept, if it is in the same component.
+        // Otherwise, the property must be kept as an alias so the change callback can refer to it
+        let has_change_callbacks_in_other_component =
+            if elem.borrow().change_callbacks.contains_key(remove.name()) {
+                let to_elem = to.element();
+                if Weak::ptr_eq(
+                    &elem.borrow().enclosing_component,
+                    &to_elem.borrow().enclosing_component,
+                ) {
+                    let old = elem.borrow_mut().change_callbacks.remove(remove.name()).unwrap();
+                    to_elem
+                        .borrow_mut()
+                        .change_callbacks
+                        .entry(to.name().to_owned())
+                        .or_default()
+                        .borrow_mut()
+                        .extend(old.into_inner());
+                    false
+                } else {
+                    true
+                }
+            } else {
+                false
+            };
+
         // Remove the declaration
         {
             let mut elem = elem.borrow_mut();
@@ -162,7 +187,10 @@ pub fn remove_aliases(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
                 .get(remove.name())
                 .map_or(false, |v| v.is_read_externally || v.is_set_externally);
             if let Some(d) = elem.property_declarations.get_mut(remove.name()) {
-                if d.expose_in_public_api || used_externally {
+                if d.expose_in_public_api
+                    || used_externally
+                    || has_change_callbacks_in_other_component
+                {
                     d.is_alias = Some(to.clone());
                     drop(elem);
                     // one must mark the aliased property as settable from outside
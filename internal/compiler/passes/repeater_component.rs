@@ -39,6 +39,7 @@ fn create_repeater_components(component: &Rc<Component>) {
                 enclosing_component: Default::default(),
                 states: std::mem::take(&mut elem.states),
                 transitions: std::mem::take(&mut elem.transitions),
+                change_callbacks: std::mem::take(&mut elem.change_callbacks),
                 child_of_layout: elem.child_of_layout || is_listview.is_some(),
                 layout_info_prop: elem.layout_info_prop.take(),
                 accessibility_props: std::mem::take(&mut elem.accessibility_props),
@@ -84,6 +85,9 @@ fn create_repeater_components(component: &Rc<Component>) {
     for p in component.popup_windows.borrow().iter() {
         create_repeater_components(&p.component);
     }
+    for w in component.secondary_windows.borrow().iter() {
+        create_repeater_components(w);
+    }
 }
 
 /// Make sure that references to property within the repeated element actually point to the reference
@@ -10,7 +10,7 @@
 
 use crate::diagnostics::{BuildDiagnostics, Spanned};
 use crate::expression_tree::*;
-use crate::langtype::{PropertyLookupResult, Type};
+use crate::langtype::{PropertyLookupResult, PropertyVisibility, Type};
 use crate::lookup::{LookupCtx, LookupObject, LookupResult};
 use crate::object_tree::*;
 use crate::parser::{identifier_text, syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode};
@@ -40,6 +40,7 @@ fn resolve_expression(
             component_scope: &scope.0,
             diag,
             arguments: vec![],
+            local_variables: vec![],
             type_register,
             type_loader: Some(type_loader),
             current_token: None,
@@ -50,6 +51,11 @@ fn resolve_expression(
                 //FIXME: proper callback support (node is a codeblock)
                 Expression::from_callback_connection(node.clone().into(), &mut lookup_ctx)
             }
+            SyntaxKind::Function => Expression::from_function(node.clone().into(), &mut lookup_ctx),
+            SyntaxKind::ChangeCallback => Expression::from_codeblock_node(
+                syntax_nodes::ChangeCallback::from(node.clone()).CodeBlock(),
+                &mut lookup_ctx,
+            ),
             SyntaxKind::Expression => {
                 //FIXME again: this happen for non-binding expression (i.e: model)
                 Expression::from_expression_node(node.clone().into(), &mut lookup_ctx)
@@ -232,6 +238,18 @@ fn from_callback_connection(
         )
     }
 
+    fn from_function(node: syntax_nodes::Function, ctx: &mut LookupCtx) -> Expression {
+        ctx.arguments = node
+            .ArgumentDeclaration()
+            .map(|x| identifier_text(&x.DeclaredIdentifier()).unwrap_or_default())
+            .collect();
+        Self::from_codeblock_node(node.CodeBlock(), ctx).maybe_convert_to(
+            ctx.return_type().clone(),
+            &node,
+            ctx.diag,
+        )
+    }
+
     fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) -> Self {
         node.Expression()
             .map(|n| Self::from_expression_node(n, ctx))
@@ -301,6 +319,16 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
             .or_else(|| node.Array().map(|n| Self::from_array_node(n, ctx)))
             .or_else(|| node.CodeBlock().map(|n| Self::from_codeblock_node(n, ctx)))
             .or_else(|| node.StringTemplate().map(|n| Self::from_string_template_node(n, ctx)))
+            .or_else(|| {
+                node.Lambda().map(|n| {
+                    ctx.diag.push_error(
+                        "Lambda expressions can only be used as the argument of 'filter' or 'map'"
+                            .into(),
+                        &n,
+                    );
+                    Self::Invalid
+                })
+            })
             .unwrap_or(Self::Invalid)
     }
 
@@ -571,6 +599,14 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                 }
                 r
             }
+            LookupResult::Expression {
+                expression: r @ Expression::FunctionReference(..), ..
+            } => {
+                if let Some(x) = it.next() {
+                    ctx.diag.push_error("Cannot access fields of a function".into(), &x)
+                }
+                r
+            }
             LookupResult::Enumeration(enumeration) => {
                 if let Some(next_identifier) = it.next() {
                     match enumeration
@@ -640,6 +676,20 @@ fn from_function_call_node(
                 .unwrap_or_else(|| Self::from_expression_node(n, ctx))
         });
 
+        if let Expression::MemberFunction { base, member, .. } = &function {
+            if let Expression::BuiltinMacroReference(
+                mac @ (BuiltinMacroFunction::ArrayFilter | BuiltinMacroFunction::ArrayMap),
+                _,
+            ) = &**member
+            {
+                let kind = match mac {
+                    BuiltinMacroFunction::ArrayFilter => ArrayIterationKind::Filter,
+                    _ => ArrayIterationKind::Map,
+                };
+                return Self::from_array_lambda_call(kind, (**base).clone(), sub_expr, &node, ctx);
+            }
+        }
+
         let sub_expr = sub_expr.map(|n| {
             (Self::from_expression_node(n.clone(), ctx), Some(NodeOrToken::from((*n).clone())))
         });
@@ -651,12 +701,42 @@ fn from_function_call_node(
             }
             Expression::MemberFunction { base, base_node, member } => {
                 arguments.push((*base, base_node));
+                if let Expression::BuiltinMacroReference(mac, n) = *member {
+                    arguments.extend(sub_expr);
+                    return crate::builtin_macros::lower_macro(
+                        mac,
+                        n,
+                        arguments.into_iter(),
+                        ctx.diag,
+                    );
+                }
                 member
             }
             _ => Box::new(function),
         };
         arguments.extend(sub_expr);
 
+        if matches!(ctx.property_type, Type::Function { .. }) {
+            let has_side_effects = match &*function {
+                Expression::CallbackReference(_) => true,
+                Expression::BuiltinFunctionReference(f, _) => f.has_side_effects(),
+                _ => false,
+            };
+            if has_side_effects {
+                ctx.diag.push_error(
+                    format!(
+                        "Cannot call '{}' from a function: functions must be pure",
+                        node.Expression().next().map_or(String::new(), |n| n
+                            .text()
+                            .to_string()
+                            .trim()
+                            .into())
+                    ),
+                    &node,
+                );
+            }
+        }
+
         let arguments = match function.ty() {
             Type::Function { args, .. } | Type::Callback { args, .. } => {
                 if arguments.len() != args.len() {
@@ -690,6 +770,69 @@ fn from_function_call_node(
         }
     }
 
+    /// `array.filter(item => condition)` or `array.map(item => expression)`
+    fn from_array_lambda_call(
+        kind: ArrayIterationKind,
+        array: Expression,
+        mut args: impl Iterator<Item = syntax_nodes::Expression>,
+        node: &syntax_nodes::FunctionCallExpression,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let lambda = match (args.next().and_then(|a| a.Lambda()), args.next()) {
+            (Some(lambda), None) => lambda,
+            _ => {
+                ctx.diag.push_error(
+                    "Expected one argument of the form 'item => expression'".into(),
+                    node,
+                );
+                return Expression::Invalid;
+            }
+        };
+        let item_ty = match array.ty() {
+            Type::Array(ty) => *ty,
+            _ => return Expression::Invalid,
+        };
+        let (item, index) = crate::builtin_macros::array_iteration_variables();
+        let name = identifier_text(&lambda.DeclaredIdentifier()).unwrap_or_default();
+        ctx.local_variables.push((
+            name,
+            Expression::ReadLocalVariable { name: item.clone(), ty: item_ty.clone() },
+        ));
+        let expression = Self::from_expression_node(lambda.Expression(), ctx);
+        ctx.local_variables.pop();
+        let expression = match kind {
+            ArrayIterationKind::Filter => {
+                expression.maybe_convert_to(Type::Bool, &lambda.Expression(), ctx.diag)
+            }
+            _ => {
+                if expression.ty() == Type::Void {
+                    ctx.diag.push_error(
+                        "The expression of 'map' must have a value".into(),
+                        &lambda.Expression(),
+                    );
+                    return Expression::Invalid;
+                }
+                expression
+            }
+        };
+        if has_side_effects(&expression) {
+            ctx.diag.push_error(
+                format!(
+                    "The expression of '{}' must be pure: it cannot assign to properties or call callbacks",
+                    if matches!(kind, ArrayIterationKind::Filter) { "filter" } else { "map" }
+                ),
+                &lambda.Expression(),
+            );
+        }
+        Expression::ArrayIteration {
+            kind,
+            array: Box::new(array),
+            item,
+            index,
+            expression: Box::new(expression),
+        }
+    }
+
     fn from_member_access_node(
         node: syntax_nodes::MemberAccess,
         ctx: &mut LookupCtx,
@@ -720,6 +863,13 @@ fn from_self_assignment_node(
                 &node,
             );
         }
+        check_assignment_access(&lhs, &node, ctx);
+        if matches!(ctx.property_type, Type::Function { .. }) {
+            ctx.diag.push_error(
+                "Cannot modify a property from a function: functions must be pure".into(),
+                &node,
+            );
+        }
         let ty = lhs.ty();
         let expected_ty = match op {
             '=' => ty,
@@ -1020,6 +1170,35 @@ fn common_target_type_for_type_list(types: impl Iterator<Item = Type>) -> Type {
     }
 }
 
+/// Report an error if the property assigned by `lhs` cannot be modified from the current component:
+/// `in` properties can only be assigned from outside, and `out` properties only from inside.
+fn check_assignment_access(
+    lhs: &Expression,
+    node: &syntax_nodes::SelfAssignment,
+    ctx: &mut LookupCtx,
+) {
+    let nr = match lhs {
+        Expression::PropertyReference(nr) => nr,
+        Expression::StructFieldAccess { base, .. } => {
+            return check_assignment_access(base, node, ctx)
+        }
+        _ => return,
+    };
+    let elem = nr.element();
+    let lookup = elem.borrow().lookup_property(nr.name());
+    let is_local = lookup.is_local_to_component && ctx.is_local_element(&elem);
+    match lookup.property_visibility {
+        PropertyVisibility::Input if is_local => ctx.diag.push_error(
+            format!("Cannot assign to input property '{}' from within its component", nr.name()),
+            node,
+        ),
+        PropertyVisibility::Output if !is_local => {
+            ctx.diag.push_error(format!("Cannot assign to output property '{}'", nr.name()), node)
+        }
+        _ => (),
+    }
+}
+
 fn continue_lookup_within_element(
     elem: &ElementRc,
     it: &mut impl Iterator<Item = crate::parser::SyntaxToken>,
@@ -1036,8 +1215,21 @@ fn continue_lookup_within_element(
     };
     let prop_name = crate::parser::normalize_identifier(second.text());
 
-    let PropertyLookupResult { resolved_name, property_type } =
-        elem.borrow().lookup_property(&prop_name);
+    let PropertyLookupResult {
+        resolved_name,
+        property_type,
+        property_visibility,
+        is_local_to_component,
+    } = elem.borrow().lookup_property(&prop_name);
+    if property_visibility == PropertyVisibility::Private
+        && !(is_local_to_component && ctx.is_local_element(elem))
+    {
+        ctx.diag.push_error(
+            format!("The property '{}' is private. Annotate it with 'in', 'out' or 'in-out' to make it accessible from other components", second.text()),
+            &second,
+        );
+        return Expression::Invalid;
+    }
     if property_type.is_property_type() {
         if resolved_name != prop_name {
             ctx.diag.push_property_deprecation_warning(&prop_name, &resolved_name, &second);
@@ -1051,6 +1243,13 @@ fn continue_lookup_within_element(
         Expression::CallbackReference(NamedReference::new(elem, &resolved_name))
     } else if matches!(property_type, Type::Function { .. }) {
         let member = elem.borrow().base_type.lookup_member_function(&resolved_name);
+        if matches!(member, Expression::Invalid) {
+            // Not a builtin member function: this is a function declared in .slint
+            if let Some(x) = it.next() {
+                ctx.diag.push_error("Cannot access fields of a function".into(), &x)
+            }
+            return Expression::FunctionReference(NamedReference::new(elem, &resolved_name));
+        }
         Expression::MemberFunction {
             base: Box::new(Expression::ElementReference(Rc::downgrade(elem))),
             base_node: Some(NodeOrToken::Node(node.into())),
@@ -1163,3 +1362,15 @@ pub fn resolve_two_way_binding(
         }
     }
 }
+
+/// Returns true if evaluating `expr` assigns to a property, calls a callback, or calls a builtin
+/// function that changes the state of the program
+fn has_side_effects(expr: &Expression) -> bool {
+    let mut result = match expr {
+        Expression::CallbackReference(_) | Expression::SelfAssignment { .. } => true,
+        Expression::BuiltinFunctionReference(function, _) => function.has_side_effects(),
+        _ => false,
+    };
+    expr.visit(|sub| result = result || has_side_effects(sub));
+    result
+}
@@ -0,0 +1,70 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! `array.map(item => expression)` only evaluates the expression when a row is read, which may be
+//! outside of the binding that created the mapped array (a repeater reads its rows untracked).
+//! This pass makes that binding depend on the properties read by the expression, by reading the
+//! same properties before the [`Expression::ArrayIteration`]. The expression itself is never
+//! evaluated by the binding. Reads that can't be known at compile time, such as the rows of a model
+//! or the properties read by functions of sub-components, are not tracked.
+
+use crate::expression_tree::{ArrayIterationKind, Expression, NamedReference};
+use crate::langtype::Type;
+use crate::object_tree::{visit_all_expressions, Component};
+
+pub fn track_map_dependencies(component: &Component) {
+    visit_all_expressions(component, |expr, _| process_expression(expr))
+}
+
+fn process_expression(expr: &mut Expression) {
+    expr.visit_mut(process_expression);
+    let expression = match expr {
+        Expression::ArrayIteration { kind: ArrayIterationKind::Map, expression, .. } => expression,
+        _ => return,
+    };
+
+    let mut statements = vec![];
+    collect_reads(expression, &mut statements, &mut vec![]);
+    if !statements.is_empty() {
+        statements.push(std::mem::take(expr));
+        *expr = Expression::CodeBlock(statements);
+    }
+}
+
+/// Push in `reads` an expression reading each property read by `expr` that is known at compile time.
+fn collect_reads(
+    expr: &Expression,
+    reads: &mut Vec<Expression>,
+    visited_functions: &mut Vec<NamedReference>,
+) {
+    if matches!(expr.ty(), Type::Array(_) | Type::Model) {
+        // The rows of a model are not tracked by reading the property holding it
+        return;
+    }
+    match expr {
+        Expression::PropertyReference(nr) => {
+            if !reads.iter().any(|r| matches!(r, Expression::PropertyReference(r) if r == nr)) {
+                reads.push(expr.clone());
+            }
+        }
+        Expression::RepeaterIndexReference { .. } | Expression::RepeaterModelReference { .. } => {
+            reads.push(expr.clone())
+        }
+        Expression::FunctionReference(nr) => {
+            if visited_functions.contains(nr) {
+                return;
+            }
+            visited_functions.push(nr.clone());
+            let element = nr.element();
+            let element = element.borrow();
+            // Only functions declared in this element can be followed, the body of functions of
+            // sub-components is relative to the sub-component
+            if let Some(body) = element.bindings.get(nr.name()).and_then(|b| b.try_borrow().ok()) {
+                collect_reads(&body.expression, reads, visited_functions);
+            }
+            return;
+        }
+        _ => (),
+    }
+    expr.visit(|sub| collect_reads(sub, reads, visited_functions));
+}
@@ -8,7 +8,9 @@
 use std::rc::Rc;
 
 use crate::expression_tree::{BuiltinFunction, Expression};
-use crate::langtype::{BuiltinPropertyInfo, Enumeration, PropertyLookupResult, Type};
+use crate::langtype::{
+    BuiltinPropertyInfo, Enumeration, PropertyLookupResult, PropertyVisibility, Type,
+};
 use crate::object_tree::Component;
 
 pub(crate) const RESERVED_GEOMETRY_PROPERTIES: &[(&str, Type)] = &[
@@ -51,6 +53,7 @@ fn new() -> Self {
                         name: stringify!($Name).replace('_', "-"),
                         values: vec![$(crate::generator::to_kebab_case(stringify!($Value).trim_start_matches("r#"))),*],
                         default_value: 0,
+                        node: None,
                     })),*
                 }
             }
@@ -86,6 +89,14 @@ fn fill_register(&self, register: &mut TypeRegister) {
     ("drop-shadow-color", Type::Color),
 ];
 
+pub(crate) const RESERVED_EFFECT_PROPERTIES: &[(&str, Type)] = &[
+    ("effect-blur", Type::LogicalLength),
+    ("effect-backdrop-blur", Type::LogicalLength),
+    ("effect-brightness", Type::Float32),
+    ("effect-saturation", Type::Float32),
+    ("effect-grayscale", Type::Float32),
+];
+
 pub(crate) const RESERVED_ROTATION_PROPERTIES: &[(&str, Type)] = &[
     ("rotation-angle", Type::Angle),
     ("rotation-origin-x", Type::LogicalLength),
@@ -112,6 +123,7 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
         .chain(RESERVED_LAYOUT_PROPERTIES.iter())
         .chain(RESERVED_OTHER_PROPERTIES.iter())
         .chain(RESERVED_DROP_SHADOW_PROPERTIES.iter())
+        .chain(RESERVED_EFFECT_PROPERTIES.iter())
         .chain(RESERVED_ROTATION_PROPERTIES.iter())
         .chain(RESERVED_ACCESSIBILITY_PROPERTIES.iter())
         .map(|(k, v)| (*k, v.clone()))
@@ -122,6 +134,14 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
                 "dialog-button-role",
                 Type::Enumeration(BUILTIN_ENUMS.with(|e| e.DialogButtonRole.clone())),
             ),
+            (
+                "stack-horizontal-alignment",
+                Type::Enumeration(BUILTIN_ENUMS.with(|e| e.StackAlignment.clone())),
+            ),
+            (
+                "stack-vertical-alignment",
+                Type::Enumeration(BUILTIN_ENUMS.with(|e| e.StackAlignment.clone())),
+            ),
             (
                 "accessible-role",
                 Type::Enumeration(BUILTIN_ENUMS.with(|e| e.AccessibleRole.clone())),
@@ -133,7 +153,12 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
 pub fn reserved_property(name: &str) -> PropertyLookupResult {
     for (p, t) in reserved_properties() {
         if p == name {
-            return PropertyLookupResult { property_type: t, resolved_name: name.into() };
+            return PropertyLookupResult {
+                property_type: t,
+                resolved_name: name.into(),
+                property_visibility: PropertyVisibility::InOut,
+                is_local_to_component: false,
+            };
         }
     }
 
@@ -146,13 +171,20 @@ pub fn reserved_property(name: &str) -> PropertyLookupResult {
                         return PropertyLookupResult {
                             property_type: Type::LogicalLength,
                             resolved_name: format!("{}-{}", pre, suf).into(),
+                            property_visibility: PropertyVisibility::InOut,
+                            is_local_to_component: false,
                         };
                     }
                 }
             }
         }
     }
-    PropertyLookupResult { resolved_name: name.into(), property_type: Type::Invalid }
+    PropertyLookupResult {
+        resolved_name: name.into(),
+        property_type: Type::Invalid,
+        property_visibility: PropertyVisibility::InOut,
+        is_local_to_component: false,
+    }
 }
 
 /// These member functions are injected in every time
@@ -243,9 +275,51 @@ pub fn builtin() -> Rc<RefCell<Self>> {
             _ => unreachable!(),
         };
 
+        register.add_builtin_member_functions(
+            "SecondaryWindow",
+            [
+                ("show", BuiltinFunction::ShowSecondaryWindow),
+                ("hide", BuiltinFunction::HideSecondaryWindow),
+            ],
+        );
+
+        register.add_builtin_member_functions(
+            "TextInput",
+            [("undo", BuiltinFunction::TextInputUndo), ("redo", BuiltinFunction::TextInputRedo)],
+        );
+
+        register.add_builtin_member_functions(
+            "FileDialog",
+            [("show", BuiltinFunction::ShowFileDialog)],
+        );
+        register.add_builtin_member_functions(
+            "MessageDialog",
+            [("show", BuiltinFunction::ShowMessageDialog)],
+        );
+
         Rc::new(RefCell::new(register))
     }
 
+    /// Adds the builtin functions as member functions (and the corresponding properties)
+    /// of the builtin element `element`
+    fn add_builtin_member_functions(
+        &mut self,
+        element: &str,
+        functions: impl IntoIterator<Item = (&'static str, BuiltinFunction)>,
+    ) {
+        match self.types.get_mut(element).unwrap() {
+            Type::Builtin(ref mut b) => {
+                let b = Rc::get_mut(b).unwrap();
+                for (name, function) in functions {
+                    b.properties.insert(name.into(), BuiltinPropertyInfo::new(function.ty()));
+                    b.member_functions
+                        .insert(name.into(), Expression::BuiltinFunctionReference(function, None));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
     pub fn new(parent: &Rc<RefCell<TypeRegister>>) -> Self {
         Self {
             parent_registry: Some(parent.clone()),
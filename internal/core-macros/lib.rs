@@ -130,7 +130,8 @@ impl #item_name {
 }
 
 fn normalize_identifier(name: &syn::Ident) -> String {
-    name.to_string().replace('_', "-")
+    let name = name.to_string();
+    name.strip_prefix("r#").unwrap_or(&name).replace('_', "-")
 }
 
 // Try to match `Property<Foo>` on the syn tree and return Foo if found
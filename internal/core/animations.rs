@@ -6,6 +6,8 @@
 
 use alloc::boxed::Box;
 use core::cell::Cell;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 mod cubic_bezier {
     //! This is a copy from lyon_algorithms::geom::cubic_bezier implementation
@@ -144,6 +146,23 @@ pub enum EasingCurve {
     Linear,
     /// A Cubic bezier curve, with its 4 parameter
     CubicBezier([f32; 4]),
+    /// A damped spring, with its stiffness, damping and mass.
+    /// The duration of an animation using this curve is the time the spring takes to settle.
+    Spring([f32; 3]),
+    /// Oscillates with a growing amplitude before reaching the target
+    EaseInElastic,
+    /// Overshoots the target and oscillates around it with a decaying amplitude
+    EaseOutElastic,
+    /// Combination of [`Self::EaseInElastic`] and [`Self::EaseOutElastic`]
+    EaseInOutElastic,
+    /// Bounces with a growing amplitude before reaching the target
+    EaseInBounce,
+    /// Bounces on the target with a decaying amplitude, like a falling ball
+    EaseOutBounce,
+    /// Combination of [`Self::EaseInBounce`] and [`Self::EaseOutBounce`]
+    EaseInOutBounce,
+    /// Jumps from the start to the end in the given number of equal steps
+    Steps(u32),
     //Custom(Box<dyn Fn(f32) -> f32>),
 }
 
@@ -153,6 +172,109 @@ fn default() -> Self {
     }
 }
 
+impl EasingCurve {
+    /// For a spring, returns the time in milliseconds it takes for the spring to settle.
+    /// This is the duration of an animation using this curve.
+    /// Returns None for the other curves, whose duration is given by the animation.
+    pub fn settling_duration(&self) -> Option<i32> {
+        match self {
+            Self::Spring(params) => {
+                Some(spring::Spring::new(params).map_or(0, |s| (s.settling_time() * 1000.) as i32))
+            }
+            _ => None,
+        }
+    }
+}
+
+mod spring {
+    //! A damped harmonic oscillator going from 0 to 1, starting with no velocity
+    #[cfg(not(feature = "std"))]
+    use num_traits::Float;
+
+    /// The spring has settled when the distance to the target stays below this threshold
+    const THRESHOLD: f32 = 0.001;
+
+    pub struct Spring {
+        /// The undamped angular frequency
+        omega0: f32,
+        /// The damping ratio
+        zeta: f32,
+    }
+
+    impl Spring {
+        /// Returns None if the parameters do not describe a spring that settles
+        pub fn new([stiffness, damping, mass]: &[f32; 3]) -> Option<Self> {
+            if !(*stiffness > 0. && *damping > 0. && *mass > 0.) {
+                return None;
+            }
+            let omega0 = (stiffness / mass).sqrt();
+            let zeta = damping / (2. * (stiffness * mass).sqrt());
+            Some(Self { omega0, zeta })
+        }
+
+        /// The signed distance to the target after `t` seconds (1 at t = 0)
+        pub fn displacement(&self, t: f32) -> f32 {
+            let Self { omega0, zeta } = *self;
+            if zeta < 1. {
+                let omega_d = omega0 * (1. - zeta * zeta).sqrt();
+                (-zeta * omega0 * t).exp()
+                    * ((omega_d * t).cos() + zeta * omega0 / omega_d * (omega_d * t).sin())
+            } else if zeta == 1. {
+                (-omega0 * t).exp() * (1. + omega0 * t)
+            } else {
+                let s = (zeta * zeta - 1.).sqrt();
+                let r1 = -omega0 * (zeta - s);
+                let r2 = -omega0 * (zeta + s);
+                (r2 * (r1 * t).exp() - r1 * (r2 * t).exp()) / (r2 - r1)
+            }
+        }
+
+        /// The time in seconds after which the displacement stays below the threshold
+        pub fn settling_time(&self) -> f32 {
+            let Self { omega0, zeta } = *self;
+            if zeta < 1. {
+                // The displacement is bounded by an exponentially decaying envelope
+                let omega_d = omega0 * (1. - zeta * zeta).sqrt();
+                let amplitude = (1. + (zeta * omega0 / omega_d).powi(2)).sqrt();
+                (amplitude / THRESHOLD).ln() / (zeta * omega0)
+            } else {
+                // The displacement decreases monotonically: find the time with a binary search
+                let mut max = 1. / omega0;
+                while self.displacement(max) > THRESHOLD {
+                    max *= 2.;
+                }
+                let mut min = 0.;
+                for _ in 0..20 {
+                    let t = (min + max) / 2.;
+                    if self.displacement(t) > THRESHOLD {
+                        min = t;
+                    } else {
+                        max = t;
+                    }
+                }
+                max
+            }
+        }
+    }
+}
+
+fn bounce_out(value: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if value < 1. / D {
+        N * value * value
+    } else if value < 2. / D {
+        let value = value - 1.5 / D;
+        N * value * value + 0.75
+    } else if value < 2.5 / D {
+        let value = value - 2.25 / D;
+        N * value * value + 0.9375
+    } else {
+        let value = value - 2.625 / D;
+        N * value * value + 0.984375
+    }
+}
+
 /// Represent an instant, in milliseconds since the AnimationDriver's initial_instant
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Default, PartialEq, Ord, PartialOrd, Eq)]
@@ -298,9 +420,91 @@ pub fn easing_curve(curve: &EasingCurve, value: f32) -> f32 {
             };
             curve.y(curve.solve_t_for_x(value, 0.0..1.0, 0.01))
         }
+        EasingCurve::Spring(params) => match spring::Spring::new(params) {
+            Some(spring) if value < 1. => {
+                1. - spring.displacement(value.max(0.) * spring.settling_time())
+            }
+            _ => value.clamp(0., 1.).ceil(),
+        },
+        EasingCurve::EaseInElastic
+        | EasingCurve::EaseOutElastic
+        | EasingCurve::EaseInOutElastic
+            if value <= 0. || value >= 1. =>
+        {
+            value.clamp(0., 1.)
+        }
+        EasingCurve::EaseInElastic => {
+            const C4: f32 = 2. * core::f32::consts::PI / 3.;
+            -(2f32.powf(10. * value - 10.)) * ((value * 10. - 10.75) * C4).sin()
+        }
+        EasingCurve::EaseOutElastic => {
+            const C4: f32 = 2. * core::f32::consts::PI / 3.;
+            2f32.powf(-10. * value) * ((value * 10. - 0.75) * C4).sin() + 1.
+        }
+        EasingCurve::EaseInOutElastic => {
+            const C5: f32 = 2. * core::f32::consts::PI / 4.5;
+            if value < 0.5 {
+                -(2f32.powf(20. * value - 10.) * ((20. * value - 11.125) * C5).sin()) / 2.
+            } else {
+                2f32.powf(-20. * value + 10.) * ((20. * value - 11.125) * C5).sin() / 2. + 1.
+            }
+        }
+        EasingCurve::EaseInBounce => 1. - bounce_out(1. - value),
+        EasingCurve::EaseOutBounce => bounce_out(value),
+        EasingCurve::EaseInOutBounce => {
+            if value < 0.5 {
+                (1. - bounce_out(1. - 2. * value)) / 2.
+            } else {
+                (1. + bounce_out(2. * value - 1.)) / 2.
+            }
+        }
+        EasingCurve::Steps(steps) => {
+            if *steps == 0 || value >= 1. {
+                value.clamp(0., 1.)
+            } else {
+                let steps = *steps as f32;
+                (value.max(0.) * steps).floor() / steps
+            }
+        }
     }
 }
 
+#[test]
+fn easing_curves_reach_their_end() {
+    let curves = [
+        EasingCurve::Spring([100., 10., 1.]),
+        EasingCurve::Spring([100., 30., 1.]),
+        EasingCurve::EaseInElastic,
+        EasingCurve::EaseOutElastic,
+        EasingCurve::EaseInOutElastic,
+        EasingCurve::EaseInBounce,
+        EasingCurve::EaseOutBounce,
+        EasingCurve::EaseInOutBounce,
+        EasingCurve::Steps(4),
+    ];
+    for curve in curves {
+        assert!(easing_curve(&curve, 0.).abs() < 0.001, "{:?}", curve);
+        assert!((easing_curve(&curve, 1.) - 1.).abs() < 0.001, "{:?}", curve);
+    }
+    assert_eq!(easing_curve(&EasingCurve::Steps(4), 0.3), 0.25);
+    assert_eq!(easing_curve(&EasingCurve::Steps(4), 0.99), 0.75);
+    // An underdamped spring overshoots
+    assert!(
+        (0..100).any(|i| easing_curve(&EasingCurve::Spring([100., 5., 1.]), i as f32 / 100.) > 1.)
+    );
+}
+
+#[test]
+fn spring_settling_duration() {
+    // Critically damped spring with omega0 = 10
+    let duration = EasingCurve::Spring([100., 20., 1.]).settling_duration().unwrap();
+    assert!((900..1000).contains(&duration), "{}", duration);
+    // A stiffer spring settles faster
+    assert!(EasingCurve::Spring([400., 40., 1.]).settling_duration().unwrap() < duration);
+    assert_eq!(EasingCurve::Spring([100., 0., 1.]).settling_duration(), Some(0));
+    assert_eq!(EasingCurve::Linear.settling_duration(), None);
+}
+
 /*
 #[test]
 fn easing_test() {
@@ -11,6 +11,7 @@
 
 use crate::component::ComponentVTable;
 use crate::window::{WindowAdapter, WindowInner};
+use crate::SharedString;
 
 /// A position represented in the coordinate space of logical pixels. That is the space before applying
 /// a display device specific scale factor.
@@ -416,6 +417,115 @@ pub fn set_size(&self, size: impl Into<WindowSize>) {
         }
     }
 
+    /// Returns the explicit minimum size of the window, in logical pixels, if one was set
+    /// with [`Self::set_min_size`].
+    pub fn min_size(&self) -> Option<LogicalSize> {
+        self.0.min_size()
+    }
+
+    /// Sets the minimum size of the window. This is combined with the constraints of the
+    /// layout, such as the `min-width` and `min-height` of the `Window` element.
+    /// Pass `None` to only use the constraints from the layout.
+    pub fn set_min_size(&self, size: Option<impl Into<WindowSize>>) {
+        let size = size.map(|s| s.into().to_logical(self.scale_factor()));
+        self.0.set_min_size(size);
+    }
+
+    /// Returns the explicit maximum size of the window, in logical pixels, if one was set
+    /// with [`Self::set_max_size`].
+    pub fn max_size(&self) -> Option<LogicalSize> {
+        self.0.max_size()
+    }
+
+    /// Sets the maximum size of the window. This is combined with the constraints of the
+    /// layout, such as the `max-width` and `max-height` of the `Window` element.
+    /// Pass `None` to only use the constraints from the layout.
+    pub fn set_max_size(&self, size: Option<impl Into<WindowSize>>) {
+        let size = size.map(|s| s.into().to_logical(self.scale_factor()));
+        self.0.set_max_size(size);
+    }
+
+    /// Returns true if the window is shown in fullscreen mode.
+    /// This is the value of the `fullscreen` property of the `Window` element.
+    pub fn is_fullscreen(&self) -> bool {
+        self.0.window_item().map_or(false, |item| item.as_pin_ref().fullscreen())
+    }
+
+    /// Sets the `fullscreen` property of the `Window` element.
+    /// If the value changes, this replaces the binding of the property.
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        if let Some(item) = self.0.window_item() {
+            let item = item.as_pin_ref();
+            item.update_state_property(
+                crate::items::WindowItem::FIELD_OFFSETS.fullscreen,
+                fullscreen,
+            );
+        }
+    }
+
+    /// Returns true if the window is maximized.
+    /// This is the value of the `maximized` property of the `Window` element.
+    pub fn is_maximized(&self) -> bool {
+        self.0.window_item().map_or(false, |item| item.as_pin_ref().maximized())
+    }
+
+    /// Sets the `maximized` property of the `Window` element.
+    /// If the value changes, this replaces the binding of the property.
+    pub fn set_maximized(&self, maximized: bool) {
+        if let Some(item) = self.0.window_item() {
+            let item = item.as_pin_ref();
+            item.update_state_property(
+                crate::items::WindowItem::FIELD_OFFSETS.maximized,
+                maximized,
+            );
+        }
+    }
+
+    /// Returns true if the window is minimized.
+    /// This is the value of the `minimized` property of the `Window` element.
+    pub fn is_minimized(&self) -> bool {
+        self.0.window_item().map_or(false, |item| item.as_pin_ref().minimized())
+    }
+
+    /// Sets the `minimized` property of the `Window` element.
+    /// If the value changes, this replaces the binding of the property.
+    pub fn set_minimized(&self, minimized: bool) {
+        if let Some(item) = self.0.window_item() {
+            let item = item.as_pin_ref();
+            item.update_state_property(
+                crate::items::WindowItem::FIELD_OFFSETS.minimized,
+                minimized,
+            );
+        }
+    }
+
+    /// Returns true if the window is kept above the other windows.
+    /// This is the value of the `always-on-top` property of the `Window` element.
+    pub fn is_always_on_top(&self) -> bool {
+        self.0.window_item().map_or(false, |item| item.as_pin_ref().always_on_top())
+    }
+
+    /// Sets the `always-on-top` property of the `Window` element.
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        if let Some(item) = self.0.window_item() {
+            item.always_on_top.set(always_on_top);
+        }
+    }
+
+    /// Returns true if the user can resize the window.
+    /// This is the value of the `resizable` property of the `Window` element.
+    pub fn is_resizable(&self) -> bool {
+        self.0.window_item().map_or(true, |item| item.as_pin_ref().resizable())
+    }
+
+    /// Sets the `resizable` property of the `Window` element. Even when resizable, the size
+    /// of the window is still bound by the minimum and maximum size.
+    pub fn set_resizable(&self, resizable: bool) {
+        if let Some(item) = self.0.window_item() {
+            item.resizable.set(resizable);
+        }
+    }
+
     /// Dispatch a window event to the scene.
     ///
     /// Use this when you're implementing your own backend and want to forward user input events.
@@ -748,6 +858,52 @@ pub enum EventLoopError {
     NoEventLoopProvider,
 }
 
+/// A filter that restricts the files shown in a file dialog.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileDialogFilter {
+    /// The name of the filter shown to the user, for example "Images"
+    pub name: SharedString,
+    /// The patterns of the file names matched by this filter, for example `*.png`
+    pub patterns: alloc::vec::Vec<SharedString>,
+}
+
+/// The options of the dialogs opened with [`open_file_dialog()`](crate::platform::Platform::open_file_dialog)
+/// and [`save_file_dialog()`](crate::platform::Platform::save_file_dialog).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileDialogOptions {
+    /// The title of the dialog window
+    pub title: SharedString,
+    /// The directory shown when the dialog opens. When empty, the current directory is used.
+    pub directory: SharedString,
+    /// The file name that is initially selected, typically used for save dialogs
+    pub file_name: SharedString,
+    /// The filters the user can choose from. When empty, all files are shown.
+    pub filters: alloc::vec::Vec<FileDialogFilter>,
+}
+
+pub use crate::items::{MessageDialogButtons, MessageDialogKind, MessageDialogResult};
+
+/// The options of the dialog opened with [`show_message_dialog()`](crate::platform::Platform::show_message_dialog)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageDialogOptions {
+    /// The title of the dialog window
+    pub title: SharedString,
+    /// The message shown to the user
+    pub text: SharedString,
+    /// The kind of message, which controls the icon
+    pub kind: MessageDialogKind,
+    /// The buttons shown in the dialog
+    pub buttons: MessageDialogButtons,
+}
+
+/// Error returned by the dialog functions of the [`Platform`](crate::platform::Platform)
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DialogError {
+    /// The platform does not provide native dialogs
+    Unsupported,
+}
+
 #[test]
 fn logical_physical_pos() {
     use crate::graphics::euclid::approxeq::ApproxEq;
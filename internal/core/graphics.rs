@@ -141,6 +141,8 @@ pub struct FontRequest {
     /// The additional spacing (or shrinking if negative) between glyphs. This is usually not submitted to
     /// the font-subsystem but collected here for API convenience
     pub letter_spacing: Option<Coord>,
+    /// Whether an italic face of the font should be used
+    pub italic: bool,
 }
 
 #[cfg(feature = "ffi")]
@@ -10,6 +10,8 @@
 
 use super::{IntRect, IntSize};
 
+#[cfg(feature = "image-decoders")]
+pub mod animated;
 #[cfg(feature = "image-decoders")]
 pub mod cache;
 #[cfg(target_arch = "wasm32")]
@@ -41,6 +43,12 @@ pub struct OpaqueImageVTable {
     pub static HTML_IMAGE_VT for htmlimage::HTMLImage
 }
 
+#[cfg(feature = "image-decoders")]
+OpaqueImageVTable_static! {
+    /// VTable for RC wrapped animated image helper struct.
+    pub static ANIMATED_IMAGE_VT for animated::AnimatedImage
+}
+
 /// SharedPixelBuffer is a container for storing image data as pixels. It is
 /// internally reference counted and cheap to clone.
 ///
@@ -297,6 +305,9 @@ pub enum ImageCacheKey {
     URL(SharedString),
     /// The image is identified by the static address of its encoded data.
     EmbeddedData(usize),
+    /// The image is a frame of an animated image, identified by the id of the
+    /// animation and the index of the frame.
+    AnimationFrame(usize, usize),
 }
 
 impl ImageCacheKey {
@@ -314,6 +325,8 @@ pub fn new(resource: &ImageInner) -> Option<Self> {
             #[cfg(target_arch = "wasm32")]
             ImageInner::HTMLImage(htmlimage) => Self::URL(htmlimage.source().into()),
             ImageInner::BackendStorage(x) => vtable::VRc::borrow(x).cache_key(),
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.cache_key(),
         };
         if matches!(key, ImageCacheKey::Invalid) {
             None
@@ -348,6 +361,8 @@ pub enum ImageInner {
     #[cfg(target_arch = "wasm32")]
     HTMLImage(vtable::VRc<OpaqueImageVTable, htmlimage::HTMLImage>),
     BackendStorage(vtable::VRc<OpaqueImageVTable>),
+    #[cfg(feature = "image-decoders")]
+    AnimatedImage(vtable::VRc<OpaqueImageVTable, animated::AnimatedImage>),
 }
 
 impl ImageInner {
@@ -431,6 +446,8 @@ pub fn render_to_buffer(
                 }
                 Some(SharedImageBuffer::RGBA8Premultiplied(buffer))
             }
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.first_frame_buffer(),
             _ => None,
         }
     }
@@ -448,6 +465,8 @@ fn eq(&self, other: &Self) -> bool {
             (Self::StaticTextures(l0), Self::StaticTextures(r0)) => l0 == r0,
             #[cfg(target_arch = "wasm32")]
             (Self::HTMLImage(l0), Self::HTMLImage(r0)) => vtable::VRc::ptr_eq(l0, r0),
+            #[cfg(feature = "image-decoders")]
+            (Self::AnimatedImage(l0), Self::AnimatedImage(r0)) => vtable::VRc::ptr_eq(l0, r0),
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -600,6 +619,29 @@ pub fn size(&self) -> IntSize {
             #[cfg(target_arch = "wasm32")]
             ImageInner::HTMLImage(htmlimage) => htmlimage.size().unwrap_or_default(),
             ImageInner::BackendStorage(x) => vtable::VRc::borrow(x).size(),
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.size(),
+        }
+    }
+
+    /// Returns the image shown at the given time in milliseconds since the start of the
+    /// animation, for images with multiple frames such as animated GIFs. For other images
+    /// this returns a clone of the image itself.
+    pub fn frame_at(&self, _elapsed: u64) -> Image {
+        match &self.0 {
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => animated.frame_at(_elapsed).image.clone(),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns the duration in milliseconds of one run of the animation if this image has
+    /// multiple frames, or None for still images.
+    pub fn animation_duration(&self) -> Option<u64> {
+        match &self.0 {
+            #[cfg(feature = "image-decoders")]
+            ImageInner::AnimatedImage(animated) => Some(animated.duration()),
+            _ => None,
         }
     }
 
@@ -0,0 +1,232 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the support for images with multiple frames, such as animated GIF, PNG and WebP.
+*/
+
+use super::{Image, ImageCacheKey, ImageInner, SharedImageBuffer, SharedPixelBuffer};
+use crate::graphics::IntSize;
+
+/// Used to give each decoded animation its own frame cache keys
+static NEXT_ANIMATION_ID: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+/// A frame of an [`AnimatedImage`]
+#[derive(Clone, Debug)]
+pub struct AnimationFrame {
+    /// The image of the frame, with the size of the whole animation
+    pub image: Image,
+    /// How long the frame is shown, in milliseconds
+    pub duration: u64,
+}
+
+/// An image made of several frames that are shown one after the other
+pub struct AnimatedImage {
+    frames: Vec<AnimationFrame>,
+    cache_key: ImageCacheKey,
+}
+
+impl super::OpaqueImage for AnimatedImage {
+    fn size(&self) -> IntSize {
+        self.size()
+    }
+    fn cache_key(&self) -> ImageCacheKey {
+        self.cache_key.clone()
+    }
+}
+
+impl core::fmt::Debug for AnimatedImage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AnimatedImage").field(&self.frames.len()).finish()
+    }
+}
+
+impl AnimatedImage {
+    /// Decodes the frames of an animated GIF, PNG or WebP image. Returns None if the image is
+    /// not animated, so that it is loaded as a still image.
+    pub fn load_from_data(
+        data: &[u8],
+        format: image::ImageFormat,
+        cache_key: ImageCacheKey,
+    ) -> Option<Self> {
+        use image::AnimationDecoder;
+        let reader = std::io::Cursor::new(data);
+        let frames = match format {
+            image::ImageFormat::Gif => {
+                image::codecs::gif::GifDecoder::new(reader).ok()?.into_frames()
+            }
+            image::ImageFormat::Png => {
+                let decoder = image::codecs::png::PngDecoder::new(reader).ok()?;
+                if !decoder.is_apng() {
+                    return None;
+                }
+                decoder.apng().into_frames()
+            }
+            image::ImageFormat::WebP => {
+                let decoder = image::codecs::webp::WebPDecoder::new(reader).ok()?;
+                if !decoder.has_animation() {
+                    return None;
+                }
+                decoder.into_frames()
+            }
+            _ => return None,
+        };
+        let frames = match frames.collect_frames() {
+            Ok(frames) => frames,
+            Err(decode_err) => {
+                eprintln!("Error decoding animated image: {}", decode_err);
+                return None;
+            }
+        };
+        if frames.len() < 2 {
+            return None;
+        }
+
+        let animation_id = NEXT_ANIMATION_ID.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        let frames = frames
+            .into_iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let (numerator, denominator) = frame.delay().numer_denom_ms();
+                let mut duration = numerator as u64 / denominator.max(1) as u64;
+                // Like web browsers, show frames without a meaningful delay for 100ms
+                if duration <= 10 {
+                    duration = 100;
+                }
+                let buffer = frame.into_buffer();
+                let buffer = SharedImageBuffer::RGBA8(SharedPixelBuffer::clone_from_slice(
+                    buffer.as_raw(),
+                    buffer.width(),
+                    buffer.height(),
+                ));
+                AnimationFrame {
+                    image: Image(ImageInner::EmbeddedImage {
+                        cache_key: ImageCacheKey::AnimationFrame(animation_id, index),
+                        buffer,
+                    }),
+                    duration,
+                }
+            })
+            .collect();
+        Some(Self { frames, cache_key })
+    }
+
+    /// Returns the frames of the animation
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    /// Returns the duration of the animation in milliseconds, which is the sum of the
+    /// duration of all frames
+    pub fn duration(&self) -> u64 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Returns the frame shown after the given time in milliseconds since the start of the
+    /// animation. The last frame is shown once the animation is over.
+    pub fn frame_at(&self, mut elapsed: u64) -> &AnimationFrame {
+        for frame in &self.frames {
+            if elapsed < frame.duration {
+                return frame;
+            }
+            elapsed -= frame.duration;
+        }
+        self.frames.last().unwrap()
+    }
+
+    pub fn size(&self) -> IntSize {
+        self.frames[0].image.size()
+    }
+
+    pub fn cache_key(&self) -> ImageCacheKey {
+        self.cache_key.clone()
+    }
+
+    /// Returns the pixels of the first frame
+    pub fn first_frame_buffer(&self) -> Option<SharedImageBuffer> {
+        match &self.frames[0].image.0 {
+            ImageInner::EmbeddedImage { buffer, .. } => Some(buffer.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animations::{Instant, CURRENT_ANIMATION_DRIVER};
+    use crate::items::ImageAnimationState;
+
+    fn two_frame_gif() -> Vec<u8> {
+        let mut data = Vec::new();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(&mut data);
+            for (color, delay) in [([255, 0, 0, 255], 100), ([0, 0, 255, 255], 300)] {
+                let buffer = image::RgbaImage::from_pixel(4, 2, image::Rgba(color));
+                let delay = image::Delay::from_numer_denom_ms(delay, 1);
+                encoder.encode_frame(image::Frame::from_parts(buffer, 0, 0, delay)).unwrap();
+            }
+        }
+        data
+    }
+
+    fn first_pixel(image: &Image) -> [u8; 4] {
+        match image.0.render_to_buffer(None).unwrap() {
+            SharedImageBuffer::RGBA8(pixels) => {
+                let pixel = pixels.as_slice()[0];
+                [pixel.r, pixel.g, pixel.b, pixel.a]
+            }
+            _ => panic!("frames are decoded as RGBA8"),
+        }
+    }
+
+    #[test]
+    fn decode_frames() {
+        let animated = AnimatedImage::load_from_data(
+            &two_frame_gif(),
+            image::ImageFormat::Gif,
+            ImageCacheKey::Invalid,
+        )
+        .unwrap();
+        assert_eq!(animated.frames().len(), 2);
+        assert_eq!(animated.size(), IntSize::new(4, 2));
+        assert_eq!(animated.duration(), 400);
+        assert_eq!(first_pixel(&animated.frame_at(0).image), [255, 0, 0, 255]);
+        assert_eq!(first_pixel(&animated.frame_at(99).image), [255, 0, 0, 255]);
+        assert_eq!(first_pixel(&animated.frame_at(100).image), [0, 0, 255, 255]);
+        assert_eq!(first_pixel(&animated.frame_at(1000).image), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn playback() {
+        let image = Image(ImageInner::AnimatedImage(vtable::VRc::new(
+            AnimatedImage::load_from_data(
+                &two_frame_gif(),
+                image::ImageFormat::Gif,
+                ImageCacheKey::Invalid,
+            )
+            .unwrap(),
+        )));
+        let set_tick =
+            |tick| CURRENT_ANIMATION_DRIVER.with(|driver| driver.update_animations(Instant(tick)));
+        let state = ImageAnimationState::default();
+        set_tick(1000);
+        assert_eq!(first_pixel(&state.current_frame(image.clone(), true, true)), [255, 0, 0, 255]);
+        assert!(CURRENT_ANIMATION_DRIVER.with(|driver| driver.has_active_animations()));
+        set_tick(1150);
+        assert_eq!(first_pixel(&state.current_frame(image.clone(), true, true)), [0, 0, 255, 255]);
+        // Looping starts again from the first frame
+        set_tick(1450);
+        assert_eq!(first_pixel(&state.current_frame(image.clone(), true, true)), [255, 0, 0, 255]);
+        // Pausing keeps the current frame
+        set_tick(1550);
+        assert_eq!(first_pixel(&state.current_frame(image.clone(), false, true)), [0, 0, 255, 255]);
+        set_tick(5000);
+        assert_eq!(first_pixel(&state.current_frame(image.clone(), false, true)), [0, 0, 255, 255]);
+        assert!(!CURRENT_ANIMATION_DRIVER.with(|driver| driver.has_active_animations()));
+        // Without looping, the animation stops on the last frame
+        set_tick(6000);
+        assert_eq!(first_pixel(&state.current_frame(image.clone(), true, false)), [0, 0, 255, 255]);
+        assert!(!CURRENT_ANIMATION_DRIVER.with(|driver| driver.has_active_animations()));
+    }
+}
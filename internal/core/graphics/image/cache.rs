@@ -11,7 +11,7 @@
 struct ImageWeightInBytes;
 
 impl clru::WeightScale<ImageCacheKey, ImageInner> for ImageWeightInBytes {
-    fn weight(&self, _: &ImageCacheKey, value: &ImageInner) -> usize {
+    fn weight(&self, key: &ImageCacheKey, value: &ImageInner) -> usize {
         match value {
             ImageInner::None => 0,
             ImageInner::EmbeddedImage { buffer, .. } => match buffer {
@@ -25,6 +25,11 @@ fn weight(&self, _: &ImageCacheKey, value: &ImageInner) -> usize {
             ImageInner::HTMLImage(_) => 512, // Something... the web browser maintainers its own cache. The purpose of this cache is to reduce the amount of DOM elements.
             ImageInner::StaticTextures(_) => 0,
             ImageInner::BackendStorage(x) => vtable::VRc::borrow(x).size().area() as usize,
+            ImageInner::AnimatedImage(animated) => animated
+                .frames()
+                .iter()
+                .map(|frame| clru::WeightScale::weight(self, key, &frame.image.0))
+                .sum(),
         }
     }
 }
@@ -94,6 +99,15 @@ pub(crate) fn load_image_from_path(&mut self, path: &SharedString) -> Option<Ima
                 }
             }
 
+            let format = image::ImageFormat::from_path(path.as_str()).ok();
+            if may_be_animated(format) {
+                if let Ok(data) = std::fs::read(path.as_str()) {
+                    if let Some(animated) = load_animated_image(&data, format, cache_key.clone()) {
+                        return Some(animated);
+                    }
+                }
+            }
+
             image::open(std::path::Path::new(&path.as_str())).map_or_else(
                 |decode_err| {
                     eprintln!("Error loading image from {}: {}", &path, decode_err);
@@ -132,6 +146,10 @@ pub(crate) fn load_image_from_embedded_data(
             let format = std::str::from_utf8(format.as_slice())
                 .ok()
                 .and_then(image::ImageFormat::from_extension);
+            if let Some(animated) = load_animated_image(data.as_slice(), format, cache_key.clone())
+            {
+                return Some(animated);
+            }
             let maybe_image = if let Some(format) = format {
                 image::load_from_memory_with_format(data.as_slice(), format)
             } else {
@@ -152,6 +170,28 @@ pub(crate) fn load_image_from_embedded_data(
     }
 }
 
+/// Returns true if images of the given format can have several frames.
+fn may_be_animated(format: Option<image::ImageFormat>) -> bool {
+    matches!(
+        format,
+        Some(image::ImageFormat::Gif | image::ImageFormat::Png | image::ImageFormat::WebP)
+    )
+}
+
+/// Decodes all frames of an animated image. Returns None for images that only have one frame.
+fn load_animated_image(
+    data: &[u8],
+    format: Option<image::ImageFormat>,
+    cache_key: ImageCacheKey,
+) -> Option<ImageInner> {
+    let format = format.or_else(|| image::guess_format(data).ok());
+    if !may_be_animated(format) {
+        return None;
+    }
+    super::animated::AnimatedImage::load_from_data(data, format?, cache_key)
+        .map(|animated| ImageInner::AnimatedImage(vtable::VRc::new(animated)))
+}
+
 fn dynamic_image_to_shared_image_buffer(dynamic_image: image::DynamicImage) -> SharedImageBuffer {
     if dynamic_image.color().has_alpha() {
         let rgba8image = dynamic_image.to_rgba8();
@@ -178,3 +218,11 @@ pub fn replace_cached_image(key: ImageCacheKey, value: ImageInner) {
     let _ =
         IMAGE_CACHE.with(|global_cache| global_cache.borrow_mut().0.put_with_weight(key, value));
 }
+
+/// Returns the image stored in the cache for the given key, if any
+pub fn cached_image(key: &ImageCacheKey) -> Option<ImageInner> {
+    if *key == ImageCacheKey::Invalid {
+        return None;
+    }
+    IMAGE_CACHE.with(|global_cache| global_cache.borrow_mut().0.get(key).cloned())
+}
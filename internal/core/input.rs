@@ -185,6 +185,10 @@ pub enum KeyEventType {
     KeyPressed,
     /// A key on a keyboard was released.
     KeyReleased,
+    /// The input method updated the text that is being composed (the pre-edit text), which is
+    /// in the `text` field of the event. An empty text means that the composition ended.
+    /// The composed text is then committed as a regular `KeyPressed` event.
+    UpdateComposition,
 }
 
 impl Default for KeyEventType {
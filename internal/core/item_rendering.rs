@@ -279,6 +279,10 @@ fn visit_layer(&mut self, _layer_item: Pin<&Layer>, _self_rc: &ItemRc) -> Render
         // Not supported
         RenderingResult::ContinueRenderingChildren
     }
+    fn visit_effect(&mut self, _effect_item: Pin<&Effect>, _self_rc: &ItemRc) -> RenderingResult {
+        // Not supported
+        RenderingResult::ContinueRenderingChildren
+    }
 
     // Apply the bounds of the Clip element, if enabled. The default implementation calls
     // combine_clip, but the render may choose an alternate way of implementing the clip.
@@ -505,6 +509,10 @@ fn filter_item(&mut self, item: Pin<ItemRef>) -> (bool, Rect) {
     forward_rendering_call!(fn draw_path(Path));
     forward_rendering_call!(fn draw_box_shadow(BoxShadow));
 
+    fn visit_effect(&mut self, effect_item: Pin<&Effect>, item_rc: &ItemRc) -> RenderingResult {
+        self.actual_renderer.visit_effect(effect_item, item_rc)
+    }
+
     fn combine_clip(&mut self, rect: Rect, radius: Coord, border_width: Coord) -> bool {
         self.actual_renderer.combine_clip(rect, radius, border_width)
     }
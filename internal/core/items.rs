@@ -39,12 +39,104 @@
 use i_slint_core_macros::*;
 use vtable::*;
 
+/// Implements the Item trait for an item that is not rendered and doesn't handle events.
+/// The optional second argument is a function called from [`Item::init`].
+macro_rules! impl_non_visual_item {
+    ($item:ident) => {
+        impl_non_visual_item!($item, |_| {});
+    };
+    ($item:ident, $init:expr) => {
+        impl $crate::items::Item for $item {
+            fn init(
+                self: core::pin::Pin<&Self>,
+                _window_adapter: &alloc::rc::Rc<dyn $crate::window::WindowAdapter>,
+            ) {
+                let init: fn(core::pin::Pin<&Self>) = $init;
+                init(self)
+            }
+
+            fn geometry(self: core::pin::Pin<&Self>) -> $crate::graphics::Rect {
+                euclid::rect(self.x(), self.y(), self.width(), self.height())
+            }
+
+            fn layout_info(
+                self: core::pin::Pin<&Self>,
+                _orientation: $crate::layout::Orientation,
+                _window_adapter: &alloc::rc::Rc<dyn $crate::window::WindowAdapter>,
+            ) -> $crate::layout::LayoutInfo {
+                $crate::layout::LayoutInfo { stretch: 1., ..Default::default() }
+            }
+
+            fn input_event_filter_before_children(
+                self: core::pin::Pin<&Self>,
+                _: $crate::input::MouseEvent,
+                _window_adapter: &alloc::rc::Rc<dyn $crate::window::WindowAdapter>,
+                _self_rc: &$crate::items::ItemRc,
+            ) -> $crate::input::InputEventFilterResult {
+                $crate::input::InputEventFilterResult::ForwardAndIgnore
+            }
+
+            fn input_event(
+                self: core::pin::Pin<&Self>,
+                _: $crate::input::MouseEvent,
+                _window_adapter: &alloc::rc::Rc<dyn $crate::window::WindowAdapter>,
+                _self_rc: &$crate::items::ItemRc,
+            ) -> $crate::input::InputEventResult {
+                $crate::input::InputEventResult::EventIgnored
+            }
+
+            fn key_event(
+                self: core::pin::Pin<&Self>,
+                _: &$crate::input::KeyEvent,
+                _window_adapter: &alloc::rc::Rc<dyn $crate::window::WindowAdapter>,
+            ) -> $crate::input::KeyEventResult {
+                $crate::input::KeyEventResult::EventIgnored
+            }
+
+            fn focus_event(
+                self: core::pin::Pin<&Self>,
+                _: &$crate::input::FocusEvent,
+                _window_adapter: &alloc::rc::Rc<dyn $crate::window::WindowAdapter>,
+            ) -> $crate::input::FocusEventResult {
+                $crate::input::FocusEventResult::FocusIgnored
+            }
+
+            fn render(
+                self: core::pin::Pin<&Self>,
+                _backend: &mut &mut dyn $crate::item_rendering::ItemRenderer,
+                _self_rc: &$crate::items::ItemRc,
+            ) -> $crate::items::RenderingResult {
+                $crate::items::RenderingResult::ContinueRenderingChildren
+            }
+        }
+
+        impl $crate::items::ItemConsts for $item {
+            const cached_rendering_data_offset: const_field_offset::FieldOffset<
+                $item,
+                $crate::item_rendering::CachedRenderingData,
+            > = $item::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+        }
+    };
+}
+
+mod effect;
+pub use effect::*;
 mod flickable;
 pub use flickable::*;
 mod text;
 pub use text::*;
 mod image;
 pub use self::image::*;
+mod menu;
+pub use menu::*;
+pub(crate) use menu::update_menus;
+mod shortcut;
+pub use shortcut::*;
+pub(crate) use shortcut::activate_shortcut;
+mod timer;
+pub use timer::*;
+mod dialogs;
+pub use dialogs::*;
 #[cfg(feature = "std")]
 mod path;
 #[cfg(feature = "std")]
@@ -59,6 +151,8 @@
 pub type KeyEventArg = (KeyEvent,);
 type PointerEventArg = (PointerEvent,);
 type PointArg = (Point,);
+type StringArg = (SharedString,);
+type MessageDialogResultArg = (MessageDialogResult,);
 
 #[cfg(all(feature = "ffi", windows))]
 #[macro_export]
@@ -581,6 +675,7 @@ fn key_event(
             KeyEventType::KeyReleased => {
                 Self::FIELD_OFFSETS.key_released.apply_pin(self).call(&(event.clone(),))
             }
+            KeyEventType::UpdateComposition => return KeyEventResult::EventIgnored,
         };
         match r {
             EventResult::Accept => KeyEventResult::EventAccepted,
@@ -913,6 +1008,10 @@ impl ItemConsts for Layer {
     fn slint_get_LayerVTable() -> LayerVTable for Layer
 }
 
+declare_item_vtable! {
+    fn slint_get_EffectVTable() -> EffectVTable for Effect
+}
+
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
 #[pin]
@@ -1017,13 +1116,21 @@ pub struct PropertyAnimation {
     pub iteration_count: f32,
     #[rtti_field]
     pub easing: crate::animations::EasingCurve,
+    #[rtti_field]
+    pub direction: AnimationDirection,
 }
 
 impl Default for PropertyAnimation {
     fn default() -> Self {
         // Defaults for PropertyAnimation are defined here (for internal Rust code doing programmatic animations)
         // as well as in `builtins.slint` (for generated C++ and Rust code)
-        Self { delay: 0, duration: 0, iteration_count: 1., easing: Default::default() }
+        Self {
+            delay: 0,
+            duration: 0,
+            iteration_count: 1.,
+            easing: Default::default(),
+            direction: Default::default(),
+        }
     }
 }
 
@@ -1037,6 +1144,11 @@ pub struct WindowItem {
     pub background: Property<Color>,
     pub title: Property<SharedString>,
     pub no_frame: Property<bool>,
+    pub fullscreen: Property<bool>,
+    pub maximized: Property<bool>,
+    pub minimized: Property<bool>,
+    pub always_on_top: Property<bool>,
+    pub resizable: Property<bool>,
     pub icon: Property<crate::graphics::Image>,
     pub default_font_family: Property<SharedString>,
     pub default_font_size: Property<Coord>,
@@ -1129,6 +1241,24 @@ pub fn font_weight(self: Pin<&Self>) -> Option<i32> {
             Some(font_weight)
         }
     }
+
+    /// Updates one of the `fullscreen`, `maximized` or `minimized` properties with the state of
+    /// the window. The property is only written if the value changes, so that its binding is kept
+    /// as long as it matches the state of the window.
+    pub fn update_state_property(
+        self: Pin<&Self>,
+        property: const_field_offset::FieldOffset<
+            Self,
+            Property<bool>,
+            const_field_offset::AllowPin,
+        >,
+        value: bool,
+    ) {
+        let property = property.apply_pin(self);
+        if property.get_untracked() != value {
+            property.set(value);
+        }
+    }
 }
 
 impl ItemConsts for WindowItem {
@@ -1248,6 +1378,38 @@ fn slint_get_ClippedImageVTable() -> ClippedImageVTable for ClippedImage
     fn slint_get_PathVTable() -> PathVTable for Path
 }
 
+declare_item_vtable! {
+    fn slint_get_MenuItemVTable() -> MenuItemVTable for MenuItem
+}
+
+declare_item_vtable! {
+    fn slint_get_MenuVTable() -> MenuVTable for Menu
+}
+
+declare_item_vtable! {
+    fn slint_get_MenuBarItemVTable() -> MenuBarItemVTable for MenuBarItem
+}
+
+declare_item_vtable! {
+    fn slint_get_SystemTrayIconVTable() -> SystemTrayIconVTable for SystemTrayIcon
+}
+
+declare_item_vtable! {
+    fn slint_get_ShortcutVTable() -> ShortcutVTable for Shortcut
+}
+
+declare_item_vtable! {
+    fn slint_get_TimerItemVTable() -> TimerItemVTable for TimerItem
+}
+
+declare_item_vtable! {
+    fn slint_get_FileDialogVTable() -> FileDialogVTable for FileDialog
+}
+
+declare_item_vtable! {
+    fn slint_get_MessageDialogVTable() -> MessageDialogVTable for MessageDialog
+}
+
 macro_rules! declare_enums {
     ($( $(#[$enum_doc:meta])* enum $Name:ident { $( $(#[$value_doc:meta])* $Value:ident,)* })*) => {
         $(
@@ -0,0 +1,160 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the `FileDialog` and `MessageDialog` elements.
+
+These items are not rendered: they hold the options of a dialog and the callbacks invoked when it
+is closed. Their `show()` function opens the dialog with [`show_file_dialog`] or [`show_message_dialog`].
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{
+    ItemRc, ItemRef, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+    MessageDialogResultArg, StringArg, VoidArg,
+};
+use crate::api::{FileDialogFilter, FileDialogOptions, MessageDialogOptions};
+use crate::item_rendering::CachedRenderingData;
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::{Callback, Coord, Property, SharedString};
+use alloc::boxed::Box;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `FileDialog` element
+pub struct FileDialog {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub title: Property<SharedString>,
+    pub directory: Property<SharedString>,
+    pub file_name: Property<SharedString>,
+    pub filter_name: Property<SharedString>,
+    /// The patterns of the filter, separated by spaces
+    pub filter_patterns: Property<SharedString>,
+    pub save: Property<bool>,
+    pub accepted: Callback<StringArg>,
+    pub canceled: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(FileDialog);
+
+impl FileDialog {
+    /// The options of the dialog, from the properties of the element
+    pub fn options(self: Pin<&Self>) -> FileDialogOptions {
+        let filter_patterns = self.filter_patterns();
+        let patterns: alloc::vec::Vec<SharedString> =
+            filter_patterns.split_whitespace().map(SharedString::from).collect();
+        FileDialogOptions {
+            title: self.title(),
+            directory: self.directory(),
+            file_name: self.file_name(),
+            filters: if patterns.is_empty() {
+                Default::default()
+            } else {
+                alloc::vec![FileDialogFilter { name: self.filter_name(), patterns }]
+            },
+        }
+    }
+
+    /// Invokes the `accepted` callback with the chosen path, or `canceled` if the user cancelled
+    pub fn finish(self: Pin<&Self>, path: Option<SharedString>) {
+        match path {
+            Some(path) => Self::FIELD_OFFSETS.accepted.apply_pin(self).call(&(path,)),
+            None => Self::FIELD_OFFSETS.canceled.apply_pin(self).call(&()),
+        }
+    }
+}
+
+/// Shows the dialog of the `FileDialog` element `item_rc` with `show_dialog`, which gets the options
+/// of the element, whether a file is saved, and the callback to invoke when the dialog is closed.
+/// That callback invokes the callbacks of the element, if it still exists.
+pub fn show_file_dialog(
+    item_rc: &ItemRc,
+    show_dialog: impl FnOnce(&FileDialogOptions, bool, Box<dyn FnOnce(Option<SharedString>)>),
+) {
+    let dialog = match ItemRef::downcast_pin::<FileDialog>(item_rc.borrow()) {
+        Some(dialog) => dialog,
+        None => return,
+    };
+    let item_weak = item_rc.downgrade();
+    show_dialog(
+        &dialog.options(),
+        dialog.save(),
+        Box::new(move |path| {
+            if let Some(item_rc) = item_weak.upgrade() {
+                if let Some(dialog) = ItemRef::downcast_pin::<FileDialog>(item_rc.borrow()) {
+                    dialog.finish(path)
+                }
+            }
+        }),
+    )
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `MessageDialog` element
+pub struct MessageDialog {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub title: Property<SharedString>,
+    pub text: Property<SharedString>,
+    pub kind: Property<MessageDialogKind>,
+    pub buttons: Property<MessageDialogButtons>,
+    pub closed: Callback<MessageDialogResultArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(MessageDialog);
+
+impl MessageDialog {
+    /// The options of the dialog, from the properties of the element
+    pub fn options(self: Pin<&Self>) -> MessageDialogOptions {
+        MessageDialogOptions {
+            title: self.title(),
+            text: self.text(),
+            kind: self.kind(),
+            buttons: self.buttons(),
+        }
+    }
+
+    /// Invokes the `closed` callback with the button the user clicked
+    pub fn finish(self: Pin<&Self>, result: MessageDialogResult) {
+        Self::FIELD_OFFSETS.closed.apply_pin(self).call(&(result,))
+    }
+}
+
+/// Shows the dialog of the `MessageDialog` element `item_rc` with `show_dialog`, which gets the
+/// options of the element and the callback to invoke when the dialog is closed.
+/// That callback invokes the `closed` callback of the element, if it still exists.
+pub fn show_message_dialog(
+    item_rc: &ItemRc,
+    show_dialog: impl FnOnce(&MessageDialogOptions, Box<dyn FnOnce(MessageDialogResult)>),
+) {
+    let dialog = match ItemRef::downcast_pin::<MessageDialog>(item_rc.borrow()) {
+        Some(dialog) => dialog,
+        None => return,
+    };
+    let item_weak = item_rc.downgrade();
+    show_dialog(
+        &dialog.options(),
+        Box::new(move |result| {
+            if let Some(item_rc) = item_weak.upgrade() {
+                if let Some(dialog) = ItemRef::downcast_pin::<MessageDialog>(item_rc.borrow()) {
+                    dialog.finish(result)
+                }
+            }
+        }),
+    )
+}
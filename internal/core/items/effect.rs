@@ -0,0 +1,217 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the `Effect` item, which applies blur, drop shadow and color filters
+to its children.
+
+The item is not meant to be used directly by the .slint code, instead the compiler wraps the
+elements that set the `blur`, `backdrop-blur`, `brightness`, `saturation`, `grayscale` or
+`drop-shadow-*` properties in an `Effect` element.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{Item, ItemConsts, ItemRc, RenderingResult};
+use crate::graphics::{Color, Rect, Rgba8Pixel};
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
+    KeyEventResult, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::item_rendering::ItemRenderer;
+use crate::layout::{LayoutInfo, Orientation};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Coord, Property};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+#[cfg(not(feature = "std"))]
+#[allow(unused)]
+use num_traits::Float;
+
+/// A 4x5 color matrix in row major order. Each row computes one of the red, green, blue and
+/// alpha output channels from the red, green, blue and alpha input channels (between 0 and 1)
+/// plus an offset.
+pub type ColorMatrix = [f32; 20];
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The Effect Item is not meant to be used directly by the .slint code, instead, the `effect-*` and
+/// `drop-shadow-*` properties should be used
+pub struct Effect {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub effect_blur: Property<Coord>,
+    pub effect_backdrop_blur: Property<Coord>,
+    pub effect_brightness: Property<f32>,
+    pub effect_saturation: Property<f32>,
+    pub effect_grayscale: Property<f32>,
+    pub drop_shadow_offset_x: Property<Coord>,
+    pub drop_shadow_offset_y: Property<Coord>,
+    pub drop_shadow_blur: Property<Coord>,
+    pub drop_shadow_color: Property<Color>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for Effect {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        backend: &mut &mut dyn ItemRenderer,
+        self_rc: &ItemRc,
+    ) -> RenderingResult {
+        backend.visit_effect(self, self_rc)
+    }
+}
+
+impl ItemConsts for Effect {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Effect,
+        CachedRenderingData,
+    > = Effect::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+impl Effect {
+    /// Returns the matrix for the `brightness`, `saturation` and `grayscale` properties,
+    /// or None if they leave the colors unchanged.
+    pub fn color_matrix(self: Pin<&Self>) -> Option<ColorMatrix> {
+        color_matrix(self.effect_brightness(), self.effect_saturation(), self.effect_grayscale())
+    }
+
+    /// Returns true if the drop shadow is visible
+    pub fn has_drop_shadow(self: Pin<&Self>) -> bool {
+        self.drop_shadow_color().alpha() > 0
+            && (self.drop_shadow_blur() > 0 as Coord
+                || self.drop_shadow_offset_x() != 0 as Coord
+                || self.drop_shadow_offset_y() != 0 as Coord)
+    }
+
+    /// Returns true if the children need to be rendered into a layer to apply the
+    /// blur, the color filters or the drop shadow.
+    pub fn has_content_effect(self: Pin<&Self>) -> bool {
+        self.effect_blur() > 0 as Coord || self.color_matrix().is_some() || self.has_drop_shadow()
+    }
+
+    /// Returns the distance, in logical pixels, by which the blur and the drop shadow may
+    /// extend the rendering of the children.
+    pub fn margin(self: Pin<&Self>) -> Coord {
+        // A gaussian blur with a standard deviation of `blur / 2` extends by about three times the
+        // standard deviation.
+        let extent = |blur: Coord| blur.max(0 as Coord) * 3 as Coord / 2 as Coord;
+        let mut margin = extent(self.effect_blur());
+        if self.has_drop_shadow() {
+            let offset = self.drop_shadow_offset_x().abs().max(self.drop_shadow_offset_y().abs());
+            margin = margin.max(extent(self.drop_shadow_blur()) + offset);
+        }
+        margin
+    }
+}
+
+/// Returns the color matrix that scales the colors by `brightness`, changes their `saturation`
+/// and converts them to gray by the `grayscale` amount (between 0 and 1).
+/// Returns None if the matrix would leave the colors unchanged.
+///
+/// The matrix only mixes the color channels, it leaves the alpha channel unchanged and has no
+/// offset, so it can be applied to colors with pre-multiplied alpha.
+pub fn color_matrix(brightness: f32, saturation: f32, grayscale: f32) -> Option<ColorMatrix> {
+    let saturation = saturation.max(0.) * (1. - grayscale.clamp(0., 1.));
+    let brightness = brightness.max(0.);
+    if brightness == 1. && saturation == 1. {
+        return None;
+    }
+    // The luminance coefficients used by the CSS `saturate()` filter function
+    let (r, g, b) = (0.213, 0.715, 0.072);
+    let s = saturation;
+    let row = |dr: f32, dg: f32, db: f32| {
+        [(r + dr * s) * brightness, (g + dg * s) * brightness, (b + db * s) * brightness, 0., 0.]
+    };
+    let mut matrix = [0.; 20];
+    matrix[0..5].copy_from_slice(&row(1. - r, -g, -b));
+    matrix[5..10].copy_from_slice(&row(-r, 1. - g, -b));
+    matrix[10..15].copy_from_slice(&row(-r, -g, 1. - b));
+    matrix[18] = 1.;
+    Some(matrix)
+}
+
+/// Applies a matrix returned by [`color_matrix()`] to a pixel with pre-multiplied alpha
+pub fn apply_color_matrix(matrix: &ColorMatrix, pixel: Rgba8Pixel) -> Rgba8Pixel {
+    let (r, g, b) = (pixel.r as f32, pixel.g as f32, pixel.b as f32);
+    let channel = |row: &[f32]| {
+        (row[0] * r + row[1] * g + row[2] * b).round().clamp(0., pixel.a as f32) as u8
+    };
+    Rgba8Pixel {
+        r: channel(&matrix[0..5]),
+        g: channel(&matrix[5..10]),
+        b: channel(&matrix[10..15]),
+        a: pixel.a,
+    }
+}
+
+#[test]
+fn test_color_matrix() {
+    assert_eq!(color_matrix(1., 1., 0.), None);
+    let pixel = Rgba8Pixel { r: 100, g: 50, b: 20, a: 128 };
+
+    let brighter = apply_color_matrix(&color_matrix(2., 1., 0.).unwrap(), pixel);
+    assert_eq!(brighter, Rgba8Pixel { r: 128, g: 100, b: 40, a: 128 });
+
+    let gray = apply_color_matrix(&color_matrix(1., 1., 1.).unwrap(), pixel);
+    assert_eq!(gray.r, gray.g);
+    assert_eq!(gray.g, gray.b);
+    assert_eq!(gray.a, 128);
+    assert_eq!(apply_color_matrix(&color_matrix(1., 0., 0.).unwrap(), pixel), gray);
+}
@@ -8,7 +8,7 @@
 Lookup the [`crate::items`] module documentation.
 */
 use super::{ImageFit, ImageRendering, Item, ItemConsts, ItemRc, RenderingResult};
-use crate::graphics::Rect;
+use crate::graphics::{IntRect, Rect, Size};
 use crate::input::{
     FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
     KeyEventResult, MouseEvent,
@@ -21,7 +21,10 @@
 use crate::window::WindowAdapter;
 use crate::{Brush, Coord, Property};
 use alloc::rc::Rc;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
+use core::cell::Cell;
+use core::ops::Range;
 use core::pin::Pin;
 use i_slint_core_macros::*;
 
@@ -37,6 +40,7 @@ pub struct ImageItem {
     pub height: Property<Coord>,
     pub image_fit: Property<ImageFit>,
     pub image_rendering: Property<ImageRendering>,
+    pub animation_state: ImageAnimationState,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -109,6 +113,14 @@ fn render(
     }
 }
 
+impl ImageItem {
+    /// Returns the frame of the source image to draw. Animated images are always played
+    /// in a loop.
+    pub fn current_frame(self: Pin<&Self>) -> crate::graphics::Image {
+        self.animation_state.current_frame(self.source(), true, true)
+    }
+}
+
 impl ItemConsts for ImageItem {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         ImageItem,
@@ -133,6 +145,13 @@ pub struct ClippedImage {
     pub source_clip_y: Property<i32>,
     pub source_clip_width: Property<i32>,
     pub source_clip_height: Property<i32>,
+    pub image_border_left: Property<i32>,
+    pub image_border_right: Property<i32>,
+    pub image_border_top: Property<i32>,
+    pub image_border_bottom: Property<i32>,
+    pub playing: Property<bool>,
+    pub r#loop: Property<bool>,
+    pub animation_state: ImageAnimationState,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -205,9 +224,253 @@ fn render(
     }
 }
 
+impl ClippedImage {
+    /// Returns the frame of the source image to draw, according to the `playing` and `loop`
+    /// properties for animated images.
+    pub fn current_frame(self: Pin<&Self>) -> crate::graphics::Image {
+        self.animation_state.current_frame(self.source(), self.playing(), self.r#loop())
+    }
+
+    /// Returns the rectangle of the source image that is shown, in image pixels.
+    /// A source clip width or height of zero extends the rectangle to the edge of the image.
+    pub fn source_clip_rect(self: Pin<&Self>) -> IntRect {
+        let source_size = self.source().size();
+        let x = self.source_clip_x();
+        let y = self.source_clip_y();
+        let mut width = self.source_clip_width();
+        if width == 0 {
+            width = source_size.width as i32 - x;
+        }
+        let mut height = self.source_clip_height();
+        if height == 0 {
+            height = source_size.height as i32 - y;
+        }
+        euclid::rect(x, y, width, height)
+    }
+
+    /// Returns the parts of the image to draw when it is scaled with the nine-slice borders set
+    /// with the `image-border-*` properties. The edges and the center are repeated with
+    /// [`ImageFit::Tile`]. Returns None if the image is drawn as a whole according to the
+    /// `image-fit` property, in which case the renderer repeats the image itself with
+    /// [`ImageFit::Tile`].
+    pub fn slices(self: Pin<&Self>) -> Option<Vec<ImageSlice>> {
+        let borders = [
+            self.image_border_top().max(0),
+            self.image_border_right().max(0),
+            self.image_border_bottom().max(0),
+            self.image_border_left().max(0),
+        ];
+        if borders == [0; 4] {
+            return None;
+        }
+        Some(image_slices(
+            self.source_clip_rect(),
+            borders,
+            euclid::size2(self.width(), self.height()),
+            matches!(self.image_fit(), ImageFit::Tile),
+        ))
+    }
+}
+
 impl ItemConsts for ClippedImage {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         ClippedImage,
         CachedRenderingData,
     > = ClippedImage::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
+
+/// The playback state of an animated image shown by an image element
+#[repr(C)]
+#[derive(Default)]
+pub struct ImageAnimationState {
+    /// The animation tick at which the animation would have started if it had never been paused
+    start_tick: Cell<u64>,
+    /// The time in milliseconds spent playing the animation, updated when it gets paused
+    elapsed: Cell<u64>,
+    /// Whether the animation was playing the last time the frame was queried
+    playing: Cell<bool>,
+}
+
+impl ImageAnimationState {
+    /// Returns the frame of `source` to show at the current animation tick. Still images are
+    /// returned as is. While an animation is playing, this requests the next animation frame
+    /// from the animation driver, and the caller is registered as a dependency of the tick.
+    pub fn current_frame(
+        &self,
+        source: crate::graphics::Image,
+        playing: bool,
+        looping: bool,
+    ) -> crate::graphics::Image {
+        let duration = match source.animation_duration() {
+            Some(duration) if duration > 0 => duration,
+            _ => return source,
+        };
+        let now = crate::animations::current_tick().0;
+        if playing != self.playing.get() {
+            self.playing.set(playing);
+            if playing {
+                self.start_tick.set(now.saturating_sub(self.elapsed.get()));
+            } else {
+                self.elapsed.set(now.saturating_sub(self.start_tick.get()));
+            }
+        }
+        let elapsed =
+            if playing { now.saturating_sub(self.start_tick.get()) } else { self.elapsed.get() };
+        if playing && (looping || elapsed < duration) {
+            crate::animations::CURRENT_ANIMATION_DRIVER
+                .with(|driver| driver.set_has_active_animations());
+        }
+        source.frame_at(if looping { elapsed % duration } else { elapsed })
+    }
+}
+
+/// A part of an image that is drawn with nine-slice scaling or tiling: the `source` rectangle of
+/// the image, in image pixels, is stretched into the `target` rectangle, in logical coordinates
+/// relative to the item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageSlice {
+    pub source: IntRect,
+    pub target: Rect,
+}
+
+/// Splits the `source` rectangle of an image along the `borders` (top, right, bottom, left, in
+/// image pixels) into nine slices placed in a rectangle of the given `size`. The corners keep their
+/// natural size, unless they don't fit, and the edges and the center are stretched, or repeated if
+/// `tile` is true.
+pub fn image_slices(source: IntRect, borders: [i32; 4], size: Size, tile: bool) -> Vec<ImageSlice> {
+    let [top, right, bottom, left] = borders;
+    let columns = slice_axis(source.min_x()..source.max_x(), left, right, size.width);
+    let rows = slice_axis(source.min_y()..source.max_y(), top, bottom, size.height);
+    let mut slices = Vec::new();
+    for (row, (source_y, target_y)) in rows.into_iter().enumerate() {
+        for (column, (source_x, target_x)) in columns.clone().into_iter().enumerate() {
+            let (tile_x, tile_y) = (tile && column == 1, tile && row == 1);
+            for (source_y, target_y) in repeat_axis(source_y.clone(), target_y.clone(), tile_y) {
+                for (source_x, target_x) in repeat_axis(source_x.clone(), target_x.clone(), tile_x)
+                {
+                    slices.push(ImageSlice {
+                        source: euclid::rect(
+                            source_x.start,
+                            source_y.start,
+                            source_x.end - source_x.start,
+                            source_y.end - source_y.start,
+                        ),
+                        target: euclid::rect(
+                            target_x.start,
+                            target_y.start,
+                            target_x.end - target_x.start,
+                            target_y.end - target_y.start,
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    slices
+}
+
+/// Returns the source and target ranges of the start border, the middle, and the end border along
+/// one axis. The borders are shrunk proportionally if they don't fit in `target_len`.
+fn slice_axis(
+    source: Range<i32>,
+    start_border: i32,
+    end_border: i32,
+    target_len: Coord,
+) -> [(Range<i32>, Range<Coord>); 3] {
+    let source_len = source.end - source.start;
+    let start_border = start_border.min(source_len);
+    let end_border = end_border.min(source_len - start_border);
+    let borders_len = (start_border + end_border) as Coord;
+    let (target_start_border, target_end_border) = if borders_len > target_len {
+        (
+            start_border as Coord * target_len / borders_len,
+            target_len - start_border as Coord * target_len / borders_len,
+        )
+    } else {
+        (start_border as Coord, end_border as Coord)
+    };
+    [
+        (source.start..source.start + start_border, 0 as Coord..target_start_border),
+        (
+            source.start + start_border..source.end - end_border,
+            target_start_border..target_len - target_end_border,
+        ),
+        (source.end - end_border..source.end, target_len - target_end_border..target_len),
+    ]
+}
+
+/// Returns the source and target ranges to draw along one axis. When `repeat` is true, the source
+/// is repeated at its natural size, and the last repetition is cut off to fit.
+fn repeat_axis(
+    source: Range<i32>,
+    target: Range<Coord>,
+    repeat: bool,
+) -> Vec<(Range<i32>, Range<Coord>)> {
+    let source_len = source.end - source.start;
+    if source_len <= 0 || target.end <= target.start {
+        return Vec::new();
+    }
+    if !repeat {
+        return alloc::vec![(source, target)];
+    }
+    let mut result = Vec::new();
+    let mut pos = target.start;
+    while pos < target.end {
+        let len = (target.end - pos).min(source_len as Coord);
+        let cut_source_len =
+            if len < source_len as Coord { (len as i32).max(1) } else { source_len };
+        result.push((source.start..source.start + cut_source_len, pos..pos + len));
+        pos += len;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        euclid::rect(x as Coord, y as Coord, width as Coord, height as Coord)
+    }
+
+    #[test]
+    fn test_nine_slices() {
+        let slices =
+            image_slices(euclid::rect(0, 0, 30, 20), [5, 10, 5, 10], Size::new(100., 50.), false);
+        assert_eq!(slices.len(), 9);
+        // The corners keep their size
+        assert_eq!(
+            slices[0],
+            ImageSlice { source: euclid::rect(0, 0, 10, 5), target: rect(0, 0, 10, 5) }
+        );
+        assert_eq!(
+            slices[8],
+            ImageSlice { source: euclid::rect(20, 15, 10, 5), target: rect(90, 45, 10, 5) }
+        );
+        // The center is stretched
+        assert_eq!(
+            slices[4],
+            ImageSlice { source: euclid::rect(10, 5, 10, 10), target: rect(10, 5, 80, 40) }
+        );
+
+        // The borders are shrunk when they don't fit
+        let slices =
+            image_slices(euclid::rect(0, 0, 30, 20), [5, 10, 5, 10], Size::new(10., 50.), false);
+        assert_eq!(slices.len(), 6);
+        assert_eq!(slices[0].target, rect(0, 0, 5, 5));
+        assert_eq!(slices[1].target, rect(5, 0, 5, 5));
+    }
+
+    #[test]
+    fn test_tiled_slices() {
+        let slices = image_slices(euclid::rect(0, 0, 10, 10), [0; 4], Size::new(25., 10.), true);
+        assert_eq!(
+            slices,
+            alloc::vec![
+                ImageSlice { source: euclid::rect(0, 0, 10, 10), target: rect(0, 0, 10, 10) },
+                ImageSlice { source: euclid::rect(0, 0, 10, 10), target: rect(10, 0, 10, 10) },
+                ImageSlice { source: euclid::rect(0, 0, 5, 10), target: rect(20, 0, 5, 10) },
+            ]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the builtin menu related items.
+
+These items are not rendered: the backends show the menus through the platform's native menu bar
+and system tray, and the MenuBar element otherwise draws them with Slint elements.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{ItemRc, VoidArg};
+use crate::graphics::Image;
+use crate::item_rendering::CachedRenderingData;
+use crate::item_tree::ItemWeak;
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Callback, Coord, Property, SharedString};
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use const_field_offset::FieldOffsets;
+use i_slint_core_macros::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `MenuItem` element
+pub struct MenuItem {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub title: Property<SharedString>,
+    pub shortcut: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub checkable: Property<bool>,
+    pub checked: Property<bool>,
+    pub activated: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(MenuItem);
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `Menu` element
+pub struct Menu {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub title: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(Menu);
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The item at the root of the `MenuBar` element. Its children are drawn when there is no native menu bar.
+pub struct MenuBarItem {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub native: Property<bool>,
+    pub native_height: Property<Coord>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(MenuBarItem);
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `SystemTrayIcon` element
+pub struct SystemTrayIcon {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub icon: Property<Image>,
+    pub tooltip: Property<SharedString>,
+    pub activated: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(SystemTrayIcon);
+
+/// An entry of a menu, built from a `Menu` or a `MenuItem` element and passed to
+/// [`Platform::set_menu_bar`](crate::platform::Platform::set_menu_bar) and
+/// [`Platform::set_system_tray_icon`](crate::platform::Platform::set_system_tray_icon).
+#[derive(Clone)]
+pub struct MenuEntry {
+    /// The title of the menu or of the item
+    pub title: SharedString,
+    /// The keyboard shortcut of the item, such as `Ctrl+O`. Empty for menus.
+    pub shortcut: SharedString,
+    /// When false, the entry is shown grayed out and can't be activated
+    pub enabled: bool,
+    /// Whether the item shows a check mark, toggled when activated
+    pub checkable: bool,
+    /// Whether the check mark of a checkable item is set
+    pub checked: bool,
+    /// The entries of the sub menu if this entry is a `Menu`, or `None` if it is a `MenuItem`
+    pub sub_menu: Option<Vec<MenuEntry>>,
+    item: ItemWeak,
+}
+
+impl MenuEntry {
+    /// Toggles the check mark of a checkable item and invokes the `activated` callback of the
+    /// `MenuItem`. This must be called from the event loop when the user activates the entry.
+    pub fn activate(&self) {
+        if let Some(menu_item) = self.item.upgrade().and_then(|i| i.downcast::<MenuItem>()) {
+            let menu_item = menu_item.as_pin_ref();
+            if !menu_item.enabled() {
+                return;
+            }
+            if menu_item.checkable() {
+                MenuItem::FIELD_OFFSETS.checked.apply_pin(menu_item).set(!menu_item.checked());
+            }
+            MenuItem::FIELD_OFFSETS.activated.apply_pin(menu_item).call(&());
+        }
+    }
+}
+
+/// The system tray icon of a window, built from a `SystemTrayIcon` element and passed to
+/// [`Platform::set_system_tray_icon`](crate::platform::Platform::set_system_tray_icon).
+#[derive(Clone)]
+pub struct SystemTrayIconData {
+    /// The icon shown in the system tray
+    pub icon: Image,
+    /// The text shown when hovering the icon
+    pub tooltip: SharedString,
+    /// The entries of the context menu of the icon
+    pub menu: Vec<MenuEntry>,
+    item: ItemWeak,
+}
+
+impl SystemTrayIconData {
+    /// Invokes the `activated` callback of the `SystemTrayIcon`. This must be called from the event
+    /// loop when the user clicks on the icon.
+    pub fn activate(&self) {
+        if let Some(tray_icon) = self.item.upgrade().and_then(|i| i.downcast::<SystemTrayIcon>()) {
+            SystemTrayIcon::FIELD_OFFSETS.activated.apply_pin(tray_icon.as_pin_ref()).call(&());
+        }
+    }
+}
+
+/// Returns the entries for the Menu and MenuItem children of the item
+fn menu_entries(item: &ItemRc) -> Vec<MenuEntry> {
+    let mut entries = Vec::new();
+    let mut child = item.first_child();
+    while let Some(current) = child {
+        if let Some(menu) = current.downcast::<Menu>() {
+            let menu = menu.as_pin_ref();
+            entries.push(MenuEntry {
+                title: menu.title(),
+                shortcut: Default::default(),
+                enabled: menu.enabled(),
+                checkable: false,
+                checked: false,
+                sub_menu: Some(menu_entries(&current)),
+                item: current.downgrade(),
+            });
+        } else if let Some(menu_item) = current.downcast::<MenuItem>() {
+            let menu_item = menu_item.as_pin_ref();
+            entries.push(MenuEntry {
+                title: menu_item.title(),
+                shortcut: menu_item.shortcut(),
+                enabled: menu_item.enabled(),
+                checkable: menu_item.checkable(),
+                checked: menu_item.checked(),
+                sub_menu: None,
+                item: current.downgrade(),
+            });
+        }
+        child = current.next_sibling();
+    }
+    entries
+}
+
+/// The `MenuBarItem` of a window, and the height of the native menu bar returned by
+/// [`Platform::set_menu_bar`](crate::platform::Platform::set_menu_bar)
+pub(crate) struct MenuBarState {
+    menu_bar: ItemRc,
+    native_height: Option<f32>,
+}
+
+impl MenuBarState {
+    /// Tells the `MenuBarItem` whether the menus are shown in a native menu bar.
+    /// This must be called outside of the property tracker of the menus, as the item tree
+    /// depends on these properties.
+    pub(crate) fn apply(&self) {
+        if let Some(menu_bar) = self.menu_bar.downcast::<MenuBarItem>() {
+            let menu_bar = menu_bar.as_pin_ref();
+            MenuBarItem::FIELD_OFFSETS.native.apply_pin(menu_bar).set(self.native_height.is_some());
+            MenuBarItem::FIELD_OFFSETS
+                .native_height
+                .apply_pin(menu_bar)
+                .set(self.native_height.unwrap_or_default() as Coord);
+        }
+    }
+}
+
+/// Looks for the first MenuBar and SystemTrayIcon of the component and hands them to the platform.
+/// This must be called from within the property tracker of the menus of the window, so that the
+/// platform is updated when the menus change. The returned state must then be applied outside of
+/// that tracker.
+pub(crate) fn update_menus(
+    component: &crate::component::ComponentRc,
+    window_adapter: &Rc<dyn WindowAdapter>,
+) -> Option<MenuBarState> {
+    let mut menu_bar = None;
+    let mut tray_icon = None;
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |component, item, index, _| {
+            if menu_bar.is_none() && super::ItemRef::downcast_pin::<MenuBarItem>(item).is_some() {
+                menu_bar = Some(ItemRc::new(component.clone(), index));
+            } else if tray_icon.is_none()
+                && super::ItemRef::downcast_pin::<SystemTrayIcon>(item).is_some()
+            {
+                tray_icon = Some(ItemRc::new(component.clone(), index));
+            }
+            crate::item_tree::ItemVisitorResult::Continue(())
+        },
+        (),
+    );
+
+    let menus = menu_bar.as_ref().map(menu_entries);
+    let tray_icon = tray_icon.as_ref().and_then(|item_rc| {
+        let tray_icon = item_rc.downcast::<SystemTrayIcon>()?;
+        let tray_icon = tray_icon.as_pin_ref();
+        Some(SystemTrayIconData {
+            icon: tray_icon.icon(),
+            tooltip: tray_icon.tooltip(),
+            menu: menu_entries(item_rc),
+            item: item_rc.downgrade(),
+        })
+    });
+
+    let native_height = crate::platform::PLATFORM_INSTANCE.with(|platform| {
+        let platform = platform.get()?;
+        let native_height = platform.set_menu_bar(window_adapter, menus.as_deref());
+        platform.set_system_tray_icon(window_adapter, tray_icon.as_ref());
+        Some(native_height)
+    })?;
+    menu_bar.map(|menu_bar| MenuBarState { menu_bar, native_height })
+}
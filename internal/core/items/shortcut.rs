@@ -0,0 +1,119 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the `Shortcut` element.
+
+The element is not rendered. The shortcuts are handled by the window before the key events are
+sent to the focus item.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{ItemRc, VoidArg};
+use crate::input::{KeyEvent, KeyEventType, KeyboardModifiers};
+use crate::item_rendering::CachedRenderingData;
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::{Callback, Coord, Property, SharedString};
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `Shortcut` element. The `keys` of the element are parsed by the
+/// compiler into `key` and `modifiers`.
+pub struct Shortcut {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub key: Property<SharedString>,
+    pub modifiers: Property<KeyboardModifiers>,
+    pub enabled: Property<bool>,
+    pub activated: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(Shortcut);
+
+impl Shortcut {
+    /// Returns true if the event is a key press of this shortcut
+    fn matches(self: Pin<&Self>, event: &KeyEvent) -> bool {
+        if event.event_type != KeyEventType::KeyPressed {
+            return false;
+        }
+        let key = self.key();
+        let mut modifiers = self.modifiers();
+        let mut event_modifiers = event.modifiers;
+        // Whether Shift is needed to type a symbol depends on the keyboard layout, so only the
+        // character is compared
+        if is_symbol(&key) {
+            modifiers.shift = false;
+            event_modifiers.shift = false;
+        }
+        event_modifiers == modifiers && event.text.to_lowercase() == key.as_str()
+    }
+}
+
+/// Returns true if the text is a single printable character that isn't a letter, such as `?` or `1`.
+fn is_symbol(text: &str) -> bool {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => {
+            !c.is_alphabetic()
+                && !c.is_whitespace()
+                && !c.is_control()
+                // The special keys are in the private use area
+                && !('\u{E000}'..='\u{F8FF}').contains(&c)
+        }
+        _ => false,
+    }
+}
+
+/// Invokes the `activated` callback of the first enabled and visible `Shortcut` of the component
+/// that matches the event. Returns true if a shortcut was activated.
+pub(crate) fn activate_shortcut(
+    component: &crate::component::ComponentRc,
+    event: &KeyEvent,
+) -> bool {
+    let mut shortcut = None;
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |component, item, index, _| {
+            if let Some(s) = super::ItemRef::downcast_pin::<Shortcut>(item) {
+                if s.enabled() && s.matches(event) {
+                    let item_rc = ItemRc::new(component.clone(), index);
+                    if item_rc.is_visible() {
+                        shortcut = Some(item_rc);
+                        return crate::item_tree::ItemVisitorResult::Abort;
+                    }
+                }
+            }
+            crate::item_tree::ItemVisitorResult::Continue(())
+        },
+        (),
+    );
+    match shortcut.as_ref().and_then(|item| item.downcast::<Shortcut>()) {
+        Some(shortcut) => {
+            Shortcut::FIELD_OFFSETS.activated.apply_pin(shortcut.as_pin_ref()).call(&());
+            true
+        }
+        None => false,
+    }
+}
+
+#[test]
+fn test_shortcut_symbols() {
+    assert!(is_symbol("?"));
+    assert!(is_symbol("1"));
+    assert!(is_symbol("+"));
+    assert!(!is_symbol("s"));
+    assert!(!is_symbol(" "));
+    assert!(!is_symbol("\u{1b}"));
+    assert!(!is_symbol("\u{F704}"));
+    assert!(!is_symbol("ab"));
+}
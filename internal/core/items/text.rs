@@ -9,9 +9,9 @@
 */
 
 use super::{
-    InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, PointArg,
-    PointerEventButton, RenderingResult, TextHorizontalAlignment, TextOverflow,
-    TextVerticalAlignment, TextWrap, VoidArg,
+    InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, MouseCursor, PointArg,
+    PointerEventButton, RenderingResult, StringArg, TextFormat, TextHorizontalAlignment,
+    TextOverflow, TextVerticalAlignment, TextWrap, VoidArg,
 };
 use crate::graphics::{Brush, Color, FontRequest, Rect};
 use crate::input::{
@@ -22,11 +22,15 @@
 use crate::layout::{LayoutInfo, Orientation};
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
+use crate::styled_text::StyledText;
 use crate::window::{WindowAdapter, WindowInner};
 use crate::{Callback, Coord, Property, SharedString};
+use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
+use core::cell::RefCell;
 use core::pin::Pin;
 #[allow(unused)]
 use euclid::num::Ceil;
@@ -42,17 +46,29 @@ pub struct Text {
     pub font_family: Property<SharedString>,
     pub font_size: Property<Coord>,
     pub font_weight: Property<i32>,
+    pub font_italic: Property<bool>,
     pub color: Property<Brush>,
     pub horizontal_alignment: Property<TextHorizontalAlignment>,
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
     pub overflow: Property<TextOverflow>,
     pub letter_spacing: Property<Coord>,
+    pub underline: Property<bool>,
+    pub strikethrough: Property<bool>,
+    pub line_height: Property<Coord>,
+    pub max_lines: Property<i32>,
+    pub text_format: Property<TextFormat>,
+    pub link_clicked: Callback<StringArg>,
     pub x: Property<Coord>,
     pub y: Property<Coord>,
     pub width: Property<Coord>,
     pub height: Property<Coord>,
+    data: TextDataBox,
     pub cached_rendering_data: CachedRenderingData,
+    /// true when a link was pressed and we are grabbing the mouse
+    pressed_link: core::cell::Cell<bool>,
+    /// true when we changed the mouse cursor because the mouse is over a link
+    hovering_link: core::cell::Cell<bool>,
 }
 
 impl Item for Text {
@@ -68,11 +84,14 @@ fn layout_info(
         window_adapter: &Rc<dyn WindowAdapter>,
     ) -> LayoutInfo {
         let window_inner = WindowInner::from_pub(window_adapter.window());
+        let styled_text = self.styled_text();
         let implicit_size = |max_width| {
-            window_adapter.renderer().text_size(
+            window_adapter.renderer().styled_text_size(
                 self.font_request(window_inner),
-                self.text().as_str(),
+                &styled_text,
                 max_width,
+                self.explicit_line_height(),
+                self.max_line_count(),
                 window_adapter.window().scale_factor(),
             )
         };
@@ -123,15 +142,62 @@ fn input_event_filter_before_children(
         _window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
-        InputEventFilterResult::ForwardAndIgnore
+        if self.text_format() == TextFormat::Markup {
+            InputEventFilterResult::ForwardEvent
+        } else {
+            InputEventFilterResult::ForwardAndIgnore
+        }
     }
 
     fn input_event(
         self: Pin<&Self>,
-        _: MouseEvent,
-        _window_adapter: &Rc<dyn WindowAdapter>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventResult {
+        let link_at = |position| {
+            let byte_offset =
+                window_adapter.renderer().text_byte_offset_for_position(self, position)?;
+            self.styled_text().link_at(byte_offset).cloned()
+        };
+
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                if link_at(position).is_some() {
+                    self.pressed_link.set(true);
+                    return InputEventResult::GrabMouse;
+                }
+            }
+            MouseEvent::Released { position, button: PointerEventButton::Left } => {
+                if self.pressed_link.replace(false) {
+                    if let Some(link) = link_at(position) {
+                        Self::FIELD_OFFSETS.link_clicked.apply_pin(self).call(&(link,));
+                    }
+                    return InputEventResult::EventAccepted;
+                }
+            }
+            MouseEvent::Moved { position } => {
+                let over_link = link_at(position).is_some();
+                if over_link {
+                    window_adapter.set_mouse_cursor(MouseCursor::Pointer);
+                } else if self.hovering_link.get() {
+                    window_adapter.set_mouse_cursor(MouseCursor::Default);
+                }
+                self.hovering_link.set(over_link);
+                if self.pressed_link.get() {
+                    return InputEventResult::GrabMouse;
+                } else if over_link {
+                    return InputEventResult::EventAccepted;
+                }
+            }
+            MouseEvent::Exit => {
+                self.pressed_link.set(false);
+                if self.hovering_link.replace(false) {
+                    window_adapter.set_mouse_cursor(MouseCursor::Default);
+                }
+            }
+            _ => {}
+        }
         InputEventResult::EventIgnored
     }
 
@@ -167,6 +233,44 @@ impl ItemConsts for Text {
 }
 
 impl Text {
+    /// Returns the text to display, with the markup parsed if `text-format` is `markup`.
+    /// The result is cached until `text` or `text-format` change.
+    pub fn styled_text(self: Pin<&Self>) -> Rc<StyledText> {
+        let text = self.text();
+        let text_format = self.text_format();
+        let mut cache = self.data.styled_text.borrow_mut();
+        match &*cache {
+            Some((cached_text, cached_format, styled_text))
+                if *cached_format == text_format && *cached_text == text =>
+            {
+                styled_text.clone()
+            }
+            _ => {
+                let styled_text = Rc::new(match text_format {
+                    TextFormat::Plain => StyledText::plain(text.clone()),
+                    TextFormat::Markup => crate::styled_text::parse_markup(text.as_str()),
+                });
+                *cache = Some((text, text_format, styled_text.clone()));
+                styled_text
+            }
+        }
+    }
+
+    /// Returns the `line-height` property, or None if the line height of the font should be used
+    pub fn explicit_line_height(self: Pin<&Self>) -> Option<Coord> {
+        let line_height = self.line_height();
+        if line_height > 0 as Coord {
+            Some(line_height)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `max-lines` property, or None if the number of lines is not limited
+    pub fn max_line_count(self: Pin<&Self>) -> Option<usize> {
+        usize::try_from(self.max_lines()).ok().filter(|max_lines| *max_lines > 0)
+    }
+
     pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
         let window_item = window.window_item();
 
@@ -196,6 +300,7 @@ pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            italic: self.font_italic(),
         }
     }
 }
@@ -209,6 +314,7 @@ pub struct TextInput {
     pub font_family: Property<SharedString>,
     pub font_size: Property<Coord>,
     pub font_weight: Property<i32>,
+    pub font_italic: Property<bool>,
     pub color: Property<Brush>,
     pub selection_foreground_color: Property<Color>,
     pub selection_background_color: Property<Color>,
@@ -216,7 +322,14 @@ pub struct TextInput {
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
     pub input_type: Property<InputType>,
+    pub minimum: Property<i32>,
+    pub maximum: Property<i32>,
+    pub input_mask: Property<SharedString>,
+    pub validator: Property<SharedString>,
     pub letter_spacing: Property<Coord>,
+    pub underline: Property<bool>,
+    pub strikethrough: Property<bool>,
+    pub line_height: Property<Coord>,
     pub x: Property<Coord>,
     pub y: Property<Coord>,
     pub width: Property<Coord>,
@@ -233,6 +346,11 @@ pub struct TextInput {
     pub pressed: core::cell::Cell<bool>,
     pub single_line: Property<bool>,
     pub read_only: Property<bool>,
+    pub can_undo: Property<bool>,
+    pub can_redo: Property<bool>,
+    /// The text being composed by the input method, shown at the cursor position
+    pub preedit_text: Property<SharedString>,
+    data: TextInputDataBox,
     pub cached_rendering_data: CachedRenderingData,
     // The x position where the cursor wants to be.
     // It is not updated when moving up and down even when the line is shorter.
@@ -253,19 +371,23 @@ fn layout_info(
         window_adapter: &Rc<dyn WindowAdapter>,
     ) -> LayoutInfo {
         let text = self.text();
-        let implicit_size = |max_width| {
-            window_adapter.renderer().text_size(
+        let text = if text.is_empty() { SharedString::from("*") } else { text };
+        let implicit_size = |max_width| match self.explicit_line_height() {
+            // Only `styled_text_size` takes the line height into account
+            Some(line_height) => window_adapter.renderer().styled_text_size(
                 self.font_request(window_adapter),
-                {
-                    if text.is_empty() {
-                        "*"
-                    } else {
-                        text.as_str()
-                    }
-                },
+                &StyledText::plain(text.clone()),
                 max_width,
+                Some(line_height),
+                None,
                 window_adapter.window().scale_factor(),
-            )
+            ),
+            None => window_adapter.renderer().text_size(
+                self.font_request(window_adapter),
+                text.as_str(),
+                max_width,
+                window_adapter.window().scale_factor(),
+            ),
         };
 
         // Stretch uses `round_layout` to explicitly align the top left and bottom right of layout nodes
@@ -441,10 +563,23 @@ fn key_event(
                         }
                         StandardShortcut::Cut if !self.read_only() => {
                             self.copy();
-                            self.delete_selection(window_adapter);
+                            if self.accepts_selection_removal() {
+                                self.delete_selection(window_adapter);
+                            }
+                            return KeyEventResult::EventAccepted;
+                        }
+                        StandardShortcut::Undo if !self.read_only() => {
+                            self.undo(window_adapter);
                             return KeyEventResult::EventAccepted;
                         }
-                        StandardShortcut::Paste | StandardShortcut::Cut => {
+                        StandardShortcut::Redo if !self.read_only() => {
+                            self.redo(window_adapter);
+                            return KeyEventResult::EventAccepted;
+                        }
+                        StandardShortcut::Paste
+                        | StandardShortcut::Cut
+                        | StandardShortcut::Undo
+                        | StandardShortcut::Redo => {
                             return KeyEventResult::EventIgnored;
                         }
                         _ => (),
@@ -454,16 +589,27 @@ fn key_event(
                 if self.read_only() || event.modifiers.control {
                     return KeyEventResult::EventIgnored;
                 }
+                // The composition ends when its text is committed
+                self.preedit_text.set(SharedString::default());
+
+                let text_to_insert = match self.validated_insertion(&event.text) {
+                    Some(text_to_insert) => text_to_insert,
+                    // Invalid keystrokes are swallowed without modifying the text
+                    None => return KeyEventResult::EventAccepted,
+                };
+
+                let replaces_selection = self.has_selection();
                 self.delete_selection(window_adapter);
 
                 let mut text: String = self.text().into();
 
                 // FIXME: respect grapheme boundaries
                 let insert_pos = self.selection_anchor_and_cursor().1;
-                text.insert_str(insert_pos, &event.text);
+                text.insert_str(insert_pos, &text_to_insert);
 
                 self.as_ref().text.set(text.into());
-                let new_cursor_pos = (insert_pos + event.text.len()) as i32;
+                let new_cursor_pos = (insert_pos + text_to_insert.len()) as i32;
+                self.record_insertion(insert_pos, text_to_insert, replaces_selection, true);
                 self.as_ref().anchor_position.set(new_cursor_pos);
                 self.set_cursor_position(new_cursor_pos, true, window_adapter);
 
@@ -475,6 +621,18 @@ fn key_event(
 
                 KeyEventResult::EventAccepted
             }
+            KeyEventType::UpdateComposition => {
+                if self.read_only() {
+                    return KeyEventResult::EventIgnored;
+                }
+                if !event.text.is_empty() {
+                    // The composed text replaces the selection
+                    self.delete_selection(window_adapter);
+                }
+                self.preedit_text.set(event.text.clone());
+                self.show_cursor(window_adapter);
+                KeyEventResult::EventAccepted
+            }
             _ => KeyEventResult::EventIgnored,
         }
     }
@@ -489,10 +647,18 @@ fn focus_event(
                 self.has_focus.set(true);
                 self.show_cursor(window_adapter);
                 window_adapter.show_virtual_keyboard(self.input_type());
+                let cursor_position = self.cursor_position();
+                if cursor_position >= 0 {
+                    let cursor_rect = window_adapter
+                        .renderer()
+                        .text_input_cursor_rect_for_byte_offset(self, cursor_position as usize);
+                    self.update_ime_position(cursor_rect, window_adapter);
+                }
             }
             FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
                 self.has_focus.set(false);
                 self.hide_cursor();
+                self.preedit_text.set(SharedString::default());
                 window_adapter.hide_virtual_keyboard();
             }
         }
@@ -509,6 +675,271 @@ fn render(
     }
 }
 
+/// The text of a [`TextInput`] as it is rendered, see [`TextInput::visual_representation`]
+pub struct TextInputVisualRepresentation {
+    pub text: SharedString,
+    /// The byte range of the pre-edit text of the input method in `text`, which is drawn underlined
+    pub preedit_range: core::ops::Range<usize>,
+    /// The byte offset of the cursor in `text`
+    pub cursor_position: i32,
+}
+
+/// The maximum number of edit operations that can be reverted with [`TextInput::undo`]
+const MAX_UNDO_ITEMS: usize = 100;
+
+/// An edit operation of the [`TextInput`]: `removed` was replaced by `inserted` at the byte offset `pos`
+#[derive(Debug)]
+struct UndoItem {
+    pos: usize,
+    removed: SharedString,
+    inserted: SharedString,
+    /// The cursor position before the edit
+    cursor: i32,
+    /// The anchor position before the edit
+    anchor: i32,
+    /// Set if the text was typed, so that it can be merged with the next typed text
+    typing: bool,
+}
+
+#[derive(Default)]
+pub struct TextData {
+    /// The `text` and `text-format` of the last call to [`Text::styled_text`], and its result
+    styled_text: RefCell<Option<(SharedString, TextFormat, Rc<StyledText>)>>,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the Text
+pub struct TextDataBox(core::ptr::NonNull<TextData>);
+
+impl Default for TextDataBox {
+    fn default() -> Self {
+        TextDataBox(Box::leak(Box::new(TextData::default())).into())
+    }
+}
+impl Drop for TextDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TextDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for TextDataBox {
+    type Target = TextData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TextDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_data_init(data: *mut TextDataBox) {
+    core::ptr::write(data, TextDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_data_free(data: *mut TextDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+#[derive(Default)]
+pub struct TextInputData {
+    undo_items: RefCell<Vec<UndoItem>>,
+    redo_items: RefCell<Vec<UndoItem>>,
+    /// The last validator pattern, and the automaton compiled from it
+    #[cfg(feature = "std")]
+    validator: RefCell<Option<(SharedString, Option<regex_automata::DenseDFA<Vec<u16>, u16>>)>>,
+}
+
+/// Returns true if `text` is an integer (or decimal) number within the range, or the beginning of one.
+/// Only the bound that can't be reached anymore by typing more digits is checked.
+fn is_valid_number(text: &str, decimal: bool, minimum: i32, maximum: i32) -> bool {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    if negative && minimum >= 0 {
+        return false;
+    }
+    let mut has_decimal_point = false;
+    for c in digits.chars() {
+        if c == '.' && decimal && !has_decimal_point {
+            has_decimal_point = true;
+        } else if !c.is_ascii_digit() {
+            return false;
+        }
+    }
+    match digits.parse::<f64>() {
+        Ok(value) if negative => -value >= minimum as f64,
+        Ok(value) => value <= maximum as f64,
+        // Only a minus sign or a decimal point
+        Err(_) => true,
+    }
+}
+
+/// An element of an input mask: `#` stands for a digit, `A` for a letter, `N` for a letter or a
+/// digit, and `*` for any character. `0`, `a` and `n` are the optional variants of `#`, `A` and `N`.
+/// The other characters, and the characters escaped with a backslash, are literals.
+#[derive(Clone, Copy, PartialEq)]
+enum MaskToken {
+    Literal(char),
+    Placeholder { kind: char, optional: bool },
+}
+
+impl MaskToken {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            MaskToken::Literal(literal) => c == literal,
+            MaskToken::Placeholder { kind: '#', .. } => c.is_ascii_digit(),
+            MaskToken::Placeholder { kind: 'A', .. } => c.is_alphabetic(),
+            MaskToken::Placeholder { kind: 'N', .. } => c.is_alphanumeric(),
+            MaskToken::Placeholder { .. } => true,
+        }
+    }
+
+    fn is_optional(self) -> bool {
+        matches!(self, MaskToken::Placeholder { optional: true, .. })
+    }
+}
+
+fn parse_input_mask(mask: &str) -> Vec<MaskToken> {
+    let mut chars = mask.chars();
+    let mut tokens = Vec::new();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '\\' => MaskToken::Literal(chars.next().unwrap_or('\\')),
+            '#' | 'A' | 'N' | '*' => MaskToken::Placeholder { kind: c, optional: false },
+            '0' => MaskToken::Placeholder { kind: '#', optional: true },
+            'a' => MaskToken::Placeholder { kind: 'A', optional: true },
+            'n' => MaskToken::Placeholder { kind: 'N', optional: true },
+            _ => MaskToken::Literal(c),
+        });
+    }
+    tokens
+}
+
+/// Adds to `positions` the indices in `tokens` that can follow `text` when matched from `start`
+fn input_mask_positions(
+    text: &str,
+    tokens: &[MaskToken],
+    start: usize,
+    positions: &mut Vec<usize>,
+) {
+    let token = tokens.get(start).copied();
+    if token.map_or(false, MaskToken::is_optional) {
+        input_mask_positions(text, tokens, start + 1, positions);
+    }
+    match text.chars().next() {
+        None => {
+            if !positions.contains(&start) {
+                positions.push(start);
+            }
+        }
+        Some(c) => {
+            if token.map_or(false, |token| token.accepts(c)) {
+                input_mask_positions(&text[c.len_utf8()..], tokens, start + 1, positions);
+            }
+        }
+    }
+}
+
+/// Returns true if `text` matches the beginning of the input mask
+fn matches_input_mask(text: &str, mask: &str) -> bool {
+    if mask.is_empty() {
+        return true;
+    }
+    let mut positions = Vec::new();
+    input_mask_positions(text, &parse_input_mask(mask), 0, &mut positions);
+    !positions.is_empty()
+}
+
+/// Returns the sequences of literals of the input mask that can directly follow `text`
+fn input_mask_literals_after(text: &str, mask: &str) -> Vec<String> {
+    let tokens = parse_input_mask(mask);
+    let mut positions = Vec::new();
+    input_mask_positions(text, &tokens, 0, &mut positions);
+    positions
+        .into_iter()
+        .map(|position| {
+            tokens[position..]
+                .iter()
+                .map_while(|token| match token {
+                    MaskToken::Literal(literal) => Some(*literal),
+                    MaskToken::Placeholder { .. } => None,
+                })
+                .collect::<String>()
+        })
+        .filter(|literals| !literals.is_empty())
+        .collect()
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the TextInput
+pub struct TextInputDataBox(core::ptr::NonNull<TextInputData>);
+
+impl Default for TextInputDataBox {
+    fn default() -> Self {
+        TextInputDataBox(Box::leak(Box::new(TextInputData::default())).into())
+    }
+}
+impl Drop for TextInputDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TextInputDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for TextInputDataBox {
+    type Target = TextInputData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TextInputDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextInputDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_input_data_init(data: *mut TextInputDataBox) {
+    core::ptr::write(data, TextInputDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextInputDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_input_data_free(data: *mut TextInputDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+/// Calls [`TextInput::undo`]
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_input_undo(
+    text_input: &TextInput,
+    window_adapter: &crate::window::WindowAdapterRc,
+) {
+    Pin::new_unchecked(text_input).undo(window_adapter)
+}
+
+/// Calls [`TextInput::redo`]
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_text_input_redo(
+    text_input: &TextInput,
+    window_adapter: &crate::window::WindowAdapterRc,
+) {
+    Pin::new_unchecked(text_input).redo(window_adapter)
+}
+
 impl ItemConsts for TextInput {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<
         TextInput,
@@ -595,14 +1026,16 @@ fn move_cursor(
         let mut grapheme_cursor =
             unicode_segmentation::GraphemeCursor::new(last_cursor_pos, text.len(), true);
 
-        let font_height = renderer
-            .text_size(
-                self.font_request(window_adapter),
-                " ",
-                None,
-                window_adapter.window().scale_factor(),
-            )
-            .height;
+        let line_height = self.explicit_line_height().unwrap_or_else(|| {
+            renderer
+                .text_size(
+                    self.font_request(window_adapter),
+                    " ",
+                    None,
+                    window_adapter.window().scale_factor(),
+                )
+                .height
+        });
 
         let mut reset_preferred_x_pos = true;
 
@@ -620,7 +1053,7 @@ fn move_cursor(
                     renderer.text_input_cursor_rect_for_byte_offset(self, last_cursor_pos);
                 let mut cursor_xy_pos = cursor_rect.center();
 
-                cursor_xy_pos.y += font_height;
+                cursor_xy_pos.y += line_height;
                 cursor_xy_pos.x = self.preferred_x_pos.get();
                 renderer.text_input_byte_offset_for_position(self, cursor_xy_pos)
             }
@@ -631,7 +1064,7 @@ fn move_cursor(
                     renderer.text_input_cursor_rect_for_byte_offset(self, last_cursor_pos);
                 let mut cursor_xy_pos = cursor_rect.center();
 
-                cursor_xy_pos.y -= font_height;
+                cursor_xy_pos.y -= line_height;
                 cursor_xy_pos.x = self.preferred_x_pos.get();
                 renderer.text_input_byte_offset_for_position(self, cursor_xy_pos)
             }
@@ -723,17 +1156,56 @@ fn set_cursor_position(
     ) {
         self.cursor_position.set(new_position);
         if new_position >= 0 {
-            let pos = window_adapter
+            let cursor_rect = window_adapter
                 .renderer()
-                .text_input_cursor_rect_for_byte_offset(self, new_position as usize)
-                .origin;
+                .text_input_cursor_rect_for_byte_offset(self, new_position as usize);
+            let pos = cursor_rect.origin;
             if reset_preferred_x_pos {
                 self.preferred_x_pos.set(pos.x);
             }
+            self.update_ime_position(cursor_rect, window_adapter);
             Self::FIELD_OFFSETS.cursor_position_changed.apply_pin(self).call(&(pos,));
         }
     }
 
+    /// Reports the bottom left corner of the cursor (given in item coordinates) to the input
+    /// method, so that it can show its candidate window next to the text being composed.
+    fn update_ime_position(
+        self: Pin<&Self>,
+        cursor_rect: Rect,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) {
+        if !self.has_focus() {
+            return;
+        }
+        let focus_item = match WindowInner::from_pub(window_adapter.window()).focus_item() {
+            Some(focus_item) => focus_item,
+            None => return,
+        };
+        let pos = focus_item.map_to_window(
+            focus_item.geometry().origin + euclid::vec2(cursor_rect.min_x(), cursor_rect.max_y()),
+        );
+        window_adapter.set_ime_position(crate::api::LogicalPosition::new(pos.x as _, pos.y as _));
+    }
+
+    /// Returns the text as it is shown, with the text being composed by the input method inserted
+    /// at the cursor position.
+    pub fn visual_representation(self: Pin<&Self>) -> TextInputVisualRepresentation {
+        let text = self.text();
+        let preedit_text = self.preedit_text();
+        let cursor_position = self.cursor_position();
+        if preedit_text.is_empty() || cursor_position < 0 {
+            return TextInputVisualRepresentation { text, preedit_range: 0..0, cursor_position };
+        }
+        let pos = (cursor_position as usize).min(text.len());
+        let preedit_range = pos..pos + preedit_text.len();
+        TextInputVisualRepresentation {
+            text: [&text[..pos], preedit_text.as_str(), &text[pos..]].concat().into(),
+            cursor_position: preedit_range.end as i32,
+            preedit_range,
+        }
+    }
+
     fn select_and_delete(
         self: Pin<&Self>,
         step: TextCursorDirection,
@@ -741,6 +1213,14 @@ fn select_and_delete(
     ) {
         if !self.has_selection() {
             self.move_cursor(step, AnchorMode::KeepAnchor, window_adapter);
+            if !self.accepts_selection_removal() {
+                // Go back to where the cursor was before
+                let anchor = self.anchor_position();
+                self.set_cursor_position(anchor, true, window_adapter);
+                return;
+            }
+        } else if !self.accepts_selection_removal() {
+            return;
         }
         self.delete_selection(window_adapter);
     }
@@ -756,6 +1236,14 @@ fn delete_selection(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
             return;
         }
 
+        self.push_undo_item(UndoItem {
+            pos: anchor,
+            removed: text[anchor..cursor].into(),
+            inserted: SharedString::default(),
+            cursor: self.cursor_position(),
+            anchor: self.anchor_position(),
+            typing: false,
+        });
         let text = [text.split_at(anchor).0, text.split_at(cursor).1].concat();
         self.text.set(text.into());
         self.anchor_position.set(anchor as i32);
@@ -783,14 +1271,21 @@ pub fn has_selection(self: Pin<&Self>) -> bool {
     }
 
     fn insert(self: Pin<&Self>, text_to_insert: &str, window_adapter: &Rc<dyn WindowAdapter>) {
+        let text_to_insert = if text_to_insert.contains('\n') && self.single_line() {
+            text_to_insert.replace('\n', " ")
+        } else {
+            text_to_insert.into()
+        };
+        let text_to_insert = match self.validated_insertion(&text_to_insert) {
+            Some(text_to_insert) => text_to_insert,
+            None => return,
+        };
+        let replaces_selection = self.has_selection();
         self.delete_selection(window_adapter);
         let mut text: String = self.text().into();
         let cursor_pos = self.selection_anchor_and_cursor().1;
-        if text_to_insert.contains('\n') && self.single_line() {
-            text.insert_str(cursor_pos, &text_to_insert.replace('\n', " "));
-        } else {
-            text.insert_str(cursor_pos, text_to_insert);
-        }
+        text.insert_str(cursor_pos, &text_to_insert);
+        self.record_insertion(cursor_pos, text_to_insert.clone(), replaces_selection, false);
         let cursor_pos = cursor_pos + text_to_insert.len();
         self.text.set(text.into());
         self.anchor_position.set(cursor_pos as i32);
@@ -798,6 +1293,93 @@ fn insert(self: Pin<&Self>, text_to_insert: &str, window_adapter: &Rc<dyn Window
         Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
+    /// Returns the text that replaces the selection when `text_to_insert` is entered, or None if
+    /// the resulting text is rejected by the input type, the input mask, or the validator.
+    /// Literal characters of the input mask are inserted automatically.
+    fn validated_insertion(self: Pin<&Self>, text_to_insert: &str) -> Option<SharedString> {
+        let text = self.text();
+        let (anchor, cursor) = self.selection_anchor_and_cursor();
+        let accepts_insertion = |inserted: &str| {
+            self.accepts_text(&[&text[..anchor], inserted, &text[cursor..]].concat())
+        };
+        if accepts_insertion(text_to_insert) {
+            return Some(text_to_insert.into());
+        }
+        input_mask_literals_after(&text[..anchor], &self.input_mask())
+            .into_iter()
+            .map(|literals| literals + text_to_insert)
+            .find(|inserted| accepts_insertion(inserted))
+            .map(Into::into)
+    }
+
+    /// Returns true if the text that remains after removing the selection is acceptable
+    fn accepts_selection_removal(self: Pin<&Self>) -> bool {
+        let text = self.text();
+        let (anchor, cursor) = self.selection_anchor_and_cursor();
+        self.accepts_text(&[&text[..anchor], &text[cursor..]].concat())
+    }
+
+    /// Returns true if `text` is accepted by the input type, the input mask, and the validator.
+    /// Text that is not complete yet, such as a lone minus sign, is accepted so that it can be typed.
+    fn accepts_text(self: Pin<&Self>, text: &str) -> bool {
+        if text.is_empty() {
+            return true;
+        }
+        let valid_number = match self.input_type() {
+            InputType::Number => is_valid_number(text, false, self.minimum(), self.maximum()),
+            InputType::Decimal => is_valid_number(text, true, self.minimum(), self.maximum()),
+            InputType::Text | InputType::Password => true,
+        };
+        valid_number && matches_input_mask(text, &self.input_mask()) && self.matches_validator(text)
+    }
+
+    /// Returns true if `text` matches the validator, or can still match it once more text is typed
+    #[cfg(feature = "std")]
+    fn matches_validator(self: Pin<&Self>, text: &str) -> bool {
+        use regex_automata::DFA;
+        let validator = self.validator();
+        if validator.is_empty() {
+            return true;
+        }
+        let mut cached_validator = self.data.validator.borrow_mut();
+        if cached_validator.as_ref().map_or(true, |(pattern, _)| *pattern != validator) {
+            // An anchored automaton that doesn't stop at the first match, so that it only reaches
+            // its dead state when no text starting with the input can match.
+            // The number of states of a DFA can grow exponentially with the size of the pattern:
+            // with 16 bits state identifiers, building it fails after 65536 states.
+            let dfa = regex_automata::dense::Builder::new()
+                .anchored(true)
+                .longest_match(true)
+                .premultiply(false)
+                .build_with_size::<u16>(&validator);
+            let dfa = match dfa {
+                Ok(dfa) => Some(dfa),
+                Err(err) => {
+                    eprintln!(
+                        "Invalid or too complex validator for TextInput '{}': {}",
+                        validator, err
+                    );
+                    None
+                }
+            };
+            *cached_validator = Some((validator, dfa));
+        }
+        let dfa = match cached_validator.as_ref().and_then(|(_, dfa)| dfa.as_ref()) {
+            Some(dfa) => dfa,
+            None => return true,
+        };
+        let mut state = dfa.start_state();
+        text.bytes().all(|byte| {
+            state = dfa.next_state(state, byte);
+            !dfa.is_dead_state(state)
+        })
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn matches_validator(self: Pin<&Self>, _text: &str) -> bool {
+        true
+    }
+
     fn select_all(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
         self.move_cursor(TextCursorDirection::StartOfText, AnchorMode::MoveAnchor, window_adapter);
         self.move_cursor(TextCursorDirection::EndOfText, AnchorMode::KeepAnchor, window_adapter);
@@ -824,6 +1406,136 @@ fn paste(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
         }
     }
 
+    /// Reverts the last edit operation and moves it to the redo stack.
+    pub fn undo(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
+        let item = match self.data.undo_items.borrow_mut().pop() {
+            Some(item) => item,
+            None => return,
+        };
+        let text = self.text();
+        let range = item.pos..item.pos + item.inserted.len();
+        if text.get(range.clone()) != Some(item.inserted.as_str()) {
+            // The text was changed programmatically, so the history no longer applies
+            self.clear_undo_history();
+            return;
+        }
+        let text = [&text[..range.start], item.removed.as_str(), &text[range.end..]].concat();
+        self.text.set(text.into());
+        self.anchor_position.set(item.anchor);
+        self.set_cursor_position(item.cursor, true, window_adapter);
+        self.data.redo_items.borrow_mut().push(item);
+        self.update_undo_state();
+        self.show_cursor(window_adapter);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
+    /// Applies again the last edit operation reverted with [`Self::undo`].
+    pub fn redo(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) {
+        let mut item = match self.data.redo_items.borrow_mut().pop() {
+            Some(item) => item,
+            None => return,
+        };
+        let text = self.text();
+        let range = item.pos..item.pos + item.removed.len();
+        if text.get(range.clone()) != Some(item.removed.as_str()) {
+            self.clear_undo_history();
+            return;
+        }
+        let text = [&text[..range.start], item.inserted.as_str(), &text[range.end..]].concat();
+        self.text.set(text.into());
+        let new_cursor_pos = (item.pos + item.inserted.len()) as i32;
+        self.anchor_position.set(new_cursor_pos);
+        self.set_cursor_position(new_cursor_pos, true, window_adapter);
+        // Typing after a redo starts a new undo step
+        item.typing = false;
+        self.data.undo_items.borrow_mut().push(item);
+        self.update_undo_state();
+        self.show_cursor(window_adapter);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
+    /// Adds a new edit operation to the undo stack, merging consecutive typing into a single step.
+    /// The oldest operation is dropped so that the stack never has more than [`MAX_UNDO_ITEMS`] items.
+    fn push_undo_item(self: Pin<&Self>, item: UndoItem) {
+        self.data.redo_items.borrow_mut().clear();
+        let mut undo_items = self.data.undo_items.borrow_mut();
+        match undo_items.last_mut() {
+            Some(last)
+                if item.typing
+                    && last.typing
+                    && item.removed.is_empty()
+                    && last.pos + last.inserted.len() == item.pos
+                    // Each word (with its trailing whitespace) is its own step
+                    && !(last.inserted.ends_with(char::is_whitespace)
+                        && !item.inserted.starts_with(char::is_whitespace)) =>
+            {
+                last.inserted = [last.inserted.as_str(), item.inserted.as_str()].concat().into();
+            }
+            _ => {
+                if undo_items.len() >= MAX_UNDO_ITEMS {
+                    undo_items.remove(0);
+                }
+                undo_items.push(item)
+            }
+        }
+        drop(undo_items);
+        self.update_undo_state();
+    }
+
+    /// Records that `inserted` was inserted at the byte offset `pos`. If the insertion replaces the
+    /// selection, it is combined with the removal that was just recorded by `delete_selection`
+    /// at the same position.
+    fn record_insertion(
+        self: Pin<&Self>,
+        pos: usize,
+        inserted: SharedString,
+        replaces_selection: bool,
+        typing: bool,
+    ) {
+        if replaces_selection {
+            if let Some(last) = self
+                .data
+                .undo_items
+                .borrow_mut()
+                .last_mut()
+                .filter(|last| last.pos == pos && last.inserted.is_empty())
+            {
+                last.inserted = inserted;
+                last.typing = typing;
+                return;
+            }
+        }
+        self.push_undo_item(UndoItem {
+            pos,
+            removed: SharedString::default(),
+            inserted,
+            cursor: pos as i32,
+            anchor: pos as i32,
+            typing,
+        });
+    }
+
+    fn clear_undo_history(self: Pin<&Self>) {
+        self.data.undo_items.borrow_mut().clear();
+        self.data.redo_items.borrow_mut().clear();
+        self.update_undo_state();
+    }
+
+    fn update_undo_state(self: Pin<&Self>) {
+        self.can_undo.set(!self.data.undo_items.borrow().is_empty());
+        self.can_redo.set(!self.data.redo_items.borrow().is_empty());
+    }
+
+    /// Returns the `line-height` property, or None if the line height of the font should be used
+    pub fn explicit_line_height(self: Pin<&Self>) -> Option<Coord> {
+        let line_height = self.line_height();
+        if line_height > 0 as Coord {
+            Some(line_height)
+        } else {
+            None
+        }
+    }
+
     pub fn font_request(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) -> FontRequest {
         let window_item = WindowInner::from_pub(window_adapter.window()).window_item();
 
@@ -853,6 +1565,7 @@ pub fn font_request(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) ->
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            italic: self.font_italic(),
         }
     }
 }
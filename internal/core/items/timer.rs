@@ -0,0 +1,129 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the `Timer` element.
+
+The element is not rendered. It owns a [`crate::timers::Timer`] that is started and stopped
+following the `running` and `interval` properties, and that invokes the `triggered` callback.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::VoidArg;
+use crate::item_rendering::CachedRenderingData;
+use crate::properties::ChangeTracker;
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::timers::{Timer, TimerMode};
+use crate::{Callback, Coord, Property};
+use alloc::boxed::Box;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `Timer` element
+pub struct TimerItem {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    /// The interval in milliseconds
+    pub interval: Property<i64>,
+    pub running: Property<bool>,
+    pub triggered: Callback<VoidArg>,
+    data: TimerItemDataBox,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_non_visual_item!(TimerItem, TimerItem::init_timer);
+
+impl TimerItem {
+    fn init_timer(self: Pin<&Self>) {
+        let self_ptr = self.get_ref() as *const Self;
+        // Safety: the tracker is owned by the item, so it is dropped before the item and the
+        // pointer is valid whenever the functions are called. The item is pinned.
+        self.data.tracker.init(
+            self_ptr,
+            |self_ptr| {
+                let this = unsafe { Pin::new_unchecked(&**self_ptr) };
+                (this.running(), this.interval())
+            },
+            |self_ptr, &(running, interval)| {
+                unsafe { Pin::new_unchecked(&**self_ptr) }.update_timer(running, interval)
+            },
+        );
+        self.update_timer(self.running(), self.interval());
+    }
+
+    /// Starts the timer if it is running with a positive interval, stops it otherwise.
+    /// (Starting an already started timer restarts it with the new interval)
+    fn update_timer(self: Pin<&Self>, running: bool, interval: i64) {
+        if running && interval > 0 {
+            let self_ptr = self.get_ref() as *const Self;
+            self.data.timer.start(
+                TimerMode::Repeated,
+                core::time::Duration::from_millis(interval as u64),
+                move || {
+                    // Safety: the timer is owned by the item, so the item is still alive
+                    // when the timer fires.
+                    let this = unsafe { Pin::new_unchecked(&*self_ptr) };
+                    Self::FIELD_OFFSETS.triggered.apply_pin(this).call(&());
+                },
+            );
+        } else {
+            self.data.timer.stop();
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TimerItemData {
+    timer: Timer,
+    /// Tracks the `running` and `interval` properties
+    tracker: ChangeTracker,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the Timer
+pub struct TimerItemDataBox(core::ptr::NonNull<TimerItemData>);
+
+impl Default for TimerItemDataBox {
+    fn default() -> Self {
+        TimerItemDataBox(Box::leak(Box::new(TimerItemData::default())).into())
+    }
+}
+impl Drop for TimerItemDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TimerItemDataBox::default
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl core::ops::Deref for TimerItemDataBox {
+    type Target = TimerItemData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TimerItemDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TimerItemDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_timer_item_data_init(data: *mut TimerItemDataBox) {
+    core::ptr::write(data, TimerItemDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TimerItemDataBox
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_timer_item_data_free(data: *mut TimerItemDataBox) {
+    core::ptr::drop_in_place(data);
+}
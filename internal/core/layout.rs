@@ -5,7 +5,7 @@
 
 // cspell:ignore coord
 
-use crate::items::{DialogButtonRole, LayoutAlignment};
+use crate::items::{DialogButtonRole, LayoutAlignment, StackAlignment};
 use crate::{slice::Slice, Coord, SharedVector};
 use alloc::vec::Vec;
 
@@ -454,21 +454,14 @@ pub struct BoxLayoutCellData {
     pub constraint: LayoutInfo,
 }
 
-/// Solve a BoxLayout
-pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> SharedVector<Coord> {
-    let mut result = SharedVector::<Coord>::default();
-    result.resize(data.cells.len() * 2 + repeater_indexes.len(), 0 as _);
-
-    if data.cells.is_empty() {
-        return result;
-    }
-
-    let mut layout_data: Vec<_> = data
-        .cells
+/// Create the LayoutData for the cells of a BoxLayout, with the percentage constraints
+/// relative to `size`
+fn box_layout_items(cells: &[BoxLayoutCellData], size: Coord) -> Vec<grid_internal::LayoutData> {
+    cells
         .iter()
         .map(|c| {
-            let min = c.constraint.min.max(c.constraint.min_percent * data.size / 100 as Coord);
-            let max = c.constraint.max.min(c.constraint.max_percent * data.size / 100 as Coord);
+            let min = c.constraint.min.max(c.constraint.min_percent * size / 100 as Coord);
+            let max = c.constraint.max.min(c.constraint.max_percent * size / 100 as Coord);
             grid_internal::LayoutData {
                 min,
                 max,
@@ -477,55 +470,63 @@ pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> S
                 ..Default::default()
             }
         })
-        .collect();
+        .collect()
+}
 
-    let size_without_padding = data.size - data.padding.begin - data.padding.end;
+/// Set the position and size of the items, one after the other, within `size` starting at `begin`
+fn align_items(
+    layout_data: &mut [grid_internal::LayoutData],
+    begin: Coord,
+    size: Coord,
+    spacing: Coord,
+    alignment: LayoutAlignment,
+) {
+    if layout_data.is_empty() {
+        return;
+    }
     let pref_size: Coord = layout_data.iter().map(|it| it.pref).sum();
     let num_spacings = (layout_data.len() - 1) as Coord;
-    let spacings = data.spacing * num_spacings;
+    let spacings = spacing * num_spacings;
 
-    let align = match data.alignment {
+    let align = match alignment {
         LayoutAlignment::Stretch => {
-            grid_internal::layout_items(
-                &mut layout_data,
-                data.padding.begin,
-                size_without_padding,
-                data.spacing,
-            );
+            grid_internal::layout_items(layout_data, begin, size, spacing);
             None
         }
-        _ if size_without_padding <= pref_size + spacings => {
-            grid_internal::layout_items(
-                &mut layout_data,
-                data.padding.begin,
-                size_without_padding,
-                data.spacing,
-            );
+        _ if size <= pref_size + spacings => {
+            grid_internal::layout_items(layout_data, begin, size, spacing);
             None
         }
-        LayoutAlignment::Center => Some((
-            data.padding.begin + (size_without_padding - pref_size - spacings) / 2 as Coord,
-            data.spacing,
-        )),
-        LayoutAlignment::Start => Some((data.padding.begin, data.spacing)),
-        LayoutAlignment::End => {
-            Some((data.padding.begin + (size_without_padding - pref_size - spacings), data.spacing))
-        }
-        LayoutAlignment::SpaceBetween => {
-            Some((data.padding.begin, (size_without_padding - pref_size) / num_spacings))
+        LayoutAlignment::Center => {
+            Some((begin + (size - pref_size - spacings) / 2 as Coord, spacing))
         }
+        LayoutAlignment::Start => Some((begin, spacing)),
+        LayoutAlignment::End => Some((begin + (size - pref_size - spacings), spacing)),
+        LayoutAlignment::SpaceBetween => Some((begin, (size - pref_size) / num_spacings)),
         LayoutAlignment::SpaceAround => {
-            let spacing = (size_without_padding - pref_size) / (num_spacings + 1 as Coord);
-            Some((data.padding.begin + spacing / 2 as Coord, spacing))
+            let spacing = (size - pref_size) / (num_spacings + 1 as Coord);
+            Some((begin + spacing / 2 as Coord, spacing))
         }
     };
     if let Some((mut pos, spacing)) = align {
-        for it in &mut layout_data {
+        for it in layout_data {
             it.pos = pos;
             it.size = it.pref;
             pos += spacing + it.size;
         }
     }
+}
+
+/// Return the layout cache with the position and size of each item.
+///
+/// The items of repeaters are put at the end of the cache, and the cache contains, for each
+/// repeater, the index at which the repeated items start.
+fn to_layout_cache(
+    layout_data: &[grid_internal::LayoutData],
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
+    let mut result = SharedVector::<Coord>::default();
+    result.resize(layout_data.len() * 2 + repeater_indexes.len(), 0 as _);
 
     let res = result.make_mut_slice();
 
@@ -564,6 +565,19 @@ pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> S
     result
 }
 
+/// Solve a BoxLayout
+pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> SharedVector<Coord> {
+    let mut layout_data = box_layout_items(data.cells.as_slice(), data.size);
+    align_items(
+        &mut layout_data,
+        data.padding.begin,
+        data.size - data.padding.begin - data.padding.end,
+        data.spacing,
+        data.alignment,
+    );
+    to_layout_cache(&layout_data, repeater_indexes)
+}
+
 /// Return the LayoutInfo for a BoxLayout with the given cells.
 pub fn box_layout_info(
     cells: Slice<BoxLayoutCellData>,
@@ -607,6 +621,253 @@ pub fn box_layout_info_ortho(cells: Slice<BoxLayoutCellData>, padding: &Padding)
     fold
 }
 
+#[repr(C)]
+#[derive(Debug)]
+/// The FlowLayoutData is used to represent a FlowLayout: the items are placed from left to right
+/// and wrap on a new line when they don't fit in the width of the layout.
+pub struct FlowLayoutData<'a> {
+    pub width: Coord,
+    pub height: Coord,
+    pub spacing: Coord,
+    pub padding_h: Padding,
+    pub padding_v: Padding,
+    /// The alignment of the items within a line
+    pub alignment: LayoutAlignment,
+    /// The alignment of the lines within the layout
+    pub line_alignment: LayoutAlignment,
+    pub cells_h: Slice<'a, BoxLayoutCellData>,
+    pub cells_v: Slice<'a, BoxLayoutCellData>,
+}
+
+/// Split the items in lines. Returns the range of items of each line.
+fn flow_layout_lines(
+    cells_h: &[BoxLayoutCellData],
+    width: Coord,
+    spacing: Coord,
+    padding: &Padding,
+) -> Vec<core::ops::Range<usize>> {
+    let available = width - padding.begin - padding.end;
+    let items = box_layout_items(cells_h, available);
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_width = 0 as Coord;
+    for (idx, item) in items.iter().enumerate() {
+        if idx > line_start && line_width + spacing + item.pref > available {
+            lines.push(line_start..idx);
+            line_start = idx;
+        }
+        line_width = if idx == line_start { item.pref } else { line_width + spacing + item.pref };
+    }
+    if line_start < items.len() {
+        lines.push(line_start..items.len());
+    }
+    lines
+}
+
+/// The LayoutData of each line: the line is as high as its highest item
+fn flow_layout_line_data(
+    cells_v: &[BoxLayoutCellData],
+    lines: &[core::ops::Range<usize>],
+    height: Coord,
+) -> Vec<grid_internal::LayoutData> {
+    let items = box_layout_items(cells_v, height);
+    lines
+        .iter()
+        .map(|line| {
+            items[line.clone()].iter().fold(
+                grid_internal::LayoutData { max: 0 as _, stretch: 0., ..Default::default() },
+                |acc, it| grid_internal::LayoutData {
+                    min: acc.min.max(it.min),
+                    max: acc.max.max(it.max),
+                    pref: acc.pref.max(it.pref),
+                    stretch: acc.stretch.max(it.stretch),
+                    ..acc
+                },
+            )
+        })
+        .collect()
+}
+
+/// Solve the horizontal position of the items of a FlowLayout. Like with a BoxLayout,
+/// the result contains the x position and the width of each item.
+pub fn solve_flow_layout_horizontal(
+    data: &FlowLayoutData,
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
+    let available = data.width - data.padding_h.begin - data.padding_h.end;
+    let mut layout_data = box_layout_items(data.cells_h.as_slice(), available);
+    for line in
+        flow_layout_lines(data.cells_h.as_slice(), data.width, data.spacing, &data.padding_h)
+    {
+        align_items(
+            &mut layout_data[line],
+            data.padding_h.begin,
+            available,
+            data.spacing,
+            data.alignment,
+        );
+    }
+    to_layout_cache(&layout_data, repeater_indexes)
+}
+
+/// Solve the vertical position of the items of a FlowLayout. Like with a BoxLayout,
+/// the result contains the y position and the height of each item.
+pub fn solve_flow_layout_vertical(
+    data: &FlowLayoutData,
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
+    let available = data.height - data.padding_v.begin - data.padding_v.end;
+    let lines =
+        flow_layout_lines(data.cells_h.as_slice(), data.width, data.spacing, &data.padding_h);
+    let mut line_data = flow_layout_line_data(data.cells_v.as_slice(), &lines, available);
+    align_items(&mut line_data, data.padding_v.begin, available, data.spacing, data.line_alignment);
+    let mut layout_data = box_layout_items(data.cells_v.as_slice(), available);
+    for (line, line_data) in lines.into_iter().zip(line_data) {
+        for it in &mut layout_data[line] {
+            it.pos = line_data.pos;
+            it.size = line_data.size.min(it.max).max(it.min);
+        }
+    }
+    to_layout_cache(&layout_data, repeater_indexes)
+}
+
+/// Return the horizontal LayoutInfo of a FlowLayout: its minimum width is the width of the widest
+/// item and its preferred width is the width of all the items on a single line.
+pub fn flow_layout_info_horizontal(
+    cells: Slice<BoxLayoutCellData>,
+    spacing: Coord,
+    padding: &Padding,
+) -> LayoutInfo {
+    let count = cells.len();
+    if count < 1 {
+        return LayoutInfo { max: 0 as _, ..LayoutInfo::default() };
+    };
+    let extra_w = padding.begin + padding.end;
+    let min = cells.iter().map(|c| c.constraint.min).fold(0 as _, Coord::max) + extra_w;
+    let preferred = cells.iter().map(|c| c.constraint.preferred_bounded()).sum::<Coord>()
+        + spacing * (count - 1) as Coord
+        + extra_w;
+    let stretch = cells.iter().map(|c| c.constraint.stretch).sum::<f32>();
+    LayoutInfo {
+        min,
+        max: Coord::MAX,
+        min_percent: 0 as _,
+        max_percent: 100 as _,
+        preferred: preferred.max(min),
+        stretch,
+    }
+}
+
+/// Return the vertical LayoutInfo of a FlowLayout, which depends on its width
+pub fn flow_layout_info_vertical(data: &FlowLayoutData) -> LayoutInfo {
+    let lines =
+        flow_layout_lines(data.cells_h.as_slice(), data.width, data.spacing, &data.padding_h);
+    if lines.is_empty() {
+        return LayoutInfo { max: 0 as _, ..LayoutInfo::default() };
+    }
+    let line_data = flow_layout_line_data(data.cells_v.as_slice(), &lines, data.height);
+    let extra_h =
+        data.padding_v.begin + data.padding_v.end + data.spacing * (lines.len() - 1) as Coord;
+    let min = line_data.iter().map(|l| l.min).sum::<Coord>() + extra_h;
+    let max = if data.line_alignment == LayoutAlignment::Stretch {
+        line_data.iter().map(|l| l.max).fold(extra_h, Saturating::add).max(min)
+    } else {
+        Coord::MAX
+    };
+    let preferred = line_data.iter().map(|l| l.pref).sum::<Coord>() + extra_h;
+    let stretch = line_data.iter().map(|l| l.stretch).sum::<f32>();
+    LayoutInfo { min, max, min_percent: 0 as _, max_percent: 100 as _, preferred, stretch }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+/// The StackLayoutData represents one orientation of a StackLayout: all the items are placed
+/// on top of each other and aligned within the layout according to their alignment.
+pub struct StackLayoutData<'a> {
+    pub size: Coord,
+    pub padding: Padding,
+    /// The alignment of each element of the layout. A repeater has only one alignment for
+    /// all its items
+    pub alignments: Slice<'a, StackAlignment>,
+    pub cells: Slice<'a, BoxLayoutCellData>,
+}
+
+/// Return the alignment of each cell, expanding the alignment of the repeaters to all their items
+fn stack_layout_alignments(
+    data: &StackLayoutData,
+    repeater_indexes: Slice<u32>,
+) -> Vec<StackAlignment> {
+    let mut result = Vec::with_capacity(data.cells.len());
+    let mut repeaters = repeater_indexes.chunks(2).peekable();
+    for alignment in data.alignments.iter() {
+        match repeaters.peek() {
+            Some(&[start, count]) if *start as usize == result.len() => {
+                result.extend(core::iter::repeat(*alignment).take(*count as usize));
+                repeaters.next();
+            }
+            _ => result.push(*alignment),
+        }
+    }
+    result
+}
+
+/// Solve one orientation of a StackLayout. Like with a BoxLayout, the result contains the
+/// position and size of each item.
+pub fn solve_stack_layout(
+    data: &StackLayoutData,
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
+    let available = data.size - data.padding.begin - data.padding.end;
+    let mut layout_data = box_layout_items(data.cells.as_slice(), available);
+    for (it, alignment) in
+        layout_data.iter_mut().zip(stack_layout_alignments(data, repeater_indexes))
+    {
+        if alignment == StackAlignment::Stretch {
+            it.size = available.min(it.max).max(it.min);
+            it.pos = data.padding.begin;
+        } else {
+            it.size = it.pref.min(available).max(it.min);
+            it.pos = data.padding.begin
+                + match alignment {
+                    StackAlignment::Center => (available - it.size) / 2 as Coord,
+                    StackAlignment::End => available - it.size,
+                    _ => 0 as _,
+                };
+        }
+    }
+    to_layout_cache(&layout_data, repeater_indexes)
+}
+
+/// Return the LayoutInfo of one orientation of a StackLayout: it must be big enough for all
+/// its items, and only the stretched items limit its maximum size.
+pub fn stack_layout_info(data: &StackLayoutData, repeater_indexes: Slice<u32>) -> LayoutInfo {
+    if data.cells.is_empty() {
+        return LayoutInfo { max: 0 as _, ..LayoutInfo::default() };
+    };
+    let extra = data.padding.begin + data.padding.end;
+    let mut fold = data.cells.iter().zip(stack_layout_alignments(data, repeater_indexes)).fold(
+        LayoutInfo { stretch: f32::MAX, ..Default::default() },
+        |a, (b, alignment)| {
+            if alignment == StackAlignment::Stretch {
+                a.merge(&b.constraint)
+            } else {
+                a.merge(&LayoutInfo {
+                    max: Coord::MAX,
+                    max_percent: 100 as _,
+                    preferred: b.constraint.preferred_bounded(),
+                    ..b.constraint
+                })
+            }
+        },
+    );
+    fold.max = fold.max.max(fold.min);
+    fold.preferred = fold.preferred.clamp(fold.min, fold.max);
+    fold.min += extra;
+    fold.max = Saturating::add(fold.max, extra);
+    fold.preferred += extra;
+    fold
+}
+
 #[cfg(feature = "std")]
 #[repr(C)]
 pub struct PathLayoutData {
@@ -804,6 +1065,72 @@ fn is_kde() -> bool {
     }
 }
 
+#[test]
+#[allow(clippy::float_cmp)] // We want bit-wise equality here
+fn test_flow_layout() {
+    let cell = |preferred: Coord| BoxLayoutCellData {
+        constraint: LayoutInfo { preferred, stretch: 1., ..Default::default() },
+    };
+    let cells_h = [cell(40 as _), cell(40 as _), cell(40 as _)];
+    let cells_v = [cell(20 as _), cell(30 as _), cell(10 as _)];
+    let data = FlowLayoutData {
+        width: 100 as _,
+        height: 200 as _,
+        spacing: 10 as _,
+        padding_h: Padding::default(),
+        padding_v: Padding::default(),
+        alignment: LayoutAlignment::Start,
+        line_alignment: LayoutAlignment::Start,
+        cells_h: Slice::from_slice(&cells_h),
+        cells_v: Slice::from_slice(&cells_v),
+    };
+    let h = solve_flow_layout_horizontal(&data, Slice::from_slice(&[]));
+    assert_eq!(h.as_slice(), &[0 as Coord, 40 as _, 50 as _, 40 as _, 0 as _, 40 as _]);
+    let v = solve_flow_layout_vertical(&data, Slice::from_slice(&[]));
+    assert_eq!(v.as_slice(), &[0 as Coord, 30 as _, 0 as _, 30 as _, 40 as _, 10 as _]);
+    let info = flow_layout_info_vertical(&data);
+    assert_eq!(info.preferred, 50 as Coord);
+    let info =
+        flow_layout_info_horizontal(Slice::from_slice(&cells_h), 10 as _, &Padding::default());
+    assert_eq!((info.min, info.preferred), (40 as Coord, 140 as Coord));
+
+    // With the Stretch alignment, the items of each line fill the width
+    let data = FlowLayoutData { alignment: LayoutAlignment::Stretch, ..data };
+    let h = solve_flow_layout_horizontal(&data, Slice::from_slice(&[]));
+    assert_eq!(h.as_slice(), &[0 as Coord, 45 as _, 55 as _, 45 as _, 0 as _, 100 as _]);
+}
+
+#[test]
+#[allow(clippy::float_cmp)] // We want bit-wise equality here
+fn test_stack_layout() {
+    let cell = |min: Coord, preferred: Coord, max: Coord| BoxLayoutCellData {
+        constraint: LayoutInfo { min, preferred, max, stretch: 1., ..Default::default() },
+    };
+    let cells = [cell(0 as _, 20 as _, Coord::MAX), cell(10 as _, 20 as _, 50 as _)];
+    let alignments = [StackAlignment::Stretch, StackAlignment::End];
+    let data = StackLayoutData {
+        size: 100 as _,
+        padding: Padding { begin: 5 as _, end: 5 as _ },
+        alignments: Slice::from_slice(&alignments),
+        cells: Slice::from_slice(&cells),
+    };
+    let result = solve_stack_layout(&data, Slice::from_slice(&[]));
+    assert_eq!(result.as_slice(), &[5 as Coord, 90 as _, 75 as _, 20 as _]);
+    let info = stack_layout_info(&data, Slice::from_slice(&[]));
+    assert_eq!((info.min, info.preferred, info.max), (20 as Coord, 30 as _, Coord::MAX));
+
+    // The second alignment applies to the two items of the repeater
+    let cells = [cell(0 as _, 20 as _, Coord::MAX), cell(0 as _, 20 as _, Coord::MAX)];
+    let alignments = [StackAlignment::Center];
+    let data = StackLayoutData {
+        alignments: Slice::from_slice(&alignments),
+        cells: Slice::from_slice(&cells),
+        ..data
+    };
+    let result = solve_stack_layout(&data, Slice::from_slice(&[0, 2]));
+    assert_eq!(result.as_slice(), &[2 as Coord, 3 as _, 40 as _, 20 as _, 40 as _, 20 as _]);
+}
+
 #[cfg(feature = "ffi")]
 pub(crate) mod ffi {
     #![allow(unsafe_code)]
@@ -856,6 +1183,55 @@ pub extern "C" fn slint_box_layout_info_ortho(
         super::box_layout_info_ortho(cells, padding)
     }
 
+    #[no_mangle]
+    pub extern "C" fn slint_solve_flow_layout_horizontal(
+        data: &FlowLayoutData,
+        repeater_indexes: Slice<u32>,
+        result: &mut SharedVector<Coord>,
+    ) {
+        *result = super::solve_flow_layout_horizontal(data, repeater_indexes)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn slint_solve_flow_layout_vertical(
+        data: &FlowLayoutData,
+        repeater_indexes: Slice<u32>,
+        result: &mut SharedVector<Coord>,
+    ) {
+        *result = super::solve_flow_layout_vertical(data, repeater_indexes)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn slint_flow_layout_info_horizontal(
+        cells: Slice<BoxLayoutCellData>,
+        spacing: Coord,
+        padding: &Padding,
+    ) -> LayoutInfo {
+        super::flow_layout_info_horizontal(cells, spacing, padding)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn slint_flow_layout_info_vertical(data: &FlowLayoutData) -> LayoutInfo {
+        super::flow_layout_info_vertical(data)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn slint_solve_stack_layout(
+        data: &StackLayoutData,
+        repeater_indexes: Slice<u32>,
+        result: &mut SharedVector<Coord>,
+    ) {
+        *result = super::solve_stack_layout(data, repeater_indexes)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn slint_stack_layout_info(
+        data: &StackLayoutData,
+        repeater_indexes: Slice<u32>,
+    ) -> LayoutInfo {
+        super::stack_layout_info(data, repeater_indexes)
+    }
+
     #[no_mangle]
     pub extern "C" fn slint_solve_path_layout(
         data: &PathLayoutData,
@@ -37,6 +37,7 @@
 pub mod slice;
 pub mod software_renderer;
 pub mod string;
+pub mod styled_text;
 pub mod tests;
 pub mod textlayout;
 pub mod timers;
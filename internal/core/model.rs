@@ -10,7 +10,7 @@
 use crate::items::ItemRef;
 use crate::layout::Orientation;
 use crate::{Coord, Property, SharedString, SharedVector};
-pub use adapters::{FilterModel, MapModel};
+pub use adapters::{FilterModel, MapModel, SliceModel};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
@@ -413,3 +413,143 @@ fn test_filter_model() {
     assert_eq!(filter.row_data(4).unwrap(), 8);
     assert_eq!(filter.row_count(), 5);
 }
+
+struct SliceModelInner<M: Model + 'static> {
+    wrapped_model: M,
+    start: usize,
+    end: Option<usize>,
+    notify: ModelNotify,
+}
+
+impl<M: Model + 'static> SliceModelInner<M> {
+    /// The range of rows of the wrapped model that are in the slice
+    fn range(&self) -> core::ops::Range<usize> {
+        let count = self.wrapped_model.row_count();
+        let end = self.end.map_or(count, |end| end.min(count));
+        self.start.min(end)..end
+    }
+}
+
+impl<M: Model + 'static> ModelChangeListener for SliceModelInner<M> {
+    fn row_changed(&self, row: usize) {
+        if self.range().contains(&row) {
+            self.notify.row_changed(row - self.start);
+        }
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        if self.end.is_none() && index >= self.start {
+            self.notify.row_added(index - self.start, count);
+        } else if self.end.map_or(true, |end| index < end) {
+            // The rows of the slice are shifted
+            self.notify.reset();
+        }
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        if self.end.is_none() && index >= self.start {
+            self.notify.row_removed(index - self.start, count);
+        } else if self.end.map_or(true, |end| index < end) {
+            self.notify.reset();
+        }
+    }
+
+    fn reset(&self) {
+        self.notify.reset();
+    }
+}
+
+/// Provides the rows of another [`Model`] from a start index up to, but not including, an end index.
+///
+/// When the other Model is updated, the `SliceModel` is updated accordingly.
+///
+/// ## Example
+///
+/// ```
+/// # use i_slint_core::model::{Model, VecModel, SliceModel};
+/// let model = VecModel::from(vec![1, 2, 3, 4, 5]);
+///
+/// let slice = SliceModel::new(model, 1, Some(3));
+///
+/// assert_eq!(slice.row_count(), 2);
+/// assert_eq!(slice.row_data(0).unwrap(), 2);
+/// assert_eq!(slice.row_data(1).unwrap(), 3);
+/// ```
+pub struct SliceModel<M: Model + 'static>(
+    Pin<Box<ModelChangeListenerContainer<SliceModelInner<M>>>>,
+);
+
+impl<M: Model + 'static> SliceModel<M> {
+    /// Creates a new SliceModel with the rows of `wrapped_model` from `start` up to `end`, or up to the
+    /// last row if `end` is None.
+    pub fn new(wrapped_model: M, start: usize, end: Option<usize>) -> Self {
+        let container = Box::pin(ModelChangeListenerContainer::new(SliceModelInner {
+            wrapped_model,
+            start,
+            end,
+            notify: Default::default(),
+        }));
+
+        container.wrapped_model.model_tracker().attach_peer(container.as_ref().model_peer());
+
+        Self(container)
+    }
+}
+
+impl<M: Model + 'static> Model for SliceModel<M> {
+    type Data = M::Data;
+
+    fn row_count(&self) -> usize {
+        self.0.range().len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        if row < self.row_count() {
+            self.0.wrapped_model.row_data(self.0.start + row)
+        } else {
+            None
+        }
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        if row < self.row_count() {
+            self.0.wrapped_model.set_row_data(self.0.start + row, data)
+        }
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.0.notify
+    }
+}
+
+#[test]
+fn test_slice_model() {
+    let wrapped_rc = Rc::new(VecModel::from(vec![1, 2, 3, 4, 5, 6]));
+    let slice = SliceModel::new(wrapped_rc.clone(), 1, Some(4));
+
+    assert_eq!(slice.iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+    wrapped_rc.set_row_data(2, 30);
+    assert_eq!(slice.iter().collect::<Vec<_>>(), vec![2, 30, 4]);
+
+    wrapped_rc.remove(0);
+    assert_eq!(slice.iter().collect::<Vec<_>>(), vec![30, 4, 5]);
+
+    wrapped_rc.insert(1, 12);
+    assert_eq!(slice.iter().collect::<Vec<_>>(), vec![12, 30, 4]);
+
+    wrapped_rc.push(7);
+    assert_eq!(slice.row_count(), 3);
+
+    slice.set_row_data(0, 42);
+    assert_eq!(wrapped_rc.row_data(1).unwrap(), 42);
+
+    let tail = SliceModel::new(wrapped_rc.clone(), 4, None);
+    assert_eq!(tail.iter().collect::<Vec<_>>(), vec![5, 6, 7]);
+    wrapped_rc.push(8);
+    assert_eq!(tail.iter().collect::<Vec<_>>(), vec![5, 6, 7, 8]);
+
+    let empty = SliceModel::new(wrapped_rc.clone(), 5, Some(2));
+    assert_eq!(empty.row_count(), 0);
+    assert_eq!(empty.row_data(0), None);
+}
@@ -7,10 +7,12 @@
 
 #![warn(missing_docs)]
 
+pub use crate::items::{MenuEntry, SystemTrayIconData};
 pub use crate::software_renderer;
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::unsafe_single_threaded::{thread_local, OnceCell};
 pub use crate::window::WindowAdapter;
+use crate::SharedString;
 use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
@@ -70,6 +72,77 @@ fn clipboard_text(&self) -> Option<String> {
         None
     }
 
+    /// Shows a native dialog to choose an existing file. The `callback` must be invoked from the event
+    /// loop once the dialog is closed, with the path of the chosen file, or `None` if the user cancelled.
+    ///
+    /// The default implementation returns [`DialogError::Unsupported`](crate::api::DialogError::Unsupported),
+    /// in which case the `slint` crate shows a dialog made of Slint elements instead, and the other
+    /// APIs report the dialog as closed by the user.
+    fn open_file_dialog(
+        &self,
+        _options: &crate::api::FileDialogOptions,
+        _callback: Box<dyn FnOnce(Option<SharedString>)>,
+    ) -> Result<(), crate::api::DialogError> {
+        Err(crate::api::DialogError::Unsupported)
+    }
+
+    /// Shows a native dialog to choose the path of a file to save. The `callback` must be invoked from the
+    /// event loop once the dialog is closed, with the chosen path, or `None` if the user cancelled.
+    ///
+    /// The default implementation returns [`DialogError::Unsupported`](crate::api::DialogError::Unsupported),
+    /// in which case the `slint` crate shows a dialog made of Slint elements instead, and the other
+    /// APIs report the dialog as closed by the user.
+    fn save_file_dialog(
+        &self,
+        _options: &crate::api::FileDialogOptions,
+        _callback: Box<dyn FnOnce(Option<SharedString>)>,
+    ) -> Result<(), crate::api::DialogError> {
+        Err(crate::api::DialogError::Unsupported)
+    }
+
+    /// Shows a native message box. The `callback` must be invoked from the event loop once the dialog
+    /// is closed, with the button the user clicked.
+    ///
+    /// The default implementation returns [`DialogError::Unsupported`](crate::api::DialogError::Unsupported),
+    /// in which case the `slint` crate shows a dialog made of Slint elements instead, and the other
+    /// APIs report the dialog as closed by the user.
+    fn show_message_dialog(
+        &self,
+        _options: &crate::api::MessageDialogOptions,
+        _callback: Box<dyn FnOnce(crate::api::MessageDialogResult)>,
+    ) -> Result<(), crate::api::DialogError> {
+        Err(crate::api::DialogError::Unsupported)
+    }
+
+    /// Shows the menus of the `MenuBar` element of the window in a native menu bar, or removes the
+    /// native menu bar when `menus` is `None`. This is called again whenever the menus change. Call
+    /// [`MenuEntry::activate()`] when the user activates an entry.
+    ///
+    /// Return the height, in logical pixels, that the native menu bar takes at the top of the window
+    /// (0 if it is shown outside of the window), so that the `MenuBar` element reserves that space.
+    /// The default implementation returns `None`, in which case the `MenuBar` element draws the
+    /// menus with Slint elements.
+    fn set_menu_bar(
+        &self,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _menus: Option<&[MenuEntry]>,
+    ) -> Option<f32> {
+        None
+    }
+
+    /// Shows the icon and context menu of the `SystemTrayIcon` element of the window in the system
+    /// tray, or removes it when `tray_icon` is `None`. This is called again whenever they change.
+    ///
+    /// Return `true` if the icon is shown. The default implementation returns `false`, as there is
+    /// no fallback for the system tray.
+    fn set_system_tray_icon(
+        &self,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _tray_icon: Option<&SystemTrayIconData>,
+    ) -> bool {
+        false
+    }
+
     /// This function is called when debug() is used in .slint files. The implementation
     /// should direct the output to some developer visible terminal. The default implementation
     /// uses stderr if available, or `console.log` when targeting wasm.
@@ -161,11 +234,13 @@ pub fn set_platform(platform: Box<dyn Platform + 'static>) -> Result<(), SetPlat
 }
 
 /// Call this function to update and potentially activate any pending timers, as well
-/// as advance the state of any active animtaions.
+/// as advance the state of any active animtaions. It also runs the `changed` handlers
+/// of the properties that have changed.
 ///
 /// This function should be called before rendering or processing input event, at the
 /// beginning of each event loop iteration.
 pub fn update_timers_and_animations() {
+    crate::properties::ChangeTracker::run_change_handlers();
     crate::timers::TimerList::maybe_activate_timers(crate::animations::Instant::now());
     crate::animations::update_animations();
 }
@@ -1317,6 +1317,216 @@ impl<B: PropertyDirtyHandler> HasBindingVTable for B {
     }
 }
 
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+thread_local!(
+    /// The change trackers that have a dirty dependency and need to be re-evaluated
+    static PENDING_CHANGE_TRACKERS : RefCell<alloc::vec::Vec<alloc::rc::Weak<dyn ChangeTrackerEvaluate>>> = RefCell::default()
+);
+
+trait ChangeTrackerEvaluate {
+    fn evaluate(&self);
+}
+
+/// The dirty handler of the PropertyTracker in a ChangeTracker: queue the change tracker
+/// so that it is evaluated in the next call to [`ChangeTracker::run_change_handlers()`]
+struct ChangeTrackerDirtyHandler(alloc::rc::Weak<dyn ChangeTrackerEvaluate>);
+
+impl PropertyDirtyHandler for ChangeTrackerDirtyHandler {
+    fn notify(&self) {
+        let was_empty = PENDING_CHANGE_TRACKERS.with(|pending| {
+            let mut pending = pending.borrow_mut();
+            pending.push(self.0.clone());
+            pending.len() == 1
+        });
+        if was_empty {
+            // Make sure that the event loop wakes up to run the handlers
+            if let Some(proxy) = crate::platform::event_loop_proxy() {
+                proxy.invoke_from_event_loop(Box::new(ChangeTracker::run_change_handlers)).ok();
+            }
+        }
+    }
+}
+
+struct ChangeTrackerInner<Data, EvalFn, NotifyFn> {
+    tracker: PropertyTracker<ChangeTrackerDirtyHandler>,
+    data: Data,
+    /// Evaluate the value, and return true if it changed
+    eval_fn: EvalFn,
+    notify_fn: NotifyFn,
+}
+
+impl<Data, EvalFn: Fn(&Data) -> bool, NotifyFn: Fn(&Data)> ChangeTrackerEvaluate
+    for ChangeTrackerInner<Data, EvalFn, NotifyFn>
+{
+    fn evaluate(&self) {
+        // Safety: the tracker is within a Rc and never moved
+        let tracker = unsafe { Pin::new_unchecked(&self.tracker) };
+        if tracker.evaluate_as_dependency_root(|| (self.eval_fn)(&self.data)) {
+            (self.notify_fn)(&self.data);
+        }
+    }
+}
+
+/// A ChangeTracker calls a handler when the value returned by an evaluation function changes.
+///
+/// The evaluation function is run within a [`PropertyTracker`]. When any of the properties it
+/// accessed becomes dirty, the change tracker is queued, and is re-evaluated lazily the next time
+/// [`ChangeTracker::run_change_handlers()`] is called. This is done by the event loop, before
+/// processing the timers and before rendering the windows. The handler is only called if the
+/// new value is different from the previous one.
+///
+/// This is used to implement the `changed <property> => { ... }` handlers.
+#[derive(Default)]
+pub struct ChangeTracker {
+    inner: Cell<Option<Rc<dyn ChangeTrackerEvaluate>>>,
+}
+
+impl ChangeTracker {
+    /// Start tracking the value returned by `eval_fn`, and call `notify_fn` after it changed.
+    ///
+    /// `eval_fn` is called once immediately to record the initial value, without calling `notify_fn`.
+    /// Calling `init` again replaces the previous evaluation function and handler.
+    pub fn init<Data: 'static, T: PartialEq + 'static>(
+        &self,
+        data: Data,
+        eval_fn: impl Fn(&Data) -> T + 'static,
+        notify_fn: impl Fn(&Data, &T) + 'static,
+    ) {
+        let value = RefCell::new(None::<T>);
+        self.init_impl(
+            (data, value),
+            move |(data, value)| {
+                let new_value = eval_fn(data);
+                let mut value = value.borrow_mut();
+                if value.as_ref() == Some(&new_value) {
+                    false
+                } else {
+                    *value = Some(new_value);
+                    true
+                }
+            },
+            move |(data, value)| {
+                if let Some(value) = value.borrow().as_ref() {
+                    notify_fn(data, value)
+                }
+            },
+        )
+    }
+
+    /// Same as [`Self::init`], but `eval_fn` compares the values itself and returns true
+    /// if the value changed
+    pub(crate) fn init_impl<Data: 'static>(
+        &self,
+        data: Data,
+        eval_fn: impl Fn(&Data) -> bool + 'static,
+        notify_fn: impl Fn(&Data) + 'static,
+    ) {
+        let inner = Rc::new_cyclic(|weak: &alloc::rc::Weak<ChangeTrackerInner<_, _, _>>| {
+            ChangeTrackerInner {
+                tracker: PropertyTracker::new_with_dirty_handler(ChangeTrackerDirtyHandler(
+                    weak.clone(),
+                )),
+                data,
+                eval_fn,
+                notify_fn,
+            }
+        });
+        // Record the dependencies and the initial value
+        // Safety: the tracker is within a Rc and never moved
+        let tracker = unsafe { Pin::new_unchecked(&inner.tracker) };
+        tracker.evaluate_as_dependency_root(|| (inner.eval_fn)(&inner.data));
+        self.inner.set(Some(inner));
+    }
+
+    /// Evaluate all the change trackers that have a dirty dependency, and call the handlers
+    /// of the ones whose value changed.
+    ///
+    /// A handler might change other properties, so this is repeated until there are no more
+    /// dirty trackers, or after a maximum number of iterations to avoid infinite loops. In that
+    /// case, the remaining trackers are evaluated in the next iteration of the event loop.
+    pub fn run_change_handlers() {
+        const MAX_ITERATIONS: usize = 10;
+        for _ in 0..MAX_ITERATIONS {
+            let pending =
+                PENDING_CHANGE_TRACKERS.with(|pending| core::mem::take(&mut *pending.borrow_mut()));
+            if pending.is_empty() {
+                return;
+            }
+            for tracker in pending {
+                if let Some(tracker) = tracker.upgrade() {
+                    tracker.evaluate();
+                }
+            }
+        }
+        // The dirty handlers only wake up the event loop when the queue was empty, so the
+        // trackers still in the queue would otherwise wait for an unrelated event
+        if PENDING_CHANGE_TRACKERS.with(|pending| !pending.borrow().is_empty()) {
+            if let Some(proxy) = crate::platform::event_loop_proxy() {
+                proxy.invoke_from_event_loop(Box::new(ChangeTracker::run_change_handlers)).ok();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_change_tracker() {
+    let prop = Rc::pin(Property::new(42));
+    let count = Rc::new(Cell::new(0));
+    let last_value = Rc::new(Cell::new(0));
+    let tracker = ChangeTracker::default();
+    tracker.init(
+        (prop.clone(), count.clone(), last_value.clone()),
+        |(prop, ..)| prop.as_ref().get(),
+        |(_, count, last_value), value| {
+            count.set(count.get() + 1);
+            last_value.set(*value);
+        },
+    );
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 0);
+
+    prop.as_ref().set(43);
+    // The handler is only called lazily
+    assert_eq!(count.get(), 0);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 1);
+    assert_eq!(last_value.get(), 43);
+
+    // Changing the value back and forth does not call the handler
+    prop.as_ref().set(44);
+    prop.as_ref().set(43);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 1);
+
+    prop.as_ref().set(45);
+    drop(tracker);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn test_change_tracker_loop() {
+    let prop = Rc::pin(Property::new(0));
+    let tracker = ChangeTracker::default();
+    // The handler changes the property it depends on, so it is never done
+    tracker.init(
+        prop.clone(),
+        |prop| prop.as_ref().get(),
+        |prop, value| prop.as_ref().set(*value + 1),
+    );
+    prop.as_ref().set(1);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(prop.as_ref().get(), 11);
+    // The remaining tracker is still queued for the next call
+    ChangeTracker::run_change_handlers();
+    assert_eq!(prop.as_ref().get(), 21);
+    drop(tracker);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(prop.as_ref().get(), 21);
+}
+
 #[test]
 fn test_property_listener_scope() {
     let scope = Box::pin(PropertyTracker::default());
@@ -159,17 +159,30 @@ pub extern "C" fn slint_property_mark_dirty(handle: &PropertyHandleOpaque) {
     core::ptr::drop_in_place(handle);
 }
 
-fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
+/// Copy the keyframes passed from C++ into a Vec
+unsafe fn c_keyframes<T: Clone>(
+    keyframes: *const KeyFrame<T>,
+    keyframes_len: usize,
+) -> alloc::vec::Vec<KeyFrame<T>> {
+    if keyframes_len == 0 {
+        alloc::vec::Vec::new()
+    } else {
+        core::slice::from_raw_parts(keyframes, keyframes_len).to_vec()
+    }
+}
+
+unsafe fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
     handle: &PropertyHandleOpaque,
     from: T,
     to: T,
     animation_data: &PropertyAnimation,
+    keyframes: *const KeyFrame<T>,
+    keyframes_len: usize,
 ) {
-    let d = RefCell::new(properties_animations::PropertyValueAnimationData::new(
-        from,
-        to,
-        animation_data.clone(),
-    ));
+    let d = RefCell::new(
+        properties_animations::PropertyValueAnimationData::new(from, to, animation_data.clone())
+            .with_keyframes(c_keyframes(keyframes, keyframes_len)),
+    );
     // Safety: The BindingCallable is for type T
     unsafe {
         handle.0.set_binding(move |val: *mut ()| {
@@ -194,8 +207,10 @@ fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
     from: i32,
     to: i32,
     animation_data: &PropertyAnimation,
+    keyframes: *const KeyFrame<i32>,
+    keyframes_len: usize,
 ) {
-    c_set_animated_value(handle, from, to, animation_data)
+    c_set_animated_value(handle, from, to, animation_data, keyframes, keyframes_len)
 }
 
 /// Internal function to set up a property animation to the specified target value for a float property.
@@ -205,8 +220,10 @@ fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
     from: f32,
     to: f32,
     animation_data: &PropertyAnimation,
+    keyframes: *const KeyFrame<f32>,
+    keyframes_len: usize,
 ) {
-    c_set_animated_value(handle, from, to, animation_data)
+    c_set_animated_value(handle, from, to, animation_data, keyframes, keyframes_len)
 }
 
 /// Internal function to set up a property animation to the specified target value for a color property.
@@ -216,8 +233,10 @@ fn c_set_animated_value<T: InterpolatedPropertyValue + Clone>(
     from: Color,
     to: Color,
     animation_data: &PropertyAnimation,
+    keyframes: *const KeyFrame<Color>,
+    keyframes_len: usize,
 ) {
-    c_set_animated_value(handle, from, to, animation_data);
+    c_set_animated_value(handle, from, to, animation_data, keyframes, keyframes_len);
 }
 
 unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
@@ -227,8 +246,15 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
     drop_user_data: Option<extern "C" fn(*mut c_void)>,
     animation_data: Option<&PropertyAnimation>,
     transition_data: Option<
-        extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
+        extern "C" fn(
+            user_data: *mut c_void,
+            start_instant: &mut u64,
+            keyframes: &mut *const KeyFrame<T>,
+            keyframes_len: &mut usize,
+        ) -> PropertyAnimation,
     >,
+    keyframes: *const KeyFrame<T>,
+    keyframes_len: usize,
 ) {
     let binding = core::mem::transmute::<
         extern "C" fn(*mut c_void, *mut T),
@@ -246,20 +272,34 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
                 | 0b10,
         ),
     };
-    let animation_data = RefCell::new(properties_animations::PropertyValueAnimationData::new(
-        T::default(),
-        T::default(),
-        animation_data.cloned().unwrap_or_default(),
-    ));
+    let animation_data = RefCell::new(
+        properties_animations::PropertyValueAnimationData::new(
+            T::default(),
+            T::default(),
+            animation_data.cloned().unwrap_or_default(),
+        )
+        .with_keyframes(c_keyframes(keyframes, keyframes_len)),
+    );
     if let Some(transition_data) = transition_data {
         handle.0.set_binding(properties_animations::AnimatedBindingCallable::<T, _> {
             original_binding,
             state: Cell::new(properties_animations::AnimatedBindingState::NotAnimating),
             animation_data,
-            compute_animation_details: move || -> properties_animations::AnimationDetail {
+            compute_animation_details: move || -> properties_animations::AnimationDetail<T> {
                 let mut start_instant = 0;
-                let anim = transition_data(user_data, &mut start_instant);
-                Some((anim, crate::animations::Instant(start_instant)))
+                let mut keyframes = core::ptr::null();
+                let mut keyframes_len = 0;
+                let anim = transition_data(
+                    user_data,
+                    &mut start_instant,
+                    &mut keyframes,
+                    &mut keyframes_len,
+                );
+                Some((
+                    anim,
+                    crate::animations::Instant(start_instant),
+                    c_keyframes(keyframes, keyframes_len),
+                ))
             },
         });
     } else {
@@ -267,7 +307,7 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
             original_binding,
             state: Cell::new(properties_animations::AnimatedBindingState::NotAnimating),
             animation_data,
-            compute_animation_details: || -> properties_animations::AnimationDetail { None },
+            compute_animation_details: || -> properties_animations::AnimationDetail<T> { None },
         });
     }
     handle.0.mark_dirty();
@@ -282,8 +322,15 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
     drop_user_data: Option<extern "C" fn(*mut c_void)>,
     animation_data: Option<&PropertyAnimation>,
     transition_data: Option<
-        extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
+        extern "C" fn(
+            user_data: *mut c_void,
+            start_instant: &mut u64,
+            keyframes: &mut *const KeyFrame<i32>,
+            keyframes_len: &mut usize,
+        ) -> PropertyAnimation,
     >,
+    keyframes: *const KeyFrame<i32>,
+    keyframes_len: usize,
 ) {
     c_set_animated_binding(
         handle,
@@ -292,6 +339,8 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         drop_user_data,
         animation_data,
         transition_data,
+        keyframes,
+        keyframes_len,
     );
 }
 
@@ -304,8 +353,15 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
     drop_user_data: Option<extern "C" fn(*mut c_void)>,
     animation_data: Option<&PropertyAnimation>,
     transition_data: Option<
-        extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
+        extern "C" fn(
+            user_data: *mut c_void,
+            start_instant: &mut u64,
+            keyframes: &mut *const KeyFrame<f32>,
+            keyframes_len: &mut usize,
+        ) -> PropertyAnimation,
     >,
+    keyframes: *const KeyFrame<f32>,
+    keyframes_len: usize,
 ) {
     c_set_animated_binding(
         handle,
@@ -314,6 +370,8 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         drop_user_data,
         animation_data,
         transition_data,
+        keyframes,
+        keyframes_len,
     );
 }
 
@@ -326,8 +384,15 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
     drop_user_data: Option<extern "C" fn(*mut c_void)>,
     animation_data: Option<&PropertyAnimation>,
     transition_data: Option<
-        extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
+        extern "C" fn(
+            user_data: *mut c_void,
+            start_instant: &mut u64,
+            keyframes: &mut *const KeyFrame<Color>,
+            keyframes_len: &mut usize,
+        ) -> PropertyAnimation,
     >,
+    keyframes: *const KeyFrame<Color>,
+    keyframes_len: usize,
 ) {
     c_set_animated_binding(
         handle,
@@ -336,6 +401,8 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         drop_user_data,
         animation_data,
         transition_data,
+        keyframes,
+        keyframes_len,
     );
 }
 
@@ -348,8 +415,15 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
     drop_user_data: Option<extern "C" fn(*mut c_void)>,
     animation_data: Option<&PropertyAnimation>,
     transition_data: Option<
-        extern "C" fn(user_data: *mut c_void, start_instant: &mut u64) -> PropertyAnimation,
+        extern "C" fn(
+            user_data: *mut c_void,
+            start_instant: &mut u64,
+            keyframes: &mut *const KeyFrame<Brush>,
+            keyframes_len: &mut usize,
+        ) -> PropertyAnimation,
     >,
+    keyframes: *const KeyFrame<Brush>,
+    keyframes_len: usize,
 ) {
     c_set_animated_binding(
         handle,
@@ -358,6 +432,8 @@ unsafe fn c_set_animated_binding<T: InterpolatedPropertyValue + Clone>(
         drop_user_data,
         animation_data,
         transition_data,
+        keyframes,
+        keyframes_len,
     );
 }
 
@@ -451,6 +527,56 @@ pub struct PropertyTrackerOpaque {
     core::ptr::drop_in_place(handle as *mut PropertyTracker);
 }
 
+#[repr(C)]
+/// Opaque type representing the ChangeTracker
+pub struct ChangeTrackerOpaque {
+    inner: [usize; 2],
+}
+
+static_assertions::assert_eq_align!(ChangeTrackerOpaque, ChangeTracker);
+static_assertions::assert_eq_size!(ChangeTrackerOpaque, ChangeTracker);
+
+/// Initialize the change tracker.
+/// `out` is assumed to be uninitialized
+/// slint_change_tracker_drop need to be called after that
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_construct(out: *mut ChangeTrackerOpaque) {
+    core::ptr::write(out as *mut ChangeTracker, ChangeTracker::default());
+}
+
+/// Set the functions of the change tracker. `eval_fn` returns true if the value changed, in which
+/// case `notify_fn` will be called. `drop_user_data` is called when the tracker no longer needs
+/// the user data.
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_init(
+    handle: *const ChangeTrackerOpaque,
+    user_data: *mut c_void,
+    drop_user_data: extern "C" fn(user_data: *mut c_void),
+    eval_fn: extern "C" fn(user_data: *mut c_void) -> bool,
+    notify_fn: extern "C" fn(user_data: *mut c_void),
+) {
+    struct UserData {
+        user_data: *mut c_void,
+        drop_user_data: extern "C" fn(user_data: *mut c_void),
+    }
+    impl Drop for UserData {
+        fn drop(&mut self) {
+            (self.drop_user_data)(self.user_data)
+        }
+    }
+    (*(handle as *const ChangeTracker)).init_impl(
+        UserData { user_data, drop_user_data },
+        move |data| eval_fn(data.user_data),
+        move |data| notify_fn(data.user_data),
+    );
+}
+
+/// Destroy the change tracker
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_drop(handle: *mut ChangeTrackerOpaque) {
+    core::ptr::drop_in_place(handle as *mut ChangeTracker);
+}
+
 /// return the current animation tick for the `animation-tick` function
 #[no_mangle]
 pub extern "C" fn slint_animation_tick() -> u64 {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use super::*;
-use crate::items::PropertyAnimation;
+use crate::items::{AnimationDirection, PropertyAnimation};
+use alloc::vec::Vec;
 
 enum AnimationState {
     Delaying,
@@ -10,10 +11,25 @@ enum AnimationState {
     Done,
 }
 
+/// A keyframe of a property animation: the value that the property has at a given
+/// position of an iteration of the animation.
+#[repr(C)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyFrame<T> {
+    /// The position of the keyframe within one iteration, between 0 and 1
+    pub position: f32,
+    /// The value of the property at this position
+    pub value: T,
+    /// The easing curve used to interpolate from this keyframe to the next one
+    pub easing: crate::animations::EasingCurve,
+}
+
 pub(super) struct PropertyValueAnimationData<T> {
     from_value: T,
     to_value: T,
     details: PropertyAnimation,
+    /// Sorted by position. The segment before the first keyframe uses the easing of `details`.
+    keyframes: Vec<KeyFrame<T>>,
     start_time: crate::animations::Instant,
     state: AnimationState,
 }
@@ -22,7 +38,52 @@ impl<T: InterpolatedPropertyValue + Clone> PropertyValueAnimationData<T> {
     pub fn new(from_value: T, to_value: T, details: PropertyAnimation) -> Self {
         let start_time = crate::animations::current_tick();
 
-        Self { from_value, to_value, details, start_time, state: AnimationState::Delaying }
+        Self {
+            from_value,
+            to_value,
+            details,
+            keyframes: Vec::new(),
+            start_time,
+            state: AnimationState::Delaying,
+        }
+    }
+
+    pub fn with_keyframes(mut self, keyframes: Vec<KeyFrame<T>>) -> Self {
+        self.set_keyframes(keyframes);
+        self
+    }
+
+    fn set_keyframes(&mut self, mut keyframes: Vec<KeyFrame<T>>) {
+        keyframes.retain(|k| (0. ..=1.).contains(&k.position));
+        keyframes.sort_by(|a, b| {
+            a.position.partial_cmp(&b.position).unwrap_or(core::cmp::Ordering::Equal)
+        });
+        self.keyframes = keyframes;
+    }
+
+    /// Returns the value at the given progress (between 0 and 1) of one iteration.
+    /// At the position of a keyframe, including 0% and 100%, the value is the one of the keyframe.
+    fn value_at(&self, progress: f32) -> T {
+        let mut start = (0., &self.from_value, &self.details.easing);
+        for k in &self.keyframes {
+            if progress <= k.position {
+                return Self::interpolate_segment(start, (k.position, &k.value), progress);
+            }
+            start = (k.position, &k.value, &k.easing);
+        }
+        Self::interpolate_segment(start, (1., &self.to_value), progress)
+    }
+
+    fn interpolate_segment(
+        (start_position, start_value, easing): (f32, &T, &crate::animations::EasingCurve),
+        (end_position, end_value): (f32, &T),
+        progress: f32,
+    ) -> T {
+        let length = end_position - start_position;
+        let local_progress =
+            if length > 0. { ((progress - start_position) / length).clamp(0., 1.) } else { 1. };
+        let t = crate::animations::easing_curve(easing, local_progress);
+        start_value.interpolate(end_value, t)
     }
 
     pub fn compute_interpolated_value(&mut self) -> (T, bool) {
@@ -50,12 +111,15 @@ pub fn compute_interpolated_value(&mut self) -> (T, bool) {
                 }
             }
             AnimationState::Animating { mut current_iteration } => {
-                if self.details.duration <= 0 || self.details.iteration_count == 0. {
+                // A spring easing curve has its own duration
+                let duration =
+                    self.details.easing.settling_duration().unwrap_or(self.details.duration);
+                if duration <= 0 || self.details.iteration_count == 0. {
                     self.state = AnimationState::Done;
                     return self.compute_interpolated_value();
                 }
 
-                let duration = self.details.duration as u64;
+                let duration = duration as u64;
                 if time_progress >= duration {
                     // wrap around
                     current_iteration += time_progress / duration;
@@ -70,10 +134,14 @@ pub fn compute_interpolated_value(&mut self) -> (T, bool) {
                 {
                     self.state = AnimationState::Animating { current_iteration };
 
-                    let progress =
-                        (time_progress as f32 / self.details.duration as f32).clamp(0., 1.);
-                    let t = crate::animations::easing_curve(&self.details.easing, progress);
-                    let val = self.from_value.interpolate(&self.to_value, t);
+                    let progress = (time_progress as f32 / duration as f32).clamp(0., 1.);
+                    let reversed = match self.details.direction {
+                        AnimationDirection::Normal => false,
+                        AnimationDirection::Reverse => true,
+                        AnimationDirection::Alternate => current_iteration % 2 == 1,
+                        AnimationDirection::AlternateReverse => current_iteration % 2 == 0,
+                    };
+                    let val = self.value_at(if reversed { 1. - progress } else { progress });
 
                     (val, false)
                 } else {
@@ -105,9 +173,11 @@ pub(super) struct AnimatedBindingCallable<T, A> {
     pub(super) compute_animation_details: A,
 }
 
-pub(super) type AnimationDetail = Option<(PropertyAnimation, crate::animations::Instant)>;
+/// The animation of a transition, the time at which the transition started, and the keyframes
+pub(super) type AnimationDetail<T> =
+    Option<(PropertyAnimation, crate::animations::Instant, Vec<KeyFrame<T>>)>;
 
-unsafe impl<T: InterpolatedPropertyValue + Clone, A: Fn() -> AnimationDetail> BindingCallable
+unsafe impl<T: InterpolatedPropertyValue + Clone, A: Fn() -> AnimationDetail<T>> BindingCallable
     for AnimatedBindingCallable<T, A>
 {
     unsafe fn evaluate(self: Pin<&Self>, value: *mut ()) -> BindingResult {
@@ -137,9 +207,10 @@ unsafe fn evaluate(self: Pin<&Self>, value: *mut ()) -> BindingResult {
                 // animation_data.details.iteration_count = 1.;
                 animation_data.from_value = value.clone();
                 self.original_binding.update((&mut animation_data.to_value) as *mut T as *mut ());
-                if let Some((details, start_time)) = (self.compute_animation_details)() {
+                if let Some((details, start_time, keyframes)) = (self.compute_animation_details)() {
                     animation_data.start_time = start_time;
                     animation_data.details = details;
+                    animation_data.set_keyframes(keyframes);
                 }
                 let (val, finished) = animation_data.compute_interpolated_value();
                 *value = val;
@@ -208,12 +279,26 @@ impl<T: Clone + InterpolatedPropertyValue + 'static> Property<T> {
     /// If other properties have binding depending of this property, these properties will
     /// be marked as dirty.
     pub fn set_animated_value(&self, value: T, animation_data: PropertyAnimation) {
+        self.set_animated_value_with_keyframes(value, animation_data, Vec::new())
+    }
+
+    /// Same as [`Self::set_animated_value`], but the animation goes through the values
+    /// of the specified keyframes.
+    pub fn set_animated_value_with_keyframes(
+        &self,
+        value: T,
+        animation_data: PropertyAnimation,
+        keyframes: Vec<KeyFrame<T>>,
+    ) {
         // FIXME if the current value is a dirty binding, we must run it, but we do not have the context
-        let d = RefCell::new(properties_animations::PropertyValueAnimationData::new(
-            self.get_internal(),
-            value,
-            animation_data,
-        ));
+        let d = RefCell::new(
+            properties_animations::PropertyValueAnimationData::new(
+                self.get_internal(),
+                value,
+                animation_data,
+            )
+            .with_keyframes(keyframes),
+        );
         // Safety: the BindingCallable will cast its argument to T
         unsafe {
             self.handle.set_binding(
@@ -244,6 +329,17 @@ pub fn set_animated_binding(
         &self,
         binding: impl Binding<T> + 'static,
         animation_data: PropertyAnimation,
+    ) {
+        self.set_animated_binding_with_keyframes(binding, animation_data, Vec::new())
+    }
+
+    /// Set a binding to this property. When the value of the binding changes, the property
+    /// is animated through the values of the specified keyframes.
+    pub fn set_animated_binding_with_keyframes(
+        &self,
+        binding: impl Binding<T> + 'static,
+        animation_data: PropertyAnimation,
+        keyframes: Vec<KeyFrame<T>>,
     ) {
         let binding_callable = properties_animations::AnimatedBindingCallable::<T, _> {
             original_binding: PropertyHandle {
@@ -257,12 +353,15 @@ pub fn set_animated_binding(
                 ),
             },
             state: Cell::new(properties_animations::AnimatedBindingState::NotAnimating),
-            animation_data: RefCell::new(properties_animations::PropertyValueAnimationData::new(
-                T::default(),
-                T::default(),
-                animation_data,
-            )),
-            compute_animation_details: || -> properties_animations::AnimationDetail { None },
+            animation_data: RefCell::new(
+                properties_animations::PropertyValueAnimationData::new(
+                    T::default(),
+                    T::default(),
+                    animation_data,
+                )
+                .with_keyframes(keyframes),
+            ),
+            compute_animation_details: || -> properties_animations::AnimationDetail<T> { None },
         };
 
         // Safety: the `AnimatedBindingCallable`'s type match the property type
@@ -279,12 +378,13 @@ pub fn set_animated_binding(
         );
     }
 
-    /// Set a binding to this property, providing a callback for the transition animation
-    ///
+    /// Set a binding to this property, providing a callback for the transition animation.
+    /// The callback returns the animation, the time at which the transition started, and
+    /// the keyframes of the animation.
     pub fn set_animated_binding_for_transition(
         &self,
         binding: impl Binding<T> + 'static,
-        compute_animation_details: impl Fn() -> (PropertyAnimation, crate::animations::Instant)
+        compute_animation_details: impl Fn() -> (PropertyAnimation, crate::animations::Instant, Vec<KeyFrame<T>>)
             + 'static,
     ) {
         let binding_callable = properties_animations::AnimatedBindingCallable::<T, _> {
@@ -959,4 +1059,113 @@ fn test_loop_via_binding() {
 
         assert_eq!(get_prop_value(&compo.width), 300);
     }
+
+    #[test]
+    fn test_keyframes() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+
+        let start_time = crate::animations::current_tick();
+
+        compo.width.set_animated_value_with_keyframes(
+            200,
+            animation_details,
+            vec![KeyFrame { position: 0.5, value: 300, easing: Default::default() }],
+        );
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        assert_eq!(get_prop_value(&compo.width), 300);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION * 3 / 4));
+        assert_eq!(get_prop_value(&compo.width), 250);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION));
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        // the binding should be removed
+        compo.width.handle.access(|binding| assert!(binding.is_none()));
+    }
+
+    #[test]
+    fn test_alternate_direction() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            iteration_count: 2.,
+            direction: AnimationDirection::Alternate,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+
+        let start_time = crate::animations::current_tick();
+
+        compo.width.set_animated_value(200, animation_details);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 125);
+
+        // The second iteration runs backwards
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 175);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION + DURATION * 3 / 4));
+        assert_eq!(get_prop_value(&compo.width), 125);
+
+        // At the end, the property has its target value
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION * 2));
+        assert_eq!(get_prop_value(&compo.width), 200);
+    }
+
+    #[test]
+    fn test_keyframes_at_bounds() {
+        let animation = PropertyValueAnimationData::new(
+            100,
+            200,
+            PropertyAnimation { duration: 1000, ..PropertyAnimation::default() },
+        )
+        .with_keyframes(vec![
+            KeyFrame { position: 1., value: 400, easing: Default::default() },
+            KeyFrame { position: 0., value: 0, easing: Default::default() },
+        ]);
+        assert_eq!(animation.value_at(0.), 0);
+        assert_eq!(animation.value_at(0.25), 100);
+        assert_eq!(animation.value_at(0.5), 200);
+        assert_eq!(animation.value_at(1.), 400);
+
+        // Without keyframes at the bounds, the animation goes from the start to the end value
+        let animation = PropertyValueAnimationData::new(
+            100,
+            200,
+            PropertyAnimation { duration: 1000, ..PropertyAnimation::default() },
+        )
+        .with_keyframes(vec![KeyFrame {
+            position: 0.5,
+            value: 300,
+            easing: Default::default(),
+        }]);
+        assert_eq!(animation.value_at(0.), 100);
+        assert_eq!(animation.value_at(0.5), 300);
+        assert_eq!(animation.value_at(1.), 200);
+    }
 }
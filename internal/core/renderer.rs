@@ -18,6 +18,35 @@ fn text_size(
         scale_factor: f32,
     ) -> Size;
 
+    /// Returns the size of the given styled text in logical pixels, taking the font of each span
+    /// into account.
+    /// `line_height` is the distance between two lines (the height of the font when None), and when
+    /// `max_lines` is set, only the first `max_lines` lines are measured.
+    /// The default implementation measures the text as if it was rendered with the font of `font_request`,
+    /// and ignores `line_height` and `max_lines`
+    fn styled_text_size(
+        &self,
+        font_request: crate::graphics::FontRequest,
+        styled_text: &crate::styled_text::StyledText,
+        max_width: Option<Coord>,
+        _line_height: Option<Coord>,
+        _max_lines: Option<usize>,
+        scale_factor: f32,
+    ) -> Size {
+        self.text_size(font_request, styled_text.text.as_str(), max_width, scale_factor)
+    }
+
+    /// Returns the (UTF-8) byte offset in the [`crate::items::Text::styled_text`] of the character that
+    /// is at the given coordinate, or None if there is no character there.
+    /// This is used to find out which link was clicked.
+    fn text_byte_offset_for_position(
+        &self,
+        _text: Pin<&crate::items::Text>,
+        _pos: Point,
+    ) -> Option<usize> {
+        None
+    }
+
     /// Returns the (UTF-8) byte offset in the text property that refers to the character that contributed to
     /// the glyph cluster that's visually nearest to the given coordinate. This is used for hit-testing,
     /// for example when receiving a mouse click into a text field. Then this function returns the "cursor"
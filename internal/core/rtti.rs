@@ -8,7 +8,9 @@
 
 pub type FieldOffset<T, U> = const_field_offset::FieldOffset<T, U, const_field_offset::AllowPin>;
 use crate::items::PropertyAnimation;
+use crate::properties::KeyFrame;
 use alloc::rc::Rc;
+use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 use core::pin::Pin;
 
@@ -47,39 +49,58 @@ macro_rules! declare_ValueType_2 {
 i_slint_common::for_each_enums!(declare_ValueType_2);
 
 /// What kind of animation is on a binding
-pub enum AnimatedBindingKind {
+pub enum AnimatedBindingKind<Value> {
     /// No animation is on the binding
     NotAnimated,
-    /// Single animation
-    Animation(PropertyAnimation),
-    /// Transition
-    Transition(Box<dyn Fn() -> (PropertyAnimation, crate::animations::Instant)>),
+    /// Single animation, going through the keyframes if there are any
+    Animation(PropertyAnimation, Vec<KeyFrame<Value>>),
+    /// Transition: the callback returns the animation, the time at which the transition
+    /// started, and the keyframes of the animation
+    Transition(
+        Box<dyn Fn() -> (PropertyAnimation, crate::animations::Instant, Vec<KeyFrame<Value>>)>,
+    ),
 }
 
-impl AnimatedBindingKind {
-    /// return a PropertyAnimation if self contains AnimatedBindingKind::Animation
-    pub fn as_animation(self) -> Option<PropertyAnimation> {
+impl<Value> AnimatedBindingKind<Value> {
+    /// return a PropertyAnimation and its keyframes if self contains AnimatedBindingKind::Animation
+    pub fn as_animation(self) -> Option<(PropertyAnimation, Vec<KeyFrame<Value>>)> {
         match self {
             AnimatedBindingKind::NotAnimated => None,
-            AnimatedBindingKind::Animation(a) => Some(a),
+            AnimatedBindingKind::Animation(a, keyframes) => Some((a, keyframes)),
             AnimatedBindingKind::Transition(_) => None,
         }
     }
 }
 
+/// Convert the values of the keyframes to the type of the property
+fn convert_keyframes<T, Value: TryInto<T>>(
+    keyframes: Vec<KeyFrame<Value>>,
+) -> Result<Vec<KeyFrame<T>>, ()> {
+    keyframes
+        .into_iter()
+        .map(|k| {
+            Ok(KeyFrame {
+                position: k.position,
+                value: k.value.try_into().map_err(|_| ())?,
+                easing: k.easing,
+            })
+        })
+        .collect()
+}
+
 pub trait PropertyInfo<Item, Value> {
     fn get(&self, item: Pin<&Item>) -> Result<Value, ()>;
     fn set(
         &self,
         item: Pin<&Item>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Vec<KeyFrame<Value>>)>,
     ) -> Result<(), ()>;
     fn set_binding(
         &self,
         item: Pin<&Item>,
         binding: Box<dyn Fn() -> Value>,
-        animation: AnimatedBindingKind,
+        animation: AnimatedBindingKind<Value>,
     ) -> Result<(), ()>;
 
     /// The offset of the property in the item.
@@ -116,7 +137,7 @@ fn set(
         &self,
         item: Pin<&Item>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Vec<KeyFrame<Value>>)>,
     ) -> Result<(), ()> {
         if animation.is_some() {
             Err(())
@@ -129,7 +150,7 @@ fn set_binding(
         &self,
         item: Pin<&Item>,
         binding: Box<dyn Fn() -> Value>,
-        animation: AnimatedBindingKind,
+        animation: AnimatedBindingKind<Value>,
     ) -> Result<(), ()> {
         if !matches!(animation, AnimatedBindingKind::NotAnimated) {
             Err(())
@@ -172,10 +193,14 @@ fn set(
         &self,
         item: Pin<&Item>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Vec<KeyFrame<Value>>)>,
     ) -> Result<(), ()> {
-        if let Some(animation) = animation {
-            self.apply_pin(item).set_animated_value(value.try_into().map_err(|_| ())?, animation);
+        if let Some((animation, keyframes)) = animation {
+            self.apply_pin(item).set_animated_value_with_keyframes(
+                value.try_into().map_err(|_| ())?,
+                animation,
+                convert_keyframes(keyframes)?,
+            );
             Ok(())
         } else {
             self.0.set(item, value, None)
@@ -185,13 +210,13 @@ fn set_binding(
         &self,
         item: Pin<&Item>,
         binding: Box<dyn Fn() -> Value>,
-        animation: AnimatedBindingKind,
+        animation: AnimatedBindingKind<Value>,
     ) -> Result<(), ()> {
         // Put in a function that does not depends on Item to avoid code bloat
         fn set_binding_impl<T: Clone + 'static, Value: 'static>(
             p: Pin<&crate::Property<T>>,
             binding: Box<dyn Fn() -> Value>,
-            animation: AnimatedBindingKind,
+            animation: AnimatedBindingKind<Value>,
         ) -> Result<(), ()>
         where
             Value: TryInto<T>,
@@ -205,8 +230,8 @@ fn set_binding_impl<T: Clone + 'static, Value: 'static>(
                     });
                     Ok(())
                 }
-                AnimatedBindingKind::Animation(animation) => {
-                    p.set_animated_binding(
+                AnimatedBindingKind::Animation(animation, keyframes) => {
+                    p.set_animated_binding_with_keyframes(
                         move || {
                             binding()
                                 .try_into()
@@ -214,6 +239,7 @@ fn set_binding_impl<T: Clone + 'static, Value: 'static>(
                                 .expect("binding was of the wrong type")
                         },
                         animation,
+                        convert_keyframes(keyframes)?,
                     );
                     Ok(())
                 }
@@ -225,7 +251,14 @@ fn set_binding_impl<T: Clone + 'static, Value: 'static>(
                                 .map_err(|_| ())
                                 .expect("binding was of the wrong type")
                         },
-                        tr,
+                        move || {
+                            let (animation, start_time, keyframes) = tr();
+                            (
+                                animation,
+                                start_time,
+                                convert_keyframes(keyframes).unwrap_or_default(),
+                            )
+                        },
                     );
                     Ok(())
                 }
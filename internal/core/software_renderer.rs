@@ -6,18 +6,22 @@
 #![warn(missing_docs)]
 
 mod draw_functions;
+mod effects;
 mod fonts;
 
 use crate::api::Window;
-use crate::graphics::{IntRect, PixelFormat, Rect as RectF, SharedImageBuffer};
+use crate::graphics::{
+    IntRect, PixelFormat, Rect as RectF, Rgba8Pixel, SharedImageBuffer, SharedPixelBuffer,
+};
 use crate::item_rendering::ItemRenderer;
-use crate::items::{ImageFit, ItemRc};
+use crate::items::{ImageFit, ItemRc, RenderingResult};
 use crate::lengths::{
     LogicalItemGeometry, LogicalLength, LogicalPoint, LogicalRect, PhysicalLength, PhysicalPoint,
     PhysicalPx, PhysicalRect, PhysicalSize, PointLengths, RectLengths, ScaleFactor, SizeLengths,
 };
 use crate::renderer::Renderer;
-use crate::textlayout::{FontMetrics as _, TextParagraphLayout};
+use crate::styled_text::BOLD_FONT_WEIGHT;
+use crate::textlayout::{FontMetrics as _, FontRun, TextParagraphLayout};
 use crate::window::{WindowAdapter, WindowInner};
 use crate::{Color, Coord, ImageInner, StaticTextures};
 use alloc::rc::{Rc, Weak};
@@ -254,7 +258,28 @@ fn text_size(
         max_width: Option<Coord>,
         scale_factor: f32,
     ) -> crate::graphics::Size {
-        fonts::text_size(font_request, text, max_width, ScaleFactor::new(scale_factor)).to_untyped()
+        fonts::text_size(font_request, text, max_width, None, None, ScaleFactor::new(scale_factor))
+            .to_untyped()
+    }
+
+    fn styled_text_size(
+        &self,
+        font_request: crate::graphics::FontRequest,
+        styled_text: &crate::styled_text::StyledText,
+        max_width: Option<Coord>,
+        line_height: Option<Coord>,
+        max_lines: Option<usize>,
+        scale_factor: f32,
+    ) -> crate::graphics::Size {
+        fonts::text_size(
+            font_request,
+            &styled_text.text,
+            max_width,
+            line_height,
+            max_lines,
+            ScaleFactor::new(scale_factor),
+        )
+        .to_untyped()
     }
 
     fn text_input_byte_offset_for_position(
@@ -264,6 +289,47 @@ fn text_input_byte_offset_for_position(
     ) -> usize {
         0
     }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&crate::items::Text>,
+        pos: crate::graphics::Point,
+    ) -> Option<usize> {
+        let window = self.window.upgrade()?;
+        let window_inner = WindowInner::from_pub(window.window());
+        let scale_factor = ScaleFactor::new(window_inner.scale_factor());
+        let styled_text = text.styled_text();
+
+        let font_request = text.font_request(window_inner);
+        let font = fonts::match_font(&font_request, scale_factor);
+        let span_fonts = fonts::match_span_fonts(&styled_text, &font_request, scale_factor);
+        let font_runs = span_fonts
+            .iter()
+            .map(|(byte_range, font)| FontRun { byte_range: byte_range.clone(), font })
+            .collect::<Vec<_>>();
+        let max_size: PhysicalSize =
+            (text.logical_geometry().size_length().cast() * scale_factor).cast();
+
+        let paragraph = TextParagraphLayout {
+            string: &styled_text.text,
+            layout: fonts::text_layout_for_font(&font, &font_request, scale_factor),
+            max_width: max_size.width_length(),
+            max_height: max_size.height_length(),
+            horizontal_alignment: text.horizontal_alignment(),
+            vertical_alignment: text.vertical_alignment(),
+            wrap: text.wrap(),
+            overflow: text.overflow(),
+            single_line: false,
+            font_runs: &font_runs,
+            line_height: text
+                .explicit_line_height()
+                .map(|line_height| (LogicalLength::new(line_height).cast() * scale_factor).cast()),
+            max_lines: text.max_line_count(),
+        };
+
+        let pos: PhysicalPoint = (LogicalPoint::from_untyped(pos).cast() * scale_factor).cast();
+        paragraph.byte_offset_for_position((pos.x_length(), pos.y_length()))
+    }
     fn text_input_cursor_rect_for_byte_offset(
         &self,
         _text_input: Pin<&crate::items::TextInput>,
@@ -680,6 +746,8 @@ trait ProcessScene {
     fn process_rectangle(&mut self, geometry: PhysicalRect, color: Color);
     fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, data: RoundedRectangle);
     fn process_shared_image_buffer(&mut self, geometry: PhysicalRect, buffer: SharedBufferCommand);
+    /// Blurs what was already drawn within `geometry`, reading only the pixels within `clip`
+    fn process_backdrop_blur(&mut self, geometry: PhysicalRect, clip: PhysicalRect, blur: f32);
 }
 
 struct RenderToBuffer<'a, TargetPixel> {
@@ -732,6 +800,10 @@ fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, rr: RoundedRecta
             );
         }
     }
+
+    fn process_backdrop_blur(&mut self, geometry: PhysicalRect, clip: PhysicalRect, blur: f32) {
+        effects::blur_backdrop(self.buffer, self.stride, geometry, clip, blur);
+    }
 }
 
 #[derive(Default)]
@@ -794,6 +866,10 @@ fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, data: RoundedRec
             });
         }
     }
+
+    fn process_backdrop_blur(&mut self, _geometry: PhysicalRect, _clip: PhysicalRect, _blur: f32) {
+        // The scene is rendered line by line, so the content underneath is not available
+    }
 }
 
 struct SceneBuilder<'a, T> {
@@ -827,12 +903,76 @@ fn new(
         }
     }
 
+    /// Draws the rows `rows` of the glyph, whose top left corner is at `glyph_origin` (relative
+    /// to the current offset)
+    fn draw_glyph_rows(
+        &mut self,
+        glyph: &fonts::PlatformGlyph,
+        glyph_origin: PhysicalPoint,
+        rows: core::ops::Range<i16>,
+        physical_clip: &euclid::Rect<f32, PhysicalPx>,
+        color: Color,
+    ) {
+        let src_rect = PhysicalRect::new(
+            glyph_origin + euclid::vec2(0, rows.start),
+            PhysicalSize::new(glyph.width().get(), rows.end - rows.start),
+        )
+        .cast();
+        let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
+
+        if let Some(clipped_src) = src_rect.intersection(physical_clip) {
+            let geometry = clipped_src.translate(offset).round();
+            let origin = (geometry.origin - offset.round()).cast::<i16>();
+            let actual_x = (origin.x - glyph_origin.x) as usize;
+            let actual_y = (origin.y - glyph_origin.y) as usize;
+            let stride = glyph.width().get() as u16;
+            let geometry = geometry.cast();
+            self.processor.process_texture(
+                geometry,
+                SceneTexture {
+                    data: &glyph.data().as_slice()[actual_x + actual_y * stride as usize..],
+                    stride,
+                    source_size: geometry.size,
+                    format: PixelFormat::AlphaMap,
+                    color,
+                },
+            );
+        }
+    }
+
     fn should_draw(&self, rect: &LogicalRect) -> bool {
         !rect.size.is_empty()
             && self.current_state.alpha > 0.01
             && self.current_state.clip.intersects(rect)
     }
 
+    /// Draws the parts of an image that is scaled with nine-slice borders or tiled, each of them
+    /// stretched into its target rectangle.
+    fn draw_image_slices(
+        &mut self,
+        source: &crate::graphics::Image,
+        slices: &[crate::items::ImageSlice],
+        colorize: Color,
+    ) {
+        let (offset, clip) = (self.current_state.offset, self.current_state.clip);
+        for slice in slices {
+            let target = LogicalRect::from_untyped(&slice.target);
+            if !self.should_draw(&target) {
+                continue;
+            }
+            self.translate(target.origin.x, target.origin.y);
+            self.draw_image_impl(
+                LogicalRect::new(LogicalPoint::default(), target.size),
+                source,
+                slice.source,
+                ImageFit::Fill,
+                colorize,
+            );
+            self.current_state.offset = offset;
+            self.current_state.clip = clip;
+        }
+    }
+
     fn draw_image_impl(
         &mut self,
         geom: LogicalRect,
@@ -841,6 +981,13 @@ fn draw_image_impl(
         image_fit: ImageFit,
         colorize: Color,
     ) {
+        if image_fit == ImageFit::Tile {
+            // There is no texture repeat here, so each repetition is drawn as a slice
+            let slices =
+                crate::items::image_slices(source_rect, [0; 4], geom.size.to_untyped(), true);
+            self.draw_image_slices(source, &slices, colorize);
+            return;
+        }
         let image_inner: &ImageInner = source.into();
         let size: euclid::default::Size2D<u32> = source_rect.size.cast();
         let phys_size = geom.size_length().cast() * self.scale_factor;
@@ -848,7 +995,8 @@ fn draw_image_impl(
         let source_to_target_y = phys_size.height / (size.height as f32);
         let mut image_fit_offset = euclid::Vector2D::default();
         let (source_to_target_x, source_to_target_y) = match image_fit {
-            ImageFit::Fill => (source_to_target_x, source_to_target_y),
+            // Tiled images are drawn in slices, see above
+            ImageFit::Fill | ImageFit::Tile => (source_to_target_x, source_to_target_y),
             ImageFit::Cover => {
                 let ratio = f32::max(source_to_target_x, source_to_target_y);
                 if size.width as f32 > phys_size.width / ratio {
@@ -1089,7 +1237,7 @@ fn draw_image(&mut self, image: Pin<&crate::items::ImageItem>, _: &ItemRc) {
         let geom =
             LogicalRect::new(LogicalPoint::default(), image.logical_geometry().size_length());
         if self.should_draw(&geom) {
-            let source = image.source();
+            let source = image.current_frame();
             self.draw_image_impl(
                 geom,
                 &source,
@@ -1104,25 +1252,15 @@ fn draw_clipped_image(&mut self, image: Pin<&crate::items::ClippedImage>, _: &It
         let geom =
             LogicalRect::new(LogicalPoint::default(), image.logical_geometry().size_length());
         if self.should_draw(&geom) {
-            let source = image.source();
-
-            let source_clip_x = image.source_clip_x();
-            let source_clip_y = image.source_clip_y();
-            let source_size = source.size();
-            let mut source_clip_width = image.source_clip_width();
-            // when the source_clip size is empty, make it full
-            if source_clip_width == 0 {
-                source_clip_width = source_size.width as i32 - source_clip_x;
-            }
-            let mut source_clip_height = image.source_clip_height();
-            if source_clip_height == 0 {
-                source_clip_height = source_size.height as i32 - source_clip_y;
+            let source = image.current_frame();
+            if let Some(slices) = image.slices() {
+                self.draw_image_slices(&source, &slices, image.colorize().color());
+                return;
             }
-
             self.draw_image_impl(
                 geom,
                 &source,
-                euclid::rect(source_clip_x, source_clip_y, source_clip_width, source_clip_height),
+                image.source_clip_rect(),
                 image.image_fit(),
                 image.colorize().color(),
             );
@@ -1130,8 +1268,8 @@ fn draw_clipped_image(&mut self, image: Pin<&crate::items::ClippedImage>, _: &It
     }
 
     fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
-        let string = text.text();
-        if string.trim().is_empty() {
+        let styled_text = text.styled_text();
+        if styled_text.text.trim().is_empty() {
             return;
         }
         let geom = LogicalRect::new(LogicalPoint::default(), text.logical_geometry().size_length());
@@ -1141,13 +1279,18 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
 
         let font_request = text.font_request(self.window);
         let font = fonts::match_font(&font_request, self.scale_factor);
+        let span_fonts = fonts::match_span_fonts(&styled_text, &font_request, self.scale_factor);
+        let font_runs = span_fonts
+            .iter()
+            .map(|(byte_range, font)| FontRun { byte_range: byte_range.clone(), font })
+            .collect::<Vec<_>>();
         let layout = fonts::text_layout_for_font(&font, &font_request, self.scale_factor);
 
-        let color = text.color().color();
+        let default_color = text.color().color();
         let max_size = (geom.size.cast() * self.scale_factor).cast();
 
         let paragraph = TextParagraphLayout {
-            string: &string,
+            string: &styled_text.text,
             layout,
             max_width: max_size.width_length(),
             max_height: max_size.height_length(),
@@ -1156,6 +1299,11 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
             wrap: text.wrap(),
             overflow: text.overflow(),
             single_line: false,
+            font_runs: &font_runs,
+            line_height: text.explicit_line_height().map(|line_height| {
+                (LogicalLength::new(line_height).cast() * self.scale_factor).cast()
+            }),
+            max_lines: text.max_line_count(),
         };
 
         // Clip glyphs not only against the global clip but also against the Text's geometry to avoid drawing outside
@@ -1169,38 +1317,78 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
         };
         let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
 
+        // The bitmap fonts don't have metrics for the decorations, so approximate them from the font size
+        let decoration_thickness = PhysicalLength::new((font.height().get() / 16).max(1));
+        let mut decorations = Vec::new();
+        if text.underline() {
+            decorations.push(decoration_thickness);
+        }
+        if text.strikethrough() {
+            decorations.push(-font.ascent() * 3 / 10);
+        }
+
+        let bold = font_request.weight.map_or(false, |weight| weight >= BOLD_FONT_WEIGHT);
+        let italic = font_request.italic;
+
         paragraph.layout_lines(|glyphs, line_x, line_y| {
             let baseline_y = line_y + font.ascent();
             while let Some(positioned_glyph) = glyphs.next() {
-                let src_rect = PhysicalRect::new(
-                    PhysicalPoint::from_lengths(
-                        line_x + positioned_glyph.x + positioned_glyph.platform_glyph.x(),
-                        baseline_y
-                            - positioned_glyph.platform_glyph.y()
-                            - positioned_glyph.platform_glyph.height(),
-                    ),
-                    positioned_glyph.platform_glyph.size(),
-                )
-                .cast();
+                let span = styled_text.span_at(positioned_glyph.text_byte_offset);
+                let color = span.and_then(|span| span.color).unwrap_or(default_color);
+                let bold = bold || span.map_or(false, |span| span.bold);
+                let italic = italic || span.map_or(false, |span| span.italic);
+
+                // The decorations are drawn glyph by glyph, in the color of the glyph
+                for decoration_offset in &decorations {
+                    let decoration_rect = PhysicalRect::new(
+                        PhysicalPoint::from_lengths(
+                            line_x + positioned_glyph.x,
+                            baseline_y + *decoration_offset,
+                        ),
+                        PhysicalSize::from_lengths(positioned_glyph.advance, decoration_thickness),
+                    );
+                    if let Some(clipped_rect) = decoration_rect.cast().intersection(&physical_clip)
+                    {
+                        self.processor.process_rectangle(
+                            clipped_rect.translate(offset).round().cast(),
+                            color,
+                        );
+                    }
+                }
 
-                if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
-                    let geometry = clipped_src.translate(offset).round();
-                    let origin = (geometry.origin - offset.round()).cast::<usize>();
-                    let actual_x = origin.x - src_rect.origin.x as usize;
-                    let actual_y = origin.y - src_rect.origin.y as usize;
-                    let stride = positioned_glyph.platform_glyph.width().get() as u16;
-                    let geometry = geometry.cast();
-                    self.processor.process_texture(
-                        geometry,
-                        SceneTexture {
-                            data: &positioned_glyph.platform_glyph.data().as_slice()
-                                [actual_x + actual_y * stride as usize..],
-                            stride,
-                            source_size: geometry.size,
-                            format: PixelFormat::AlphaMap,
+                let glyph = &positioned_glyph.platform_glyph;
+                let glyph_origin = PhysicalPoint::from_lengths(
+                    line_x + positioned_glyph.x + glyph.x(),
+                    baseline_y - glyph.y() - glyph.height(),
+                );
+                // The bitmap fonts have no bold or italic variants: bold is synthesized by drawing
+                // the glyph a second time, shifted by a stroke width, and italic by shearing
+                // the rows of the glyph.
+                let stroke_count = if bold { 2 } else { 1 };
+                let shear = |row: i16| {
+                    if italic {
+                        (baseline_y.get() - glyph_origin.y - row).div_euclid(5)
+                    } else {
+                        0
+                    }
+                };
+                let mut row = 0;
+                while row < glyph.height().get() {
+                    let first_row = row;
+                    let x_shift = shear(row);
+                    while row < glyph.height().get() && shear(row) == x_shift {
+                        row += 1;
+                    }
+                    for stroke in 0..stroke_count {
+                        self.draw_glyph_rows(
+                            glyph,
+                            glyph_origin
+                                + euclid::vec2(x_shift + stroke * decoration_thickness.get(), 0),
+                            first_row..row,
+                            &physical_clip,
                             color,
-                        },
-                    );
+                        );
+                    }
                 }
             }
         });
@@ -1222,6 +1410,105 @@ fn draw_box_shadow(&mut self, box_shadow: Pin<&crate::items::BoxShadow>, _: &Ite
         // TODO
     }
 
+    fn visit_effect(
+        &mut self,
+        effect_item: Pin<&crate::items::Effect>,
+        item_rc: &ItemRc,
+    ) -> RenderingResult {
+        let backdrop_blur = effect_item.effect_backdrop_blur();
+        if backdrop_blur > 0 as Coord {
+            let geom = LogicalRect::new(
+                LogicalPoint::default(),
+                effect_item.logical_geometry().size_length(),
+            );
+            if self.should_draw(&geom) {
+                let (offset, scale_factor) =
+                    (self.current_state.offset.to_vector(), self.scale_factor);
+                let to_physical = |rect: LogicalRect| -> PhysicalRect {
+                    (rect.translate(offset).cast() * scale_factor).round().cast()
+                };
+                // The pixels outside of the clip may not have been drawn for this frame
+                self.processor.process_backdrop_blur(
+                    to_physical(geom),
+                    to_physical(self.current_state.clip),
+                    backdrop_blur as f32 * scale_factor.0,
+                );
+            }
+        }
+
+        if !effect_item.has_content_effect() {
+            return RenderingResult::ContinueRenderingChildren;
+        }
+
+        // The children are rendered into a buffer that extends by the margin on each side.
+        let margin = effect_item.margin();
+        let size = effect_item.logical_geometry().size;
+        let bounds = LogicalRect::new(
+            LogicalPoint::new(-margin, -margin),
+            euclid::size2(size.width + 2 as Coord * margin, size.height + 2 as Coord * margin),
+        );
+        if !self.should_draw(&bounds) {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        }
+        let buffer_size: PhysicalSize = (bounds.size.cast() * self.scale_factor).ceil().cast();
+        let width = buffer_size.width as usize;
+        let mut buffer =
+            vec![PremultipliedRgbaColor::default(); width * buffer_size.height as usize];
+        if buffer.is_empty() {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        }
+        {
+            let mut layer_renderer = SceneBuilder::new(
+                buffer_size,
+                self.scale_factor,
+                self.window,
+                RenderToBuffer { buffer: &mut buffer, stride: width },
+            );
+            layer_renderer.translate(margin, margin);
+            crate::item_rendering::render_item_children(
+                &mut layer_renderer,
+                &item_rc.component(),
+                item_rc.index() as isize,
+            );
+        }
+
+        let scale_factor = self.scale_factor.0;
+        let drop_shadow = effect_item.has_drop_shadow().then(|| effects::DropShadow {
+            offset_x: (effect_item.drop_shadow_offset_x() as f32 * scale_factor).round() as i32,
+            offset_y: (effect_item.drop_shadow_offset_y() as f32 * scale_factor).round() as i32,
+            blur: effect_item.drop_shadow_blur() as f32 * scale_factor,
+            color: effect_item.drop_shadow_color().into(),
+        });
+        effects::apply_effects(
+            &mut buffer,
+            width,
+            effect_item.color_matrix(),
+            effect_item.effect_blur() as f32 * scale_factor,
+            drop_shadow,
+        );
+
+        let mut pixel_buffer =
+            SharedPixelBuffer::<Rgba8Pixel>::new(width as u32, buffer_size.height as u32);
+        for (target, pixel) in pixel_buffer.make_mut_slice().iter_mut().zip(buffer) {
+            *target = Rgba8Pixel { r: pixel.red, g: pixel.green, b: pixel.blue, a: pixel.alpha };
+        }
+        let image = crate::graphics::Image::from_rgba8_premultiplied(pixel_buffer);
+
+        let (offset, clip) = (self.current_state.offset, self.current_state.clip);
+        self.translate(-margin, -margin);
+        self.draw_image_impl(
+            LogicalRect::new(LogicalPoint::default(), bounds.size),
+            &image,
+            euclid::rect(0, 0, width as i32, buffer_size.height as i32),
+            ImageFit::Fill,
+            Default::default(),
+        );
+        self.current_state.offset = offset;
+        self.current_state.clip = clip;
+
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+
     fn combine_clip(&mut self, other: RectF, _radius: Coord, _border_width: Coord) -> bool {
         match self.current_state.clip.intersection(&LogicalRect::from_untyped(&other)) {
             Some(r) => {
@@ -328,6 +328,12 @@ fn blend_slice(slice: &mut [Self], color: PremultipliedRgbaColor) {
     }
     /// Create a pixel from the red, gree, blue component in the range 0..=255
     fn from_rgb(red: u8, green: u8, blue: u8) -> Self;
+    /// Returns the red, green and blue component of the pixel in the range 0..=255, or None if the
+    /// pixel can't be read back. The renderer reads the pixels to blur what is drawn underneath
+    /// an element with a `backdrop-blur`.
+    fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        None
+    }
 }
 
 #[cfg(feature = "embedded-graphics")]
@@ -344,6 +350,10 @@ fn blend(&mut self, color: PremultipliedRgbaColor) {
     fn from_rgb(r: u8, g: u8, b: u8) -> Self {
         Self::new(r, g, b)
     }
+
+    fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        Some((self.r(), self.g(), self.b()))
+    }
 }
 
 #[cfg(feature = "embedded-graphics")]
@@ -360,6 +370,24 @@ fn blend(&mut self, color: PremultipliedRgbaColor) {
     fn from_rgb(r: u8, g: u8, b: u8) -> Self {
         Self::new(r >> 3, g >> 2, b >> 3)
     }
+
+    fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        Some((self.r() << 3, self.g() << 2, self.b() << 3))
+    }
+}
+
+impl TargetPixel for PremultipliedRgbaColor {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        self.red = (self.red as u16 * a / 255) as u8 + color.red;
+        self.green = (self.green as u16 * a / 255) as u8 + color.green;
+        self.blue = (self.blue as u16 * a / 255) as u8 + color.blue;
+        self.alpha = (self.alpha as u16 * a / 255) as u8 + color.alpha;
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { red: r, green: g, blue: b, alpha: u8::MAX }
+    }
 }
 
 impl TargetPixel for crate::graphics::image::Rgb8Pixel {
@@ -373,6 +401,10 @@ fn blend(&mut self, color: PremultipliedRgbaColor) {
     fn from_rgb(r: u8, g: u8, b: u8) -> Self {
         Self::new(r, g, b)
     }
+
+    fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        Some((self.r, self.g, self.b))
+    }
 }
 
 /// A 16bit pixel that has 5 red bits, 6 green bits and  5 blue bits
@@ -430,6 +462,10 @@ fn blend(&mut self, color: PremultipliedRgbaColor) {
     fn from_rgb(r: u8, g: u8, b: u8) -> Self {
         Self(((r as u16 & 0b11111000) << 8) | ((g as u16 & 0b11111100) << 3) | (b as u16 >> 3))
     }
+
+    fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        Some((self.red(), self.green(), self.blue()))
+    }
 }
 
 impl From<Rgb8Pixel> for Rgb565Pixel {
@@ -0,0 +1,237 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! This module contains the functions that apply the color filters, the blur and the drop shadow
+//! of the `Effect` element to the pixels of its children, once they were rendered into a buffer,
+//! and the function that blurs the pixels underneath an element with a `backdrop-blur`.
+
+use super::draw_functions::{PremultipliedRgbaColor, TargetPixel};
+use crate::graphics::Rgba8Pixel;
+use crate::items::ColorMatrix;
+use crate::lengths::PhysicalRect;
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+#[allow(unused)]
+use num_traits::Float;
+
+/// The drop shadow of the content, in physical pixels
+pub(super) struct DropShadow {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub blur: f32,
+    pub color: PremultipliedRgbaColor,
+}
+
+/// Applies the effects to the pixels of the buffer, which has `width` pixels per line.
+/// `blur` is in physical pixels.
+pub(super) fn apply_effects(
+    pixels: &mut [PremultipliedRgbaColor],
+    width: usize,
+    color_matrix: Option<ColorMatrix>,
+    blur: f32,
+    drop_shadow: Option<DropShadow>,
+) {
+    if let Some(matrix) = color_matrix {
+        for pixel in pixels.iter_mut() {
+            let filtered = crate::items::apply_color_matrix(
+                &matrix,
+                Rgba8Pixel { r: pixel.red, g: pixel.green, b: pixel.blue, a: pixel.alpha },
+            );
+            *pixel = PremultipliedRgbaColor {
+                red: filtered.r,
+                green: filtered.g,
+                blue: filtered.b,
+                alpha: filtered.a,
+            };
+        }
+    }
+    box_blur(pixels, width, blur);
+    if let Some(drop_shadow) = drop_shadow {
+        draw_drop_shadow(pixels, width, &drop_shadow);
+    }
+}
+
+/// Blurs the pixels of `geometry` in the buffer, which has `stride` pixels per line. Only the pixels
+/// within `clip` are read and written. `blur` is in physical pixels.
+/// Does nothing if the pixels of the buffer can't be read back.
+pub(super) fn blur_backdrop<T: TargetPixel>(
+    buffer: &mut [T],
+    stride: usize,
+    geometry: PhysicalRect,
+    clip: PhysicalRect,
+    blur: f32,
+) {
+    let buffer_rect = PhysicalRect::new(
+        Default::default(),
+        euclid::size2(stride as i16, (buffer.len() / stride.max(1)) as i16),
+    );
+    let clip = match clip.intersection(&buffer_rect) {
+        Some(clip) => clip,
+        None => return,
+    };
+    // The pixels around the geometry, within the reach of the blur, are part of the backdrop
+    let margin = blur.ceil() as i16;
+    let (geometry, area) = match (
+        geometry.intersection(&clip),
+        geometry.inflate(margin, margin).intersection(&clip),
+    ) {
+        (Some(geometry), Some(area)) => (geometry, area),
+        _ => return,
+    };
+
+    let width = area.width() as usize;
+    let mut pixels = Vec::with_capacity(width * area.height() as usize);
+    for y in area.y_range() {
+        let line = &buffer[y as usize * stride + area.min_x() as usize..][..width];
+        for pixel in line {
+            let (red, green, blue) = match pixel.to_rgb() {
+                Some(rgb) => rgb,
+                None => return,
+            };
+            pixels.push(PremultipliedRgbaColor { red, green, blue, alpha: u8::MAX });
+        }
+    }
+
+    box_blur(&mut pixels, width, blur);
+
+    for y in geometry.y_range() {
+        let line = &pixels[(y - area.min_y()) as usize * width..][..width];
+        for x in geometry.x_range() {
+            let pixel = line[(x - area.min_x()) as usize];
+            // The blur considers the pixels outside of the area as transparent, so the pixels
+            // close to its edges are made opaque again
+            let alpha = pixel.alpha.max(1) as u16;
+            let channel = |c: u8| (c as u16 * 255 / alpha).min(255) as u8;
+            buffer[y as usize * stride + x as usize] =
+                T::from_rgb(channel(pixel.red), channel(pixel.green), channel(pixel.blue));
+        }
+    }
+}
+
+/// Draws the shadow underneath the content: the content filled with the shadow color, blurred and offset.
+fn draw_drop_shadow(pixels: &mut [PremultipliedRgbaColor], width: usize, drop_shadow: &DropShadow) {
+    let height = pixels.len() / width;
+    let color = drop_shadow.color;
+    let mut shadow = pixels
+        .iter()
+        .map(|pixel| {
+            let a = pixel.alpha as u16;
+            PremultipliedRgbaColor {
+                red: (color.red as u16 * a / 255) as u8,
+                green: (color.green as u16 * a / 255) as u8,
+                blue: (color.blue as u16 * a / 255) as u8,
+                alpha: (color.alpha as u16 * a / 255) as u8,
+            }
+        })
+        .collect::<Vec<_>>();
+    box_blur(&mut shadow, width, drop_shadow.blur);
+
+    for y in 0..height {
+        let shadow_y = y as i32 - drop_shadow.offset_y;
+        if shadow_y < 0 || shadow_y >= height as i32 {
+            continue;
+        }
+        for x in 0..width {
+            let shadow_x = x as i32 - drop_shadow.offset_x;
+            if shadow_x < 0 || shadow_x >= width as i32 {
+                continue;
+            }
+            let pixel = &mut pixels[y * width + x];
+            let mut result = shadow[shadow_y as usize * width + shadow_x as usize];
+            result.blend(*pixel);
+            *pixel = result;
+        }
+    }
+}
+
+/// Approximates a gaussian blur with a standard deviation of `blur / 2` with three passes
+/// of a box blur.
+fn box_blur(pixels: &mut [PremultipliedRgbaColor], width: usize, blur: f32) {
+    // Three passes of a box of `w = 2 * radius + 1` pixels have a variance of `(w² - 1) / 4`
+    let radius = (((blur * blur + 1.).sqrt() - 1.) / 2.).round() as usize;
+    if radius == 0 || width == 0 {
+        return;
+    }
+    let height = pixels.len() / width;
+    let mut line = Vec::with_capacity(width.max(height));
+    for _ in 0..3 {
+        for y in 0..height {
+            blur_line(pixels, y * width, 1, width, radius, &mut line);
+        }
+        for x in 0..width {
+            blur_line(pixels, x, width, height, radius, &mut line);
+        }
+    }
+}
+
+/// Blurs the `len` pixels that start at `start` and are `step` pixels apart, with a box of
+/// `2 * radius + 1` pixels. The pixels outside of the line are transparent.
+fn blur_line(
+    pixels: &mut [PremultipliedRgbaColor],
+    start: usize,
+    step: usize,
+    len: usize,
+    radius: usize,
+    line: &mut Vec<PremultipliedRgbaColor>,
+) {
+    line.clear();
+    line.extend((0..len).map(|i| pixels[start + i * step]));
+
+    let window = 2 * radius as u32 + 1;
+    let mut sum = [0u32; 4];
+    let channels = |pixel: &PremultipliedRgbaColor| {
+        [pixel.red as u32, pixel.green as u32, pixel.blue as u32, pixel.alpha as u32]
+    };
+    for pixel in line.iter().take(radius) {
+        sum.iter_mut().zip(channels(pixel)).for_each(|(s, c)| *s += c);
+    }
+    for i in 0..len {
+        if let Some(pixel) = line.get(i + radius) {
+            sum.iter_mut().zip(channels(pixel)).for_each(|(s, c)| *s += c);
+        }
+        pixels[start + i * step] = PremultipliedRgbaColor {
+            red: (sum[0] / window) as u8,
+            green: (sum[1] / window) as u8,
+            blue: (sum[2] / window) as u8,
+            alpha: (sum[3] / window) as u8,
+        };
+        if i >= radius {
+            sum.iter_mut().zip(channels(&line[i - radius])).for_each(|(s, c)| *s -= c);
+        }
+    }
+}
+
+#[test]
+fn blur_line_with_box() {
+    let mut pixels = alloc::vec![PremultipliedRgbaColor::default(); 11 * 11];
+    pixels[5 * 11 + 5] = PremultipliedRgbaColor { red: 0, green: 0, blue: 0, alpha: 255 };
+    let mut line = Vec::new();
+    blur_line(&mut pixels, 5 * 11, 1, 11, 1, &mut line);
+    assert_eq!(pixels[5 * 11 + 4].alpha, 85);
+    assert_eq!(pixels[5 * 11 + 5].alpha, 85);
+    assert_eq!(pixels[5 * 11 + 6].alpha, 85);
+    assert_eq!(pixels[5 * 11 + 7].alpha, 0);
+}
+
+#[test]
+fn blur_backdrop_within_geometry() {
+    use crate::graphics::Rgb8Pixel;
+    let white = Rgb8Pixel::new(255, 255, 255);
+    let mut buffer = [Rgb8Pixel::default(); 12 * 3];
+    for line in buffer.chunks_mut(12) {
+        line[6..].fill(white);
+    }
+    let geometry = PhysicalRect::new(euclid::point2(4, 0), euclid::size2(4, 3));
+    let clip = PhysicalRect::new(euclid::point2(0, 0), euclid::size2(12, 3));
+    blur_backdrop(&mut buffer, 12, geometry, clip, 2.);
+    for line in buffer.chunks(12) {
+        assert_eq!(line[3], Rgb8Pixel::default());
+        assert!(line[5].r > 0 && line[5].r < 255);
+        assert!(line[6].r > 0 && line[6].r < 255);
+        assert_eq!(line[8], white);
+    }
+    // Pixels that can't be read back are left untouched
+    let mut buffer = [PremultipliedRgbaColor::default(); 12 * 3];
+    blur_backdrop(&mut buffer, 12, geometry, clip, 2.);
+    assert!(buffer.iter().all(|pixel| pixel.alpha == 0));
+}
@@ -3,6 +3,7 @@
 
 use alloc::vec::Vec;
 use core::cell::RefCell;
+use core::ops::Range;
 
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::thread_local_ as thread_local;
@@ -10,6 +11,7 @@
 use crate::graphics::{BitmapFont, BitmapGlyph, BitmapGlyphs, FontRequest};
 use crate::lengths::{LogicalLength, LogicalSize, PhysicalLength, PhysicalSize, ScaleFactor};
 use crate::slice::Slice;
+use crate::styled_text::StyledText;
 use crate::textlayout::{Glyph, TextLayout, TextShaper};
 use crate::Coord;
 
@@ -178,6 +180,25 @@ pub fn match_font(request: &FontRequest, scale_factor: ScaleFactor) -> PixelFont
     PixelFont { bitmap_font: font, glyphs: matching_glyphs }
 }
 
+/// Returns the fonts for the spans of the styled text that are not rendered with the font
+/// of the element itself.
+/// Note that bitmap fonts don't have a weight or a style: the glyphs of bold and italic spans
+/// are the same, and the renderer synthesizes the style when drawing them.
+pub fn match_span_fonts(
+    styled_text: &StyledText,
+    font_request: &FontRequest,
+    scale_factor: ScaleFactor,
+) -> Vec<(Range<usize>, PixelFont)> {
+    styled_text
+        .spans
+        .iter()
+        .filter(|span| !span.has_default_font())
+        .map(|span| {
+            (span.range.clone(), match_font(&span.font_request(font_request), scale_factor))
+        })
+        .collect()
+}
+
 pub fn text_layout_for_font<'a>(
     font: &'a PixelFont,
     font_request: &FontRequest,
@@ -198,6 +219,8 @@ pub fn text_size(
     font_request: FontRequest,
     text: &str,
     max_width: Option<Coord>,
+    line_height: Option<Coord>,
+    max_lines: Option<usize>,
     scale_factor: ScaleFactor,
 ) -> LogicalSize {
     let font = match_font(&font_request, scale_factor);
@@ -206,6 +229,9 @@ pub fn text_size(
     let (longest_line_width, height) = layout.text_size(
         text,
         max_width.map(|max_width| (LogicalLength::new(max_width).cast() * scale_factor).cast()),
+        line_height
+            .map(|line_height| (LogicalLength::new(line_height).cast() * scale_factor).cast()),
+        max_lines,
     );
 
     (PhysicalSize::from_lengths(longest_line_width, height).cast() / scale_factor).cast()
@@ -238,6 +238,71 @@ pub fn format(args: core::fmt::Arguments<'_>) -> SharedString {
     output
 }
 
+/// Formats the number with exactly `digits` digits after the decimal point.
+/// This implements the `to-fixed()` function of the .slint language.
+pub fn number_to_fixed(n: f64, digits: i32) -> SharedString {
+    format(format_args!("{:.*}", digits.clamp(0, 100) as usize, n))
+}
+
+/// Formats the number with `precision` significant digits. Like JavaScript's
+/// `Number.toPrecision()`, the exponential notation is used when the exponent is too
+/// big or too small.
+/// This implements the `to-precision()` function of the .slint language.
+pub fn number_to_precision(n: f64, precision: i32) -> SharedString {
+    let precision = precision.clamp(1, 100) as usize;
+    if !n.is_finite() {
+        return format(format_args!("{}", n));
+    }
+    // Let the formatter do the rounding, so that the exponent is the one of the rounded value
+    let exp_repr = format(format_args!("{:.*e}", precision - 1, n));
+    let exponent: i32 =
+        exp_repr.as_str().rsplit_once('e').and_then(|(_, e)| e.parse().ok()).unwrap_or(0);
+    if exponent < -6 || exponent >= precision as i32 {
+        exp_repr
+    } else {
+        format(format_args!("{:.*}", (precision as i32 - 1 - exponent).max(0) as usize, n))
+    }
+}
+
+/// Returns the sub-string from the character at index `start` (included) to the character
+/// at index `end` (excluded). The indices are clamped to the length of the string, and they
+/// are swapped if `start` is greater than `end`.
+/// This implements the `substring()` function of the .slint language.
+pub fn substring(s: &str, start: i32, end: i32) -> SharedString {
+    let len = s.chars().count() as i32;
+    let (start, end) = (start.clamp(0, len), end.clamp(0, len));
+    let (start, end) = if start > end { (end, start) } else { (start, end) };
+    let mut chars = s.char_indices().map(|(i, _)| i).chain(core::iter::once(s.len()));
+    let begin = chars.nth(start as usize).unwrap_or(s.len());
+    let end =
+        if end == start { begin } else { chars.nth((end - start - 1) as usize).unwrap_or(s.len()) };
+    SharedString::from(&s[begin..end])
+}
+
+#[test]
+fn number_formatting() {
+    assert_eq!(number_to_fixed(3.14159, 2), "3.14");
+    assert_eq!(number_to_fixed(2.7, 0), "3");
+    assert_eq!(number_to_fixed(42., 3), "42.000");
+    assert_eq!(number_to_fixed(-1.005, -3), "-1");
+    assert_eq!(number_to_precision(3.14159, 3), "3.14");
+    assert_eq!(number_to_precision(123.456, 2), "1.2e2");
+    assert_eq!(number_to_precision(0.000123, 2), "0.00012");
+    assert_eq!(number_to_precision(99.96, 3), "100");
+    assert_eq!(number_to_precision(0., 3), "0.00");
+    assert_eq!(number_to_precision(5., 0), "5");
+}
+
+#[test]
+fn substring_test() {
+    assert_eq!(substring("Hello", 1, 3), "el");
+    assert_eq!(substring("Hello", 3, 1), "el");
+    assert_eq!(substring("Hello", -4, 100), "Hello");
+    assert_eq!(substring("Hello", 2, 2), "");
+    assert_eq!(substring("Grüße", 2, 5), "üße");
+    assert_eq!(substring("", 0, 1), "");
+}
+
 #[test]
 fn simple_test() {
     let x = SharedString::from("hello world!");
@@ -364,6 +429,55 @@ fn test_slint_shared_string_from_number() {
         }
     }
 
+    /// Formats a number with a fixed amount of digits after the decimal point
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_from_number_fixed(
+        out: &mut SharedString,
+        n: f64,
+        digits: i32,
+    ) {
+        *out = number_to_fixed(n, digits);
+    }
+
+    /// Formats a number with the given amount of significant digits
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_from_number_precision(
+        out: &mut SharedString,
+        n: f64,
+        precision: i32,
+    ) {
+        *out = number_to_precision(n, precision);
+    }
+
+    /// Converts the string to upper case
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_to_uppercase(out: &mut SharedString, ss: &SharedString) {
+        *out = ss.to_uppercase().into();
+    }
+
+    /// Converts the string to lower case
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_to_lowercase(out: &mut SharedString, ss: &SharedString) {
+        *out = ss.to_lowercase().into();
+    }
+
+    /// Returns the amount of characters (unicode scalar values) in the string
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_character_count(ss: &SharedString) -> i32 {
+        ss.chars().count() as i32
+    }
+
+    /// Extracts the characters between `start` and `end`. See [`substring`]
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_substring(
+        out: &mut SharedString,
+        ss: &SharedString,
+        start: i32,
+        end: i32,
+    ) {
+        *out = substring(ss, start, end);
+    }
+
     /// Append some bytes to an existing shared string
     ///
     /// bytes must be a valid utf8 array of size `len`, without null bytes inside
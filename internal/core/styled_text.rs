@@ -0,0 +1,375 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the styled text representation used by the `Text` element when its
+`text-format` is set to `markup`.
+
+The supported markup is a small subset of HTML:
+ * `<b>` / `<strong>`: bold text
+ * `<i>` / `<em>`: italic text
+ * `<font color="#rrggbb">`: colored text (`#rgb`, `#rrggbb` and `#rrggbbaa` are accepted)
+ * `<a href="...">`: a link. Clicking on it invokes the `link-clicked` callback with the `href`
+ * `<br>`: a line break
+ * the `&lt;`, `&gt;`, `&amp;`, `&quot;`, `&apos;` and `&#...;` entities
+
+Anything that can't be parsed as one of these tags is kept as literal text.
+*/
+
+use crate::graphics::{Color, FontRequest};
+use crate::SharedString;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The font weight used for `<b>` spans
+pub const BOLD_FONT_WEIGHT: i32 = 700;
+
+/// The style of a range of a [`StyledText`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyledTextSpan {
+    /// The byte range in [`StyledText::text`] this span applies to
+    pub range: Range<usize>,
+    pub bold: bool,
+    pub italic: bool,
+    /// The color of the span, or None if the color of the element should be used
+    pub color: Option<Color>,
+    /// The link target if this span is part of a link
+    pub link: Option<SharedString>,
+}
+
+impl StyledTextSpan {
+    /// Returns true if this span is rendered with the same font as plain text
+    pub fn has_default_font(&self) -> bool {
+        !self.bold && !self.italic
+    }
+
+    /// Returns the font request for this span, derived from the font request of the element
+    pub fn font_request(&self, base: &FontRequest) -> FontRequest {
+        FontRequest {
+            weight: if self.bold {
+                Some(BOLD_FONT_WEIGHT.max(base.weight.unwrap_or(0)))
+            } else {
+                base.weight
+            },
+            italic: base.italic || self.italic,
+            ..base.clone()
+        }
+    }
+}
+
+/// A text with the markup removed, and the list of spans that describe the style of each
+/// range of the text. The spans are sorted and cover the whole text without overlapping.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyledText {
+    pub text: SharedString,
+    pub spans: Vec<StyledTextSpan>,
+}
+
+impl StyledText {
+    /// Creates a styled text that uses the default style for the whole `text`
+    pub fn plain(text: SharedString) -> Self {
+        let spans = if text.is_empty() {
+            Vec::new()
+        } else {
+            alloc::vec![StyledTextSpan { range: 0..text.len(), ..Default::default() }]
+        };
+        Self { text, spans }
+    }
+
+    /// Returns the span that contains the given byte offset
+    pub fn span_at(&self, byte_offset: usize) -> Option<&StyledTextSpan> {
+        let index = self.spans.partition_point(|span| span.range.end <= byte_offset);
+        self.spans.get(index).filter(|span| span.range.contains(&byte_offset))
+    }
+
+    /// Returns the link target of the span that contains the given byte offset
+    pub fn link_at(&self, byte_offset: usize) -> Option<&SharedString> {
+        self.span_at(byte_offset).and_then(|span| span.link.as_ref())
+    }
+
+    /// Returns true if any span is rendered with a different font than plain text
+    pub fn has_font_runs(&self) -> bool {
+        self.spans.iter().any(|span| !span.has_default_font())
+    }
+}
+
+#[derive(Clone, Default)]
+struct Style {
+    tag: &'static str,
+    bold: bool,
+    italic: bool,
+    color: Option<Color>,
+    link: Option<SharedString>,
+}
+
+/// Parses the markup and returns the corresponding [`StyledText`]
+pub fn parse_markup(markup: &str) -> StyledText {
+    let mut text = String::with_capacity(markup.len());
+    let mut spans: Vec<StyledTextSpan> = Vec::new();
+    let mut stack = alloc::vec![Style::default()];
+
+    let mut push_text = |text: &mut String, stack: &[Style], str: &str| {
+        if str.is_empty() {
+            return;
+        }
+        let style = stack.last().unwrap();
+        let start = text.len();
+        text.push_str(str);
+        match spans.last_mut() {
+            Some(last)
+                if last.range.end == start
+                    && last.bold == style.bold
+                    && last.italic == style.italic
+                    && last.color == style.color
+                    && last.link == style.link =>
+            {
+                last.range.end = text.len()
+            }
+            _ => spans.push(StyledTextSpan {
+                range: start..text.len(),
+                bold: style.bold,
+                italic: style.italic,
+                color: style.color,
+                link: style.link.clone(),
+            }),
+        }
+    };
+
+    let mut rest = markup;
+    while !rest.is_empty() {
+        let special = rest.find(|c: char| c == '<' || c == '&').unwrap_or(rest.len());
+        push_text(&mut text, &stack, &rest[..special]);
+        rest = &rest[special..];
+        if rest.starts_with('&') {
+            match parse_entity(rest) {
+                Some((ch, len)) => {
+                    let mut buffer = [0; 4];
+                    push_text(&mut text, &stack, ch.encode_utf8(&mut buffer));
+                    rest = &rest[len..];
+                }
+                None => {
+                    push_text(&mut text, &stack, "&");
+                    rest = &rest[1..];
+                }
+            }
+        } else if rest.starts_with('<') {
+            match parse_tag(rest) {
+                Some((tag, len)) => {
+                    rest = &rest[len..];
+                    match tag {
+                        Tag::LineBreak => push_text(&mut text, &stack, "\n"),
+                        Tag::Open(change) => {
+                            let mut style = stack.last().unwrap().clone();
+                            style.tag = change.tag;
+                            style.bold |= change.bold;
+                            style.italic |= change.italic;
+                            style.color = change.color.or(style.color);
+                            style.link = change.link.or(style.link);
+                            stack.push(style);
+                        }
+                        Tag::Close(name) => {
+                            // Unbalanced closing tags are ignored
+                            if let Some(pos) = stack.iter().rposition(|s| s.tag == name) {
+                                stack.truncate(pos);
+                            }
+                        }
+                    }
+                }
+                None => {
+                    push_text(&mut text, &stack, "<");
+                    rest = &rest[1..];
+                }
+            }
+        }
+    }
+
+    StyledText { text: text.into(), spans }
+}
+
+enum Tag {
+    /// An opening tag, with the style properties it sets
+    Open(Style),
+    Close(&'static str),
+    LineBreak,
+}
+
+/// Parse the tag at the beginning of `str`, which starts with `<`.
+/// Returns the tag and the length of the tag in bytes.
+fn parse_tag(str: &str) -> Option<(Tag, usize)> {
+    let end = str.find('>')?;
+    let content = str[1..end].trim();
+    let len = end + 1;
+
+    if let Some(name) = content.strip_prefix('/') {
+        let name = canonical_tag_name(name.trim())?;
+        return Some((Tag::Close(name), len));
+    }
+
+    let content = content.strip_suffix('/').unwrap_or(content).trim_end();
+    let (name, attributes) =
+        content.split_once(|c: char| c.is_whitespace()).unwrap_or((content, ""));
+    let name = canonical_tag_name(name)?;
+    let attributes = parse_attributes(attributes)?;
+    let attribute = |attr: &str| {
+        attributes.iter().find(|(name, _)| name.eq_ignore_ascii_case(attr)).map(|(_, v)| v.clone())
+    };
+
+    let style = Style { tag: name, ..Default::default() };
+    let tag = match name {
+        "br" => Tag::LineBreak,
+        "b" => Tag::Open(Style { bold: true, ..style }),
+        "i" => Tag::Open(Style { italic: true, ..style }),
+        "font" => {
+            let color = match attribute("color") {
+                Some(color) => Some(parse_color(&color)?),
+                None => None,
+            };
+            Tag::Open(Style { color, ..style })
+        }
+        "a" => {
+            let href = attribute("href").unwrap_or_default();
+            Tag::Open(Style { link: Some(href.into()), ..style })
+        }
+        _ => return None,
+    };
+    Some((tag, len))
+}
+
+fn canonical_tag_name(name: &str) -> Option<&'static str> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "b" | "strong" => "b",
+        "i" | "em" => "i",
+        "font" => "font",
+        "a" => "a",
+        "br" => "br",
+        _ => return None,
+    })
+}
+
+/// Parses `name="value" name='value'` pairs, the values are unescaped.
+fn parse_attributes(mut str: &str) -> Option<Vec<(&str, String)>> {
+    let mut result = Vec::new();
+    loop {
+        str = str.trim_start();
+        if str.is_empty() {
+            return Some(result);
+        }
+        let (name, rest) = str.split_once('=')?;
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value_end = rest[1..].find(quote)? + 1;
+        result.push((name.trim(), unescape(&rest[1..value_end])));
+        str = &rest[value_end + 1..];
+    }
+}
+
+fn unescape(mut str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+    while let Some(pos) = str.find('&') {
+        result.push_str(&str[..pos]);
+        str = &str[pos..];
+        match parse_entity(str) {
+            Some((ch, len)) => {
+                result.push(ch);
+                str = &str[len..];
+            }
+            None => {
+                result.push('&');
+                str = &str[1..];
+            }
+        }
+    }
+    result.push_str(str);
+    result
+}
+
+/// Parse the entity at the beginning of `str`, which starts with `&`.
+/// Returns the character and the length of the entity in bytes.
+fn parse_entity(str: &str) -> Option<(char, usize)> {
+    let end = str.find(';')?;
+    let name = &str[1..end];
+    let ch = match name {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix('x').or_else(|| number.strip_prefix('X')) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse::<u32>().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((ch, end + 1))
+}
+
+fn parse_color(str: &str) -> Option<Color> {
+    let hex = str.trim().strip_prefix('#')?;
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    Some(match hex.len() {
+        3 => Color::from_rgb_u8(digit(0) * 0x11, digit(1) * 0x11, digit(2) * 0x11),
+        6 => Color::from_rgb_u8(byte(0), byte(2), byte(4)),
+        8 => Color::from_argb_u8(byte(6), byte(0), byte(2), byte(4)),
+        _ => return None,
+    })
+}
+
+#[test]
+fn test_parse_plain() {
+    let styled = parse_markup("Hello World");
+    assert_eq!(styled, StyledText::plain("Hello World".into()));
+    assert_eq!(parse_markup(""), StyledText::default());
+}
+
+#[test]
+fn test_parse_bold_italic() {
+    let styled = parse_markup("a <b>bold <i>and italic</i></b> text");
+    assert_eq!(styled.text, "a bold and italic text");
+    let spans = styled
+        .spans
+        .iter()
+        .map(|s| (&styled.text[s.range.clone()], s.bold, s.italic))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        alloc::vec![
+            ("a ", false, false),
+            ("bold ", true, false),
+            ("and italic", true, true),
+            (" text", false, false)
+        ]
+    );
+    assert!(styled.has_font_runs());
+}
+
+#[test]
+fn test_parse_links_and_colors() {
+    let styled = parse_markup(
+        "See <a href=\"https://slint-ui.com?a=1&amp;b=2\">the <font color='#f00'>website</font></a>.",
+    );
+    assert_eq!(styled.text, "See the website.");
+    assert_eq!(styled.spans.len(), 4);
+    assert_eq!(styled.link_at(0), None);
+    assert_eq!(styled.link_at(4).unwrap(), "https://slint-ui.com?a=1&b=2");
+    assert_eq!(styled.link_at(10).unwrap(), "https://slint-ui.com?a=1&b=2");
+    assert_eq!(styled.link_at(15), None);
+    assert_eq!(styled.span_at(10).unwrap().color, Some(Color::from_rgb_u8(0xff, 0, 0)));
+    assert_eq!(styled.span_at(4).unwrap().color, None);
+    assert!(!styled.has_font_runs());
+}
+
+#[test]
+fn test_parse_entities_and_invalid_markup() {
+    let styled = parse_markup("1 &lt; 2 &amp;&amp; 3 > 2<br/>&#65;&#x42; & <unknown> </b> <b");
+    assert_eq!(styled.text, "1 < 2 && 3 > 2\nAB & <unknown>  <b");
+    assert_eq!(styled.spans.len(), 1);
+}
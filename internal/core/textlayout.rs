@@ -21,6 +21,7 @@
 //!         Emit current line as new line
 //!
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 use euclid::num::{One, Zero};
@@ -41,7 +42,7 @@
 mod glyphclusters;
 mod shaping;
 use shaping::ShapeBuffer;
-pub use shaping::{AbstractFont, FontMetrics, Glyph, TextShaper};
+pub use shaping::{AbstractFont, FontMetrics, FontRun, Glyph, TextShaper};
 
 mod linebreaker;
 pub use linebreaker::TextLine;
@@ -55,12 +56,15 @@ pub struct TextLayout<'a, Font: AbstractFont> {
 
 impl<'a, Font: AbstractFont> TextLayout<'a, Font> {
     // Measures the size of the given text when rendered with the specified font and optionally constrained
-    // by the provided `max_width`.
+    // by the provided `max_width`. Lines are `line_height` high (the height of the font if None), and only
+    // the first `max_lines` lines are measured.
     // Returns a tuple of the width of the longest line as well as height of all lines.
     pub fn text_size(
         &self,
         text: &str,
         max_width: Option<Font::Length>,
+        line_height: Option<Font::Length>,
+        max_lines: Option<usize>,
     ) -> (Font::Length, Font::Length)
     where
         Font::Length: core::fmt::Debug,
@@ -69,19 +73,24 @@ pub fn text_size(
         let mut line_count: i16 = 0;
         let shape_buffer = ShapeBuffer::new(self, text);
 
-        for line in TextLineBreaker::<Font>::new(text, &shape_buffer, max_width) {
+        for line in TextLineBreaker::<Font>::new(text, &shape_buffer, max_width)
+            .take(max_lines.unwrap_or(usize::MAX))
+        {
             max_line_width = euclid::approxord::max(max_line_width, line.text_width);
             line_count += 1;
         }
 
-        (max_line_width, self.font.height() * line_count.into())
+        (max_line_width, line_height.unwrap_or_else(|| self.font.height()) * line_count.into())
     }
 }
 
 pub struct PositionedGlyph<'a, Length, PlatformGlyphData> {
     pub x: Length,
     pub y: Length,
+    pub advance: Length,
     pub platform_glyph: &'a PlatformGlyphData,
+    /// The byte offset in the string of the character that produced this glyph
+    pub text_byte_offset: usize,
 }
 
 pub struct TextParagraphLayout<'a, Font: AbstractFont> {
@@ -94,6 +103,14 @@ pub struct TextParagraphLayout<'a, Font: AbstractFont> {
     pub wrap: TextWrap,
     pub overflow: TextOverflow,
     pub single_line: bool,
+    /// The ranges of the string that are shaped with a different font than the one of the layout.
+    /// The line height is always the one of the layout's font.
+    pub font_runs: &'a [FontRun<'a, Font>],
+    /// The distance between the top of two lines. When None, the height of the font is used.
+    pub line_height: Option<Font::Length>,
+    /// When set, only that many lines are shown. With [`TextOverflow::Elide`], the last shown line
+    /// is elided if there is more text after it.
+    pub max_lines: Option<usize>,
 }
 
 impl<'a, Font: AbstractFont> TextParagraphLayout<'a, Font> {
@@ -117,7 +134,7 @@ pub fn layout_lines(
         let max_width_without_elision =
             self.max_width - elide_glyph.as_ref().map_or(Font::Length::zero(), |g| g.advance);
 
-        let shape_buffer = ShapeBuffer::new(&self.layout, self.string);
+        let shape_buffer = ShapeBuffer::with_font_runs(&self.layout, self.string, self.font_runs);
 
         let new_line_break_iter = || {
             TextLineBreaker::<Font>::new(
@@ -128,81 +145,140 @@ pub fn layout_lines(
         };
         let mut text_lines = None;
 
+        let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
+        let line_height = self.line_height();
+        // The glyphs are vertically centered in their line
+        let half_leading = (line_height - self.layout.font.height()) / two;
+        let max_lines = self.max_lines.unwrap_or(usize::MAX);
+
         let mut text_height = || {
             if self.single_line {
-                self.layout.font.height()
+                line_height
             } else {
                 text_lines = Some(new_line_break_iter().collect::<Vec<_>>());
-                self.layout.font.height() * (text_lines.as_ref().unwrap().len() as i16).into()
+                let line_count = text_lines.as_ref().unwrap().len().min(max_lines);
+                line_height * (line_count as i16).into()
             }
         };
 
-        let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
-
         let baseline_y = match self.vertical_alignment {
             TextVerticalAlignment::Top => Font::Length::zero(),
             TextVerticalAlignment::Center => self.max_height / two - text_height() / two,
             TextVerticalAlignment::Bottom => self.max_height - text_height(),
         };
 
-        let mut y = baseline_y;
+        let mut y = baseline_y + half_leading;
+
+        // `elide_at_end` is true when the line is the last one shown but is followed by more text
+        let mut process_line = |line: &TextLine<Font::Length>,
+                                glyphs: &[Glyph<Font::Length, Font::PlatformGlyphData>],
+                                elide_at_end: bool| {
+            let x = match self.horizontal_alignment {
+                TextHorizontalAlignment::Left => Font::Length::zero(),
+                TextHorizontalAlignment::Center => {
+                    self.max_width / two
+                        - euclid::approxord::min(self.max_width, line.text_width) / two
+                }
+                TextHorizontalAlignment::Right => {
+                    self.max_width - euclid::approxord::min(self.max_width, line.text_width)
+                }
+            };
 
-        let mut process_line =
-            |line: &TextLine<Font::Length>,
-             glyphs: &[Glyph<Font::Length, Font::PlatformGlyphData>]| {
-                let x = match self.horizontal_alignment {
-                    TextHorizontalAlignment::Left => Font::Length::zero(),
-                    TextHorizontalAlignment::Center => {
-                        self.max_width / two
-                            - euclid::approxord::min(self.max_width, line.text_width) / two
+            let mut elide_glyph = elide_glyph.as_ref().clone();
+
+            let mut glyph_it = glyphs[line.glyph_range.clone()].iter();
+            let mut glyph_x = Font::Length::zero();
+            let mut positioned_glyph_it = core::iter::from_fn(|| {
+                let glyph = match glyph_it.next() {
+                    Some(glyph) => glyph,
+                    None if elide_at_end => {
+                        let elide_glyph = elide_glyph.take()?;
+                        return Some(PositionedGlyph {
+                            x: glyph_x,
+                            y: Font::Length::zero(),
+                            advance: elide_glyph.advance,
+                            platform_glyph: &elide_glyph.platform_glyph,
+                            text_byte_offset: line.byte_range.end,
+                        });
                     }
-                    TextHorizontalAlignment::Right => {
-                        self.max_width - euclid::approxord::min(self.max_width, line.text_width)
+                    None => return None,
+                };
+                // When eliding at the end of the line, the glyph needs to fit before the elide glyph
+                let glyph_end = if elide_at_end { glyph_x + glyph.advance } else { glyph_x };
+                // TODO: cut off at grapheme boundaries
+                if glyph_end > max_width_without_elision {
+                    if let Some(elide_glyph) = elide_glyph.take() {
+                        return Some(PositionedGlyph {
+                            x: glyph_x,
+                            y: Font::Length::zero(),
+                            advance: elide_glyph.advance,
+                            platform_glyph: &elide_glyph.platform_glyph,
+                            text_byte_offset: glyph.text_byte_offset,
+                        });
+                    } else {
+                        return None;
                     }
+                }
+                let positioned_glyph = PositionedGlyph {
+                    x: glyph_x,
+                    y: Font::Length::zero(),
+                    advance: glyph.advance,
+                    platform_glyph: &glyph.platform_glyph,
+                    text_byte_offset: glyph.text_byte_offset,
                 };
+                glyph_x += glyph.advance;
+                Some(positioned_glyph)
+            });
 
-                let mut elide_glyph = elide_glyph.as_ref().clone();
-
-                let glyph_it = glyphs[line.glyph_range.clone()].iter();
-                let mut glyph_x = Font::Length::zero();
-                let mut positioned_glyph_it = glyph_it.map_while(|glyph| {
-                    // TODO: cut off at grapheme boundaries
-                    if glyph_x > max_width_without_elision {
-                        if let Some(elide_glyph) = elide_glyph.take() {
-                            return Some(PositionedGlyph {
-                                x: glyph_x,
-                                y: Font::Length::zero(),
-                                platform_glyph: &elide_glyph.platform_glyph,
-                            });
-                        } else {
-                            return None;
-                        }
-                    }
-                    let positioned_glyph = PositionedGlyph {
-                        x: glyph_x,
-                        y: Font::Length::zero(),
-                        platform_glyph: &glyph.platform_glyph,
-                    };
-                    glyph_x += glyph.advance;
-                    Some(positioned_glyph)
-                });
-
-                line_callback(&mut positioned_glyph_it, x, y);
-                y += self.layout.font.height();
-            };
+            line_callback(&mut positioned_glyph_it, x, y);
+            y += line_height;
+        };
 
-        if let Some(lines_vec) = text_lines.take() {
-            for line in lines_vec {
-                process_line(&line, &shape_buffer.glyphs);
-            }
-        } else {
-            for line in new_line_break_iter() {
-                process_line(&line, &shape_buffer.glyphs);
+        let mut lines = match text_lines.take() {
+            Some(lines_vec) => Box::new(lines_vec.into_iter())
+                as Box<dyn Iterator<Item = TextLine<Font::Length>> + '_>,
+            None => Box::new(new_line_break_iter()),
+        }
+        .peekable();
+        let mut line_count = 0;
+        while let Some(line) = lines.next() {
+            line_count += 1;
+            let last_line = line_count == max_lines;
+            process_line(&line, &shape_buffer.glyphs, last_line && lines.peek().is_some());
+            if last_line {
+                break;
             }
         }
 
         baseline_y
     }
+
+    /// The distance between the top of two lines
+    fn line_height(&self) -> Font::Length {
+        self.line_height.unwrap_or_else(|| self.layout.font.height())
+    }
+
+    /// Returns the byte offset of the character whose glyph is at the given position, relative to
+    /// the top left corner of the paragraph. Returns None if there is no glyph at that position.
+    pub fn byte_offset_for_position(
+        &self,
+        (pos_x, pos_y): (Font::Length, Font::Length),
+    ) -> Option<usize> {
+        let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
+        let line_height = self.line_height();
+        let half_leading = (line_height - self.layout.font.height()) / two;
+        let mut result = None;
+        self.layout_lines(|glyphs, line_x, line_y| {
+            let line_top = line_y - half_leading;
+            if result.is_some() || pos_y < line_top || pos_y >= line_top + line_height {
+                return;
+            }
+            result = glyphs
+                .find(|glyph| pos_x >= line_x + glyph.x && pos_x < line_x + glyph.x + glyph.advance)
+                .map(|glyph| glyph.text_byte_offset);
+        });
+        result
+    }
 }
 
 #[test]
@@ -290,6 +366,9 @@ fn test_elision() {
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
         single_line: true,
+        font_runs: &[],
+        line_height: None,
+        max_lines: None,
     };
     paragraph.layout_lines(|glyphs, _, _| {
         lines.push(
@@ -322,6 +401,9 @@ fn test_exact_fit() {
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
         single_line: true,
+        font_runs: &[],
+        line_height: None,
+        max_lines: None,
     };
     paragraph.layout_lines(|glyphs, _, _| {
         lines.push(
@@ -354,6 +436,9 @@ fn test_no_line_separators_characters_rendered() {
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Clip,
         single_line: true,
+        font_runs: &[],
+        line_height: None,
+        max_lines: None,
     };
     paragraph.layout_lines(|glyphs, _, _| {
         lines.push(
@@ -375,3 +460,93 @@ fn test_no_line_separators_characters_rendered() {
         .collect::<Vec<_>>();
     debug_assert_eq!(rendered_text, vec!["Hello", "World"]);
 }
+
+#[test]
+fn test_byte_offset_for_position() {
+    let font = FixedTestFont;
+    let text = "Hello World";
+
+    let paragraph = TextParagraphLayout {
+        string: text,
+        layout: TextLayout { font: &font, letter_spacing: None },
+        max_width: 60.,
+        max_height: 100.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap: TextWrap::WordWrap,
+        overflow: TextOverflow::Clip,
+        single_line: false,
+        font_runs: &[],
+        line_height: None,
+        max_lines: None,
+    };
+    assert_eq!(paragraph.byte_offset_for_position((5., 5.)), Some(0));
+    assert_eq!(paragraph.byte_offset_for_position((45., 5.)), Some(4));
+    assert_eq!(paragraph.byte_offset_for_position((25., 15.)), Some(8));
+    assert_eq!(paragraph.byte_offset_for_position((55., 15.)), None);
+    assert_eq!(paragraph.byte_offset_for_position((5., 25.)), None);
+}
+
+#[test]
+fn test_max_lines_elision() {
+    let font = FixedTestFont;
+    let text = "This is a longer piece of text";
+
+    let mut lines = Vec::new();
+
+    let paragraph = TextParagraphLayout {
+        string: text,
+        layout: TextLayout { font: &font, letter_spacing: None },
+        max_width: 13. * 10.,
+        max_height: 100.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap: TextWrap::WordWrap,
+        overflow: TextOverflow::Elide,
+        single_line: false,
+        font_runs: &[],
+        line_height: None,
+        max_lines: Some(2),
+    };
+    paragraph.layout_lines(|glyphs, _, _| {
+        lines.push(
+            glyphs
+                .map(|positioned_glyph| positioned_glyph.platform_glyph.char.unwrap())
+                .collect::<String>(),
+        );
+    });
+
+    assert_eq!(lines, ["This is a ", "longer piece…"]);
+}
+
+#[test]
+fn test_line_height() {
+    let font = FixedTestFont;
+    let text = "Hello World";
+
+    let mut line_ys = Vec::new();
+
+    let paragraph = TextParagraphLayout {
+        string: text,
+        layout: TextLayout { font: &font, letter_spacing: None },
+        max_width: 60.,
+        max_height: 100.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap: TextWrap::WordWrap,
+        overflow: TextOverflow::Clip,
+        single_line: false,
+        font_runs: &[],
+        line_height: Some(20.),
+        max_lines: None,
+    };
+    paragraph.layout_lines(|_, _, line_y| line_ys.push(line_y));
+    // The glyphs are centered in lines of 20 pixels
+    assert_eq!(line_ys, [5., 25.]);
+
+    assert_eq!(paragraph.byte_offset_for_position((25., 22.)), Some(8));
+    assert_eq!(paragraph.byte_offset_for_position((5., 45.)), None);
+
+    let (width, height) = paragraph.layout.text_size(text, Some(60.), Some(20.), Some(1));
+    assert_eq!((width, height), (50., 20.));
+}
@@ -155,6 +155,13 @@ fn next(&mut self) -> Option<Self::Item> {
     }
 }
 
+/// A range of the text that is shaped with a different font than the one of the [`TextLayout`],
+/// for example the bold or italic span of a styled text.
+pub struct FontRun<'a, Font> {
+    pub byte_range: Range<usize>,
+    pub font: &'a Font,
+}
+
 pub struct TextRun {
     pub byte_range: Range<usize>,
     //pub glyph_range: Range<usize>,
@@ -168,16 +175,56 @@ pub struct ShapeBuffer<Length, PlatformGlyphData> {
 
 impl<Length, PlatformGlyphData> ShapeBuffer<Length, PlatformGlyphData> {
     pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
+    where
+        Font: AbstractFont<Length = Length, PlatformGlyphData = PlatformGlyphData>,
+        Length: Copy + core::ops::AddAssign,
+    {
+        Self::with_font_runs(layout, text, &[])
+    }
+
+    /// Same as [`Self::new`], but the byte ranges of `font_runs` are shaped with their own font
+    /// instead of the font of the layout. The ranges must be sorted and must not overlap.
+    pub fn with_font_runs<Font>(
+        layout: &TextLayout<Font>,
+        text: &str,
+        font_runs: &[FontRun<'_, Font>],
+    ) -> Self
     where
         Font: AbstractFont<Length = Length, PlatformGlyphData = PlatformGlyphData>,
         Length: Copy + core::ops::AddAssign,
     {
         let mut glyphs = Vec::new();
-        let text_runs = ShapeBoundaries::new(text)
-            .scan(0, |run_start, run_end| {
+        let mut text_runs = Vec::new();
+
+        let mut run_start = 0;
+        for boundary in ShapeBoundaries::new(text) {
+            // Further split the run at the boundaries of the font runs, so that each run is
+            // shaped with a single font.
+            while run_start < boundary {
+                let (font, run_end) = match font_runs
+                    .iter()
+                    .find(|run| run.byte_range.start <= run_start && run_start < run.byte_range.end)
+                {
+                    Some(run) => (run.font, run.byte_range.end.min(boundary)),
+                    None => (
+                        layout.font,
+                        font_runs
+                            .iter()
+                            .map(|run| run.byte_range.start)
+                            .find(|start| *start > run_start)
+                            .map_or(boundary, |start| start.min(boundary)),
+                    ),
+                };
+
                 let glyphs_start = glyphs.len();
 
-                layout.font.shape_text(&text[*run_start..run_end], &mut glyphs);
+                font.shape_text(&text[run_start..run_end], &mut glyphs);
+
+                // The shaper reports byte offsets relative to the shaped string, make them
+                // relative to the whole text.
+                for glyph in &mut glyphs[glyphs_start..] {
+                    glyph.text_byte_offset += run_start;
+                }
 
                 if let Some(letter_spacing) = layout.letter_spacing {
                     if glyphs.len() > glyphs_start {
@@ -195,18 +242,16 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
                     }
                 }
 
-                let run = TextRun {
-                    byte_range: Range { start: *run_start, end: run_end },
+                text_runs.push(TextRun {
+                    byte_range: Range { start: run_start, end: run_end },
                     //glyph_range: Range {
                     //     start: glyphs_start,
                     //     end: glyph_buffer.borrow().as_ref().len(),
                     // },
-                };
-                *run_start = run_end;
-
-                Some(run)
-            })
-            .collect();
+                });
+                run_start = run_end;
+            }
+        }
 
         Self { glyphs, text_runs }
     }
@@ -420,3 +465,23 @@ fn test_letter_spacing() {
         );
     });
 }
+
+#[test]
+fn test_font_runs() {
+    with_dejavu_font(|face| {
+        let text = "ab cd";
+        let layout = TextLayout { font: &face, letter_spacing: None };
+        let font_runs = [FontRun { byte_range: 1..4, font: &face }];
+        let buffer = ShapeBuffer::with_font_runs(&layout, text, &font_runs);
+
+        assert_eq!(
+            buffer.text_runs.iter().map(|run| run.byte_range.clone()).collect::<Vec<_>>(),
+            vec![0..1, 1..4, 4..5]
+        );
+        // byte offsets are relative to the whole text, not to the run
+        assert_eq!(
+            buffer.glyphs.iter().map(|glyph| glyph.text_byte_offset).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    });
+}
@@ -7,7 +7,8 @@
 //! Exposed Window API
 
 use crate::api::{
-    CloseRequestResponse, PhysicalPosition, PhysicalSize, Window, WindowPosition, WindowSize,
+    CloseRequestResponse, LogicalSize, PhysicalPosition, PhysicalSize, Window, WindowPosition,
+    WindowSize,
 };
 use crate::component::{ComponentRc, ComponentRef, ComponentVTable, ComponentWeak};
 use crate::graphics::{Point, Rect, Size};
@@ -105,6 +106,10 @@ fn set_mouse_cursor(&self, _cursor: MouseCursor) {}
     fn show_virtual_keyboard(&self, _: crate::items::InputType) {}
     /// This is called when the widget that needed the keyboard loses focus
     fn hide_virtual_keyboard(&self) {}
+    /// This is called when the text cursor of the focused text input moved, with the position of the
+    /// bottom left corner of the cursor in window coordinates. The input method uses it to place its
+    /// candidate window.
+    fn set_ime_position(&self, _position: crate::api::LogicalPosition) {}
 
     /// Return self as any so the backend can upcast
     fn as_any(&self) -> &dyn core::any::Any {
@@ -186,6 +191,8 @@ pub struct WindowInner {
     redraw_tracker: Pin<Box<PropertyTracker<WindowRedrawTracker>>>,
     /// Gets dirty when the layout restrictions, or some other property of the windows change
     window_properties_tracker: Pin<Box<PropertyTracker<WindowPropertiesTracker>>>,
+    /// Gets dirty when the MenuBar or the SystemTrayIcon of the component change
+    menus_tracker: Pin<Box<PropertyTracker<WindowPropertiesTracker>>>,
 
     focus_item: RefCell<crate::item_tree::ItemWeak>,
     cursor_blinker: RefCell<pin_weak::rc::PinWeak<crate::input::TextCursorBlinker>>,
@@ -194,6 +201,10 @@ pub struct WindowInner {
     active: Pin<Box<Property<bool>>>,
     active_popup: RefCell<Option<PopupWindow>>,
     close_requested: Callback<(), CloseRequestResponse>,
+    /// Explicit minimum size set with [`Window::set_min_size`], on top of the layout constraints
+    min_size: Pin<Box<Property<Option<LogicalSize>>>>,
+    /// Explicit maximum size set with [`Window::set_max_size`], on top of the layout constraints
+    max_size: Pin<Box<Property<Option<LogicalSize>>>>,
     /// This is a cache of the size set by the set_inner_size setter.
     /// It should be mapping with the WindowItem::width and height (only in physical)
     pub(crate) inner_size: Cell<PhysicalSize>,
@@ -221,11 +232,16 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             window_adapter_weak: window_adapter_weak.clone(),
         });
 
+        let mut menus_tracker = PropertyTracker::new_with_dirty_handler(WindowPropertiesTracker {
+            window_adapter_weak: window_adapter_weak.clone(),
+        });
+
         #[cfg(slint_debug_property)]
         {
             window_properties_tracker
                 .set_debug_name("i_slint_core::Window::window_properties_tracker".into());
             redraw_tracker.set_debug_name("i_slint_core::Window::redraw_tracker".into());
+            menus_tracker.set_debug_name("i_slint_core::Window::menus_tracker".into());
         }
 
         let window = Self {
@@ -234,12 +250,15 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             mouse_input_state: Default::default(),
             redraw_tracker: Box::pin(redraw_tracker),
             window_properties_tracker: Box::pin(window_properties_tracker),
+            menus_tracker: Box::pin(menus_tracker),
             focus_item: Default::default(),
             cursor_blinker: Default::default(),
             scale_factor: Box::pin(Property::new_named(1., "i_slint_core::Window::scale_factor")),
             active: Box::pin(Property::new_named(false, "i_slint_core::Window::active")),
             active_popup: Default::default(),
             close_requested: Default::default(),
+            min_size: Box::pin(Property::new_named(None, "i_slint_core::Window::min_size")),
+            max_size: Box::pin(Property::new_named(None, "i_slint_core::Window::max_size")),
             inner_size: Default::default(),
         };
 
@@ -254,6 +273,7 @@ pub fn set_component(&self, component: &ComponentRc) {
         self.mouse_input_state.replace(Default::default());
         self.component.replace(ComponentRc::downgrade(component));
         self.window_properties_tracker.set_dirty(); // component changed, layout constraints for sure must be re-calculated
+        self.menus_tracker.set_dirty();
         self.window_adapter().request_window_properties_update();
         let window_adapter = self.window_adapter();
         window_adapter.request_window_properties_update();
@@ -347,6 +367,11 @@ pub(crate) fn process_delayed_event(&self) {
     /// * `event`: The key event received by the windowing system.
     /// * `component`: The Slint compiled component that provides the tree of items.
     pub fn process_key_input(&self, event: &KeyEvent) {
+        // The shortcuts are active regardless of which item has the focus
+        if crate::items::activate_shortcut(&self.component(), event) {
+            return;
+        }
+
         let mut item = self.focus_item.borrow().clone().upgrade();
         while let Some(focus_item) = item {
             if !focus_item.is_visible() {
@@ -408,6 +433,11 @@ pub fn set_focus(&self, have_focus: bool) {
         }
     }
 
+    /// Returns the item that currently has the keyboard focus
+    pub(crate) fn focus_item(&self) -> Option<ItemRc> {
+        self.focus_item.borrow().upgrade()
+    }
+
     /// Take the focus_item out of this Window
     ///
     /// This sends the FocusOut event!
@@ -497,6 +527,28 @@ pub fn active(&self) -> bool {
         self.active.as_ref().get()
     }
 
+    /// Returns the minimum size set with [`Self::set_min_size`], in logical pixels.
+    pub fn min_size(&self) -> Option<LogicalSize> {
+        self.min_size.as_ref().get()
+    }
+
+    /// Sets an explicit minimum size for the window, in logical pixels. It is combined with the
+    /// constraints of the layout. `None` removes the explicit constraint.
+    pub fn set_min_size(&self, size: Option<LogicalSize>) {
+        self.min_size.as_ref().set(size);
+    }
+
+    /// Returns the maximum size set with [`Self::set_max_size`], in logical pixels.
+    pub fn max_size(&self) -> Option<LogicalSize> {
+        self.max_size.as_ref().get()
+    }
+
+    /// Sets an explicit maximum size for the window, in logical pixels. It is combined with the
+    /// constraints of the layout. `None` removes the explicit constraint.
+    pub fn set_max_size(&self, size: Option<LogicalSize>) {
+        self.max_size.as_ref().set(size);
+    }
+
     /// If the component's root item is a Window element, then this function synchronizes its properties, such as the title
     /// for example, with the properties known to the windowing system.
     pub fn update_window_properties(&self) {
@@ -505,19 +557,50 @@ pub fn update_window_properties(&self) {
         self.window_properties_tracker.as_ref().evaluate_as_dependency_root(|| {
             let component = self.component();
             let component = ComponentRc::borrow_pin(&component);
-            self.window_adapter().apply_geometry_constraint(
-                component.as_ref().layout_info(crate::layout::Orientation::Horizontal),
-                component.as_ref().layout_info(crate::layout::Orientation::Vertical),
-            );
+            let mut constraints_horizontal =
+                component.as_ref().layout_info(crate::layout::Orientation::Horizontal);
+            let mut constraints_vertical =
+                component.as_ref().layout_info(crate::layout::Orientation::Vertical);
+            if let Some(min_size) = self.min_size() {
+                constraints_horizontal.min =
+                    constraints_horizontal.min.max(min_size.width as Coord);
+                constraints_vertical.min = constraints_vertical.min.max(min_size.height as Coord);
+            }
+            if let Some(max_size) = self.max_size() {
+                constraints_horizontal.max =
+                    constraints_horizontal.max.min(max_size.width as Coord);
+                constraints_vertical.max = constraints_vertical.max.min(max_size.height as Coord);
+            }
+            self.window_adapter()
+                .apply_geometry_constraint(constraints_horizontal, constraints_vertical);
             if let Some(window_item) = self.window_item() {
                 self.window_adapter().apply_window_properties(window_item.as_pin_ref());
             }
         });
+        self.update_menus();
+    }
+
+    /// Hands the MenuBar and the SystemTrayIcon of the component to the platform, if they changed
+    /// since the last call. Their tracker is independent from the one of the window properties, so
+    /// that the menus are only rebuilt when they change.
+    fn update_menus(&self) {
+        if !self.menus_tracker.is_dirty() {
+            return;
+        }
+        let menu_bar_state = self.menus_tracker.as_ref().evaluate_as_dependency_root(|| {
+            crate::items::update_menus(&self.component(), &self.window_adapter())
+        });
+        // The MenuBar element reads these properties, so they are set outside of the tracker
+        if let Some(menu_bar_state) = menu_bar_state {
+            menu_bar_state.apply();
+        }
     }
 
     /// Calls the render_components to render the main component and any sub-window components, tracked by a
     /// property dependency tracker.
     pub fn draw_contents(&self, render_components: impl FnOnce(&[(&ComponentRc, Point)])) {
+        // Make sure that the `changed` handlers have run before rendering
+        crate::properties::ChangeTracker::run_change_handlers();
         let draw_fn = || {
             let component_rc = self.component();
 
@@ -631,6 +631,20 @@ pub fn callbacks(&self) -> impl Iterator<Item = String> + '_ {
         })
     }
 
+    /// Returns the names of all publicly declared functions.
+    pub fn functions(&self) -> impl Iterator<Item = String> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).properties().filter_map(|(prop_name, prop_type)| {
+            if matches!(prop_type, LangType::Function { .. }) {
+                Some(prop_name)
+            } else {
+                None
+            }
+        })
+    }
+
     /// Returns the names of all exported global singletons
     ///
     /// **Note:** Only globals that are exported or re-exported from the main .slint file will
@@ -677,6 +691,22 @@ pub fn global_callbacks(&self, global_name: &str) -> Option<impl Iterator<Item =
         })
     }
 
+    /// List of publicly declared functions in the exported global singleton specified by its name.
+    pub fn global_functions(&self, global_name: &str) -> Option<impl Iterator<Item = String> + '_> {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).global_properties(global_name).map(|iter| {
+            iter.filter_map(|(prop_name, prop_type)| {
+                if matches!(prop_type, LangType::Function { .. }) {
+                    Some(prop_name)
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
     /// The name of this Component as written in the .slint file
     pub fn name(&self) -> &str {
         // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
@@ -802,6 +832,8 @@ pub fn set_callback(
 
     /// Call the given callback with the arguments
     ///
+    /// This can also be used to call a function declared with `public function`.
+    ///
     /// ## Examples
     /// See the documentation of [`Self::set_callback`] for an example
     pub fn invoke_callback(
@@ -922,6 +954,8 @@ pub fn set_global_callback(
 
     /// Call the given callback within a global singleton with the arguments
     ///
+    /// This can also be used to call a function declared with `public function`.
+    ///
     /// ## Examples
     /// See the documentation of [`Self::set_global_callback`] for an example
     pub fn invoke_global_callback(
@@ -1018,6 +1052,9 @@ pub enum SetPropertyError {
     /// The property exist but does not have a type matching the dynamic value
     #[error("wrong type")]
     WrongType,
+    /// Attempt to set an output property
+    #[error("access denied")]
+    AccessDenied,
 }
 
 /// Error returned by [`ComponentInstance::set_callback`]
@@ -1150,6 +1187,46 @@ fn component_definition_properties2() {
     assert_eq!(callbacks[0], "hello");
 }
 
+#[test]
+fn property_visibility() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Rectangle {
+        in property <int> input: 1;
+        out property <int> output: 2;
+        in-out property <int> both: 3;
+        private property <int> hidden: 4;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let props = comp_def.properties().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(props, ["both", "input", "output"]);
+
+    let instance = comp_def.create();
+    assert_eq!(instance.set_property("input", Value::Number(11.)), Ok(()));
+    assert_eq!(instance.get_property("input"), Ok(Value::Number(11.)));
+    assert_eq!(
+        instance.set_property("output", Value::Number(12.)),
+        Err(SetPropertyError::AccessDenied)
+    );
+    assert_eq!(instance.get_property("output"), Ok(Value::Number(2.)));
+    assert_eq!(instance.set_property("both", Value::Number(13.)), Ok(()));
+    assert_eq!(instance.get_property("both"), Ok(Value::Number(13.)));
+    assert_eq!(
+        instance.set_property("hidden", Value::Number(14.)),
+        Err(SetPropertyError::NoSuchProperty)
+    );
+    assert_eq!(instance.get_property("hidden"), Err(GetPropertyError::NoSuchProperty));
+}
+
 #[test]
 fn globals() {
     i_slint_backend_testing::init();
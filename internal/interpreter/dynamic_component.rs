@@ -9,8 +9,8 @@
 use core::ptr::NonNull;
 use dynamic_type::{Instance, InstanceBox};
 use i_slint_compiler::expression_tree::{Expression, NamedReference};
-use i_slint_compiler::langtype::Type;
-use i_slint_compiler::object_tree::ElementRc;
+use i_slint_compiler::langtype::{PropertyVisibility, Type};
+use i_slint_compiler::object_tree::{ElementRc, KeyFrameProperties};
 use i_slint_compiler::*;
 use i_slint_compiler::{diagnostics::BuildDiagnostics, object_tree::PropertyDeclaration};
 use i_slint_core::accessibility::AccessibleStringProperty;
@@ -25,7 +25,7 @@
 use i_slint_core::layout::{BoxLayoutCellData, LayoutInfo, Orientation};
 use i_slint_core::model::RepeatedComponent;
 use i_slint_core::model::Repeater;
-use i_slint_core::properties::InterpolatedPropertyValue;
+use i_slint_core::properties::{ChangeTracker, InterpolatedPropertyValue, KeyFrame};
 use i_slint_core::rtti::{self, AnimatedBindingKind, FieldOffset, PropertyInfo};
 use i_slint_core::slice::Slice;
 use i_slint_core::window::{WindowAdapter, WindowInner};
@@ -226,6 +226,12 @@ pub(crate) struct ComponentExtraData {
         once_cell::unsync::OnceCell<vtable::VWeak<ComponentVTable, ErasedComponentBox>>,
     // resource id -> file path
     pub(crate) embedded_file_resources: HashMap<usize, String>,
+    /// The instances of the SecondaryWindow that have been shown, by index in the
+    /// `secondary_windows` of the component
+    pub(crate) secondary_windows:
+        core::cell::RefCell<HashMap<usize, vtable::VRc<ComponentVTable, ErasedComponentBox>>>,
+    /// The trackers for the `changed <property> => {...}` callbacks, initialized in `run_setup_code`
+    pub(crate) change_trackers: once_cell::unsync::OnceCell<Vec<ChangeTracker>>,
 }
 
 struct ErasedRepeaterWithinComponent<'id>(RepeaterWithinComponent<'id, 'static>);
@@ -342,11 +348,8 @@ fn internal_properties_to_public<'a>(
         let name = v
             .node
             .as_ref()
-            .and_then(|n| {
-                n.as_ref()
-                    .either(|n| n.DeclaredIdentifier(), |n| n.DeclaredIdentifier())
-                    .child_token(parser::SyntaxKind::Identifier)
-            })
+            .and_then(|n| n.child_node(parser::SyntaxKind::DeclaredIdentifier))
+            .and_then(|n| n.child_token(parser::SyntaxKind::Identifier))
             .map(|n| n.to_string())
             .unwrap_or_else(|| s.clone());
         (name, v.property_type.clone())
@@ -427,6 +430,15 @@ pub fn set_property(
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             panic!("mismatch instance and vtable");
         }
+        match self.public_properties.get(name).map(|d| d.visibility) {
+            Some(PropertyVisibility::Private) => {
+                return Err(crate::api::SetPropertyError::NoSuchProperty)
+            }
+            Some(PropertyVisibility::Output) => {
+                return Err(crate::api::SetPropertyError::AccessDenied)
+            }
+            _ => (),
+        }
         generativity::make_guard!(guard);
         let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
         if let Some(alias) = self
@@ -478,6 +490,13 @@ pub fn get_property(&self, component: ComponentRefPin, name: &str) -> Result<Val
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             return Err(());
         }
+        if self
+            .public_properties
+            .get(name)
+            .map_or(false, |d| d.visibility == PropertyVisibility::Private)
+        {
+            return Err(());
+        }
         generativity::make_guard!(guard);
         // Safety: we just verified that the component has the right vtable
         let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
@@ -570,7 +589,26 @@ pub fn invoke_callback(
         generativity::make_guard!(guard);
         // Safety: we just verified that the component has the right vtable
         let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
-        if let Some(alias) = self
+        let function_decl = self
+            .original
+            .root_element
+            .borrow()
+            .property_declarations
+            .get(name)
+            .filter(|d| matches!(d.property_type, Type::Function { .. }))
+            .map(|d| d.expose_in_public_api);
+        if let Some(is_public) = function_decl {
+            if !is_public {
+                return Err(());
+            }
+            eval::call_function(
+                eval::ComponentInstance::InstanceRef(c),
+                &self.original.root_element,
+                name,
+                args.to_vec(),
+            )
+            .ok_or(())
+        } else if let Some(alias) = self
             .original
             .root_element
             .borrow()
@@ -780,6 +818,15 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<Rotate>(),
                 rtti_for::<Opacity>(),
                 rtti_for::<Layer>(),
+                rtti_for::<Effect>(),
+                rtti_for::<MenuItem>(),
+                rtti_for::<Menu>(),
+                rtti_for::<MenuBarItem>(),
+                rtti_for::<SystemTrayIcon>(),
+                rtti_for::<Shortcut>(),
+                rtti_for::<TimerItem>(),
+                rtti_for::<FileDialog>(),
+                rtti_for::<MessageDialog>(),
             ]
             .iter()
             .cloned(),
@@ -972,12 +1019,14 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                     .insert(name.clone(), builder.type_builder.add_field_type::<Callback>());
                 continue;
             }
+            Type::Function { .. } => continue,
             Type::Struct { name: Some(name), .. } if name.ends_with("::StateInfo") => {
                 property_info::<i_slint_core::properties::StateInfo>()
             }
             Type::Struct { .. } => property_info::<Value>(),
             Type::Array(_) => property_info::<Value>(),
             Type::Percent => property_info::<f32>(),
+            Type::Enumeration(e) if e.node.is_some() => property_info::<Value>(),
             Type::Enumeration(e) => {
                 macro_rules! match_enum_type {
                     ($( $(#[$enum_doc:meta])* enum $Name:ident { $($body:tt)* })*) => {
@@ -1101,13 +1150,12 @@ macro_rules! match_enum_type {
 pub fn animation_for_property(
     component: InstanceRef,
     animation: &Option<i_slint_compiler::object_tree::PropertyAnimation>,
-) -> AnimatedBindingKind {
+) -> AnimatedBindingKind<Value> {
     match animation {
         Some(i_slint_compiler::object_tree::PropertyAnimation::Static(anim_elem)) => {
-            AnimatedBindingKind::Animation(eval::new_struct_with_bindings(
-                &anim_elem.borrow().bindings,
-                &mut eval::EvalLocalContext::from_component_instance(component),
-            ))
+            let mut context = eval::EvalLocalContext::from_component_instance(component);
+            let (animation, keyframes) = eval_animation(&anim_elem.borrow(), &mut context);
+            AnimatedBindingKind::Animation(animation, keyframes)
         }
         Some(i_slint_compiler::object_tree::PropertyAnimation::Transition {
             animations,
@@ -1118,7 +1166,7 @@ pub fn animation_for_property(
             let animations = animations.clone();
             let state_ref = state_ref.clone();
             AnimatedBindingKind::Transition(Box::new(
-                move || -> (PropertyAnimation, i_slint_core::animations::Instant) {
+                move || -> (PropertyAnimation, i_slint_core::animations::Instant, Vec<KeyFrame<Value>>) {
                     generativity::make_guard!(guard);
                     let component = unsafe {
                         InstanceRef::from_pin_ref(
@@ -1137,13 +1185,9 @@ pub fn animation_for_property(
                         if (a.is_out && a.state_id == state_info.previous_state)
                             || (!a.is_out && a.state_id == state_info.current_state)
                         {
-                            return (
-                                eval::new_struct_with_bindings(
-                                    &a.animation.borrow().bindings,
-                                    &mut context,
-                                ),
-                                state_info.change_time,
-                            );
+                            let (animation, keyframes) =
+                                eval_animation(&a.animation.borrow(), &mut context);
+                            return (animation, state_info.change_time, keyframes);
                         }
                     }
                     Default::default()
@@ -1154,6 +1198,33 @@ pub fn animation_for_property(
     }
 }
 
+/// Evaluate the properties of an animation element and its keyframes
+fn eval_animation(
+    anim_elem: &i_slint_compiler::object_tree::Element,
+    context: &mut eval::EvalLocalContext,
+) -> (PropertyAnimation, Vec<KeyFrame<Value>>) {
+    let animation: PropertyAnimation = eval::new_struct_with_bindings(&anim_elem.bindings, context);
+    let keyframes = KeyFrameProperties::for_animation(anim_elem)
+        .into_iter()
+        .filter_map(|k| {
+            let mut eval_binding = |name: &str| {
+                Some(eval::eval_expression(&anim_elem.bindings.get(name)?.borrow(), context))
+            };
+            let position: f64 = eval_binding(&k.position)?.try_into().ok()?;
+            Some(KeyFrame {
+                position: position as f32,
+                value: eval_binding(&k.value)?,
+                // Without easing, the keyframe uses the easing of the animation
+                easing: match eval_binding(&k.easing) {
+                    Some(easing) => easing.try_into().ok()?,
+                    None => animation.easing,
+                },
+            })
+        })
+        .collect();
+    (animation, keyframes)
+}
+
 fn make_callback_eval_closure(
     expr: Expression,
     self_weak: &vtable::VWeak<ComponentVTable, ErasedComponentBox>,
@@ -1449,6 +1520,52 @@ pub fn run_setup_code(&self) {
                 &mut eval::EvalLocalContext::from_component_instance(instance_ref),
             );
         }
+
+        let self_weak = instance_ref.self_weak().get().unwrap();
+        let mut change_trackers = Vec::new();
+        object_tree::recurse_elem(
+            &self.0.component_type.original.root_element,
+            &(),
+            &mut |elem, _| {
+                for (prop, handlers) in &elem.borrow().change_callbacks {
+                    for code in handlers.borrow().iter() {
+                        let prop = Expression::PropertyReference(NamedReference::new(elem, prop));
+                        let code = code.clone();
+                        let tracker = ChangeTracker::default();
+                        tracker.init(
+                            self_weak.clone(),
+                            move |self_weak| {
+                                let self_rc = self_weak.upgrade().unwrap();
+                                generativity::make_guard!(guard);
+                                let self_ = self_rc.unerase(guard);
+                                let instance_ref = self_.borrow_instance();
+                                eval::eval_expression(
+                                    &prop,
+                                    &mut eval::EvalLocalContext::from_component_instance(
+                                        instance_ref,
+                                    ),
+                                )
+                            },
+                            move |self_weak, _| {
+                                let self_rc = self_weak.upgrade().unwrap();
+                                generativity::make_guard!(guard);
+                                let self_ = self_rc.unerase(guard);
+                                let instance_ref = self_.borrow_instance();
+                                eval::eval_expression(
+                                    &code,
+                                    &mut eval::EvalLocalContext::from_component_instance(
+                                        instance_ref,
+                                    ),
+                                );
+                            },
+                        );
+                        change_trackers.push(tracker);
+                    }
+                }
+            },
+        );
+        let extra_data = instance_ref.component_type.extra_data_offset.apply(instance_ref.as_ref());
+        extra_data.change_trackers.set(change_trackers).ok();
     }
 }
 impl<'id> From<ComponentBox<'id>> for ErasedComponentBox {
@@ -1735,3 +1852,41 @@ pub fn show_popup(
         parent_item,
     );
 }
+
+/// Show the SecondaryWindow in its own window, creating it the first time it is shown
+pub fn show_secondary_window(
+    window_component: &Rc<object_tree::Component>,
+    window_index: usize,
+    parent_instance: InstanceRef,
+) {
+    let extra_data =
+        parent_instance.component_type.extra_data_offset.apply(parent_instance.as_ref());
+    let existing = extra_data.secondary_windows.borrow().get(&window_index).cloned();
+    let inst = existing.unwrap_or_else(|| {
+        generativity::make_guard!(guard);
+        let compiled = generate_component(window_component, guard);
+        let window_adapter = i_slint_backend_selector::with_platform(|b| b.create_window_adapter());
+        let inst = instantiate(
+            compiled,
+            Some(parent_instance.borrow()),
+            Some(&window_adapter),
+            Default::default(),
+        );
+        WindowInner::from_pub(window_adapter.window())
+            .set_component(&vtable::VRc::into_dyn(inst.clone()));
+        extra_data.secondary_windows.borrow_mut().insert(window_index, inst.clone());
+        inst.run_setup_code();
+        inst
+    });
+    inst.window_adapter().window().show();
+}
+
+/// Hide the SecondaryWindow if it was shown before
+pub fn hide_secondary_window(window_index: usize, parent_instance: InstanceRef) {
+    let extra_data =
+        parent_instance.component_type.extra_data_offset.apply(parent_instance.as_ref());
+    let inst = extra_data.secondary_windows.borrow().get(&window_index).cloned();
+    if let Some(inst) = inst {
+        inst.window_adapter().window().hide();
+    }
+}
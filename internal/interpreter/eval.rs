@@ -2,17 +2,19 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use crate::api::{SetPropertyError, Struct, Value};
-use crate::dynamic_component::InstanceRef;
+use crate::dynamic_component::{ErasedComponentBox, InstanceRef};
 use core::convert::TryInto;
 use core::pin::Pin;
 use corelib::graphics::{GradientStop, LinearGradientBrush, PathElement, RadialGradientBrush};
 use corelib::items::{ItemRef, PropertyAnimation};
 use corelib::model::{Model, ModelRc};
+use corelib::properties::KeyFrame;
 use corelib::rtti::AnimatedBindingKind;
 use corelib::window::WindowInner;
 use corelib::{Brush, Color, PathData, SharedString, SharedVector};
 use i_slint_compiler::expression_tree::{
-    BuiltinFunction, EasingCurve, Expression, Path as ExprPath, PathElement as ExprPathElement,
+    ArrayIterationKind, BuiltinFunction, EasingCurve, Expression, Path as ExprPath,
+    PathElement as ExprPathElement,
 };
 use i_slint_compiler::langtype::Type;
 use i_slint_compiler::object_tree::ElementRc;
@@ -26,13 +28,13 @@ fn set(
         &self,
         item: Pin<ItemRef>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Vec<KeyFrame<Value>>)>,
     ) -> Result<(), ()>;
     fn set_binding(
         &self,
         item: Pin<ItemRef>,
         binding: Box<dyn Fn() -> Value>,
-        animation: AnimatedBindingKind,
+        animation: AnimatedBindingKind<Value>,
     );
     fn offset(&self) -> usize;
 
@@ -51,7 +53,7 @@ fn set(
         &self,
         item: Pin<ItemRef>,
         value: Value,
-        animation: Option<PropertyAnimation>,
+        animation: Option<(PropertyAnimation, Vec<KeyFrame<Value>>)>,
     ) -> Result<(), ()> {
         (*self).set(ItemRef::downcast_pin(item).unwrap(), value, animation)
     }
@@ -59,7 +61,7 @@ fn set_binding(
         &self,
         item: Pin<ItemRef>,
         binding: Box<dyn Fn() -> Value>,
-        animation: AnimatedBindingKind,
+        animation: AnimatedBindingKind<Value>,
     ) {
         (*self).set_binding(ItemRef::downcast_pin(item).unwrap(), binding, animation).unwrap();
     }
@@ -130,6 +132,68 @@ pub fn from_function_arguments(
     }
 }
 
+/// Returns a function that evaluates the expression of an array `filter` or `map` for a row, which is
+/// in the `item` local variable. The function outlives the binding, so it holds a weak reference to
+/// the component and a copy of the local variables.
+fn array_lambda(
+    item: &str,
+    expression: &Expression,
+    local_context: &EvalLocalContext,
+) -> impl Fn(Value) -> Value {
+    enum WeakComponentInstance {
+        InstanceRef(vtable::VWeak<corelib::component::ComponentVTable, ErasedComponentBox>),
+        GlobalComponent(std::rc::Weak<dyn crate::global_component::GlobalComponent>),
+    }
+    let component = match local_context.component_instance {
+        ComponentInstance::InstanceRef(instance_ref) => {
+            WeakComponentInstance::InstanceRef(instance_ref.self_weak().get().unwrap().clone())
+        }
+        ComponentInstance::GlobalComponent(global) => {
+            // Safety: the Rc is only used to create a weak reference, the global is not moved
+            let global = unsafe { Pin::into_inner_unchecked(global.clone()) };
+            WeakComponentInstance::GlobalComponent(Rc::downgrade(&global))
+        }
+    };
+    let item = item.to_owned();
+    let expression = expression.clone();
+    let local_variables = local_context.local_variables.clone();
+    let function_arguments = local_context.function_arguments.clone();
+    move |row| {
+        let mut local_variables = local_variables.clone();
+        local_variables.insert(item.clone(), row);
+        let function_arguments = function_arguments.clone();
+        match &component {
+            WeakComponentInstance::InstanceRef(weak) => {
+                if let Some(self_rc) = weak.upgrade() {
+                    generativity::make_guard!(guard);
+                    let self_ = self_rc.unerase(guard);
+                    let mut local_context = EvalLocalContext {
+                        local_variables,
+                        function_arguments,
+                        component_instance: ComponentInstance::InstanceRef(self_.borrow_instance()),
+                        return_value: None,
+                    };
+                    return eval_expression(&expression, &mut local_context);
+                }
+            }
+            WeakComponentInstance::GlobalComponent(weak) => {
+                if let Some(global) = weak.upgrade() {
+                    // Safety: the global was pinned when the weak reference was created
+                    let global = unsafe { Pin::new_unchecked(global) };
+                    let mut local_context = EvalLocalContext {
+                        local_variables,
+                        function_arguments,
+                        component_instance: ComponentInstance::GlobalComponent(&global),
+                        return_value: None,
+                    };
+                    return eval_expression(&expression, &mut local_context);
+                }
+            }
+        }
+        default_value_for_type(&expression.ty())
+    }
+}
+
 /// Evaluate an expression and return a Value as the result of this expression
 pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalContext) -> Value {
     if let Some(r) = &local_context.return_value {
@@ -142,6 +206,7 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
         Expression::NumberLiteral(n, unit) => Value::Number(unit.normalize(*n)),
         Expression::BoolLiteral(b) => Value::Bool(*b),
         Expression::CallbackReference { .. } => panic!("callback in expression"),
+        Expression::FunctionReference(_) => panic!("naked function reference not allowed, should be handled by function call"),
         Expression::BuiltinFunctionReference(..) => panic!(
             "naked builtin function reference not allowed, should be handled by function call"
         ),
@@ -188,6 +253,71 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 }
             }
         }
+        Expression::ArrayIteration { kind: ArrayIterationKind::Filter, array, item, expression, .. } => {
+            let model = match eval_expression(array, local_context) {
+                Value::Model(model) => model,
+                _ => return default_value_for_type(&array.ty()),
+            };
+            let filter_function = array_lambda(item, expression, local_context);
+            Value::Model(ModelRc::new(corelib::model::FilterModel::new(model, move |row| {
+                filter_function(row.clone()) == Value::Bool(true)
+            })))
+        }
+        Expression::ArrayIteration { kind: ArrayIterationKind::Map, array, item, expression, .. } => {
+            let model = match eval_expression(array, local_context) {
+                Value::Model(model) => model,
+                _ => return default_value_for_type(&Type::Array(expression.ty().into())),
+            };
+            let map_function = array_lambda(item, expression, local_context);
+            Value::Model(ModelRc::new(corelib::model::MapModel::new(model, map_function)))
+        }
+        Expression::ArraySlice { array, start, end } => {
+            let model = match eval_expression(array, local_context) {
+                Value::Model(model) => model,
+                _ => return default_value_for_type(&array.ty()),
+            };
+            let start = match eval_expression(start, local_context) {
+                Value::Number(start) => start.max(0.) as usize,
+                _ => 0,
+            };
+            let end = end.as_ref().map(|end| match eval_expression(end, local_context) {
+                Value::Number(end) => end.max(0.) as usize,
+                _ => 0,
+            });
+            Value::Model(ModelRc::new(corelib::model::SliceModel::new(model, start, end)))
+        }
+        Expression::ArrayIteration { kind, array, item, index, expression } => {
+            let model = match eval_expression(array, local_context) {
+                Value::Model(model) => model,
+                _ => return default_value_for_type(&expression.ty()),
+            };
+            model.model_tracker().track_row_count_changes();
+            let mut sum = 0.;
+            for i in 0..model.row_count() {
+                model.model_tracker().track_row_data_changes(i);
+                let row = model.row_data(i).unwrap_or_default();
+                local_context.local_variables.insert(item.clone(), row);
+                local_context.local_variables.insert(index.clone(), Value::Number(i as f64));
+                let value = eval_expression(expression, local_context);
+                match kind {
+                    ArrayIterationKind::FindIndex => {
+                        if value == Value::Bool(true) {
+                            return Value::Number(i as f64);
+                        }
+                    }
+                    ArrayIterationKind::Sum => {
+                        if let Value::Number(n) = value {
+                            sum += n;
+                        }
+                    }
+                    ArrayIterationKind::Filter | ArrayIterationKind::Map => unreachable!(),
+                }
+            }
+            match kind {
+                ArrayIterationKind::Sum => Value::Number(sum),
+                _ => Value::Number(-1.),
+            }
+        }
         Expression::Cast { from, to } => {
             let v = eval_expression(&*from, local_context);
             match (v, to) {
@@ -215,6 +345,10 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let args = arguments.iter().map(|e| eval_expression(e, local_context)).collect::<Vec<_>>();
                 invoke_callback(local_context.component_instance, &nr.element(), nr.name(), &args).unwrap()
             }
+            Expression::FunctionReference(nr) => {
+                let args = arguments.iter().map(|e| eval_expression(e, local_context)).collect::<Vec<_>>();
+                call_function(local_context.component_instance, &nr.element(), nr.name(), args).unwrap()
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowScaleFactor, _) => {
                 match local_context.component_instance {
                     ComponentInstance::InstanceRef(component) => Value::Number(window_ref(component).unwrap().scale_factor() as _),
@@ -348,6 +482,85 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(f @ (BuiltinFunction::ShowSecondaryWindow | BuiltinFunction::HideSecondaryWindow), _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to {:?}", f)
+                }
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot show a window from a global component")
+                };
+                if let Expression::ElementReference(window) = &arguments[0] {
+                    let window = window.upgrade().unwrap();
+                    let window_comp = window.borrow().enclosing_component.upgrade().unwrap();
+                    let parent_element = window_comp.parent_element.upgrade().unwrap();
+                    let parent_component = parent_element.borrow().enclosing_component.upgrade().unwrap();
+                    let window_index = parent_component.secondary_windows.borrow().iter().position(|w| Rc::ptr_eq(w, &window_comp)).unwrap();
+
+                    generativity::make_guard!(guard);
+                    let enclosing_component =
+                        enclosing_component_for_element(&parent_element, component, guard);
+                    if matches!(f, BuiltinFunction::ShowSecondaryWindow) {
+                        crate::dynamic_component::show_secondary_window(&window_comp, window_index, enclosing_component);
+                    } else {
+                        crate::dynamic_component::hide_secondary_window(window_index, enclosing_component);
+                    }
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to {:?} must be an element", f)
+                }
+            }
+            Expression::BuiltinFunctionReference(f @ (BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo), _) => {
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot access a TextInput from a global component")
+                };
+                if let [Expression::ElementReference(item)] = arguments.as_slice() {
+                    generativity::make_guard!(guard);
+
+                    let item = item.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&item, component, guard);
+                    let component_type = enclosing_component.component_type;
+                    let item_info = &component_type.items[item.borrow().id.as_str()];
+                    let item_ref = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+                    let text_input = ItemRef::downcast_pin::<corelib::items::TextInput>(item_ref).unwrap();
+
+                    let window_adapter = window_adapter_ref(component).unwrap();
+                    if matches!(f, BuiltinFunction::TextInputUndo) {
+                        text_input.undo(window_adapter);
+                    } else {
+                        text_input.redo(window_adapter);
+                    }
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to {:?} must be an element", f)
+                }
+            }
+            Expression::BuiltinFunctionReference(f @ (BuiltinFunction::ShowFileDialog | BuiltinFunction::ShowMessageDialog), _) => {
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot show a dialog from a global component")
+                };
+                if let [Expression::ElementReference(item)] = arguments.as_slice() {
+                    generativity::make_guard!(guard);
+
+                    let item = item.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&item, component, guard);
+                    let item_info = &enclosing_component.component_type.items[item.borrow().id.as_str()];
+                    let item_comp = enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
+                    let item_rc = corelib::items::ItemRc::new(vtable::VRc::into_dyn(item_comp), item_info.item_index());
+                    if matches!(f, BuiltinFunction::ShowFileDialog) {
+                        i_slint_backend_selector::show_file_dialog_element(&item_rc);
+                    } else {
+                        i_slint_backend_selector::show_message_dialog_element(&item_rc);
+                    }
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to {:?} must be an element", f)
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::StringIsFloat, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to StringIsFloat")
@@ -368,6 +581,73 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("Argument not a string");
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringToUppercase, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to StringToUppercase")
+                }
+                if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                    Value::String(s.to_uppercase().into())
+                } else {
+                    panic!("Argument not a string");
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringToLowercase, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to StringToLowercase")
+                }
+                if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                    Value::String(s.to_lowercase().into())
+                } else {
+                    panic!("Argument not a string");
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringLength, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to StringLength")
+                }
+                if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                    Value::Number(s.chars().count() as f64)
+                } else {
+                    panic!("Argument not a string");
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringSubstring, _) => {
+                if arguments.len() != 3 {
+                    panic!("internal error: incorrect argument count to StringSubstring")
+                }
+                if let Value::String(s) = eval_expression(&arguments[0], local_context) {
+                    let start: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                    let end: i32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                    Value::String(i_slint_core::string::substring(&s, start, end))
+                } else {
+                    panic!("First argument not a string");
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringContains, _) => {
+                if arguments.len() != 2 {
+                    panic!("internal error: incorrect argument count to StringContains")
+                }
+                match (eval_expression(&arguments[0], local_context), eval_expression(&arguments[1], local_context)) {
+                    (Value::String(s), Value::String(needle)) => Value::Bool(s.contains(needle.as_str())),
+                    _ => panic!("Arguments not strings"),
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::NumberToFixed, _) => {
+                if arguments.len() != 2 {
+                    panic!("internal error: incorrect argument count to NumberToFixed")
+                }
+                let n: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let digits: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::String(i_slint_core::string::number_to_fixed(n, digits))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::NumberToPrecision, _) => {
+                if arguments.len() != 2 {
+                    panic!("internal error: incorrect argument count to NumberToPrecision")
+                }
+                let n: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let precision: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::String(i_slint_core::string::number_to_precision(n, precision))
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ColorBrighter, _) => {
                 if arguments.len() != 2 {
                     panic!("internal error: incorrect argument count to ColorBrighter")
@@ -599,6 +879,16 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
             EasingCurve::CubicBezier(a, b, c, d) => {
                 corelib::animations::EasingCurve::CubicBezier([*a, *b, *c, *d])
             }
+            EasingCurve::Spring(stiffness, damping, mass) => {
+                corelib::animations::EasingCurve::Spring([*stiffness, *damping, *mass])
+            }
+            EasingCurve::EaseInElastic => corelib::animations::EasingCurve::EaseInElastic,
+            EasingCurve::EaseOutElastic => corelib::animations::EasingCurve::EaseOutElastic,
+            EasingCurve::EaseInOutElastic => corelib::animations::EasingCurve::EaseInOutElastic,
+            EasingCurve::EaseInBounce => corelib::animations::EasingCurve::EaseInBounce,
+            EasingCurve::EaseOutBounce => corelib::animations::EasingCurve::EaseOutBounce,
+            EasingCurve::EaseInOutBounce => corelib::animations::EasingCurve::EaseInOutBounce,
+            EasingCurve::Steps(steps) => corelib::animations::EasingCurve::Steps(*steps),
         }),
         Expression::LinearGradient{angle, stops} => {
             let angle = eval_expression(angle, local_context);
@@ -967,6 +1257,28 @@ pub(crate) fn invoke_callback(
     }
 }
 
+/// Evaluate the body of the function `function_name` declared in `element`
+pub(crate) fn call_function(
+    component_instance: ComponentInstance,
+    element: &ElementRc,
+    function_name: &str,
+    args: Vec<Value>,
+) -> Option<Value> {
+    generativity::make_guard!(guard);
+    match enclosing_component_instance_for_element(element, component_instance, guard) {
+        ComponentInstance::InstanceRef(enclosing_component) => {
+            let mut ctx = EvalLocalContext::from_function_arguments(enclosing_component, args);
+            let element = element.borrow();
+            let binding = element.bindings.get(function_name)?;
+            let result = eval_expression(&binding.borrow().expression, &mut ctx);
+            Some(result)
+        }
+        ComponentInstance::GlobalComponent(global) => {
+            global.as_ref().eval_function(function_name, args).ok()
+        }
+    }
+}
+
 fn root_component_instance<'a, 'old_id, 'new_id>(
     component: InstanceRef<'a, 'old_id>,
     guard: generativity::Guard<'new_id>,
@@ -6,10 +6,12 @@
 use crate::Value;
 use i_slint_compiler::expression_tree::Expression;
 use i_slint_compiler::langtype::Type;
-use i_slint_compiler::layout::{Layout, LayoutConstraints, LayoutGeometry, Orientation};
+use i_slint_compiler::layout::{
+    Layout, LayoutConstraints, LayoutGeometry, LayoutItem, Orientation,
+};
 use i_slint_compiler::namedreference::NamedReference;
 use i_slint_compiler::object_tree::ElementRc;
-use i_slint_core::items::DialogButtonRole;
+use i_slint_core::items::{DialogButtonRole, LayoutAlignment, StackAlignment};
 use i_slint_core::layout::{self as core_layout};
 use i_slint_core::model::RepeatedComponent;
 use i_slint_core::slice::Slice;
@@ -68,6 +70,38 @@ pub(crate) fn compute_layout_info(
             }
             .into()
         }
+        Layout::FlowLayout(flow_layout) => match orientation {
+            Orientation::Horizontal => {
+                let cells = box_layout_cells(
+                    &flow_layout.elems,
+                    Orientation::Horizontal,
+                    component,
+                    &expr_eval,
+                    None,
+                );
+                let (padding, spacing) =
+                    padding_and_spacing(&flow_layout.geometry, orientation, &expr_eval);
+                core_layout::flow_layout_info_horizontal(
+                    Slice::from(cells.as_slice()),
+                    spacing,
+                    &padding,
+                )
+                .into()
+            }
+            Orientation::Vertical => {
+                flow_layout_data(flow_layout, None, component, &expr_eval, |data, _| {
+                    core_layout::flow_layout_info_vertical(data).into()
+                })
+            }
+        },
+        Layout::StackLayout(stack_layout) => stack_layout_data(
+            stack_layout,
+            orientation,
+            false,
+            component,
+            &expr_eval,
+            |data, ri| core_layout::stack_layout_info(data, ri).into(),
+        ),
         Layout::PathLayout(_) => unimplemented!(),
     }
 }
@@ -137,6 +171,25 @@ pub(crate) fn solve_layout(
             )
             .into()
         }
+        Layout::FlowLayout(flow_layout) => flow_layout_data(
+            flow_layout,
+            Some(orientation),
+            component,
+            &expr_eval,
+            |data, repeated_indices| match orientation {
+                Orientation::Horizontal => {
+                    core_layout::solve_flow_layout_horizontal(data, repeated_indices).into()
+                }
+                Orientation::Vertical => {
+                    core_layout::solve_flow_layout_vertical(data, repeated_indices).into()
+                }
+            },
+        ),
+        Layout::StackLayout(stack_layout) => {
+            stack_layout_data(stack_layout, orientation, true, component, &expr_eval, |data, ri| {
+                core_layout::solve_stack_layout(data, ri).into()
+            })
+        }
         Layout::PathLayout(path_layout) => {
             let repeated_indices = repeater_indices(&path_layout.elements, component);
             core_layout::solve_path_layout(
@@ -208,11 +261,134 @@ fn box_layout_data(
     orientation: Orientation,
     component: InstanceRef,
     expr_eval: &impl Fn(&NamedReference) -> f32,
+    repeater_indices: Option<&mut Vec<u32>>,
+) -> (Vec<core_layout::BoxLayoutCellData>, LayoutAlignment) {
+    let cells =
+        box_layout_cells(&box_layout.elems, orientation, component, expr_eval, repeater_indices);
+    let alignment =
+        layout_alignment(box_layout.geometry.alignment.as_ref(), component, Default::default());
+    (cells, alignment)
+}
+
+/// Build the FlowLayoutData for the given layout and call `f` with it and the repeater indices.
+/// `solve` is the orientation being solved, or None for the vertical layout info.
+/// The vertical cells are not computed when solving the horizontal orientation, and the height
+/// of the layout is only used when solving the vertical orientation.
+fn flow_layout_data(
+    flow_layout: &i_slint_compiler::layout::FlowLayout,
+    solve: Option<Orientation>,
+    component: InstanceRef,
+    expr_eval: &impl Fn(&NamedReference) -> f32,
+    f: impl FnOnce(&core_layout::FlowLayoutData, Slice<u32>) -> Value,
+) -> Value {
+    let mut repeated_indices = Vec::new();
+    let cells_h = box_layout_cells(
+        &flow_layout.elems,
+        Orientation::Horizontal,
+        component,
+        expr_eval,
+        Some(&mut repeated_indices),
+    );
+    let cells_v = if solve != Some(Orientation::Horizontal) {
+        box_layout_cells(&flow_layout.elems, Orientation::Vertical, component, expr_eval, None)
+    } else {
+        Vec::new()
+    };
+    let (padding_h, spacing) =
+        padding_and_spacing(&flow_layout.geometry, Orientation::Horizontal, expr_eval);
+    let (padding_v, _) =
+        padding_and_spacing(&flow_layout.geometry, Orientation::Vertical, expr_eval);
+    let rect = &flow_layout.geometry.rect;
+    let data = core_layout::FlowLayoutData {
+        width: rect.width_reference.as_ref().map(expr_eval).unwrap_or(0.),
+        height: match solve {
+            Some(Orientation::Vertical) => rect.height_reference.as_ref().map(expr_eval),
+            _ => None,
+        }
+        .unwrap_or(0.),
+        spacing,
+        padding_h,
+        padding_v,
+        alignment: layout_alignment(
+            flow_layout.geometry.alignment.as_ref(),
+            component,
+            LayoutAlignment::Start,
+        ),
+        line_alignment: layout_alignment(
+            flow_layout.line_alignment.as_ref(),
+            component,
+            LayoutAlignment::Start,
+        ),
+        cells_h: Slice::from(cells_h.as_slice()),
+        cells_v: Slice::from(cells_v.as_slice()),
+    };
+    f(&data, Slice::from(repeated_indices.as_slice()))
+}
+
+/// Build the StackLayoutData for the given orientation and call `f` with it and the repeater indices.
+/// The size of the layout is only used when `solve` is true.
+fn stack_layout_data(
+    stack_layout: &i_slint_compiler::layout::StackLayout,
+    orientation: Orientation,
+    solve: bool,
+    component: InstanceRef,
+    expr_eval: &impl Fn(&NamedReference) -> f32,
+    f: impl FnOnce(&core_layout::StackLayoutData, Slice<u32>) -> Value,
+) -> Value {
+    let items = stack_layout.elems.iter().map(|e| e.item.clone()).collect::<Vec<_>>();
+    let mut repeated_indices = Vec::new();
+    let cells =
+        box_layout_cells(&items, orientation, component, expr_eval, Some(&mut repeated_indices));
+    let alignments = stack_layout
+        .elems
+        .iter()
+        .map(|e| match e.alignment(orientation) {
+            i_slint_compiler::layout::StackAlignment::Constant(value) => {
+                StackAlignment::from_str(&value.to_string()).unwrap()
+            }
+            i_slint_compiler::layout::StackAlignment::Property(nr) => {
+                eval::load_property(component, &nr.element(), nr.name())
+                    .unwrap()
+                    .try_into()
+                    .unwrap_or_default()
+            }
+        })
+        .collect::<Vec<_>>();
+    let (padding, _) = padding_and_spacing(&stack_layout.geometry, orientation, expr_eval);
+    let size_ref = stack_layout.geometry.rect.size_reference(orientation).filter(|_| solve);
+    let data = core_layout::StackLayoutData {
+        size: size_ref.map(expr_eval).unwrap_or(0.),
+        padding,
+        alignments: Slice::from(alignments.as_slice()),
+        cells: Slice::from(cells.as_slice()),
+    };
+    f(&data, Slice::from(repeated_indices.as_slice()))
+}
+
+fn layout_alignment(
+    nr: Option<&NamedReference>,
+    component: InstanceRef,
+    default: LayoutAlignment,
+) -> LayoutAlignment {
+    nr.map(|nr| {
+        eval::load_property(component, &nr.element(), nr.name())
+            .unwrap()
+            .try_into()
+            .unwrap_or_default()
+    })
+    .unwrap_or(default)
+}
+
+fn box_layout_cells(
+    elems: &[LayoutItem],
+    orientation: Orientation,
+    component: InstanceRef,
+    expr_eval: &impl Fn(&NamedReference) -> f32,
     mut repeater_indices: Option<&mut Vec<u32>>,
-) -> (Vec<core_layout::BoxLayoutCellData>, i_slint_core::items::LayoutAlignment) {
+) -> Vec<core_layout::BoxLayoutCellData> {
     let window_adapter = eval::window_adapter_ref(component).unwrap();
-    let mut cells = Vec::with_capacity(box_layout.elems.len());
-    for cell in &box_layout.elems {
+    let mut cells = Vec::with_capacity(elems.len());
+    for cell in elems {
         if cell.element.borrow().repeated.is_some() {
             generativity::make_guard!(guard);
             let rep = crate::dynamic_component::get_repeater_by_name(
@@ -252,18 +428,7 @@ fn box_layout_data(
             cells.push(core_layout::BoxLayoutCellData { constraint: layout_info });
         }
     }
-    let alignment = box_layout
-        .geometry
-        .alignment
-        .as_ref()
-        .map(|nr| {
-            eval::load_property(component, &nr.element(), nr.name())
-                .unwrap()
-                .try_into()
-                .unwrap_or_default()
-        })
-        .unwrap_or_default();
-    (cells, alignment)
+    cells
 }
 
 fn repeater_indices(children: &[ElementRc], component: InstanceRef) -> Vec<u32> {
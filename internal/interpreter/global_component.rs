@@ -89,6 +89,9 @@ fn set_property(
     fn get_property(self: Pin<&Self>, prop_name: &str) -> Result<Value, ()>;
 
     fn get_property_ptr(self: Pin<&Self>, prop_name: &str) -> *const ();
+
+    /// Evaluate the function declared in this global, regardless of its visibility
+    fn eval_function(self: Pin<&Self>, fn_name: &str, args: Vec<Value>) -> Result<Value, ()>;
 }
 
 /// Instantiate the global singleton and store it in `globals`
@@ -178,6 +181,19 @@ fn set_callback_handler(
         let comp = self.0.unerase(guard);
         comp.description().set_callback_handler(comp.borrow(), callback_name, handler)
     }
+
+    fn eval_function(self: Pin<&Self>, fn_name: &str, args: Vec<Value>) -> Result<Value, ()> {
+        generativity::make_guard!(guard);
+        let comp = self.0.unerase(guard);
+        let root_element = comp.description().original.root_element.clone();
+        crate::eval::call_function(
+            crate::eval::ComponentInstance::InstanceRef(comp.borrow_instance()),
+            &root_element,
+            fn_name,
+            args,
+        )
+        .ok_or(())
+    }
 }
 
 impl<T: rtti::BuiltinItem + 'static> GlobalComponent for T {
@@ -218,6 +234,10 @@ fn set_callback_handler(
         let cb = Self::callbacks().into_iter().find(|(k, _)| *k == callback_name).ok_or(())?.1;
         cb.set_handler(self, handler)
     }
+
+    fn eval_function(self: Pin<&Self>, _fn_name: &str, _args: Vec<Value>) -> Result<Value, ()> {
+        Err(())
+    }
 }
 
 pub(crate) fn generate(component: &Rc<Component>) -> CompiledGlobal {
@@ -72,6 +72,8 @@ pub(crate) fn completion_at(
                 [
                     ("property", "property <$1> $2;"),
                     ("callback", "callback $1();"),
+                    ("function", "function $1() { $2 }"),
+                    ("changed", "changed $1 => { $2 }"),
                     ("animate", "animate $1 { $2 }"),
                     ("states", "states [ $1 ]"),
                     ("transitions", "transitions [ $1 ]"),
@@ -454,6 +456,7 @@ fn completion_item_from_expression(str: &str, lookup_result: LookupResult) -> Co
             c.kind = match expression {
                 Expression::BoolLiteral(_) => Some(CompletionItemKind::CONSTANT),
                 Expression::CallbackReference(_) => Some(CompletionItemKind::METHOD),
+                Expression::FunctionReference(_) => Some(CompletionItemKind::FUNCTION),
                 Expression::PropertyReference(_) => Some(CompletionItemKind::PROPERTY),
                 Expression::BuiltinFunctionReference(..) => Some(CompletionItemKind::FUNCTION),
                 Expression::BuiltinMacroReference(..) => Some(CompletionItemKind::FUNCTION),
@@ -25,7 +25,7 @@ macro_rules! declare_legend {
     (@ [], $n:expr) => {};
 }
 // the id of the element
-declare_legend!(LEGEND_TYPES : SemanticTokenType = [TYPE PARAMETER VARIABLE PROPERTY FUNCTION MACRO KEYWORD COMMENT STRING NUMBER OPERATOR]);
+declare_legend!(LEGEND_TYPES : SemanticTokenType = [TYPE PARAMETER VARIABLE PROPERTY FUNCTION MACRO KEYWORD COMMENT STRING NUMBER OPERATOR ENUM_MEMBER]);
 declare_legend!(LEGEND_MODS: SemanticTokenModifier = [DEFINITION DECLARATION]);
 
 pub fn get_semantic_tokens(
@@ -55,8 +55,11 @@ pub fn get_semantic_tokens(
                 SyntaxKind::ConditionalElement => Some((self::KEYWORD, 0)),
                 SyntaxKind::CallbackDeclaration => Some((self::KEYWORD, 0)),
                 SyntaxKind::CallbackConnection => Some((self::FUNCTION, 0)),
+                SyntaxKind::Function => Some((self::KEYWORD, 0)),
+                SyntaxKind::ChangeCallback => Some((self::KEYWORD, 0)),
                 SyntaxKind::PropertyDeclaration => Some((self::KEYWORD, 0)),
                 SyntaxKind::PropertyAnimation => Some((self::KEYWORD, 0)),
+                SyntaxKind::AnimationGroup => Some((self::KEYWORD, 0)),
                 SyntaxKind::QualifiedName => match token.parent().parent()?.kind() {
                     SyntaxKind::Type => Some((self::TYPE, 0)),
                     // the base type
@@ -79,6 +82,11 @@ pub fn get_semantic_tokens(
                         SyntaxKind::CallbackConnection => {
                             Some((self::PARAMETER, 1 << self::DEFINITION))
                         }
+                        SyntaxKind::Function => Some((self::FUNCTION, 1 << self::DEFINITION)),
+                        SyntaxKind::ChangeCallback => Some((self::PROPERTY, 0)),
+                        SyntaxKind::ArgumentDeclaration => {
+                            Some((self::PARAMETER, 1 << self::DEFINITION))
+                        }
                         SyntaxKind::PropertyDeclaration => {
                             Some((self::PROPERTY, 1 << self::DEFINITION))
                         }
@@ -87,10 +95,11 @@ pub fn get_semantic_tokens(
                             None
                         }
                         SyntaxKind::StructDeclaration => Some((self::TYPE, 1 << self::DEFINITION)),
+                        SyntaxKind::EnumDeclaration => Some((self::TYPE, 1 << self::DEFINITION)),
                         _ => None,
                     }
                 }
-                SyntaxKind::ChildrenPlaceholder => Some((self::MACRO, 0)),
+                SyntaxKind::ChildrenPlaceholder | SyntaxKind::SlotMarker => Some((self::MACRO, 0)),
                 SyntaxKind::Binding | SyntaxKind::TwoWayBinding => Some((self::PROPERTY, 0)),
                 SyntaxKind::ReturnStatement => Some((self::KEYWORD, 0)),
                 SyntaxKind::AtImageUrl => Some((self::MACRO, 0)),
@@ -131,6 +140,8 @@ pub fn get_semantic_tokens(
                 SyntaxKind::InternalName => Some((self::TYPE, 1 << self::DECLARATION)),
                 SyntaxKind::ObjectTypeMember => Some((self::PROPERTY, 1 << self::DEFINITION)),
                 SyntaxKind::StructDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::EnumDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::EnumValue => Some((self::ENUM_MEMBER, 1 << self::DEFINITION)),
                 _ => None,
             },
             SyntaxKind::PlusEqual